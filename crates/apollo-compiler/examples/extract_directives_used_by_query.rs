@@ -1,33 +1,32 @@
 //! This example collects all directives declared on the fields that are queried by an operation.
 
 use apollo_compiler::executable::Directive;
+use apollo_compiler::executable::Field;
+use apollo_compiler::executable::TypeInfo;
+use apollo_compiler::executable::VisitControl;
+use apollo_compiler::executable::Visitor;
+use apollo_compiler::validation::Valid;
 use apollo_compiler::ExecutableDocument;
 use apollo_compiler::Node;
 use apollo_compiler::Schema;
 
-fn get_field_directives(doc: &ExecutableDocument) -> Vec<&Node<Directive>> {
-    // seed the stack with top-level fields
-    let mut stack: Vec<_> = doc
-        .operations
-        .iter()
-        .flat_map(|op| op.selection_set.fields())
-        .chain(
-            // in case the query has fragments
-            doc.fragments
-                .iter()
-                .flat_map(|(_, fragment)| fragment.selection_set.fields()),
-        )
-        .collect();
-
-    let mut directives = vec![];
-
-    // depth first search for nested fields with directives
-    while let Some(field) = stack.pop() {
-        directives.extend(field.definition.directives.iter());
-        stack.extend(field.selection_set.fields());
+struct FieldDirectives(Vec<Node<Directive>>);
+
+impl Visitor<'_> for FieldDirectives {
+    fn enter_field(&mut self, info: &TypeInfo<'_>, _field: &Node<Field>) -> VisitControl {
+        // The definition of the field being visited, looked up in the schema
+        if let Some(definition) = info.field_definition() {
+            self.0.extend(definition.directives.iter().cloned());
+        }
+        VisitControl::Continue
     }
+}
 
-    directives
+fn get_field_directives(schema: &Valid<Schema>, doc: &ExecutableDocument) -> Vec<Node<Directive>> {
+    // Visits fields of operations and fragment definitions, including nested fields
+    let mut visitor = FieldDirectives(Vec::new());
+    doc.walk(schema, &mut visitor);
+    visitor.0
 }
 
 fn main() {
@@ -74,7 +73,7 @@ fn main() {
     let query = ExecutableDocument::parse_and_validate(&schema, query_src, "not-used-here.graphql")
         .unwrap();
 
-    let directives = get_field_directives(&query);
+    let directives = get_field_directives(&schema, &query);
     assert_eq!(directives.len(), 4);
 
     let query_src = r#"query {
@@ -85,7 +84,7 @@ fn main() {
         "#;
     let query = ExecutableDocument::parse(&schema, query_src, "not-used-here.graphql").unwrap();
 
-    let directives = get_field_directives(&query);
+    let directives = get_field_directives(&schema, &query);
     assert_eq!(directives.len(), 2);
 
     let query_src = r#"query {
@@ -96,7 +95,7 @@ fn main() {
         "#;
     let query = ExecutableDocument::parse(&schema, query_src, "not-used-here.graphql").unwrap();
 
-    let directives = get_field_directives(&query);
+    let directives = get_field_directives(&schema, &query);
     assert_eq!(directives.len(), 0);
 
     let query_src = r#"query {
@@ -110,6 +109,6 @@ fn main() {
         "#;
     let query = ExecutableDocument::parse(&schema, query_src, "not-used-here.graphql").unwrap();
 
-    let directives = get_field_directives(&query);
+    let directives = get_field_directives(&schema, &query);
     assert_eq!(directives.len(), 2);
 }
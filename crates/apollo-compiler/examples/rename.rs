@@ -1,8 +1,12 @@
-//! This example shows how to rename a type definition
+//! This example shows how to rename a type definition and every reference to it
 
+use apollo_compiler::executable::VisitControl;
 use apollo_compiler::name;
 use apollo_compiler::schema::ExtendedType;
+use apollo_compiler::schema::NamedType;
+use apollo_compiler::schema::VisitorMut;
 use apollo_compiler::validation::Valid;
+use apollo_compiler::Name;
 use apollo_compiler::Schema;
 
 #[cfg(not(test))]
@@ -10,34 +14,48 @@ fn main() {
     print!("{}", renamed())
 }
 
-fn renamed() -> Valid<Schema> {
-    let input = "type Query { field: Int }";
-    let mut schema = Schema::parse(input, "schema.graphql").unwrap();
+struct RenameType {
+    from: Name,
+    to: Name,
+}
 
-    // 1. Remove the definition from the `types` map, using its old name as a key
-    let mut type_def = schema.types.shift_remove("Query").unwrap();
-
-    // 2. Set the new name in the struct
-    let ExtendedType::Object(obj) = &mut type_def else {
-        panic!("expected an object type")
-    };
-    let new_name = name!("MyQuery");
-    obj.make_mut().name = new_name.clone();
-
-    // 3. Insert back into the map using the new name as the key
-    // WARNING: it’s your responsibility to make sure to use the same name as in the struct!
-    // Failing to do so make cause code elsewhere to behave incorrectly, or potentially panic.
-    schema.types.insert(new_name.clone(), type_def);
-
-    // 4. Update any existing reference to the old name
-    schema
-        .schema_definition
-        .make_mut()
-        .query
-        .as_mut()
-        .unwrap()
-        .name = new_name;
+impl VisitorMut for RenameType {
+    // 1. Set the new name in the definition.
+    // Walking a schema also updates the key of the definition in the `types` map.
+    fn enter_type(&mut self, ty: &mut ExtendedType) -> VisitControl {
+        if *ty.name() == self.from {
+            let name = match ty {
+                ExtendedType::Scalar(def) => &mut def.make_mut().name,
+                ExtendedType::Object(def) => &mut def.make_mut().name,
+                ExtendedType::Interface(def) => &mut def.make_mut().name,
+                ExtendedType::Union(def) => &mut def.make_mut().name,
+                ExtendedType::Enum(def) => &mut def.make_mut().name,
+                ExtendedType::InputObject(def) => &mut def.make_mut().name,
+            };
+            *name = self.to.clone();
+        }
+        VisitControl::Continue
+    }
 
+    // 2. Update every reference to the old name: field types, root operations, etc.
+    fn named_type(&mut self, name: &mut NamedType) {
+        if *name == self.from {
+            *name = self.to.clone()
+        }
+    }
+}
+
+fn renamed() -> Valid<Schema> {
+    let input = "type Query { me: User } type User { friends: [User!] }";
+    let mut schema = Schema::parse(input, "schema.graphql").unwrap();
+    schema.walk_mut(&mut RenameType {
+        from: name!("User"),
+        to: name!("Person"),
+    });
+    schema.walk_mut(&mut RenameType {
+        from: name!("Query"),
+        to: name!("MyQuery"),
+    });
     schema.validate().unwrap()
 }
 
@@ -49,7 +67,11 @@ fn test_renamed() {
         }
 
         type MyQuery {
-          field: Int
+          me: Person
+        }
+
+        type Person {
+          friends: [Person!]
         }
     "#]];
     expected.assert_eq(&renamed().to_string());
@@ -0,0 +1,270 @@
+use super::*;
+use std::fmt;
+
+/// Builder for formatting a [`Document`] in a consistent style,
+/// returned by [`Document::format`].
+/// Implements [`Display`][fmt::Display] and [`ToString`].
+///
+/// Formatting is built on [serialization][Document::serialize] and so also preserves
+/// [`Comments`] of definitions and of fields, enum values, and selections.
+/// In addition it:
+///
+/// * Breaks lists of arguments and variable definitions into one item per line
+///   when they would not fit within the [maximum line width][Self::max_line_width]
+/// * Prints descriptions as block strings when possible,
+///   on multiple lines when they would not fit within the maximum line width
+/// * Can optionally sort definitions, fields, enum values, and directives by name
+///
+/// Formatting is idempotent: parsing and formatting again the output of formatting
+/// returns the same string.
+///
+/// ```
+/// use apollo_compiler::ast::Document;
+///
+/// let input = "type Query { b: Int, a(first: Int, after: String): [String] }";
+/// let doc = Document::parse(input, "schema.graphql").unwrap();
+/// let formatted = doc.format().max_line_width(30).sort_fields(true).to_string();
+/// assert_eq!(
+///     formatted,
+///     "type Query {
+///   a(
+///     first: Int,
+///     after: String,
+///   ): [String]
+///   b: Int
+/// }
+/// "
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Format<'a> {
+    document: &'a Document,
+    indent_prefix: &'a str,
+    max_line_width: usize,
+    sort_definitions: bool,
+    sort_fields: bool,
+    sort_enum_values: bool,
+    sort_directives: bool,
+}
+
+impl<'a> Format<'a> {
+    pub(crate) fn new(document: &'a Document) -> Self {
+        Self {
+            document,
+            indent_prefix: "  ",
+            max_line_width: 80,
+            sort_definitions: false,
+            sort_fields: false,
+            sort_enum_values: false,
+            sort_directives: false,
+        }
+    }
+
+    /// Set the string repeated at the start of each line by the number of indentation levels.
+    /// The default is `"  "`, two spaces.
+    pub fn indent_prefix(mut self, prefix: &'a str) -> Self {
+        self.indent_prefix = prefix;
+        self
+    }
+
+    /// Set the line width that lists and descriptions are wrapped to fit in.
+    /// The default is 80 characters.
+    ///
+    /// Lines can still be longer when a single item does not fit.
+    pub fn max_line_width(mut self, width: usize) -> Self {
+        self.max_line_width = width;
+        self
+    }
+
+    /// Configure whether to reorder top-level definitions.
+    /// Disabled by default.
+    ///
+    /// When enabled, definitions are grouped by kind in this order:
+    /// schema definitions and extensions, directive definitions,
+    /// type definitions and extensions, operations, then fragments.
+    /// Each group is sorted by name.
+    /// Definitions with the same name, such as a type and its extensions,
+    /// keep their relative order.
+    pub fn sort_definitions(mut self, sort: bool) -> Self {
+        self.sort_definitions = sort;
+        self
+    }
+
+    /// Configure whether to sort by name the fields of object types, interfaces,
+    /// and input objects.
+    /// Disabled by default.
+    ///
+    /// Selections in executable definitions are never sorted,
+    /// as their order determines the order of fields in a response.
+    pub fn sort_fields(mut self, sort: bool) -> Self {
+        self.sort_fields = sort;
+        self
+    }
+
+    /// Configure whether to sort the values of enum types.
+    /// Disabled by default.
+    pub fn sort_enum_values(mut self, sort: bool) -> Self {
+        self.sort_enum_values = sort;
+        self
+    }
+
+    /// Configure whether to sort by name directives applied in type system definitions,
+    /// including on fields, arguments, and enum values.
+    /// Disabled by default.
+    ///
+    /// The relative order of repeated applications of the same directive is preserved.
+    pub fn sort_directives(mut self, sort: bool) -> Self {
+        self.sort_directives = sort;
+        self
+    }
+
+    fn sorting(&self) -> bool {
+        self.sort_definitions || self.sort_fields || self.sort_enum_values || self.sort_directives
+    }
+
+    fn sort(&self, document: &mut Document) {
+        if self.sort_definitions {
+            document
+                .definitions
+                .sort_by(|a, b| definition_order(a).cmp(&definition_order(b)));
+        }
+        for definition in &mut document.definitions {
+            match definition {
+                Definition::OperationDefinition(_) | Definition::FragmentDefinition(_) => {}
+                Definition::DirectiveDefinition(def) => {
+                    if self.sort_directives {
+                        self.input_values(&mut def.make_mut().arguments, false)
+                    }
+                }
+                Definition::SchemaDefinition(def) => {
+                    self.directives(&mut def.make_mut().directives)
+                }
+                Definition::SchemaExtension(def) => self.directives(&mut def.make_mut().directives),
+                Definition::ScalarTypeDefinition(def) => {
+                    self.directives(&mut def.make_mut().directives)
+                }
+                Definition::ScalarTypeExtension(def) => {
+                    self.directives(&mut def.make_mut().directives)
+                }
+                Definition::ObjectTypeDefinition(def) => {
+                    let def = def.make_mut();
+                    self.directives(&mut def.directives);
+                    self.fields(&mut def.fields);
+                }
+                Definition::ObjectTypeExtension(def) => {
+                    let def = def.make_mut();
+                    self.directives(&mut def.directives);
+                    self.fields(&mut def.fields);
+                }
+                Definition::InterfaceTypeDefinition(def) => {
+                    let def = def.make_mut();
+                    self.directives(&mut def.directives);
+                    self.fields(&mut def.fields);
+                }
+                Definition::InterfaceTypeExtension(def) => {
+                    let def = def.make_mut();
+                    self.directives(&mut def.directives);
+                    self.fields(&mut def.fields);
+                }
+                Definition::UnionTypeDefinition(def) => {
+                    self.directives(&mut def.make_mut().directives)
+                }
+                Definition::UnionTypeExtension(def) => {
+                    self.directives(&mut def.make_mut().directives)
+                }
+                Definition::EnumTypeDefinition(def) => {
+                    let def = def.make_mut();
+                    self.directives(&mut def.directives);
+                    self.enum_values(&mut def.values);
+                }
+                Definition::EnumTypeExtension(def) => {
+                    let def = def.make_mut();
+                    self.directives(&mut def.directives);
+                    self.enum_values(&mut def.values);
+                }
+                Definition::InputObjectTypeDefinition(def) => {
+                    let def = def.make_mut();
+                    self.directives(&mut def.directives);
+                    self.input_values(&mut def.fields, self.sort_fields);
+                }
+                Definition::InputObjectTypeExtension(def) => {
+                    let def = def.make_mut();
+                    self.directives(&mut def.directives);
+                    self.input_values(&mut def.fields, self.sort_fields);
+                }
+            }
+        }
+    }
+
+    fn directives(&self, directives: &mut DirectiveList) {
+        if self.sort_directives {
+            directives.sort_by(|a, b| a.name.cmp(&b.name))
+        }
+    }
+
+    fn fields(&self, fields: &mut [Node<FieldDefinition>]) {
+        if self.sort_fields {
+            fields.sort_by(|a, b| a.name.cmp(&b.name))
+        }
+        if self.sort_directives {
+            for field in fields {
+                let field = field.make_mut();
+                self.directives(&mut field.directives);
+                self.input_values(&mut field.arguments, false);
+            }
+        }
+    }
+
+    /// `sort` is only set for input object fields: argument order is kept as written
+    fn input_values(&self, values: &mut [Node<InputValueDefinition>], sort: bool) {
+        if sort {
+            values.sort_by(|a, b| a.name.cmp(&b.name))
+        }
+        if self.sort_directives {
+            for value in values {
+                self.directives(&mut value.make_mut().directives)
+            }
+        }
+    }
+
+    fn enum_values(&self, values: &mut [Node<EnumValueDefinition>]) {
+        if self.sort_enum_values {
+            values.sort_by(|a, b| a.value.cmp(&b.value))
+        }
+        if self.sort_directives {
+            for value in values {
+                self.directives(&mut value.make_mut().directives)
+            }
+        }
+    }
+}
+
+fn definition_order(definition: &Definition) -> (u8, Option<&Name>) {
+    let group = match definition {
+        Definition::SchemaDefinition(_) | Definition::SchemaExtension(_) => 0,
+        Definition::DirectiveDefinition(_) => 1,
+        Definition::OperationDefinition(_) => 3,
+        Definition::FragmentDefinition(_) => 4,
+        _ => 2,
+    };
+    (group, definition.name())
+}
+
+impl fmt::Display for Format<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sorted;
+        let document = if self.sorting() {
+            let mut document = self.document.clone();
+            self.sort(&mut document);
+            sorted = document;
+            &sorted
+        } else {
+            self.document
+        };
+        document
+            .serialize()
+            .indent_prefix(self.indent_prefix)
+            .max_line_width(self.max_line_width)
+            .fmt(f)
+    }
+}
@@ -6,7 +6,10 @@ use crate::parser::SourceSpan;
 use crate::Node;
 use apollo_parser::cst;
 use apollo_parser::cst::CstNode;
+use apollo_parser::SyntaxElement;
+use apollo_parser::SyntaxKind;
 use apollo_parser::SyntaxNode;
+use apollo_parser::SyntaxToken;
 use apollo_parser::S;
 
 impl Document {
@@ -29,6 +32,60 @@ pub(crate) trait Convert {
 
 fn with_location<T>(file_id: FileId, syntax_node: &SyntaxNode, node: T) -> Node<T> {
     Node::new_parsed(node, SourceSpan::new(file_id, syntax_node))
+        .with_parsed_comments(comments(syntax_node))
+}
+
+/// Collect comments from sibling trivia of `syntax_node`:
+/// comments on their own lines right before it, and a comment on the same line right after it.
+///
+/// A comment after a preceding sibling node on the same line is that node’s trailing comment,
+/// not a leading comment of this one.
+fn comments(syntax_node: &SyntaxNode) -> Option<ast::Comments> {
+    let comment_text = |token: &SyntaxToken| token.text().trim_start_matches('#').to_owned();
+
+    let mut leading = Vec::new();
+    let mut element = syntax_node.prev_sibling_or_token();
+    let mut pending = None;
+    while let Some(SyntaxElement::Token(token)) = &element {
+        match token.kind() {
+            SyntaxKind::WHITESPACE if token.text().contains(['\n', '\r']) => {
+                leading.extend(pending.take());
+            }
+            SyntaxKind::WHITESPACE | SyntaxKind::COMMA => {}
+            SyntaxKind::COMMENT => {
+                leading.extend(pending.take());
+                pending = Some(comment_text(token));
+            }
+            _ => break,
+        }
+        element = token.prev_sibling_or_token();
+    }
+    // Unless on the same line as a preceding node
+    if !matches!(element, Some(SyntaxElement::Node(_))) {
+        leading.extend(pending);
+    }
+    leading.reverse();
+
+    let mut trailing = None;
+    let mut element = syntax_node.next_sibling_or_token();
+    while let Some(SyntaxElement::Token(token)) = &element {
+        match token.kind() {
+            SyntaxKind::WHITESPACE if token.text().contains(['\n', '\r']) => break,
+            SyntaxKind::WHITESPACE | SyntaxKind::COMMA => {}
+            SyntaxKind::COMMENT => {
+                trailing = Some(comment_text(token));
+                break;
+            }
+            _ => break,
+        }
+        element = token.next_sibling_or_token();
+    }
+
+    if leading.is_empty() && trailing.is_none() {
+        None
+    } else {
+        Some(ast::Comments { leading, trailing })
+    }
 }
 
 /// Convert and collect, silently skipping entries with conversion errors
@@ -52,6 +109,20 @@ where
         .collect()
 }
 
+/// Convert a name that may be missing because of a syntax error,
+/// using [`Name::MISSING`][crate::Name::MISSING] to keep the partially-parsed parent node
+fn name_or_missing(name: Option<cst::Name>, file_id: FileId) -> crate::Name {
+    name.and_then(|name| name.convert(file_id))
+        .unwrap_or(crate::Name::MISSING)
+}
+
+/// Convert a type that may be missing because of a syntax error,
+/// using a placeholder named type without a location
+fn type_node_or_missing(ty: Option<cst::Type>, file_id: FileId) -> Node<ast::Type> {
+    ty.and_then(|ty| Some(with_location(file_id, ty.syntax(), ty.convert(file_id)?)))
+        .unwrap_or_else(|| Node::new(ast::Type::Named(crate::Name::MISSING)))
+}
+
 #[inline]
 fn collect_opt<CstType1, CstType2, AstType, F, I>(
     file_id: FileId,
@@ -134,10 +205,9 @@ impl Convert for cst::OperationDefinition {
                 x.directives()
             })),
             selection_set: self
-                .selection_set()?
-                .selections()
-                .filter_map(|sel| sel.convert(file_id))
-                .collect(),
+                .selection_set()
+                .map(|selection_set| convert_selection_set(&selection_set, file_id))
+                .unwrap_or_default(),
         })
     }
 }
@@ -147,12 +217,15 @@ impl Convert for cst::FragmentDefinition {
 
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
-            name: self.fragment_name()?.name()?.convert(file_id)?,
-            type_condition: self.type_condition()?.convert(file_id)?,
+            name: name_or_missing(self.fragment_name().and_then(|name| name.name()), file_id),
+            type_condition: self
+                .type_condition()
+                .and_then(|condition| condition.convert(file_id))
+                .unwrap_or(crate::Name::MISSING),
             directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
-            selection_set: self.selection_set().convert(file_id)??,
+            selection_set: self.selection_set().convert(file_id)?.unwrap_or_default(),
         })
     }
 }
@@ -171,7 +244,7 @@ impl Convert for cst::DirectiveDefinition {
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
             description: self.description().convert(file_id)?,
-            name: self.name()?.convert(file_id)?,
+            name: name_or_missing(self.name(), file_id),
             arguments: collect_opt(file_id, self.arguments_definition(), |x| {
                 x.input_value_definitions()
             }),
@@ -215,7 +288,7 @@ impl Convert for cst::ScalarTypeDefinition {
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
             description: self.description().convert(file_id)?,
-            name: self.name()?.convert(file_id)?,
+            name: name_or_missing(self.name(), file_id),
             directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
@@ -229,7 +302,7 @@ impl Convert for cst::ObjectTypeDefinition {
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
             description: self.description().convert(file_id)?,
-            name: self.name()?.convert(file_id)?,
+            name: name_or_missing(self.name(), file_id),
             implements_interfaces: self.implements_interfaces().convert(file_id)?,
             directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
@@ -245,7 +318,7 @@ impl Convert for cst::InterfaceTypeDefinition {
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
             description: self.description().convert(file_id)?,
-            name: self.name()?.convert(file_id)?,
+            name: name_or_missing(self.name(), file_id),
             implements_interfaces: self.implements_interfaces().convert(file_id)?,
             directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
@@ -261,7 +334,7 @@ impl Convert for cst::UnionTypeDefinition {
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
             description: self.description().convert(file_id)?,
-            name: self.name()?.convert(file_id)?,
+            name: name_or_missing(self.name(), file_id),
             directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
@@ -283,7 +356,7 @@ impl Convert for cst::EnumTypeDefinition {
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
             description: self.description().convert(file_id)?,
-            name: self.name()?.convert(file_id)?,
+            name: name_or_missing(self.name(), file_id),
             directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
@@ -300,7 +373,7 @@ impl Convert for cst::InputObjectTypeDefinition {
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
             description: self.description().convert(file_id)?,
-            name: self.name()?.convert(file_id)?,
+            name: name_or_missing(self.name(), file_id),
             directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
@@ -332,7 +405,7 @@ impl Convert for cst::ScalarTypeExtension {
 
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
-            name: self.name()?.convert(file_id)?,
+            name: name_or_missing(self.name(), file_id),
             directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
@@ -345,7 +418,7 @@ impl Convert for cst::ObjectTypeExtension {
 
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
-            name: self.name()?.convert(file_id)?,
+            name: name_or_missing(self.name(), file_id),
             implements_interfaces: self.implements_interfaces().convert(file_id)?,
             directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
@@ -360,7 +433,7 @@ impl Convert for cst::InterfaceTypeExtension {
 
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
-            name: self.name()?.convert(file_id)?,
+            name: name_or_missing(self.name(), file_id),
             implements_interfaces: self.implements_interfaces().convert(file_id)?,
             directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
@@ -375,7 +448,7 @@ impl Convert for cst::UnionTypeExtension {
 
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
-            name: self.name()?.convert(file_id)?,
+            name: name_or_missing(self.name(), file_id),
             directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
@@ -396,7 +469,7 @@ impl Convert for cst::EnumTypeExtension {
 
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
-            name: self.name()?.convert(file_id)?,
+            name: name_or_missing(self.name(), file_id),
             directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
@@ -412,7 +485,7 @@ impl Convert for cst::InputObjectTypeExtension {
 
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
-            name: self.name()?.convert(file_id)?,
+            name: name_or_missing(self.name(), file_id),
             directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
@@ -528,10 +601,10 @@ impl Convert for cst::VariableDefinition {
         } else {
             None
         };
-        let ty = &self.ty()?;
+
         Some(Self::Target {
-            name: self.variable()?.name()?.convert(file_id)?,
-            ty: with_location(file_id, ty.syntax(), ty.convert(file_id)?),
+            name: name_or_missing(self.variable().and_then(|var| var.name()), file_id),
+            ty: type_node_or_missing(self.ty(), file_id),
             default_value,
             directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
@@ -547,8 +620,13 @@ impl Convert for cst::Type {
         use ast::Type as A;
         use cst::Type as C;
         match self {
-            C::NamedType(name) => Some(A::Named(name.name()?.convert(file_id)?)),
-            C::ListType(inner) => Some(A::List(Box::new(inner.ty()?.convert(file_id)?))),
+            C::NamedType(name) => Some(A::Named(name_or_missing(name.name(), file_id))),
+            C::ListType(inner) => Some(A::List(Box::new(
+                inner
+                    .ty()
+                    .and_then(|ty| ty.convert(file_id))
+                    .unwrap_or(A::Named(crate::Name::MISSING)),
+            ))),
             C::NonNullType(inner) => {
                 if let Some(named) = inner.named_type() {
                     Some(A::NonNullNamed(named.name()?.convert(file_id)?))
@@ -568,11 +646,14 @@ impl Convert for cst::FieldDefinition {
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
             description: self.description().convert(file_id)?,
-            name: self.name()?.convert(file_id)?,
+            name: name_or_missing(self.name(), file_id),
             arguments: collect_opt(file_id, self.arguments_definition(), |x| {
                 x.input_value_definitions()
             }),
-            ty: self.ty()?.convert(file_id)?,
+            ty: self
+                .ty()
+                .and_then(|ty| ty.convert(file_id))
+                .unwrap_or(ast::Type::Named(crate::Name::MISSING)),
             directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
@@ -605,11 +686,11 @@ impl Convert for cst::InputValueDefinition {
         } else {
             None
         };
-        let ty = &self.ty()?;
+
         Some(Self::Target {
             description: self.description().convert(file_id)?,
-            name: self.name()?.convert(file_id)?,
-            ty: with_location(file_id, ty.syntax(), ty.convert(file_id)?),
+            name: name_or_missing(self.name(), file_id),
+            ty: type_node_or_missing(self.ty(), file_id),
             default_value,
             directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
@@ -675,7 +756,7 @@ impl Convert for cst::Field {
     fn convert(&self, file_id: FileId) -> Option<Self::Target> {
         Some(Self::Target {
             alias: self.alias().convert(file_id)?,
-            name: self.name()?.convert(file_id)?,
+            name: name_or_missing(self.name(), file_id),
             arguments: collect_opt(file_id, self.arguments(), |x| x.arguments()),
             directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
@@ -708,7 +789,7 @@ impl Convert for cst::InlineFragment {
             directives: ast::DirectiveList(collect_opt(file_id, self.directives(), |x| {
                 x.directives()
             })),
-            selection_set: self.selection_set().convert(file_id)??,
+            selection_set: self.selection_set().convert(file_id)?.unwrap_or_default(),
         })
     }
 }
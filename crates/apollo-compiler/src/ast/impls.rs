@@ -95,6 +95,20 @@ impl Document {
         errors.into_valid_result(document)
     }
 
+    /// Traverse type system definitions and extensions depth-first,
+    /// calling [`schema::Visitor`][crate::schema::Visitor] methods for each node.
+    /// Executable definitions are skipped.
+    pub fn walk<'doc>(&'doc self, visitor: &mut impl crate::schema::Visitor<'doc>) {
+        crate::schema::visit::walk_document(self, visitor)
+    }
+
+    /// Traverse type system definitions and extensions depth-first,
+    /// calling [`schema::VisitorMut`][crate::schema::VisitorMut] methods for each node.
+    /// Executable definitions are skipped.
+    pub fn walk_mut(&mut self, visitor: &mut impl crate::schema::VisitorMut) {
+        crate::schema::visit::walk_document_mut(self, visitor)
+    }
+
     pub(crate) fn to_executable_inner(
         &self,
         schema: &Valid<Schema>,
@@ -138,6 +152,13 @@ impl Document {
     }
 
     serialize_method!();
+
+    /// Returns a builder that has chaining methods for setting formatting configuration,
+    /// and implements the [`Display`][std::fmt::Display] and [`ToString`] traits
+    /// by writing GraphQL syntax in a consistent style.
+    pub fn format(&self) -> Format<'_> {
+        Format::new(self)
+    }
 }
 
 /// `source` is ignored for comparison
@@ -241,6 +262,29 @@ impl Definition {
         }
     }
 
+    /// Returns `#` comments attached to this definition, if any.
+    pub fn comments(&self) -> Option<&Comments> {
+        match self {
+            Self::OperationDefinition(def) => def.comments(),
+            Self::FragmentDefinition(def) => def.comments(),
+            Self::DirectiveDefinition(def) => def.comments(),
+            Self::SchemaDefinition(def) => def.comments(),
+            Self::ScalarTypeDefinition(def) => def.comments(),
+            Self::ObjectTypeDefinition(def) => def.comments(),
+            Self::InterfaceTypeDefinition(def) => def.comments(),
+            Self::UnionTypeDefinition(def) => def.comments(),
+            Self::EnumTypeDefinition(def) => def.comments(),
+            Self::InputObjectTypeDefinition(def) => def.comments(),
+            Self::SchemaExtension(def) => def.comments(),
+            Self::ScalarTypeExtension(def) => def.comments(),
+            Self::ObjectTypeExtension(def) => def.comments(),
+            Self::InterfaceTypeExtension(def) => def.comments(),
+            Self::UnionTypeExtension(def) => def.comments(),
+            Self::EnumTypeExtension(def) => def.comments(),
+            Self::InputObjectTypeExtension(def) => def.comments(),
+        }
+    }
+
     /// Return the name of this type definition or extension.
     ///
     /// Operations may be anonymous, and schema definitions and extensions never have a name.
@@ -988,6 +1032,15 @@ impl Selection {
         }
     }
 
+    /// Returns `#` comments attached to this selection, if any.
+    pub fn comments(&self) -> Option<&Comments> {
+        match self {
+            Self::Field(field) => field.comments(),
+            Self::FragmentSpread(fragment) => fragment.comments(),
+            Self::InlineFragment(fragment) => fragment.comments(),
+        }
+    }
+
     pub fn as_field(&self) -> Option<&Node<Field>> {
         if let Self::Field(x) = self {
             Some(x)
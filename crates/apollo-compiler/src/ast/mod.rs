@@ -5,6 +5,7 @@
 //! This AST aims to faithfully represent documents
 //! that conform to the GraphQL [syntactic grammar],
 //! except that [ignored tokens] such as whitespace are not preserved.
+//! `#` comments are the exception: they are attached as [`Comments`] to nearby nodes.
 //! These documents may or may not be [valid].
 //!
 //! Parsing an input that does not conform to the grammar results in parse errors
@@ -32,6 +33,8 @@
 //! [`serialize`][Document::serialize] methods return a builder
 //! that has chaining methods for setting serialization configuration,
 //! and also implements `Display` and `ToString`.
+//! [`Document::format`] is a formatter built on serialization,
+//! with line width and sorting options.
 //!
 //! ## Example
 //!
@@ -55,10 +58,12 @@ use crate::parser::SourceMap;
 use crate::Name;
 use crate::Node;
 
+mod format;
 pub(crate) mod from_cst;
 pub(crate) mod impls;
 pub(crate) mod serialize;
 
+pub use self::format::Format;
 pub use self::serialize::Serialize;
 
 /// AST for a GraphQL [_Document_](https://spec.graphql.org/draft/#Document)
@@ -85,6 +90,18 @@ const _: () = {
     assert_sync::<Document>();
 };
 
+/// [_Comments_](https://spec.graphql.org/draft/#sec-Comments) attached to a [`Node`],
+/// accessed with [`Node::comments`] and [`Node::comments_mut`].
+///
+/// Comment text is stored without its `#` prefix or line terminator.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Comments {
+    /// Comments on their own lines immediately before the node, in source order
+    pub leading: Vec<String>,
+    /// A comment after the end of the node, on the same line
+    pub trailing: Option<String>,
+}
+
 /// A [_NamedType_](https://spec.graphql.org/draft/#NamedType)
 /// references by name a GraphQL type defined elsewhere.
 pub type NamedType = Name;
@@ -16,6 +16,7 @@ pub struct Serialize<'a, T> {
 pub(crate) struct Config<'a> {
     indent_prefix: Option<&'a str>,
     initial_indent_level: usize,
+    max_line_width: Option<usize>,
 }
 
 pub(crate) struct State<'config, 'fmt, 'fmt2> {
@@ -24,6 +25,8 @@ pub(crate) struct State<'config, 'fmt, 'fmt2> {
     output: &'fmt mut fmt::Formatter<'fmt2>,
     /// Have we not written anything yet?
     output_empty: bool,
+    /// Number of characters written since the last line break
+    column: usize,
 }
 
 impl<'a, T> Serialize<'a, T> {
@@ -46,6 +49,17 @@ impl<'a, T> Serialize<'a, T> {
         self.config.initial_indent_level = initial_indent_level;
         self
     }
+
+    /// Break lists of arguments, argument definitions, and variable definitions
+    /// into one item per line when they would not fit on a line of `width` characters.
+    /// Also use `width` to decide when a description needs a multi-line block string.
+    ///
+    /// By default these lists stay on a single line regardless of their length.
+    /// Ignored when indentation and line breaks are disabled.
+    pub fn max_line_width(mut self, width: usize) -> Self {
+        self.config.max_line_width = Some(width);
+        self
+    }
 }
 
 impl Default for Config<'_> {
@@ -53,16 +67,17 @@ impl Default for Config<'_> {
         Self {
             indent_prefix: Some("  "),
             initial_indent_level: 0,
+            max_line_width: None,
         }
     }
 }
 
 macro_rules! display {
     ($state: expr, $e: expr) => {
-        fmt::Display::fmt(&$e, $state.output)
+        display!($state, "{}", $e)
     };
     ($state: expr, $($tt: tt)+) => {
-        display!($state, format_args!($($tt)+))
+        fmt::Write::write_fmt($state, format_args!($($tt)+))
     };
 
 }
@@ -70,9 +85,64 @@ macro_rules! display {
 impl State<'_, '_, '_> {
     pub(crate) fn write(&mut self, str: &str) -> fmt::Result {
         self.output_empty = false;
+        if let Some((_, last_line)) = str.rsplit_once('\n') {
+            self.column = last_line.chars().count()
+        } else {
+            self.column += str.chars().count()
+        }
         self.output.write_str(str)
     }
 
+    /// Returns whether the single-line output of `serialize` fits on the current line
+    /// without exceeding the configured maximum line width.
+    /// Always returns true if there is no maximum or line breaks are disabled.
+    pub(crate) fn fits_on_line(&self, serialize: impl Fn(&mut State) -> fmt::Result) -> bool {
+        let Some(max_line_width) = self.config.max_line_width else {
+            return true;
+        };
+        if !self.newlines_enabled() {
+            return true;
+        }
+
+        struct Measure<'config, F> {
+            config: Config<'config>,
+            serialize: F,
+        }
+
+        impl<F: Fn(&mut State) -> fmt::Result> Display for Measure<'_, F> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let mut state = State {
+                    config: self.config.clone(),
+                    indent_level: 0,
+                    output: f,
+                    output_empty: false,
+                    column: 0,
+                };
+                (self.serialize)(&mut state)
+            }
+        }
+
+        /// Errors as soon as the output exceeds the remaining width
+        struct Remaining(usize);
+
+        impl fmt::Write for Remaining {
+            fn write_str(&mut self, str: &str) -> fmt::Result {
+                self.0 = self.0.checked_sub(str.chars().count()).ok_or(fmt::Error)?;
+                Ok(())
+            }
+        }
+
+        let measure = Measure {
+            config: Config {
+                indent_prefix: None,
+                ..self.config.clone()
+            },
+            serialize,
+        };
+        let remaining = max_line_width.saturating_sub(self.column);
+        fmt::write(&mut Remaining(remaining), format_args!("{measure}")).is_ok()
+    }
+
     pub(crate) fn indent(&mut self) -> fmt::Result {
         self.indent_level += 1;
         self.new_line_common(false)
@@ -134,6 +204,12 @@ impl State<'_, '_, '_> {
     }
 }
 
+impl fmt::Write for State<'_, '_, '_> {
+    fn write_str(&mut self, str: &str) -> fmt::Result {
+        self.write(str)
+    }
+}
+
 impl Document {
     pub(crate) fn serialize_impl(&self, state: &mut State) -> fmt::Result {
         top_level(state, &self.definitions, |state, def| {
@@ -168,6 +244,12 @@ pub(crate) fn top_level<T>(
 
 impl Definition {
     pub(crate) fn serialize_impl(&self, state: &mut State) -> fmt::Result {
+        serialize_with_comments(state, self.comments(), |state| {
+            self.serialize_without_comments(state)
+        })
+    }
+
+    fn serialize_without_comments(&self, state: &mut State) -> fmt::Result {
         match self {
             Definition::OperationDefinition(def) => def.serialize_impl(state),
             Definition::FragmentDefinition(def) => def.serialize_impl(state),
@@ -213,12 +295,17 @@ impl OperationDefinition {
                 state.write(" ")?;
                 state.write(name)?;
             }
+            let rest = |state: &mut State| {
+                directives.serialize_impl(state)?;
+                state.write(" {")
+            };
             if !variables.is_empty() {
-                state.on_single_line(|state| {
-                    comma_separated(state, "(", ")", variables, |state, var| {
-                        var.serialize_impl(state)
-                    })
-                })?
+                wrapped_list(
+                    state,
+                    variables,
+                    |state, var| var.serialize_impl(state),
+                    rest,
+                )?
             }
             directives.serialize_impl(state)?;
             state.write(" ")?;
@@ -254,26 +341,30 @@ impl DirectiveDefinition {
         serialize_description(state, description)?;
         state.write("directive @")?;
         state.write(name)?;
-        serialize_arguments_definition(state, arguments)?;
-
-        if *repeatable {
-            state.write(" repeatable")?;
-        }
-        if let Some((first, rest)) = locations.split_first() {
-            state.write(" on ")?;
-            state.write(first.name())?;
-            for location in rest {
-                state.write(" | ")?;
-                state.write(location.name())?;
+        let rest = |state: &mut State| {
+            if *repeatable {
+                state.write(" repeatable")?;
             }
-        }
-        Ok(())
+            if let Some((first, rest)) = locations.split_first() {
+                state.write(" on ")?;
+                state.write(first.name())?;
+                for location in rest {
+                    state.write(" | ")?;
+                    state.write(location.name())?;
+                }
+            }
+            Ok(())
+        };
+        serialize_arguments_definition(state, arguments, rest)?;
+        rest(state)
     }
 }
 
+/// `rest` is what follows on the same line, used to decide whether to wrap
 fn serialize_arguments_definition(
     state: &mut State,
     arguments: &[Node<InputValueDefinition>],
+    rest: impl Fn(&mut State) -> fmt::Result,
 ) -> fmt::Result {
     if !arguments.is_empty() {
         let serialize_arguments = |state: &mut State| {
@@ -287,7 +378,12 @@ fn serialize_arguments_definition(
         {
             serialize_arguments(state)?
         } else {
-            state.on_single_line(serialize_arguments)?
+            wrapped_list(
+                state,
+                arguments,
+                |state, arg| arg.serialize_impl(state),
+                rest,
+            )?
         }
     }
     Ok(())
@@ -305,8 +401,10 @@ impl SchemaDefinition {
         directives.serialize_impl(state)?;
         state.write(" ")?;
         curly_brackets_space_separated(state, root_operations, |state, op| {
-            let (operation_type, operation_name) = &**op;
-            display!(state, "{}: {}", operation_type, operation_name)
+            serialize_with_comments(state, op.comments(), |state| {
+                let (operation_type, operation_name) = &**op;
+                display!(state, "{}: {}", operation_type, operation_name)
+            })
         })
     }
 }
@@ -360,7 +458,9 @@ fn serialize_object_type_like(
 
     if !fields.is_empty() {
         state.write(" ")?;
-        curly_brackets_space_separated(state, fields, |state, field| field.serialize_impl(state))?;
+        curly_brackets_space_separated(state, fields, |state, field| {
+            serialize_with_comments(state, field.comments(), |state| field.serialize_impl(state))
+        })?;
     }
     Ok(())
 }
@@ -428,7 +528,9 @@ impl EnumTypeDefinition {
         if !values.is_empty() {
             state.write(" ")?;
             curly_brackets_space_separated(state, values, |state, value| {
-                value.serialize_impl(state)
+                serialize_with_comments(state, value.comments(), |state| {
+                    value.serialize_impl(state)
+                })
             })?;
         }
         Ok(())
@@ -449,7 +551,9 @@ impl InputObjectTypeDefinition {
         directives.serialize_impl(state)?;
         if !fields.is_empty() {
             state.write(" ")?;
-            curly_brackets_space_separated(state, fields, |state, f| f.serialize_impl(state))?;
+            curly_brackets_space_separated(state, fields, |state, f| {
+                serialize_with_comments(state, f.comments(), |state| f.serialize_impl(state))
+            })?;
         }
         Ok(())
     }
@@ -466,8 +570,10 @@ impl SchemaExtension {
         if !root_operations.is_empty() {
             state.write(" ")?;
             curly_brackets_space_separated(state, root_operations, |state, op| {
-                let (operation_type, operation_name) = &**op;
-                display!(state, "{}: {}", operation_type, operation_name)
+                serialize_with_comments(state, op.comments(), |state| {
+                    let (operation_type, operation_name) = &**op;
+                    display!(state, "{}: {}", operation_type, operation_name)
+                })
             })?;
         }
         Ok(())
@@ -534,7 +640,9 @@ impl EnumTypeExtension {
         if !values.is_empty() {
             state.write(" ")?;
             curly_brackets_space_separated(state, values, |state, value| {
-                value.serialize_impl(state)
+                serialize_with_comments(state, value.comments(), |state| {
+                    value.serialize_impl(state)
+                })
             })?;
         }
         Ok(())
@@ -553,7 +661,9 @@ impl InputObjectTypeExtension {
         directives.serialize_impl(state)?;
         if !fields.is_empty() {
             state.write(" ")?;
-            curly_brackets_space_separated(state, fields, |state, f| f.serialize_impl(state))?;
+            curly_brackets_space_separated(state, fields, |state, f| {
+                serialize_with_comments(state, f.comments(), |state| f.serialize_impl(state))
+            })?;
         }
         Ok(())
     }
@@ -574,7 +684,7 @@ impl Directive {
         let Self { name, arguments } = self;
         state.write("@")?;
         state.write(name)?;
-        serialize_arguments(state, arguments)
+        serialize_arguments(state, arguments, |_| Ok(()))
     }
 }
 
@@ -609,10 +719,13 @@ impl FieldDefinition {
         } = self;
         serialize_description(state, description)?;
         state.write(name)?;
-        serialize_arguments_definition(state, arguments)?;
-        state.write(": ")?;
-        display!(state, ty)?;
-        directives.serialize_impl(state)
+        let rest = |state: &mut State| {
+            state.write(": ")?;
+            display!(state, ty)?;
+            directives.serialize_impl(state)
+        };
+        serialize_arguments_definition(state, arguments, rest)?;
+        rest(state)
     }
 }
 
@@ -652,11 +765,11 @@ impl EnumValueDefinition {
 
 impl Selection {
     pub(crate) fn serialize_impl(&self, state: &mut State) -> fmt::Result {
-        match self {
+        serialize_with_comments(state, self.comments(), |state| match self {
             Selection::Field(x) => x.serialize_impl(state),
             Selection::FragmentSpread(x) => x.serialize_impl(state),
             Selection::InlineFragment(x) => x.serialize_impl(state),
-        }
+        })
     }
 }
 
@@ -674,7 +787,13 @@ impl Field {
             state.write(": ")?;
         }
         state.write(name)?;
-        serialize_arguments(state, arguments)?;
+        serialize_arguments(state, arguments, |state| {
+            directives.serialize_impl(state)?;
+            if !selection_set.is_empty() {
+                state.write(" {")?;
+            }
+            Ok(())
+        })?;
         directives.serialize_impl(state)?;
         if !selection_set.is_empty() {
             state.write(" ")?;
@@ -753,17 +872,46 @@ impl Argument {
     }
 }
 
-fn serialize_arguments(state: &mut State, arguments: &[Node<Argument>]) -> fmt::Result {
+/// `rest` is what follows on the same line, used to decide whether to wrap
+fn serialize_arguments(
+    state: &mut State,
+    arguments: &[Node<Argument>],
+    rest: impl Fn(&mut State) -> fmt::Result,
+) -> fmt::Result {
     if !arguments.is_empty() {
-        state.on_single_line(|state| {
-            comma_separated(state, "(", ")", arguments, |state, argument| {
-                argument.serialize_impl(state)
-            })
-        })?
+        wrapped_list(
+            state,
+            arguments,
+            |state, argument| argument.serialize_impl(state),
+            rest,
+        )?
     }
     Ok(())
 }
 
+/// Parenthesized list on a single line if it fits within the maximum line width
+/// together with `rest` of the line, or else with each item on its own line.
+fn wrapped_list<T>(
+    state: &mut State,
+    values: &[T],
+    serialize_one: impl Fn(&mut State, &T) -> fmt::Result,
+    rest: impl Fn(&mut State) -> fmt::Result,
+) -> fmt::Result {
+    let single_line = |state: &mut State| {
+        state.on_single_line(|state| comma_separated(state, "(", ")", values, &serialize_one))
+    };
+    if state.fits_on_line(|state| {
+        single_line(state)?;
+        rest(state)
+    }) {
+        single_line(state)
+    } else {
+        comma_separated(state, "(", ")", values, |state, value| {
+            state.on_single_line(|state| serialize_one(state, value))
+        })
+    }
+}
+
 /// Example output: `[a, b, c]` or
 ///
 /// ```text
@@ -871,8 +1019,13 @@ fn serialize_block_string(state: &mut State, contains_newline: bool, str: &str)
         state.write(line)
     }
 
-    let multi_line =
-        contains_newline || str.len() > 70 || str.ends_with('"') || str.ends_with('\\');
+    let too_long = if let Some(max_line_width) = state.config.max_line_width {
+        // Account for the opening and closing triple quotes
+        state.column + str.chars().count() + 6 > max_line_width
+    } else {
+        str.len() > 70
+    };
+    let multi_line = contains_newline || too_long || str.ends_with('"') || str.ends_with('\\');
 
     state.write(TRIPLE_QUOTE)?;
     if !multi_line {
@@ -943,6 +1096,30 @@ fn can_be_block_string(value: &str) -> bool {
     common_indent == 0
 }
 
+/// Emit leading comments on their own lines before the output of `serialize_one`,
+/// and a trailing comment after it.
+/// Comments are skipped when line breaks are disabled, as they would comment out what follows.
+fn serialize_with_comments(
+    state: &mut State,
+    comments: Option<&Comments>,
+    serialize_one: impl FnOnce(&mut State) -> fmt::Result,
+) -> fmt::Result {
+    let Some(comments) = comments.filter(|_| state.newlines_enabled()) else {
+        return serialize_one(state);
+    };
+    for comment in &comments.leading {
+        state.write("#")?;
+        state.write(comment)?;
+        state.require_new_line()?;
+    }
+    serialize_one(state)?;
+    if let Some(comment) = &comments.trailing {
+        state.write(" #")?;
+        state.write(comment)?;
+    }
+    Ok(())
+}
+
 fn serialize_description(state: &mut State, description: &Option<Node<str>>) -> fmt::Result {
     if let Some(description) = description {
         let is_description = true;
@@ -993,6 +1170,7 @@ macro_rules! impl_display {
                         indent_level: self.config.initial_indent_level,
                         output: f,
                         output_empty: true,
+                        column: 0,
                     };
                     // Indent the first line.
                     // Subsequent lines will be indented when writing a line break.
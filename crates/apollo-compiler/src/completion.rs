@@ -0,0 +1,547 @@
+//! Completion suggestions for executable documents being edited
+//!
+//! [`complete`] parses a possibly incomplete or invalid document,
+//! finds what can be typed at a given cursor position,
+//! and suggests candidates from the schema:
+//!
+//! * Fields of the parent type, in a selection set or after an alias
+//! * Arguments of the field or directive, after `(`
+//! * Enum values of the expected input type, in an argument, input object field,
+//!   list, or variable default value
+//! * Input object fields, after `{` in a value
+//! * Variables defined by the enclosing operation, after `$`
+//! * Fragments whose type condition can apply to the parent type, after `...`
+//! * Directives valid at the location of the enclosing element, after `@`
+//!
+//! Candidates are not filtered by a partially typed name at the cursor:
+//! editors usually do their own (fuzzy) matching.
+//! Arguments, input object fields, and non-repeatable directives
+//! that are already present are not suggested again.
+//!
+//! ```rust
+//! use apollo_compiler::completion::complete;
+//! use apollo_compiler::completion::CompletionKind;
+//! use apollo_compiler::Schema;
+//!
+//! let schema = Schema::parse_and_validate(
+//!     "type Query { user(id: ID!): User }  type User { name: String, email: String }",
+//!     "schema.graphql",
+//! )
+//! .unwrap();
+//! let source = "{ user(id: 1) { na";
+//! let items = complete(&schema, source, source.len());
+//! let labels: Vec<_> = items.iter().map(|item| item.label.as_str()).collect();
+//! assert_eq!(labels, ["name", "email", "__typename"]);
+//! assert_eq!(items[0].kind, CompletionKind::Field);
+//! assert_eq!(items[0].detail.as_deref(), Some("String"));
+//! ```
+
+use crate::ast::DirectiveLocation;
+use crate::ast::OperationType;
+use crate::collections::HashSet;
+use crate::schema::ExtendedType;
+use crate::schema::InputValueDefinition;
+use crate::validation::fragment::get_possible_types;
+use crate::validation::Valid;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+use apollo_parser::cst;
+use apollo_parser::cst::CstNode;
+use apollo_parser::SyntaxKind;
+use apollo_parser::SyntaxNode;
+use apollo_parser::SyntaxToken;
+use rowan::TextSize;
+
+/// A candidate returned by [`complete`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletionItem {
+    /// The text to insert: a name without `$`, `@`, or `...` prefix
+    pub label: Name,
+    pub kind: CompletionKind,
+    /// The type of a field, argument, input field, or variable,
+    /// or the type condition of a fragment
+    pub detail: Option<String>,
+    /// The description of the schema element
+    pub description: Option<Node<str>>,
+    /// Whether the schema element has a `@deprecated` directive
+    pub deprecated: bool,
+}
+
+/// What kind of element a [`CompletionItem`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CompletionKind {
+    Field,
+    Argument,
+    EnumValue,
+    InputField,
+    Variable,
+    Fragment,
+    Directive,
+}
+
+/// Returns completion candidates for the cursor at byte `offset` in `source_text`,
+/// an executable document that may be incomplete or have syntax errors.
+///
+/// Returns an empty list if nothing from the schema or document can be typed at that position,
+/// or if `offset` is not a character boundary of `source_text`.
+pub fn complete(schema: &Valid<Schema>, source_text: &str, offset: usize) -> Vec<CompletionItem> {
+    if !source_text.is_char_boundary(offset) {
+        return Vec::new();
+    }
+    let Ok(offset) = u32::try_from(offset) else {
+        return Vec::new();
+    };
+    let tree = apollo_parser::Parser::new(source_text).parse();
+    let root = tree.document().syntax().clone();
+    let mut completer = Completer {
+        schema,
+        offset: TextSize::from(offset),
+        items: Vec::new(),
+    };
+    if let Some(anchor) = completer.anchor(&root) {
+        completer.complete_after(&anchor);
+    }
+    completer.items
+}
+
+struct Completer<'a> {
+    schema: &'a Schema,
+    offset: TextSize,
+    items: Vec<CompletionItem>,
+}
+
+impl<'a> Completer<'a> {
+    /// Returns the last significant token before the cursor,
+    /// skipping a name being typed at the cursor.
+    fn anchor(&self, root: &SyntaxNode) -> Option<SyntaxToken> {
+        if self.offset > root.text_range().end() {
+            // Within text that failed to lex, such as an unterminated string
+            return None;
+        }
+        let token = root.token_at_offset(self.offset).left_biased()?;
+        let typing_name = token
+            .parent()
+            .is_some_and(|parent| parent.kind() == SyntaxKind::NAME)
+            && token.text_range().start() < self.offset;
+        if typing_name || is_trivia(&token) {
+            std::iter::successors(token.prev_token(), |token| token.prev_token())
+                .find(|token| !is_trivia(token))
+        } else {
+            Some(token)
+        }
+    }
+
+    fn complete_after(&mut self, anchor: &SyntaxToken) {
+        let Some(parent) = anchor.parent() else {
+            return;
+        };
+        match anchor.kind() {
+            SyntaxKind::AT => return self.directives(&parent),
+            SyntaxKind::SPREAD => return self.fragments(&parent),
+            SyntaxKind::DOLLAR => return self.variables(&parent),
+            SyntaxKind::COLON => match parent.kind() {
+                SyntaxKind::ARGUMENT | SyntaxKind::OBJECT_FIELD => {
+                    return self.values(&parent);
+                }
+                SyntaxKind::ALIAS => {
+                    if let Some(selection_set) = enclosing_selection_set(&parent) {
+                        self.fields(&selection_set)
+                    }
+                    return;
+                }
+                _ => {}
+            },
+            SyntaxKind::EQ if parent.kind() == SyntaxKind::DEFAULT_VALUE => {
+                return self.values(&parent);
+            }
+            _ => {}
+        }
+        // After a complete element, suggest what comes next in the innermost open list
+        for node in anchor.parent_ancestors() {
+            let closed = node == parent
+                && matches!(
+                    anchor.kind(),
+                    SyntaxKind::R_CURLY | SyntaxKind::R_PAREN | SyntaxKind::R_BRACK
+                );
+            if closed {
+                continue;
+            }
+            match node.kind() {
+                SyntaxKind::SELECTION_SET => return self.fields(&node),
+                SyntaxKind::ARGUMENTS => return self.arguments(&node),
+                SyntaxKind::OBJECT_VALUE => return self.input_fields(&node),
+                SyntaxKind::LIST_VALUE => return self.values(&node),
+                SyntaxKind::VARIABLE_DEFINITIONS | SyntaxKind::DOCUMENT => return,
+                _ => {}
+            }
+        }
+    }
+
+    fn fields(&mut self, selection_set: &SyntaxNode) {
+        let Some(parent_type) = self.parent_type(selection_set) else {
+            return;
+        };
+        let fields = match self.schema.types.get(&parent_type) {
+            Some(ExtendedType::Object(ty)) => &ty.fields,
+            Some(ExtendedType::Interface(ty)) => &ty.fields,
+            Some(ExtendedType::Union(_)) => {
+                return self.typename(&parent_type);
+            }
+            _ => return,
+        };
+        for (name, field) in fields {
+            self.items.push(CompletionItem {
+                label: name.clone(),
+                kind: CompletionKind::Field,
+                detail: Some(field.ty.to_string()),
+                description: field.description.clone(),
+                deprecated: field.directives.has("deprecated"),
+            })
+        }
+        self.typename(&parent_type)
+    }
+
+    fn typename(&mut self, parent_type: &str) {
+        if let Ok(field) = self.schema.type_field(parent_type, "__typename") {
+            self.items.push(CompletionItem {
+                label: field.name.clone(),
+                kind: CompletionKind::Field,
+                detail: Some(field.ty.to_string()),
+                description: field.description.clone(),
+                deprecated: false,
+            })
+        }
+    }
+
+    fn arguments(&mut self, arguments: &SyntaxNode) {
+        let Some(definitions) = arguments
+            .parent()
+            .and_then(|owner| self.argument_definitions(&owner))
+        else {
+            return;
+        };
+        let existing = self.existing_names(
+            arguments
+                .children()
+                .filter_map(cst::Argument::cast)
+                .map(|argument| (argument.syntax().clone(), argument.name())),
+        );
+        for definition in definitions {
+            if !existing.contains(definition.name.as_str()) {
+                self.push_input_value(definition, CompletionKind::Argument)
+            }
+        }
+    }
+
+    fn input_fields(&mut self, object_value: &SyntaxNode) {
+        let Some(ty) = self.input_type(object_value) else {
+            return;
+        };
+        let Some(input_object) = self.schema.get_input_object(&ty) else {
+            return;
+        };
+        let existing = self.existing_names(
+            object_value
+                .children()
+                .filter_map(cst::ObjectField::cast)
+                .map(|field| (field.syntax().clone(), field.name())),
+        );
+        for definition in input_object.fields.values() {
+            if !existing.contains(definition.name.as_str()) {
+                self.push_input_value(definition, CompletionKind::InputField)
+            }
+        }
+    }
+
+    /// Names of arguments or input fields already given,
+    /// except the one being typed at the cursor
+    fn existing_names(
+        &self,
+        nodes: impl Iterator<Item = (SyntaxNode, Option<cst::Name>)>,
+    ) -> HashSet<String> {
+        nodes
+            .filter(|(node, _)| !node.text_range().contains_inclusive(self.offset))
+            .filter_map(|(_, name)| Some(name?.text().to_string()))
+            .collect()
+    }
+
+    fn push_input_value(&mut self, definition: &InputValueDefinition, kind: CompletionKind) {
+        self.items.push(CompletionItem {
+            label: definition.name.clone(),
+            kind,
+            detail: Some(definition.ty.to_string()),
+            description: definition.description.clone(),
+            deprecated: definition.directives.has("deprecated"),
+        })
+    }
+
+    /// Enum values of the type expected by `node`
+    fn values(&mut self, node: &SyntaxNode) {
+        let Some(ty) = self.input_type(node) else {
+            return;
+        };
+        let Some(enum_) = self.schema.get_enum(&ty) else {
+            return;
+        };
+        for (name, value) in &enum_.values {
+            self.items.push(CompletionItem {
+                label: name.clone(),
+                kind: CompletionKind::EnumValue,
+                detail: None,
+                description: value.description.clone(),
+                deprecated: value.directives.has("deprecated"),
+            })
+        }
+    }
+
+    fn variables(&mut self, variable: &SyntaxNode) {
+        if variable
+            .parent()
+            .is_some_and(|parent| parent.kind() == SyntaxKind::VARIABLE_DEFINITION)
+        {
+            // Defining a new variable
+            return;
+        }
+        let Some(operation) = variable
+            .ancestors()
+            .find_map(cst::OperationDefinition::cast)
+        else {
+            return;
+        };
+        let Some(definitions) = operation.variable_definitions() else {
+            return;
+        };
+        for definition in definitions.variable_definitions() {
+            let Some(name) = definition.variable().and_then(|var| to_name(var.name())) else {
+                continue;
+            };
+            self.items.push(CompletionItem {
+                label: name,
+                kind: CompletionKind::Variable,
+                detail: definition
+                    .ty()
+                    .map(|ty| ty.source_string().trim().to_owned()),
+                description: None,
+                deprecated: false,
+            })
+        }
+    }
+
+    fn fragments(&mut self, spread_parent: &SyntaxNode) {
+        let Some(selection_set) = spread_parent
+            .ancestors()
+            .find(|node| node.kind() == SyntaxKind::SELECTION_SET)
+        else {
+            return;
+        };
+        let Some(parent_type) = self
+            .parent_type(&selection_set)
+            .and_then(|name| self.schema.types.get(&name))
+        else {
+            return;
+        };
+        let implementers_map = self.schema.implementers_map();
+        let possible_types = get_possible_types(parent_type, &implementers_map);
+        let enclosing_fragment = spread_parent
+            .ancestors()
+            .find_map(cst::FragmentDefinition::cast)
+            .and_then(|fragment| fragment_name(&fragment));
+        let Some(document) = spread_parent.ancestors().last() else {
+            return;
+        };
+        for fragment in document
+            .descendants()
+            .filter_map(cst::FragmentDefinition::cast)
+        {
+            let Some(name) = fragment_name(&fragment) else {
+                continue;
+            };
+            if enclosing_fragment.as_ref() == Some(&name) {
+                // Would be a cycle
+                continue;
+            }
+            let Some(type_condition) = to_name(
+                fragment
+                    .type_condition()
+                    .and_then(|condition| condition.named_type())
+                    .and_then(|ty| ty.name()),
+            ) else {
+                continue;
+            };
+            let Some(condition_type) = self.schema.types.get(&type_condition) else {
+                continue;
+            };
+            let applicable = get_possible_types(condition_type, &implementers_map)
+                .iter()
+                .any(|ty| possible_types.contains(ty));
+            if applicable {
+                self.items.push(CompletionItem {
+                    label: name,
+                    kind: CompletionKind::Fragment,
+                    detail: Some(type_condition.to_string()),
+                    description: None,
+                    deprecated: false,
+                })
+            }
+        }
+    }
+
+    fn directives(&mut self, directive: &SyntaxNode) {
+        let Some(directives) = directive.parent() else {
+            return;
+        };
+        let Some(location) = directives
+            .parent()
+            .and_then(|owner| directive_location(&owner))
+        else {
+            return;
+        };
+        let existing: HashSet<String> = directives
+            .children()
+            .filter(|node| node != directive)
+            .filter_map(cst::Directive::cast)
+            .filter_map(|directive| Some(directive.name()?.text().to_string()))
+            .collect();
+        for (name, definition) in &self.schema.directive_definitions {
+            if !definition.locations.contains(&location)
+                || (!definition.repeatable && existing.contains(name.as_str()))
+            {
+                continue;
+            }
+            self.items.push(CompletionItem {
+                label: name.clone(),
+                kind: CompletionKind::Directive,
+                detail: None,
+                description: definition.description.clone(),
+                deprecated: false,
+            })
+        }
+    }
+
+    /// The type whose fields are selected in `selection_set`
+    fn parent_type(&self, selection_set: &SyntaxNode) -> Option<Name> {
+        let parent = selection_set.parent()?;
+        match parent.kind() {
+            SyntaxKind::OPERATION_DEFINITION => {
+                let operation = cst::OperationDefinition::cast(parent)?;
+                let operation_type = match operation.operation_type() {
+                    Some(ty) if ty.mutation_token().is_some() => OperationType::Mutation,
+                    Some(ty) if ty.subscription_token().is_some() => OperationType::Subscription,
+                    _ => OperationType::Query,
+                };
+                self.schema.root_operation(operation_type).cloned()
+            }
+            SyntaxKind::FRAGMENT_DEFINITION => {
+                type_condition(cst::FragmentDefinition::cast(parent)?.type_condition())
+            }
+            SyntaxKind::INLINE_FRAGMENT => {
+                type_condition(cst::InlineFragment::cast(parent.clone())?.type_condition())
+                    .or_else(|| self.parent_type(&enclosing_selection_set(&parent)?))
+            }
+            SyntaxKind::FIELD => {
+                let outer_type = self.parent_type(&enclosing_selection_set(&parent)?)?;
+                let name = cst::Field::cast(parent)?.name()?.text();
+                let field = self.schema.type_field(&outer_type, &name).ok()?;
+                Some(field.ty.inner_named_type().clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Argument definitions of the field or directive `owner`
+    fn argument_definitions(&self, owner: &SyntaxNode) -> Option<&'a [Node<InputValueDefinition>]> {
+        match owner.kind() {
+            SyntaxKind::FIELD => {
+                let outer_type = self.parent_type(&enclosing_selection_set(owner)?)?;
+                let name = cst::Field::cast(owner.clone())?.name()?.text();
+                let field = self.schema.type_field(&outer_type, &name).ok()?;
+                Some(&field.arguments)
+            }
+            SyntaxKind::DIRECTIVE => {
+                let name = cst::Directive::cast(owner.clone())?.name()?.text();
+                let definition = self.schema.directive_definitions.get(&*name)?;
+                Some(&definition.arguments)
+            }
+            _ => None,
+        }
+    }
+
+    /// The named type of values expected in an argument, input object field,
+    /// variable default value, or in an object or list value.
+    fn input_type(&self, node: &SyntaxNode) -> Option<Name> {
+        match node.kind() {
+            SyntaxKind::ARGUMENT => {
+                let name = cst::Argument::cast(node.clone())?.name()?.text();
+                let owner = node.parent()?.parent()?;
+                let definition = self
+                    .argument_definitions(&owner)?
+                    .iter()
+                    .find(|definition| definition.name == *name)?;
+                Some(definition.ty.inner_named_type().clone())
+            }
+            SyntaxKind::OBJECT_FIELD => {
+                let name = cst::ObjectField::cast(node.clone())?.name()?.text();
+                let object_type = self.input_type(&node.parent()?)?;
+                let input_object = self.schema.get_input_object(&object_type)?;
+                let definition = input_object.fields.get(&*name)?;
+                Some(definition.ty.inner_named_type().clone())
+            }
+            SyntaxKind::OBJECT_VALUE | SyntaxKind::LIST_VALUE => self.input_type(&node.parent()?),
+            SyntaxKind::DEFAULT_VALUE => {
+                let definition = cst::VariableDefinition::cast(node.parent()?)?;
+                let named_type = definition
+                    .ty()?
+                    .syntax()
+                    .descendants()
+                    .find_map(cst::NamedType::cast)?;
+                to_name(named_type.name())
+            }
+            _ => None,
+        }
+    }
+}
+
+fn is_trivia(token: &SyntaxToken) -> bool {
+    matches!(
+        token.kind(),
+        SyntaxKind::WHITESPACE | SyntaxKind::COMMENT | SyntaxKind::COMMA
+    )
+}
+
+fn enclosing_selection_set(node: &SyntaxNode) -> Option<SyntaxNode> {
+    node.ancestors()
+        .skip(1)
+        .find(|node| node.kind() == SyntaxKind::SELECTION_SET)
+}
+
+fn directive_location(owner: &SyntaxNode) -> Option<DirectiveLocation> {
+    Some(match owner.kind() {
+        SyntaxKind::FIELD => DirectiveLocation::Field,
+        SyntaxKind::FRAGMENT_SPREAD => DirectiveLocation::FragmentSpread,
+        SyntaxKind::INLINE_FRAGMENT => DirectiveLocation::InlineFragment,
+        SyntaxKind::FRAGMENT_DEFINITION => DirectiveLocation::FragmentDefinition,
+        SyntaxKind::VARIABLE_DEFINITION => DirectiveLocation::VariableDefinition,
+        SyntaxKind::OPERATION_DEFINITION => {
+            match cst::OperationDefinition::cast(owner.clone())?.operation_type() {
+                Some(ty) if ty.mutation_token().is_some() => DirectiveLocation::Mutation,
+                Some(ty) if ty.subscription_token().is_some() => DirectiveLocation::Subscription,
+                _ => DirectiveLocation::Query,
+            }
+        }
+        _ => return None,
+    })
+}
+
+fn type_condition(condition: Option<cst::TypeCondition>) -> Option<Name> {
+    to_name(condition?.named_type()?.name())
+}
+
+fn fragment_name(fragment: &cst::FragmentDefinition) -> Option<Name> {
+    to_name(fragment.fragment_name()?.name())
+}
+
+fn to_name(name: Option<cst::Name>) -> Option<Name> {
+    Name::new(&name?.text()).ok()
+}
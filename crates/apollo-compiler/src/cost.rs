@@ -0,0 +1,534 @@
+//! Static cost analysis of operations, to reject expensive requests before execution.
+//!
+//! [`analyze`] measures an operation: nesting depth, number of fields, aliases, root fields,
+//! and an estimated cost weighted by list sizes.
+//! [`check`] compares these measurements to the limits configured in [`CostOptions`].
+//!
+//! # Weighted cost
+//!
+//! Each selected field costs its weight, multiplied by the estimated list size for list fields,
+//! plus the weights of the fields selected inside it.
+//! By default, fields of object, interface, or union type weigh 1
+//! and fields of scalar or enum type weigh 0.
+//!
+//! The schema can customize this with the directives of the
+//! [IBM cost specification](https://ibm.github.io/graphql-specs/cost-spec.html),
+//! which it needs to define:
+//!
+//! ```graphql
+//! directive @cost(weight: Int!) on
+//!   | ARGUMENT_DEFINITION | ENUM | FIELD_DEFINITION | INPUT_FIELD_DEFINITION | OBJECT | SCALAR
+//! directive @listSize(
+//!   assumedSize: Int,
+//!   slicingArguments: [String!],
+//!   sizedFields: [String!],
+//!   requireOneSlicingArgument: Boolean = true
+//! ) on FIELD_DEFINITION
+//! ```
+//!
+//! * `@cost(weight:)` on a field definition, or else on the type it returns,
+//!   replaces the default weight of a field.
+//!   On an argument or input field definition, its weight is added when it is used.
+//! * `@listSize` estimates the size of a list field:
+//!   the largest value of its `slicingArguments` (such as `first` or `last`) given in the query,
+//!   through variables, or as argument default values.
+//!   Otherwise `assumedSize` is used, or else [`CostOptions::default_list_size`].
+//!   Negative sizes count as zero.
+//! * With `sizedFields` (such as `edges` in a connection type),
+//!   the list size applies to these child fields instead of the field itself.
+//! * With `requireOneSlicingArgument: true`, its default value,
+//!   [`check`] rejects a selection of the field that does not have exactly one
+//!   of its `slicingArguments` set to a non-null value.
+//!
+//! Selections excluded by `@skip` or `@include` with the given variable values are not counted.
+//! Fields selected on different types through fragments are all counted,
+//! so the result is an upper bound for abstract types.
+//!
+//! ```
+//! use apollo_compiler::cost;
+//! use apollo_compiler::cost::CostOptions;
+//! use apollo_compiler::request::coerce_variable_values;
+//! use apollo_compiler::response::JsonMap;
+//! use apollo_compiler::ExecutableDocument;
+//! use apollo_compiler::Schema;
+//!
+//! let schema = r#"
+//!   directive @listSize(assumedSize: Int, slicingArguments: [String!]) on FIELD_DEFINITION
+//!   type Query { users(first: Int): [User] @listSize(slicingArguments: ["first"]) }
+//!   type User { name: String, friends: [User] @listSize(assumedSize: 10) }
+//! "#;
+//! let schema = Schema::parse_and_validate(schema, "schema.graphql").unwrap();
+//! let query = "query($n: Int) { users(first: $n) { friends { name } } }";
+//! let document = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+//! let operation = document.operations.get(None).unwrap();
+//! let variables: JsonMap = serde_json_bytes::json!({"n": 5}).as_object().unwrap().clone();
+//! let variables = coerce_variable_values(&schema, operation, &variables).unwrap();
+//!
+//! let options = CostOptions::new().max_cost(100.0);
+//! let measured = cost::analyze(&schema, &document, operation, &variables, &options);
+//! assert_eq!(measured.max_depth, 3);
+//! // 5 users × (1 + 10 friends × 1)
+//! assert_eq!(measured.cost, 55.0);
+//! assert!(cost::check(&schema, &document, operation, &variables, &options).is_ok());
+//! ```
+
+use crate::ast::Type;
+use crate::ast::Value;
+use crate::collections::HashMap;
+use crate::executable::Field;
+use crate::executable::Operation;
+use crate::executable::Selection;
+use crate::executable::SelectionSet;
+use crate::execution::engine::eval_if_arg;
+use crate::request::RequestError;
+use crate::response::JsonMap;
+use crate::response::JsonValue;
+use crate::schema::Directive;
+use crate::schema::ExtendedType;
+use crate::validation::Valid;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+
+/// Configuration for [`analyze`] and limits for [`check`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CostOptions {
+    default_list_size: f64,
+    max_depth: Option<usize>,
+    max_field_count: Option<usize>,
+    max_alias_count: Option<usize>,
+    max_root_field_count: Option<usize>,
+    max_cost: Option<f64>,
+}
+
+/// Measurements of an operation, returned by [`analyze`]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct OperationCost {
+    /// The largest number of nested fields. Root fields have depth 1.
+    pub max_depth: usize,
+    /// The number of fields selected, including in every use of a fragment.
+    pub field_count: usize,
+    /// The number of fields selected with an alias, including in every use of a fragment.
+    pub alias_count: usize,
+    /// The number of fields selected at the root of the operation.
+    pub root_field_count: usize,
+    /// The estimated cost of executing the operation, weighted by list sizes.
+    pub cost: f64,
+}
+
+impl Default for CostOptions {
+    fn default() -> Self {
+        Self {
+            default_list_size: 10.0,
+            max_depth: None,
+            max_field_count: None,
+            max_alias_count: None,
+            max_root_field_count: None,
+            max_cost: None,
+        }
+    }
+}
+
+impl CostOptions {
+    /// Returns options with a default list size of 10 and no limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the size assumed for list fields without a `@listSize` directive.
+    ///
+    /// Defaults to 10.
+    pub fn default_list_size(mut self, size: u32) -> Self {
+        self.default_list_size = size.into();
+        self
+    }
+
+    /// Set the maximum [depth][OperationCost::max_depth] accepted by [`check`].
+    pub fn max_depth(mut self, limit: usize) -> Self {
+        self.max_depth = Some(limit);
+        self
+    }
+
+    /// Set the maximum [field count][OperationCost::field_count] accepted by [`check`].
+    pub fn max_field_count(mut self, limit: usize) -> Self {
+        self.max_field_count = Some(limit);
+        self
+    }
+
+    /// Set the maximum [alias count][OperationCost::alias_count] accepted by [`check`].
+    pub fn max_alias_count(mut self, limit: usize) -> Self {
+        self.max_alias_count = Some(limit);
+        self
+    }
+
+    /// Set the maximum [root field count][OperationCost::root_field_count]
+    /// accepted by [`check`].
+    pub fn max_root_field_count(mut self, limit: usize) -> Self {
+        self.max_root_field_count = Some(limit);
+        self
+    }
+
+    /// Set the maximum [weighted cost][OperationCost::cost] accepted by [`check`].
+    pub fn max_cost(mut self, limit: f64) -> Self {
+        self.max_cost = Some(limit);
+        self
+    }
+}
+
+/// Measure the depth, size, and estimated cost of an operation.
+///
+/// * `operation` is expected to be from
+///   [`document.operations.get(operation_name)?`][crate::executable::OperationMap::get]
+/// * `variable_values` is expected to be from
+///   [`coerce_variable_values`][crate::request::coerce_variable_values]
+///
+/// The exact criteria may change in future apollo-compiler versions.
+pub fn analyze(
+    schema: &Valid<Schema>,
+    document: &Valid<ExecutableDocument>,
+    operation: &Node<Operation>,
+    variable_values: &Valid<JsonMap>,
+    options: &CostOptions,
+) -> OperationCost {
+    measure(schema, document, operation, variable_values, options).0
+}
+
+/// Check that an operation does not exceed the limits configured in `options`.
+///
+/// Returns the measurements from [`analyze`] if no limit is exceeded.
+/// An error result is a [request error](https://spec.graphql.org/draft/#request-error):
+/// execution must not run at all,
+/// and the GraphQL response must not have a `data` key (which is different from `data: null`).
+pub fn check(
+    schema: &Valid<Schema>,
+    document: &Valid<ExecutableDocument>,
+    operation: &Node<Operation>,
+    variable_values: &Valid<JsonMap>,
+    options: &CostOptions,
+) -> Result<OperationCost, RequestError> {
+    let (measured, slicing_error) = measure(schema, document, operation, variable_values, options);
+    if let Some(error) = slicing_error {
+        return Err(error);
+    }
+    let exceeded = |what: &str, actual: &dyn std::fmt::Display, limit: &dyn std::fmt::Display| {
+        Err(RequestError {
+            message: format!("Maximum {what} exceeded: {actual} > {limit}"),
+            location: operation.location(),
+            is_suspected_validation_bug: false,
+        })
+    };
+    if let Some(limit) = options
+        .max_depth
+        .filter(|&limit| measured.max_depth > limit)
+    {
+        return exceeded("depth", &measured.max_depth, &limit);
+    }
+    if let Some(limit) = options
+        .max_field_count
+        .filter(|&limit| measured.field_count > limit)
+    {
+        return exceeded("field count", &measured.field_count, &limit);
+    }
+    if let Some(limit) = options
+        .max_alias_count
+        .filter(|&limit| measured.alias_count > limit)
+    {
+        return exceeded("alias count", &measured.alias_count, &limit);
+    }
+    if let Some(limit) = options
+        .max_root_field_count
+        .filter(|&limit| measured.root_field_count > limit)
+    {
+        return exceeded("root field count", &measured.root_field_count, &limit);
+    }
+    if let Some(limit) = options.max_cost.filter(|&limit| measured.cost > limit) {
+        return exceeded("cost", &measured.cost, &limit);
+    }
+    Ok(measured)
+}
+
+/// Returns measurements and the first selection with a missing or extra slicing argument
+fn measure(
+    schema: &Schema,
+    document: &ExecutableDocument,
+    operation: &Operation,
+    variable_values: &JsonMap,
+    options: &CostOptions,
+) -> (OperationCost, Option<RequestError>) {
+    let mut analyzer = Analyzer {
+        schema,
+        document,
+        variable_values,
+        options,
+        fragments: HashMap::default(),
+        slicing_error: None,
+    };
+    let measured = analyzer.selection_set(&operation.selection_set, None);
+    let cost = OperationCost {
+        max_depth: measured.depth,
+        field_count: measured.fields,
+        alias_count: measured.aliases,
+        root_field_count: measured.direct_fields,
+        cost: measured.cost,
+    };
+    (cost, analyzer.slicing_error)
+}
+
+/// Measurements of a selection set, relative to its own depth
+#[derive(Debug, Clone, Copy, Default)]
+struct Measured {
+    cost: f64,
+    depth: usize,
+    fields: usize,
+    aliases: usize,
+    /// Fields directly in this selection set, through fragments but not nested fields
+    direct_fields: usize,
+}
+
+impl Measured {
+    fn add(&mut self, other: Self) {
+        self.cost += other.cost;
+        self.depth = self.depth.max(other.depth);
+        self.fields = self.fields.saturating_add(other.fields);
+        self.aliases = self.aliases.saturating_add(other.aliases);
+        self.direct_fields = self.direct_fields.saturating_add(other.direct_fields);
+    }
+}
+
+/// Child fields of a `@listSize(sizedFields:)` field, and their size
+struct SizedFields {
+    names: Vec<String>,
+    size: f64,
+}
+
+struct Analyzer<'a> {
+    schema: &'a Schema,
+    document: &'a ExecutableDocument,
+    variable_values: &'a JsonMap,
+    options: &'a CostOptions,
+    /// Measurements of fragments outside of sized fields, each computed once
+    fragments: HashMap<&'a Name, Measured>,
+    /// The first violation of `@listSize(requireOneSlicingArgument: true)`
+    slicing_error: Option<RequestError>,
+}
+
+impl<'a> Analyzer<'a> {
+    fn is_included(&self, selection: &Selection) -> bool {
+        let variable_values = Valid::assume_valid_ref(self.variable_values);
+        !eval_if_arg(selection, "skip", variable_values).unwrap_or(false)
+            && eval_if_arg(selection, "include", variable_values).unwrap_or(true)
+    }
+
+    fn selection_set(
+        &mut self,
+        selection_set: &'a SelectionSet,
+        sized: Option<&SizedFields>,
+    ) -> Measured {
+        let mut measured = Measured::default();
+        for selection in &selection_set.selections {
+            if !self.is_included(selection) {
+                continue;
+            }
+            match selection {
+                Selection::Field(field) => measured.add(self.field(field, sized)),
+                Selection::InlineFragment(inline) => {
+                    measured.add(self.selection_set(&inline.selection_set, sized))
+                }
+                Selection::FragmentSpread(spread) => {
+                    let name = &spread.fragment_name;
+                    if sized.is_none() {
+                        if let Some(fragment) = self.fragments.get(name) {
+                            measured.add(*fragment);
+                            continue;
+                        }
+                    }
+                    // Validation guarantees that fragments are defined and not recursive
+                    let Some(fragment) = self.document.fragments.get(name) else {
+                        continue;
+                    };
+                    let fragment_measured = self.selection_set(&fragment.selection_set, sized);
+                    if sized.is_none() {
+                        self.fragments.insert(name, fragment_measured);
+                    }
+                    measured.add(fragment_measured)
+                }
+            }
+        }
+        measured
+    }
+
+    fn field(&mut self, field: &'a Field, sized: Option<&SizedFields>) -> Measured {
+        let definition = &field.definition;
+        let type_definition = self.schema.types.get(definition.ty.inner_named_type());
+        let field_weight = weight(definition.directives.get("cost").map(|d| &**d))
+            .or_else(|| {
+                type_definition.and_then(|ty| weight(ty.directives().get("cost").map(|d| &***d)))
+            })
+            .unwrap_or(match type_definition {
+                Some(
+                    ExtendedType::Object(_) | ExtendedType::Interface(_) | ExtendedType::Union(_),
+                ) => 1.0,
+                _ => 0.0,
+            });
+
+        let mut arguments_cost = 0.0;
+        for argument in &field.arguments {
+            if let Some(argument_definition) = definition.argument_by_name(&argument.name) {
+                arguments_cost +=
+                    weight(argument_definition.directives.get("cost").map(|d| &**d)).unwrap_or(0.0);
+                arguments_cost += self.input_cost(&argument_definition.ty, &argument.value);
+            }
+        }
+
+        let mut own_size = None;
+        let mut sized_children = None;
+        if let Some(list_size) = definition.directives.get("listSize") {
+            let slicing_values = self.slicing_values(field, list_size);
+            self.check_one_slicing_argument(field, list_size, slicing_values.len());
+            let size = slicing_values
+                .into_iter()
+                .reduce(f64::max)
+                .or_else(|| number_argument(list_size, "assumedSize"))
+                // A negative size would make the cost smaller than selecting nothing
+                .map(|size| size.max(0.0));
+            let sized_fields = string_list_argument(list_size, "sizedFields");
+            match size {
+                Some(size) if !sized_fields.is_empty() => {
+                    sized_children = Some(SizedFields {
+                        names: sized_fields,
+                        size,
+                    })
+                }
+                _ => own_size = size,
+            }
+        }
+        let multiplier = if definition.ty.is_list() {
+            own_size
+                .or_else(|| {
+                    sized
+                        .filter(|sized| sized.names.iter().any(|name| field.name == name.as_str()))
+                        .map(|sized| sized.size)
+                })
+                .unwrap_or(self.options.default_list_size)
+        } else {
+            1.0
+        };
+
+        let children = self.selection_set(&field.selection_set, sized_children.as_ref());
+        Measured {
+            cost: multiplier * (field_weight + children.cost) + arguments_cost,
+            depth: children.depth + 1,
+            fields: children.fields.saturating_add(1),
+            aliases: children
+                .aliases
+                .saturating_add(usize::from(field.alias.is_some())),
+            direct_fields: 1,
+        }
+    }
+
+    /// The values of the slicing arguments set on a field, including default values
+    fn slicing_values(&self, field: &Field, list_size: &Directive) -> Vec<f64> {
+        string_list_argument(list_size, "slicingArguments")
+            .iter()
+            .filter_map(|name| {
+                let value = match field.specified_argument_by_name(name) {
+                    Some(value) => value,
+                    None => field
+                        .definition
+                        .argument_by_name(name)?
+                        .default_value
+                        .as_ref()?,
+                };
+                match value.as_ref() {
+                    Value::Variable(variable) => self
+                        .variable_values
+                        .get(variable.as_str())
+                        .and_then(JsonValue::as_f64),
+                    value => to_f64(value),
+                }
+            })
+            .collect()
+    }
+
+    fn check_one_slicing_argument(&mut self, field: &Field, list_size: &Directive, count: usize) {
+        if count == 1 || self.slicing_error.is_some() {
+            return;
+        }
+        let required = list_size
+            .argument_by_name("requireOneSlicingArgument", self.schema)
+            .ok()
+            .and_then(|value| value.to_bool())
+            // Default value from the IBM specification,
+            // for schemas that define `@listSize` without this argument
+            .unwrap_or(true);
+        if required && !string_list_argument(list_size, "slicingArguments").is_empty() {
+            self.slicing_error = Some(RequestError {
+                message: format!(
+                    "Expected exactly one slicing argument on field `{}`, found {count}",
+                    field.name
+                ),
+                location: field.name.location(),
+                is_suspected_validation_bug: false,
+            })
+        }
+    }
+
+    /// The weights of input fields used in an argument value
+    fn input_cost(&self, ty: &Type, value: &Value) -> f64 {
+        match value {
+            Value::List(items) => items
+                .iter()
+                .map(|item| self.input_cost(ty.item_type(), item))
+                .sum(),
+            Value::Object(fields) => {
+                let Some(ExtendedType::InputObject(input_object)) =
+                    self.schema.types.get(ty.inner_named_type())
+                else {
+                    return 0.0;
+                };
+                fields
+                    .iter()
+                    .filter_map(|(name, value)| {
+                        let definition = input_object.fields.get(name)?;
+                        Some(
+                            weight(definition.directives.get("cost").map(|d| &**d)).unwrap_or(0.0)
+                                + self.input_cost(&definition.ty, value),
+                        )
+                    })
+                    .sum()
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+/// The `weight` argument of a `@cost` directive.
+/// Accepts strings as in the IBM specification, or numbers.
+fn weight(cost: Option<&Directive>) -> Option<f64> {
+    number_argument(cost?, "weight")
+}
+
+fn number_argument(directive: &Directive, name: &str) -> Option<f64> {
+    to_f64(directive.specified_argument_by_name(name)?)
+}
+
+fn to_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Int(int) => int.try_to_f64().ok(),
+        Value::Float(float) => float.try_to_f64().ok(),
+        Value::String(string) => string.parse().ok(),
+        _ => None,
+    }
+}
+
+fn string_list_argument(directive: &Directive, name: &str) -> Vec<String> {
+    match directive.specified_argument_by_name(name).map(|v| &**v) {
+        Some(Value::List(items)) => items
+            .iter()
+            .filter_map(|item| item.as_str().map(ToOwned::to_owned))
+            .collect(),
+        Some(Value::String(item)) => vec![item.clone()],
+        _ => Vec::new(),
+    }
+}
@@ -73,6 +73,7 @@ use crate::parser::SourceFile;
 use crate::parser::SourceMap;
 use crate::parser::SourceSpan;
 use crate::response::GraphQLError;
+use crate::validation::Severity;
 #[cfg(doc)]
 use crate::ExecutableDocument;
 #[cfg(doc)]
@@ -113,6 +114,9 @@ pub struct CliReport<'s> {
     sources: &'s SourceMap,
     colors: ColorGenerator,
     report: ariadne::ReportBuilder<'static, AriadneSpan>,
+    /// Kept for machine-readable output
+    pub(crate) labels: Vec<(SourceSpan, String)>,
+    pub(crate) help: Option<String>,
 }
 
 /// Indicate when to use ANSI colors for printing.
@@ -203,11 +207,25 @@ impl<'s> CliReport<'s> {
         sources: &'s SourceMap,
         main_location: Option<SourceSpan>,
         color: Color,
+    ) -> Self {
+        Self::builder_with_severity(sources, main_location, Severity::Error, color)
+    }
+
+    /// Returns a builder for creating diagnostic reports, labeled as an error or as a warning.
+    pub fn builder_with_severity(
+        sources: &'s SourceMap,
+        main_location: Option<SourceSpan>,
+        severity: Severity,
+        color: Color,
     ) -> Self {
         let span = main_location
             .and_then(to_span)
             .unwrap_or((FileId::NONE, 0..0));
-        let report = ariadne::Report::build(ReportKind::Error, span);
+        let kind = match severity {
+            Severity::Error => ReportKind::Error,
+            Severity::Warning => ReportKind::Warning,
+        };
+        let report = ariadne::Report::build(kind, span);
         let enable_color = match color {
             Color::Never => false,
             // Rely on ariadne's `auto-color` feature, which uses `concolor` to enable colors
@@ -221,6 +239,8 @@ impl<'s> CliReport<'s> {
             sources,
             colors: ColorGenerator::new(),
             report: report.with_config(config),
+            labels: Vec::new(),
+            help: None,
         }
     }
 
@@ -231,7 +251,9 @@ impl<'s> CliReport<'s> {
 
     /// Set the help message for the report, usually a suggestion on how to fix the error.
     pub fn with_help(&mut self, help: impl ToString) {
-        self.report.set_help(help);
+        let help = help.to_string();
+        self.report.set_help(&help);
+        self.help = Some(help);
     }
 
     /// Set a note for the report, providing additional information that isn't related to a
@@ -242,12 +264,17 @@ impl<'s> CliReport<'s> {
 
     /// Add a label at a given location. If the location is `None`, the message is discarded.
     pub fn with_label_opt(&mut self, location: Option<SourceSpan>, message: impl ToString) {
-        if let Some(span) = location.and_then(to_span) {
+        let Some(location) = location else {
+            return;
+        };
+        if let Some(span) = to_span(location) {
+            let message = message.to_string();
             self.report.add_label(
                 ariadne::Label::new(span)
-                    .with_message(message)
+                    .with_message(&message)
                     .with_color(self.colors.next()),
             );
+            self.labels.push((location, message));
         }
     }
 
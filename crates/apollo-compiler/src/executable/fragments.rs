@@ -0,0 +1,207 @@
+use super::*;
+
+/// Replaces every fragment spread with an inline fragment and removes fragment definitions
+pub(crate) fn inline_fragments(document: &ExecutableDocument) -> ExecutableDocument {
+    let mut result = ExecutableDocument {
+        sources: document.sources.clone(),
+        operations: document.operations.clone(),
+        fragments: FragmentMap::default(),
+    };
+    let mut inlining = Vec::new();
+    let operations = result
+        .operations
+        .anonymous
+        .iter_mut()
+        .chain(result.operations.named.values_mut());
+    for operation in operations {
+        inline_selection_set(
+            document,
+            &mut operation.make_mut().selection_set,
+            &mut inlining,
+        )
+    }
+    result
+}
+
+/// `inlining` contains fragments being inlined, to skip spreads that would recurse infinitely
+fn inline_selection_set(
+    document: &ExecutableDocument,
+    selection_set: &mut SelectionSet,
+    inlining: &mut Vec<Name>,
+) {
+    for selection in std::mem::take(&mut selection_set.selections) {
+        let selection = match selection {
+            Selection::Field(mut field) => {
+                inline_selection_set(document, &mut field.make_mut().selection_set, inlining);
+                Selection::Field(field)
+            }
+            Selection::InlineFragment(mut inline) => {
+                inline_selection_set(document, &mut inline.make_mut().selection_set, inlining);
+                Selection::InlineFragment(inline)
+            }
+            Selection::FragmentSpread(spread) => {
+                let Some(fragment) = document.fragments.get(&spread.fragment_name) else {
+                    continue;
+                };
+                if inlining.contains(&fragment.name) {
+                    continue;
+                }
+                let mut fragment_selection_set = fragment.selection_set.clone();
+                inlining.push(fragment.name.clone());
+                inline_selection_set(document, &mut fragment_selection_set, inlining);
+                inlining.pop();
+                let type_condition = (fragment_selection_set.ty != selection_set.ty)
+                    .then(|| fragment_selection_set.ty.clone());
+                Selection::InlineFragment(spread.same_location(InlineFragment {
+                    type_condition,
+                    directives: spread.directives.clone(),
+                    selection_set: fragment_selection_set,
+                }))
+            }
+        };
+        selection_set.selections.push(selection)
+    }
+}
+
+/// Moves sub-selections of fields that occur more than once into new named fragments
+pub(crate) fn extract_fragments(document: &ExecutableDocument) -> ExecutableDocument {
+    let mut result = document.clone();
+    // Extracting the largest repeated selection set first means that selection sets
+    // only repeated within it end up in a single fragment and are not extracted separately.
+    while let Some(repeated) = largest_repeated_selection_set(&result) {
+        let name = new_fragment_name(&result.fragments, &repeated.ty);
+        let mut replacement = SelectionSet::new(repeated.ty.clone());
+        replacement.push(FragmentSpread::new(name.clone()));
+        for operation in result
+            .operations
+            .anonymous
+            .iter_mut()
+            .chain(result.operations.named.values_mut())
+        {
+            replace_selection_sets(
+                &mut operation.make_mut().selection_set,
+                &repeated,
+                &replacement,
+            )
+        }
+        for fragment in result.fragments.values_mut() {
+            replace_selection_sets(
+                &mut fragment.make_mut().selection_set,
+                &repeated,
+                &replacement,
+            )
+        }
+        let fragment = Fragment {
+            name: name.clone(),
+            directives: DirectiveList::new(),
+            selection_set: repeated,
+        };
+        result.fragments.insert(name, Node::new(fragment));
+    }
+    result
+}
+
+fn largest_repeated_selection_set(document: &ExecutableDocument) -> Option<SelectionSet> {
+    let mut counts = IndexMap::default();
+    for operation in document.operations.iter() {
+        count_selection_sets(&operation.selection_set, &mut counts)
+    }
+    for fragment in document.fragments.values() {
+        count_selection_sets(&fragment.selection_set, &mut counts)
+    }
+    let mut largest: Option<(&SelectionSet, usize)> = None;
+    for (selection_set, count) in counts {
+        if count < 2 || is_single_spread(selection_set) {
+            continue;
+        }
+        let size = selection_count(selection_set);
+        // Ties go to the first selection set in document order
+        if largest.is_none_or(|(_, largest_size)| size > largest_size) {
+            largest = Some((selection_set, size))
+        }
+    }
+    largest.map(|(selection_set, _)| selection_set.clone())
+}
+
+/// Counts sub-selections of fields, recursively
+fn count_selection_sets<'doc>(
+    selection_set: &'doc SelectionSet,
+    counts: &mut IndexMap<&'doc SelectionSet, usize>,
+) {
+    for selection in &selection_set.selections {
+        match selection {
+            Selection::Field(field) => {
+                if !field.selection_set.selections.is_empty() {
+                    *counts.entry(&field.selection_set).or_insert(0) += 1;
+                    count_selection_sets(&field.selection_set, counts)
+                }
+            }
+            Selection::InlineFragment(inline) => {
+                count_selection_sets(&inline.selection_set, counts)
+            }
+            Selection::FragmentSpread(_) => {}
+        }
+    }
+}
+
+fn replace_selection_sets(
+    selection_set: &mut SelectionSet,
+    repeated: &SelectionSet,
+    replacement: &SelectionSet,
+) {
+    for selection in &mut selection_set.selections {
+        match selection {
+            Selection::Field(field) => {
+                if field.selection_set == *repeated {
+                    field.make_mut().selection_set = replacement.clone()
+                } else if !field.selection_set.selections.is_empty() {
+                    replace_selection_sets(
+                        &mut field.make_mut().selection_set,
+                        repeated,
+                        replacement,
+                    )
+                }
+            }
+            Selection::InlineFragment(inline) => {
+                replace_selection_sets(&mut inline.make_mut().selection_set, repeated, replacement)
+            }
+            Selection::FragmentSpread(_) => {}
+        }
+    }
+}
+
+/// Whether extracting this selection set would only add a level of indirection
+fn is_single_spread(selection_set: &SelectionSet) -> bool {
+    matches!(
+        selection_set.selections.as_slice(),
+        [Selection::FragmentSpread(_)]
+    )
+}
+
+/// The number of selections in a selection set, recursively
+fn selection_count(selection_set: &SelectionSet) -> usize {
+    selection_set
+        .selections
+        .iter()
+        .map(|selection| {
+            1 + match selection {
+                Selection::Field(field) => selection_count(&field.selection_set),
+                Selection::InlineFragment(inline) => selection_count(&inline.selection_set),
+                Selection::FragmentSpread(_) => 0,
+            }
+        })
+        .sum()
+}
+
+/// `{Type}Fields`, with a numeric suffix if that fragment name is already taken
+fn new_fragment_name(fragments: &FragmentMap, ty: &NamedType) -> Name {
+    let base = format!("{ty}Fields");
+    let mut name = base.clone();
+    let mut suffix = 1;
+    while fragments.contains_key(name.as_str()) {
+        suffix += 1;
+        name = format!("{base}{suffix}");
+    }
+    // Type names are valid names, and so are they with letters and digits appended
+    Name::new_unchecked(&name)
+}
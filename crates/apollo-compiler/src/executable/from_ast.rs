@@ -89,6 +89,12 @@ pub(crate) fn document_from_ast(
                     }
                 }
             }
+            ast::Definition::FragmentDefinition(fragment)
+                if fragment.name.is_missing_placeholder() =>
+            {
+                // Already a syntax error. Leave it out so that spreads of a missing name
+                // do not resolve to an unrelated fragment.
+            }
             ast::Definition::FragmentDefinition(fragment) => {
                 if let Entry::Vacant(entry) = fragments.entry(fragment.name.clone()) {
                     errors.path.root = ExecutableDefinitionName::Fragment(fragment.name.clone());
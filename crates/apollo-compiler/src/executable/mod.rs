@@ -51,11 +51,16 @@
 use crate::ast;
 use crate::collections::IndexMap;
 use crate::coordinate::FieldArgumentCoordinate;
+use crate::coordinate::SchemaCoordinate;
 use crate::coordinate::TypeAttributeCoordinate;
+use crate::parser::FileId;
 use crate::parser::Parser;
 use crate::parser::SourceMap;
 use crate::parser::SourceSpan;
+use crate::response::JsonMap;
 use crate::schema;
+use crate::validation::lint::LintConfig;
+use crate::validation::DeprecatedUsage;
 use crate::validation::DiagnosticList;
 use crate::validation::Valid;
 use crate::validation::WithErrors;
@@ -66,10 +71,19 @@ use std::fmt;
 use std::path::Path;
 use std::sync::Arc;
 
+mod fragments;
 pub(crate) mod from_ast;
+mod normalize;
 mod serialize;
+mod skip_include;
 pub(crate) mod validation;
+pub(crate) mod visit;
 
+pub use self::normalize::NormalizeOptions;
+pub use self::visit::TypeInfo;
+pub use self::visit::VisitControl;
+pub use self::visit::Visitor;
+pub use self::visit::VisitorMut;
 pub use crate::ast::Argument;
 use crate::ast::ArgumentByNameError;
 pub use crate::ast::Directive;
@@ -80,6 +94,7 @@ pub use crate::ast::Type;
 pub use crate::ast::Value;
 pub use crate::ast::VariableDefinition;
 use crate::collections::HashSet;
+pub use crate::lookup::ExecutableElement;
 use crate::request::RequestError;
 pub use crate::Name;
 
@@ -387,6 +402,175 @@ impl ExecutableDocument {
         errors.into_valid_result(self)
     }
 
+    /// Returns every use of a schema element marked `@deprecated`:
+    /// selected fields, arguments, input object fields, and enum values,
+    /// including in variable default values and directive arguments.
+    ///
+    /// Each fragment definition is checked once, regardless of how many times it is spread.
+    /// Elements that are not defined in the schema are ignored,
+    /// as [validation][Self::validate] reports them.
+    pub fn deprecated_usages(&self, schema: &Valid<Schema>) -> Vec<DeprecatedUsage> {
+        crate::validation::deprecated::find_deprecated_usages(schema, self)
+    }
+
+    /// Check the document for [lints][crate::validation::lint]:
+    /// problems that do not make it invalid, such as selecting deprecated fields.
+    ///
+    /// Returns warnings and, for rules configured as [`Deny`][crate::validation::lint::LintLevel::Deny],
+    /// errors. Use [`DiagnosticList::has_errors`] to tell them apart.
+    pub fn lint(&self, schema: &Valid<Schema>, config: &LintConfig) -> DiagnosticList {
+        let mut sources = IndexMap::clone(&schema.sources);
+        sources.extend(self.sources.iter().map(|(k, v)| (*k, v.clone())));
+        let mut diagnostics = DiagnosticList::new(Arc::new(sources));
+        crate::validation::lint::lint_executable_document(&mut diagnostics, schema, self, config);
+        diagnostics
+    }
+
+    /// Returns the innermost element at a byte offset of a source file,
+    /// such as a field selection, a variable, a fragment spread, or an argument.
+    ///
+    /// `schema` is used to resolve enum values and input object fields in argument values.
+    /// An offset at the end of a name is considered part of that name.
+    pub fn element_at(
+        &self,
+        schema: &Schema,
+        file_id: FileId,
+        offset: usize,
+    ) -> Option<ExecutableElement> {
+        crate::lookup::executable_element_at(schema, self, file_id, offset)
+    }
+
+    /// Returns the locations of names referencing a schema element in this document:
+    /// field selections, arguments, directives, type conditions, variable types,
+    /// input object fields, and enum values.
+    ///
+    /// Fields are matched by the type where they are selected,
+    /// so selections of `Node.id` through an interface are not references to `User.id`.
+    /// Use [`Schema::references`] to find references in the schema itself.
+    pub fn references(&self, schema: &Schema, coordinate: &SchemaCoordinate) -> Vec<SourceSpan> {
+        crate::lookup::executable_references(schema, self, coordinate)
+    }
+
+    /// Traverse the document depth-first, calling [`Visitor`] methods for each node
+    /// together with [`TypeInfo`] looked up in `schema`.
+    pub fn walk<'doc>(&'doc self, schema: &Valid<Schema>, visitor: &mut impl Visitor<'doc>) {
+        visit::walk(schema, self, visitor)
+    }
+
+    /// Traverse the document depth-first, calling [`VisitorMut`] methods for each node
+    /// together with [`TypeInfo`] looked up in `schema`.
+    ///
+    /// The document is typically not [`Valid`] anymore after modifications.
+    /// Consider calling [`validate`][Self::validate] afterwards.
+    pub fn walk_mut(&mut self, schema: &Valid<Schema>, visitor: &mut impl VisitorMut) {
+        visit::walk_mut(schema, self, visitor)
+    }
+
+    /// Replaces every fragment spread with an inline fragment containing
+    /// the selections of that fragment, and removes fragment definitions.
+    ///
+    /// The type condition of the fragment is kept unless it is the type
+    /// of the selection set where it is spread.
+    /// Directives of the spread move to the inline fragment,
+    /// which keeps the source location of the spread.
+    ///
+    /// The result is validated against `schema`.
+    #[allow(clippy::result_large_err)] // Typically not called very often
+    pub fn inline_fragments(
+        &self,
+        schema: &Valid<Schema>,
+    ) -> Result<Valid<Self>, WithErrors<Self>> {
+        fragments::inline_fragments(self).validate(schema)
+    }
+
+    /// Finds sub-selections of fields that are repeated, within or across operations,
+    /// and moves them into new named fragments.
+    ///
+    /// The largest repeated selection sets are extracted first.
+    /// New fragments are named after their type condition, like `UserFields`,
+    /// and keep the source locations of the first occurrence of their selections.
+    /// Existing fragments are kept.
+    ///
+    /// The result is validated against `schema`.
+    #[allow(clippy::result_large_err)] // Typically not called very often
+    pub fn extract_fragments(
+        &self,
+        schema: &Valid<Schema>,
+    ) -> Result<Valid<Self>, WithErrors<Self>> {
+        fragments::extract_fragments(self).validate(schema)
+    }
+
+    /// Returns a document with only `operation` and the fragments it uses,
+    /// where `@skip` and `@include` directives are evaluated with `variable_values`
+    /// as returned by [`coerce_variable_values`][crate::request::coerce_variable_values].
+    ///
+    /// Selections that are skipped are removed.
+    /// Selections that are included lose their `@skip` or `@include` directive.
+    /// Directives whose `if` argument cannot be decided, such as with a missing variable,
+    /// are kept.
+    /// Fields, inline fragments, and fragment definitions together with their spreads
+    /// are removed when they do not select anything anymore.
+    /// Variable definitions are removed when the variable is not used anymore,
+    /// such as when it was only used by removed selections or by evaluated conditions.
+    ///
+    /// If every selection of the operation is skipped, it is kept with an empty selection set,
+    /// which cannot be serialized as valid GraphQL syntax.
+    pub fn evaluate_skip_include(
+        &self,
+        operation: &Node<Operation>,
+        variable_values: &Valid<JsonMap>,
+    ) -> Self {
+        skip_include::evaluate_skip_include(self, operation, variable_values)
+    }
+
+    /// Returns a canonical form of this document, such that documents that differ only
+    /// in formatting, order of fields and arguments, fragment structure,
+    /// or (optionally) literal values are normalized to the same document.
+    ///
+    /// * Fragment spreads are inlined or kept depending on `options`,
+    ///   and unused fragments are removed
+    /// * Inline fragments without directives whose type condition always applies are flattened
+    /// * Identical fields with the same response key, arguments, and directives
+    ///   are merged, with their sub-selections
+    /// * Selections, arguments, directives, variable definitions, input object fields,
+    ///   and named operations and fragments are sorted
+    /// * Literal values are replaced with placeholders depending on `options`
+    /// * Comments are removed
+    ///
+    /// The result may not be valid, for example if placeholders replace required input fields.
+    pub fn normalize(&self, schema: &Valid<Schema>, options: &NormalizeOptions) -> Self {
+        normalize::normalize(schema, self, None, options)
+    }
+
+    /// Returns a stable signature of an operation, for example for operation metrics:
+    /// the [normalized][Self::normalize] operation and the fragments it uses,
+    /// serialized without line breaks.
+    ///
+    /// `operation_name` selects an operation like [`OperationMap::get`].
+    pub fn operation_signature(
+        &self,
+        schema: &Valid<Schema>,
+        operation_name: Option<&str>,
+        options: &NormalizeOptions,
+    ) -> Result<String, RequestError> {
+        let operation = self.operations.get(operation_name)?;
+        let normalized = normalize::normalize(schema, self, Some(operation), options);
+        Ok(normalized.serialize().no_indent().to_string())
+    }
+
+    /// Returns the SHA-256 hash of an [operation signature][Self::operation_signature],
+    /// as lowercase hexadecimal.
+    pub fn operation_signature_hash(
+        &self,
+        schema: &Valid<Schema>,
+        operation_name: Option<&str>,
+        options: &NormalizeOptions,
+    ) -> Result<String, RequestError> {
+        use sha2::Digest;
+        let signature = self.operation_signature(schema, operation_name, options)?;
+        Ok(format!("{:x}", sha2::Sha256::digest(signature)))
+    }
+
     serialize_method!();
 }
 
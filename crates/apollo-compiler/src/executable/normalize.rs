@@ -0,0 +1,426 @@
+use super::*;
+use crate::collections::IndexSet;
+use crate::validation::selection::can_merge_fields;
+
+/// Configuration for [`ExecutableDocument::normalize`]
+/// and [`ExecutableDocument::operation_signature`].
+///
+/// By default, fragments are inlined and literal values are replaced with placeholders.
+#[derive(Debug, Clone)]
+pub struct NormalizeOptions {
+    inline_fragments: bool,
+    hide_literals: bool,
+}
+
+impl Default for NormalizeOptions {
+    fn default() -> Self {
+        Self {
+            inline_fragments: true,
+            hide_literals: true,
+        }
+    }
+}
+
+impl NormalizeOptions {
+    /// Returns the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether to replace fragment spreads with the selections of their fragment.
+    ///
+    /// When `false`, fragment spreads are kept and fragment definitions are sorted by name.
+    /// Fragments that are not used are removed either way.
+    pub fn inline_fragments(mut self, inline: bool) -> Self {
+        self.inline_fragments = inline;
+        self
+    }
+
+    /// Whether to replace literal values with placeholders:
+    /// `0` for numbers and `""` for strings, including in lists and input objects.
+    ///
+    /// Booleans, enum values, `null`, and variables are kept,
+    /// as are the length of lists and the field names of input objects.
+    /// This way, values of different shapes do not share a placeholder.
+    pub fn hide_literals(mut self, hide: bool) -> Self {
+        self.hide_literals = hide;
+        self
+    }
+}
+
+struct Normalizer<'a> {
+    schema: &'a Schema,
+    document: &'a ExecutableDocument,
+    options: &'a NormalizeOptions,
+    /// Fragments being inlined, to skip spreads that would recurse infinitely
+    inlining: Vec<Name>,
+    /// Fragments spread in the result, when not inlining
+    used_fragments: IndexSet<Name>,
+}
+
+/// Normalizes `operation`, or all operations of `document` if `None`
+pub(crate) fn normalize(
+    schema: &Schema,
+    document: &ExecutableDocument,
+    operation: Option<&Node<Operation>>,
+    options: &NormalizeOptions,
+) -> ExecutableDocument {
+    let mut normalizer = Normalizer {
+        schema,
+        document,
+        options,
+        inlining: Vec::new(),
+        used_fragments: IndexSet::default(),
+    };
+    let mut result = ExecutableDocument {
+        sources: document.sources.clone(),
+        operations: OperationMap::default(),
+        fragments: FragmentMap::default(),
+    };
+    let operations: Vec<_> = match operation {
+        Some(operation) => vec![operation],
+        None => document.operations.iter().collect(),
+    };
+    for operation in operations {
+        let operation = normalizer.operation(operation);
+        match &operation.name {
+            Some(name) => {
+                result.operations.named.insert(name.clone(), operation);
+            }
+            None => result.operations.anonymous = Some(operation),
+        }
+    }
+    result.operations.named.sort_keys();
+
+    // Normalizing a fragment can find more used fragments
+    let mut index = 0;
+    while let Some(name) = normalizer.used_fragments.get_index(index).cloned() {
+        index += 1;
+        if let Some(fragment) = document.fragments.get(&name) {
+            let fragment = normalizer.fragment(fragment);
+            result.fragments.insert(name, fragment);
+        }
+    }
+    result.fragments.sort_keys();
+    result
+}
+
+impl Normalizer<'_> {
+    fn operation(&mut self, operation: &Node<Operation>) -> Node<Operation> {
+        let mut variables: Vec<_> = operation
+            .variables
+            .iter()
+            .map(|variable| {
+                relocate(
+                    variable,
+                    VariableDefinition {
+                        name: variable.name.clone(),
+                        ty: variable.ty.clone(),
+                        default_value: variable
+                            .default_value
+                            .as_ref()
+                            .map(|value| self.value(value)),
+                        directives: self.directives(&variable.directives),
+                    },
+                )
+            })
+            .collect();
+        variables.sort_by(|a, b| a.name.cmp(&b.name));
+        relocate(
+            operation,
+            Operation {
+                operation_type: operation.operation_type,
+                name: operation.name.clone(),
+                variables,
+                directives: self.directives(&operation.directives),
+                selection_set: self.selection_set(&operation.selection_set),
+            },
+        )
+    }
+
+    fn fragment(&mut self, fragment: &Node<Fragment>) -> Node<Fragment> {
+        self.inlining.push(fragment.name.clone());
+        let selection_set = self.selection_set(&fragment.selection_set);
+        self.inlining.pop();
+        relocate(
+            fragment,
+            Fragment {
+                name: fragment.name.clone(),
+                directives: self.directives(&fragment.directives),
+                selection_set,
+            },
+        )
+    }
+
+    fn selection_set(&mut self, selection_set: &SelectionSet) -> SelectionSet {
+        let mut result = SelectionSet::new(selection_set.ty.clone());
+        self.collect(
+            &selection_set.ty,
+            &selection_set.selections,
+            &mut result.selections,
+        );
+        self.field_arguments(&mut result);
+        sort_selections(&mut result);
+        result
+    }
+
+    /// Adds normalized `selections` to `output`,
+    /// flattening inline fragments that always apply and merging fields
+    /// that validation allows to merge.
+    /// Field arguments are kept as-is for comparison, and are normalized afterwards.
+    /// Does not sort.
+    fn collect(
+        &mut self,
+        parent_type: &NamedType,
+        selections: &[Selection],
+        output: &mut Vec<Selection>,
+    ) {
+        for selection in selections {
+            match selection {
+                Selection::Field(field) => {
+                    let directives = self.directives(&field.directives);
+                    let existing = output.iter_mut().find_map(|selection| match selection {
+                        Selection::Field(existing)
+                            if existing.response_key() == field.response_key()
+                                && existing.directives == directives
+                                && can_merge_fields(parent_type, existing, field) =>
+                        {
+                            Some(existing)
+                        }
+                        _ => None,
+                    });
+                    if let Some(existing) = existing {
+                        let existing = existing.make_mut();
+                        self.collect(
+                            &existing.selection_set.ty.clone(),
+                            &field.selection_set.selections,
+                            &mut existing.selection_set.selections,
+                        );
+                        continue;
+                    }
+                    let mut selection_set = SelectionSet::new(field.selection_set.ty.clone());
+                    self.collect(
+                        &field.selection_set.ty,
+                        &field.selection_set.selections,
+                        &mut selection_set.selections,
+                    );
+                    output.push(Selection::Field(relocate(
+                        field,
+                        Field {
+                            definition: field.definition.clone(),
+                            alias: field.alias.clone(),
+                            name: field.name.clone(),
+                            arguments: field.arguments.clone(),
+                            directives,
+                            selection_set,
+                        },
+                    )))
+                }
+                Selection::FragmentSpread(spread) => {
+                    let Some(fragment) = self.document.fragments.get(&spread.fragment_name) else {
+                        continue;
+                    };
+                    if self.inlining.contains(&fragment.name) {
+                        continue;
+                    }
+                    if self.options.inline_fragments {
+                        self.inlining.push(fragment.name.clone());
+                        self.inline_fragment(
+                            parent_type,
+                            Some(fragment.type_condition()),
+                            &spread.directives,
+                            &fragment.selection_set,
+                            spread.location(),
+                            output,
+                        );
+                        self.inlining.pop();
+                    } else {
+                        self.used_fragments.insert(fragment.name.clone());
+                        let spread = Selection::FragmentSpread(relocate(
+                            spread,
+                            FragmentSpread {
+                                fragment_name: spread.fragment_name.clone(),
+                                directives: self.directives(&spread.directives),
+                            },
+                        ));
+                        if !output.contains(&spread) {
+                            output.push(spread)
+                        }
+                    }
+                }
+                Selection::InlineFragment(inline) => self.inline_fragment(
+                    parent_type,
+                    inline.type_condition.as_ref(),
+                    &inline.directives,
+                    &inline.selection_set,
+                    inline.location(),
+                    output,
+                ),
+            }
+        }
+    }
+
+    fn inline_fragment(
+        &mut self,
+        parent_type: &NamedType,
+        type_condition: Option<&NamedType>,
+        directives: &DirectiveList,
+        selection_set: &SelectionSet,
+        location: Option<SourceSpan>,
+        output: &mut Vec<Selection>,
+    ) {
+        let directives = self.directives(directives);
+        let type_condition =
+            type_condition.filter(|condition| !self.always_applies(condition, parent_type));
+        if type_condition.is_none() && directives.is_empty() {
+            return self.collect(parent_type, &selection_set.selections, output);
+        }
+        let existing = output.iter_mut().find_map(|selection| match selection {
+            Selection::InlineFragment(existing)
+                if existing.type_condition.as_ref() == type_condition
+                    && existing.directives == directives =>
+            {
+                Some(existing)
+            }
+            _ => None,
+        });
+        if let Some(existing) = existing {
+            let existing = existing.make_mut();
+            return self.collect(
+                &existing.selection_set.ty.clone(),
+                &selection_set.selections,
+                &mut existing.selection_set.selections,
+            );
+        }
+        let mut new_selection_set = SelectionSet::new(selection_set.ty.clone());
+        self.collect(
+            &selection_set.ty,
+            &selection_set.selections,
+            &mut new_selection_set.selections,
+        );
+        output.push(Selection::InlineFragment(Node::new_opt_location(
+            InlineFragment {
+                type_condition: type_condition.cloned(),
+                directives,
+                selection_set: new_selection_set,
+            },
+            location,
+        )))
+    }
+
+    /// Normalizes arguments of fields in the selection set, recursively
+    fn field_arguments(&self, selection_set: &mut SelectionSet) {
+        for selection in &mut selection_set.selections {
+            match selection {
+                Selection::Field(field) => {
+                    let field = field.make_mut();
+                    field.arguments = self.arguments(&field.arguments);
+                    self.field_arguments(&mut field.selection_set)
+                }
+                Selection::InlineFragment(inline) => {
+                    self.field_arguments(&mut inline.make_mut().selection_set)
+                }
+                Selection::FragmentSpread(_) => {}
+            }
+        }
+    }
+
+    /// Returns whether a fragment with this type condition applies to every object
+    /// selected with `parent_type`
+    fn always_applies(&self, type_condition: &NamedType, parent_type: &NamedType) -> bool {
+        type_condition == parent_type
+            || (self.schema.get_object(parent_type).is_some()
+                && self.schema.is_subtype(type_condition, parent_type))
+    }
+
+    fn directives(&self, directives: &DirectiveList) -> DirectiveList {
+        let mut directives: Vec<_> = directives
+            .iter()
+            .map(|directive| {
+                relocate(
+                    directive,
+                    Directive {
+                        name: directive.name.clone(),
+                        arguments: self.arguments(&directive.arguments),
+                    },
+                )
+            })
+            .collect();
+        directives.sort_by(|a, b| a.name.cmp(&b.name));
+        DirectiveList(directives)
+    }
+
+    fn arguments(&self, arguments: &[Node<Argument>]) -> Vec<Node<Argument>> {
+        let mut arguments: Vec<_> = arguments
+            .iter()
+            .map(|argument| {
+                relocate(
+                    argument,
+                    Argument {
+                        name: argument.name.clone(),
+                        value: self.value(&argument.value),
+                    },
+                )
+            })
+            .collect();
+        arguments.sort_by(|a, b| a.name.cmp(&b.name));
+        arguments
+    }
+
+    fn value(&self, value: &Node<Value>) -> Node<Value> {
+        let hide = self.options.hide_literals;
+        let normalized = match value.as_ref() {
+            Value::Int(_) | Value::Float(_) if hide => Value::from(0),
+            Value::String(_) if hide => Value::String(String::new()),
+            Value::List(items) => Value::List(items.iter().map(|item| self.value(item)).collect()),
+            Value::Object(fields) => {
+                let mut fields: Vec<_> = fields
+                    .iter()
+                    .map(|(name, value)| (name.clone(), self.value(value)))
+                    .collect();
+                fields.sort_by(|a, b| a.0.cmp(&b.0));
+                Value::Object(fields)
+            }
+            other => other.clone(),
+        };
+        relocate(value, normalized)
+    }
+}
+
+/// Like [`Node::same_location`], without comments which would be serialized
+fn relocate<T, U>(node: &Node<T>, new: U) -> Node<U> {
+    Node::new_opt_location(new, node.location())
+}
+
+/// Sorts fields by response key, then fragment spreads by name,
+/// then inline fragments by type condition, recursively
+fn sort_selections(selection_set: &mut SelectionSet) {
+    for selection in &mut selection_set.selections {
+        match selection {
+            Selection::Field(field) => sort_selections(&mut field.make_mut().selection_set),
+            Selection::InlineFragment(inline) => {
+                sort_selections(&mut inline.make_mut().selection_set)
+            }
+            Selection::FragmentSpread(_) => {}
+        }
+    }
+    selection_set.selections.sort_by_cached_key(|selection| {
+        let (rank, name) = match selection {
+            Selection::Field(field) => (0, field.response_key().as_str()),
+            Selection::FragmentSpread(spread) => (1, spread.fragment_name.as_str()),
+            Selection::InlineFragment(inline) => (
+                2,
+                inline
+                    .type_condition
+                    .as_ref()
+                    .map_or("", |condition| condition.as_str()),
+            ),
+        };
+        // Serialization breaks ties, such as between fields with different directives
+        (
+            rank,
+            name.to_owned(),
+            selection.serialize().no_indent().to_string(),
+        )
+    })
+}
@@ -13,20 +13,20 @@ impl ExecutableDocument {
     pub(crate) fn to_ast(&self) -> ast::Document {
         let mut doc = ast::Document::new();
         if let Some(operation) = &self.operations.anonymous {
-            doc.definitions.push(operation.to_ast(operation.location()))
+            doc.definitions.push(operation.to_ast(Some(operation)))
         }
         for operation in self.operations.named.values() {
-            doc.definitions.push(operation.to_ast(operation.location()))
+            doc.definitions.push(operation.to_ast(Some(operation)))
         }
         for fragment in self.fragments.values() {
-            doc.definitions.push(fragment.to_ast(fragment.location()))
+            doc.definitions.push(fragment.to_ast(Some(fragment)))
         }
         doc
     }
 }
 
 impl Operation {
-    fn to_ast(&self, location: Option<SourceSpan>) -> ast::Definition {
+    fn to_ast(&self, node: Option<&Node<Self>>) -> ast::Definition {
         let def = ast::OperationDefinition {
             operation_type: self.operation_type,
             name: self.name.clone(),
@@ -34,7 +34,10 @@ impl Operation {
             directives: self.directives.clone(),
             selection_set: self.selection_set.to_ast(),
         };
-        ast::Definition::OperationDefinition(Node::new_opt_location(def, location))
+        ast::Definition::OperationDefinition(match node {
+            Some(node) => node.same_location(def),
+            None => Node::new(def),
+        })
     }
 
     pub(crate) fn serialize_impl(&self, state: &mut State) -> fmt::Result {
@@ -43,14 +46,17 @@ impl Operation {
 }
 
 impl Fragment {
-    fn to_ast(&self, location: Option<SourceSpan>) -> ast::Definition {
+    fn to_ast(&self, node: Option<&Node<Self>>) -> ast::Definition {
         let def = ast::FragmentDefinition {
             name: self.name.clone(),
             type_condition: self.selection_set.ty.clone(),
             directives: self.directives.clone(),
             selection_set: self.selection_set.to_ast(),
         };
-        ast::Definition::FragmentDefinition(Node::new_opt_location(def, location))
+        ast::Definition::FragmentDefinition(match node {
+            Some(node) => node.same_location(def),
+            None => Node::new(def),
+        })
     }
 
     pub(crate) fn serialize_impl(&self, state: &mut State) -> fmt::Result {
@@ -0,0 +1,150 @@
+use super::*;
+use crate::collections::HashSet;
+use crate::execution::engine::eval_if_arg;
+
+struct Evaluator<'a> {
+    document: &'a ExecutableDocument,
+    variable_values: &'a Valid<JsonMap>,
+    /// Evaluated fragments in order of first use,
+    /// or `None` for those that do not select anything anymore
+    fragments: IndexMap<Name, Option<Node<Fragment>>>,
+    /// Variables referenced by kept selections, arguments, and directives
+    used_variables: HashSet<Name>,
+}
+
+pub(crate) fn evaluate_skip_include(
+    document: &ExecutableDocument,
+    operation: &Node<Operation>,
+    variable_values: &Valid<JsonMap>,
+) -> ExecutableDocument {
+    let mut evaluator = Evaluator {
+        document,
+        variable_values,
+        fragments: IndexMap::default(),
+        used_variables: HashSet::default(),
+    };
+    let mut operation = operation.clone();
+    let selection_set = evaluator.selection_set(&operation.selection_set);
+    evaluator.use_variables(&operation.directives, &[]);
+    let operation_mut = operation.make_mut();
+    operation_mut.selection_set = selection_set;
+    operation_mut
+        .variables
+        .retain(|variable| evaluator.used_variables.contains(&variable.name));
+    ExecutableDocument {
+        sources: document.sources.clone(),
+        operations: OperationMap::from_one(operation),
+        fragments: evaluator
+            .fragments
+            .into_iter()
+            .filter_map(|(name, fragment)| Some((name, fragment?)))
+            .collect(),
+    }
+}
+
+impl Evaluator<'_> {
+    /// Returns whether a spread of this fragment should be kept
+    fn fragment(&mut self, name: &Name) -> bool {
+        if let Some(evaluated) = self.fragments.get(name) {
+            return evaluated.is_some();
+        }
+        let Some(fragment) = self.document.fragments.get(name) else {
+            // Undefined fragment, leave the spread for validation to report
+            return true;
+        };
+        // Reserve the position of first use. Valid fragments are not recursive,
+        // so this `None` is only seen by spreads in a cycle.
+        self.fragments.insert(name.clone(), None);
+        let selection_set = self.selection_set(&fragment.selection_set);
+        if selection_set.selections.is_empty() {
+            // Selects nothing
+            return false;
+        }
+        self.use_variables(&fragment.directives, &[]);
+        let mut fragment = fragment.clone();
+        fragment.make_mut().selection_set = selection_set;
+        self.fragments.insert(name.clone(), Some(fragment));
+        true
+    }
+
+    fn selection_set(&mut self, selection_set: &SelectionSet) -> SelectionSet {
+        let mut result = SelectionSet::new(selection_set.ty.clone());
+        for selection in &selection_set.selections {
+            let skip = eval_if_arg(selection, "skip", self.variable_values);
+            let include = eval_if_arg(selection, "include", self.variable_values);
+            if skip == Some(true) || include == Some(false) {
+                continue;
+            }
+            // Both conditions are now either known to pass or undecided
+            let decided = |directive: &Node<Directive>| match directive.name.as_str() {
+                "skip" => skip.is_some(),
+                "include" => include.is_some(),
+                _ => false,
+            };
+            let selection = match selection {
+                Selection::Field(field) => {
+                    let selection_set = self.selection_set(&field.selection_set);
+                    if selection_set.selections.is_empty()
+                        && !field.selection_set.selections.is_empty()
+                    {
+                        // All sub-selections were removed
+                        continue;
+                    }
+                    let mut field = field.clone();
+                    let field_mut = field.make_mut();
+                    field_mut.directives.retain(|directive| !decided(directive));
+                    field_mut.selection_set = selection_set;
+                    self.use_variables(&field.directives, &field.arguments);
+                    Selection::Field(field)
+                }
+                Selection::FragmentSpread(spread) => {
+                    if !self.fragment(&spread.fragment_name) {
+                        continue;
+                    }
+                    let mut spread = spread.clone();
+                    spread
+                        .make_mut()
+                        .directives
+                        .retain(|directive| !decided(directive));
+                    self.use_variables(&spread.directives, &[]);
+                    Selection::FragmentSpread(spread)
+                }
+                Selection::InlineFragment(inline) => {
+                    let selection_set = self.selection_set(&inline.selection_set);
+                    if selection_set.selections.is_empty() {
+                        // Selects nothing
+                        continue;
+                    }
+                    let mut inline = inline.clone();
+                    let inline_mut = inline.make_mut();
+                    inline_mut
+                        .directives
+                        .retain(|directive| !decided(directive));
+                    inline_mut.selection_set = selection_set;
+                    self.use_variables(&inline.directives, &[]);
+                    Selection::InlineFragment(inline)
+                }
+            };
+            result.selections.push(selection)
+        }
+        result
+    }
+
+    fn use_variables(&mut self, directives: &DirectiveList, arguments: &[Node<Argument>]) {
+        let directive_arguments = directives.iter().flat_map(|directive| &directive.arguments);
+        let mut values: Vec<&Value> = directive_arguments
+            .chain(arguments)
+            .map(|argument| &*argument.value)
+            .collect();
+        while let Some(value) = values.pop() {
+            match value {
+                Value::Variable(name) => {
+                    self.used_variables.insert(name.clone());
+                }
+                Value::List(items) => values.extend(items.iter().map(|item| &**item)),
+                Value::Object(fields) => values.extend(fields.iter().map(|(_, value)| &**value)),
+                _ => {}
+            }
+        }
+    }
+}
@@ -2,6 +2,7 @@ use super::FieldSet;
 use crate::validation::fragment::validate_fragments_used;
 use crate::validation::operation::validate_operation_definitions;
 use crate::validation::selection::FieldsInSetCanMerge;
+use crate::validation::DiagnosticCode;
 use crate::validation::DiagnosticList;
 use crate::validation::ExecutableValidationContext;
 use crate::validation::Valid;
@@ -13,7 +14,18 @@ pub(crate) fn validate_executable_document(
     schema: &Schema,
     document: &ExecutableDocument,
 ) {
-    validate_with_or_without_schema(errors, Some(schema), document);
+    let context = ExecutableValidationContext::new(Some(schema));
+    validate_executable_document_with_context(errors, &context, schema, document)
+}
+
+/// Like [`validate_executable_document`], with a context that may be shared with other rules
+pub(crate) fn validate_executable_document_with_context(
+    errors: &mut DiagnosticList,
+    context: &ExecutableValidationContext<'_>,
+    schema: &Schema,
+    document: &ExecutableDocument,
+) {
+    validate_with_or_without_schema(errors, context, document);
     validate_with_schema(errors, schema, document);
 }
 
@@ -21,7 +33,8 @@ pub(crate) fn validate_standalone_executable(
     errors: &mut DiagnosticList,
     document: &ExecutableDocument,
 ) {
-    validate_with_or_without_schema(errors, None, document);
+    let context = ExecutableValidationContext::new(None);
+    validate_with_or_without_schema(errors, &context, document);
 }
 
 fn validate_with_schema(
@@ -31,20 +44,40 @@ fn validate_with_schema(
 ) {
     let alloc = typed_arena::Arena::new();
     let mut fields_in_set_can_merge = FieldsInSetCanMerge::new(&alloc, schema, document);
+    let check_subscriptions = errors.should_check(&[
+        DiagnosticCode::SubscriptionUsesMultipleFields,
+        DiagnosticCode::SubscriptionUsesIntrospection,
+        DiagnosticCode::SubscriptionUsesConditionalSelection,
+        DiagnosticCode::RecursionError,
+    ]);
+    let check_field_merging = errors.should_check(&[
+        DiagnosticCode::ConflictingFieldType,
+        DiagnosticCode::ConflictingFieldArgument,
+        DiagnosticCode::ConflictingFieldName,
+        DiagnosticCode::RecursionLimitError,
+    ]);
     for operation in document.operations.iter() {
-        crate::validation::operation::validate_subscription(document, operation, errors);
-        fields_in_set_can_merge.validate_operation(operation, errors);
+        if check_subscriptions {
+            crate::validation::operation::validate_subscription(document, operation, errors);
+        }
+        if check_field_merging {
+            fields_in_set_can_merge.validate_operation(operation, errors);
+        }
     }
 }
 
-pub(crate) fn validate_with_or_without_schema(
+fn validate_with_or_without_schema(
     errors: &mut DiagnosticList,
-    schema: Option<&Schema>,
+    context: &ExecutableValidationContext<'_>,
     document: &ExecutableDocument,
 ) {
-    let context = ExecutableValidationContext::new(schema);
-    validate_operation_definitions(errors, document, &context);
-    validate_fragments_used(errors, document);
+    validate_operation_definitions(errors, document, context);
+    if errors.should_check(&[
+        DiagnosticCode::UnusedFragment,
+        DiagnosticCode::RecursionLimitError,
+    ]) {
+        validate_fragments_used(errors, document);
+    }
 }
 
 pub(crate) fn validate_field_set(
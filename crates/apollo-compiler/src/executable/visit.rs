@@ -0,0 +1,922 @@
+//! Depth-first traversal of an executable document with type information
+
+use crate::executable::Argument;
+use crate::executable::Directive;
+use crate::executable::DirectiveList;
+use crate::executable::ExecutableDocument;
+use crate::executable::Field;
+use crate::executable::Fragment;
+use crate::executable::FragmentSpread;
+use crate::executable::InlineFragment;
+use crate::executable::Operation;
+use crate::executable::Selection;
+use crate::executable::SelectionSet;
+use crate::executable::Type;
+use crate::executable::Value;
+use crate::executable::VariableDefinition;
+use crate::schema::Component;
+use crate::schema::DirectiveDefinition;
+use crate::schema::ExtendedType;
+use crate::schema::FieldDefinition;
+use crate::schema::InputValueDefinition;
+use crate::validation::Valid;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+
+/// Returned by [`Visitor`] and [`VisitorMut`] methods to control the traversal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VisitControl {
+    /// Visit children of this node, then call the corresponding `leave_*` method
+    #[default]
+    Continue,
+    /// Don’t visit children of this node, nor call the corresponding `leave_*` method
+    SkipChildren,
+    /// Stop the traversal entirely
+    Stop,
+}
+
+/// Definitions from the schema relevant to the node being visited,
+/// similar to `TypeInfo` in graphql-js.
+///
+/// Methods return `None` when the corresponding definition does not exist,
+/// for example when visiting a document that is not valid against the schema.
+pub struct TypeInfo<'schema> {
+    schema: &'schema Valid<Schema>,
+    parent_types: Vec<Option<&'schema ExtendedType>>,
+    field_definitions: Vec<Option<&'schema Component<FieldDefinition>>>,
+    directive_definition: Option<&'schema Node<DirectiveDefinition>>,
+    argument_definition: Option<&'schema Node<InputValueDefinition>>,
+    input_types: Vec<Option<Type>>,
+}
+
+/// Read-only visitor for [`ExecutableDocument::walk`].
+///
+/// All methods have default implementations that do nothing,
+/// so implementations only need to define the methods they’re interested in.
+/// `enter_*` methods are called before visiting children, `leave_*` methods after.
+///
+/// Operations are visited first (anonymous then named), then fragment definitions.
+/// Fragment spreads are not followed: fragment definitions are visited once each.
+///
+/// These traits only cover [`ExecutableDocument`].
+/// An [`ast::Document`][crate::ast::Document] with executable definitions
+/// can be converted with [`to_executable`][crate::ast::Document::to_executable] first.
+/// Type system definitions are visited with [`schema::Visitor`][crate::schema::Visitor].
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::executable::Field;
+/// use apollo_compiler::executable::TypeInfo;
+/// use apollo_compiler::executable::VisitControl;
+/// use apollo_compiler::executable::Visitor;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Node;
+/// use apollo_compiler::Schema;
+///
+/// #[derive(Default)]
+/// struct DeprecatedFields(Vec<String>);
+///
+/// impl<'doc> Visitor<'doc> for DeprecatedFields {
+///     fn enter_field(&mut self, info: &TypeInfo<'_>, field: &'doc Node<Field>) -> VisitControl {
+///         if let (Some(parent), Some(def)) = (info.parent_type(), info.field_definition()) {
+///             if def.directives.has("deprecated") {
+///                 self.0.push(format!("{}.{}", parent.name(), field.name))
+///             }
+///         }
+///         VisitControl::Continue
+///     }
+/// }
+///
+/// let schema = Schema::parse_and_validate(
+///     "type Query { a: Int @deprecated, b: Query }",
+///     "schema.graphql",
+/// ).unwrap();
+/// let doc = ExecutableDocument::parse_and_validate(
+///     &schema,
+///     "{ a b { a } }",
+///     "query.graphql",
+/// ).unwrap();
+/// let mut visitor = DeprecatedFields::default();
+/// doc.walk(&schema, &mut visitor);
+/// assert_eq!(visitor.0, ["Query.a", "Query.a"]);
+/// ```
+#[allow(unused_variables)]
+pub trait Visitor<'doc> {
+    fn enter_operation(
+        &mut self,
+        info: &TypeInfo<'_>,
+        operation: &'doc Node<Operation>,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_operation(&mut self, info: &TypeInfo<'_>, operation: &'doc Node<Operation>) {}
+
+    fn enter_fragment(
+        &mut self,
+        info: &TypeInfo<'_>,
+        fragment: &'doc Node<Fragment>,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_fragment(&mut self, info: &TypeInfo<'_>, fragment: &'doc Node<Fragment>) {}
+
+    fn enter_variable_definition(
+        &mut self,
+        info: &TypeInfo<'_>,
+        variable: &'doc Node<VariableDefinition>,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_variable_definition(
+        &mut self,
+        info: &TypeInfo<'_>,
+        variable: &'doc Node<VariableDefinition>,
+    ) {
+    }
+
+    fn enter_selection_set(
+        &mut self,
+        info: &TypeInfo<'_>,
+        selection_set: &'doc SelectionSet,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_selection_set(&mut self, info: &TypeInfo<'_>, selection_set: &'doc SelectionSet) {}
+
+    fn enter_field(&mut self, info: &TypeInfo<'_>, field: &'doc Node<Field>) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_field(&mut self, info: &TypeInfo<'_>, field: &'doc Node<Field>) {}
+
+    fn enter_fragment_spread(
+        &mut self,
+        info: &TypeInfo<'_>,
+        spread: &'doc Node<FragmentSpread>,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_fragment_spread(&mut self, info: &TypeInfo<'_>, spread: &'doc Node<FragmentSpread>) {}
+
+    fn enter_inline_fragment(
+        &mut self,
+        info: &TypeInfo<'_>,
+        inline: &'doc Node<InlineFragment>,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_inline_fragment(&mut self, info: &TypeInfo<'_>, inline: &'doc Node<InlineFragment>) {}
+
+    fn enter_directive(
+        &mut self,
+        info: &TypeInfo<'_>,
+        directive: &'doc Node<Directive>,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_directive(&mut self, info: &TypeInfo<'_>, directive: &'doc Node<Directive>) {}
+
+    fn enter_argument(
+        &mut self,
+        info: &TypeInfo<'_>,
+        argument: &'doc Node<Argument>,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_argument(&mut self, info: &TypeInfo<'_>, argument: &'doc Node<Argument>) {}
+
+    /// Called for values of arguments and variable default values,
+    /// and recursively for list items and input object fields
+    fn enter_value(&mut self, info: &TypeInfo<'_>, value: &'doc Node<Value>) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_value(&mut self, info: &TypeInfo<'_>, value: &'doc Node<Value>) {}
+}
+
+/// Mutable visitor for [`ExecutableDocument::walk_mut`].
+///
+/// This has the same methods as [`Visitor`] but with mutable references,
+/// allowing to modify or replace nodes.
+/// If an `enter_*` method replaces its node, the children of the new node are visited.
+/// To remove selections, use [`leave_selection_set`][Self::leave_selection_set].
+///
+/// During an `enter_*` call, [`TypeInfo`] describes the node as it was before the call.
+/// If the method renames or replaces the node,
+/// `TypeInfo` is updated before visiting children and calling `leave_*`.
+///
+/// Children are visited on copies, and a node is only made mutable with [`Node::make_mut`]
+/// when the visitor modified it or one of its descendants.
+/// Unmodified nodes stay shared with other documents.
+#[allow(unused_variables)]
+pub trait VisitorMut {
+    fn enter_operation(
+        &mut self,
+        info: &TypeInfo<'_>,
+        operation: &mut Node<Operation>,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_operation(&mut self, info: &TypeInfo<'_>, operation: &mut Node<Operation>) {}
+
+    fn enter_fragment(
+        &mut self,
+        info: &TypeInfo<'_>,
+        fragment: &mut Node<Fragment>,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_fragment(&mut self, info: &TypeInfo<'_>, fragment: &mut Node<Fragment>) {}
+
+    fn enter_variable_definition(
+        &mut self,
+        info: &TypeInfo<'_>,
+        variable: &mut Node<VariableDefinition>,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_variable_definition(
+        &mut self,
+        info: &TypeInfo<'_>,
+        variable: &mut Node<VariableDefinition>,
+    ) {
+    }
+
+    fn enter_selection_set(
+        &mut self,
+        info: &TypeInfo<'_>,
+        selection_set: &mut SelectionSet,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_selection_set(&mut self, info: &TypeInfo<'_>, selection_set: &mut SelectionSet) {}
+
+    fn enter_field(&mut self, info: &TypeInfo<'_>, field: &mut Node<Field>) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_field(&mut self, info: &TypeInfo<'_>, field: &mut Node<Field>) {}
+
+    fn enter_fragment_spread(
+        &mut self,
+        info: &TypeInfo<'_>,
+        spread: &mut Node<FragmentSpread>,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_fragment_spread(&mut self, info: &TypeInfo<'_>, spread: &mut Node<FragmentSpread>) {}
+
+    fn enter_inline_fragment(
+        &mut self,
+        info: &TypeInfo<'_>,
+        inline: &mut Node<InlineFragment>,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_inline_fragment(&mut self, info: &TypeInfo<'_>, inline: &mut Node<InlineFragment>) {}
+
+    fn enter_directive(
+        &mut self,
+        info: &TypeInfo<'_>,
+        directive: &mut Node<Directive>,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_directive(&mut self, info: &TypeInfo<'_>, directive: &mut Node<Directive>) {}
+
+    fn enter_argument(
+        &mut self,
+        info: &TypeInfo<'_>,
+        argument: &mut Node<Argument>,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_argument(&mut self, info: &TypeInfo<'_>, argument: &mut Node<Argument>) {}
+
+    /// Called for values of arguments and variable default values,
+    /// and recursively for list items and input object fields
+    fn enter_value(&mut self, info: &TypeInfo<'_>, value: &mut Node<Value>) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_value(&mut self, info: &TypeInfo<'_>, value: &mut Node<Value>) {}
+}
+
+impl<'schema> TypeInfo<'schema> {
+    fn new(schema: &'schema Valid<Schema>) -> Self {
+        Self {
+            schema,
+            parent_types: Vec::new(),
+            field_definitions: Vec::new(),
+            directive_definition: None,
+            argument_definition: None,
+            input_types: Vec::new(),
+        }
+    }
+
+    pub fn schema(&self) -> &'schema Valid<Schema> {
+        self.schema
+    }
+
+    /// The type of the innermost selection set being visited:
+    /// the parent type of a field being visited
+    pub fn parent_type(&self) -> Option<&'schema ExtendedType> {
+        self.parent_types.last().copied().flatten()
+    }
+
+    /// The definition of the innermost field being visited
+    pub fn field_definition(&self) -> Option<&'schema Component<FieldDefinition>> {
+        self.field_definitions.last().copied().flatten()
+    }
+
+    /// The definition of the directive being visited
+    pub fn directive_definition(&self) -> Option<&'schema Node<DirectiveDefinition>> {
+        self.directive_definition
+    }
+
+    /// The definition of the field or directive argument being visited
+    pub fn argument_definition(&self) -> Option<&'schema Node<InputValueDefinition>> {
+        self.argument_definition
+    }
+
+    /// The expected type of the value being visited,
+    /// or the type of the variable definition being visited
+    pub fn input_type(&self) -> Option<&Type> {
+        self.input_types.last()?.as_ref()
+    }
+
+    /// The expected type of the list or input object value that contains the value being visited
+    pub fn parent_input_type(&self) -> Option<&Type> {
+        let len = self.input_types.len();
+        self.input_types.get(len.checked_sub(2)?)?.as_ref()
+    }
+
+    fn push_selection_set(&mut self, selection_set: &SelectionSet) {
+        self.parent_types
+            .push(self.schema.types.get(&selection_set.ty))
+    }
+
+    fn pop_selection_set(&mut self) {
+        self.parent_types.pop();
+    }
+
+    fn push_field(&mut self, name: &Name) {
+        let def = self
+            .parent_type()
+            .and_then(|parent| self.schema.type_field(parent.name(), name).ok());
+        self.field_definitions.push(def)
+    }
+
+    fn pop_field(&mut self) {
+        self.field_definitions.pop();
+    }
+
+    fn push_directive(&mut self, name: &Name) {
+        self.directive_definition = self.schema.directive_definitions.get(name)
+    }
+
+    fn pop_directive(&mut self) {
+        self.directive_definition = None
+    }
+
+    fn push_argument(&mut self, name: &Name) {
+        let arguments = if let Some(def) = self.directive_definition {
+            &def.arguments
+        } else if let Some(def) = self.field_definition() {
+            &def.arguments
+        } else {
+            self.argument_definition = None;
+            self.input_types.push(None);
+            return;
+        };
+        self.argument_definition = arguments.iter().find(|def| def.name == *name);
+        self.input_types
+            .push(self.argument_definition.map(|def| (*def.ty).clone()))
+    }
+
+    fn pop_argument(&mut self) {
+        self.argument_definition = None;
+        self.input_types.pop();
+    }
+
+    fn push_input_type(&mut self, ty: Option<Type>) {
+        self.input_types.push(ty)
+    }
+
+    fn pop_input_type(&mut self) {
+        self.input_types.pop();
+    }
+
+    fn list_item_type(&self) -> Option<Type> {
+        self.input_type()
+            .filter(|ty| ty.is_list())
+            .map(|ty| ty.item_type().clone())
+    }
+
+    fn input_field_type(&self, name: &Name) -> Option<Type> {
+        let ty = self.input_type()?;
+        let def = self.schema.get_input_object(ty.inner_named_type())?;
+        Some((*def.fields.get(name)?.ty).clone())
+    }
+}
+
+/// Marker for [`VisitControl::Stop`], propagated with `?`
+pub(crate) struct Stop;
+
+/// Calls an `enter_*` method, returning early if the traversal should not continue
+macro_rules! enter {
+    ($walker: ident . $method: ident ( $node: expr )) => {
+        match $walker.visitor.$method(&$walker.info, $node) {
+            VisitControl::Continue => true,
+            VisitControl::SkipChildren => false,
+            VisitControl::Stop => return Err(Stop),
+        }
+    };
+}
+
+pub(crate) fn walk<'doc>(
+    schema: &Valid<Schema>,
+    document: &'doc ExecutableDocument,
+    visitor: &mut impl Visitor<'doc>,
+) {
+    let mut walker = Walker {
+        info: TypeInfo::new(schema),
+        visitor,
+    };
+    let _: Result<(), Stop> = walker.document(document);
+}
+
+pub(crate) fn walk_mut(
+    schema: &Valid<Schema>,
+    document: &mut ExecutableDocument,
+    visitor: &mut impl VisitorMut,
+) {
+    let mut walker = WalkerMut {
+        info: TypeInfo::new(schema),
+        visitor,
+    };
+    let _: Result<(), Stop> = walker.document(document);
+}
+
+struct Walker<'schema, 'v, V> {
+    info: TypeInfo<'schema>,
+    visitor: &'v mut V,
+}
+
+impl<'doc, V: Visitor<'doc>> Walker<'_, '_, V> {
+    fn document(&mut self, document: &'doc ExecutableDocument) -> Result<(), Stop> {
+        for operation in document.operations.iter() {
+            self.operation(operation)?
+        }
+        for fragment in document.fragments.values() {
+            self.fragment(fragment)?
+        }
+        Ok(())
+    }
+
+    fn operation(&mut self, operation: &'doc Node<Operation>) -> Result<(), Stop> {
+        self.info.push_selection_set(&operation.selection_set);
+        if enter!(self.enter_operation(operation)) {
+            for variable in &operation.variables {
+                self.variable_definition(variable)?
+            }
+            self.directives(&operation.directives)?;
+            self.selection_set(&operation.selection_set)?;
+            self.visitor.leave_operation(&self.info, operation)
+        }
+        self.info.pop_selection_set();
+        Ok(())
+    }
+
+    fn fragment(&mut self, fragment: &'doc Node<Fragment>) -> Result<(), Stop> {
+        self.info.push_selection_set(&fragment.selection_set);
+        if enter!(self.enter_fragment(fragment)) {
+            self.directives(&fragment.directives)?;
+            self.selection_set(&fragment.selection_set)?;
+            self.visitor.leave_fragment(&self.info, fragment)
+        }
+        self.info.pop_selection_set();
+        Ok(())
+    }
+
+    fn variable_definition(
+        &mut self,
+        variable: &'doc Node<VariableDefinition>,
+    ) -> Result<(), Stop> {
+        self.info.push_input_type(Some((*variable.ty).clone()));
+        if enter!(self.enter_variable_definition(variable)) {
+            if let Some(value) = &variable.default_value {
+                self.value(value)?
+            }
+            self.directives(&variable.directives)?;
+            self.visitor.leave_variable_definition(&self.info, variable)
+        }
+        self.info.pop_input_type();
+        Ok(())
+    }
+
+    /// Expects `self.info` to already have the type of this selection set
+    fn selection_set(&mut self, selection_set: &'doc SelectionSet) -> Result<(), Stop> {
+        if !enter!(self.enter_selection_set(selection_set)) {
+            return Ok(());
+        }
+        for selection in &selection_set.selections {
+            match selection {
+                Selection::Field(field) => self.field(field)?,
+                Selection::FragmentSpread(spread) => {
+                    if enter!(self.enter_fragment_spread(spread)) {
+                        self.directives(&spread.directives)?;
+                        self.visitor.leave_fragment_spread(&self.info, spread)
+                    }
+                }
+                Selection::InlineFragment(inline) => {
+                    if enter!(self.enter_inline_fragment(inline)) {
+                        self.directives(&inline.directives)?;
+                        self.info.push_selection_set(&inline.selection_set);
+                        self.selection_set(&inline.selection_set)?;
+                        self.info.pop_selection_set();
+                        self.visitor.leave_inline_fragment(&self.info, inline)
+                    }
+                }
+            }
+        }
+        self.visitor.leave_selection_set(&self.info, selection_set);
+        Ok(())
+    }
+
+    fn field(&mut self, field: &'doc Node<Field>) -> Result<(), Stop> {
+        self.info.push_field(&field.name);
+        if enter!(self.enter_field(field)) {
+            self.arguments(&field.arguments)?;
+            self.directives(&field.directives)?;
+            self.info.push_selection_set(&field.selection_set);
+            self.selection_set(&field.selection_set)?;
+            self.info.pop_selection_set();
+            self.visitor.leave_field(&self.info, field)
+        }
+        self.info.pop_field();
+        Ok(())
+    }
+
+    fn directives(&mut self, directives: &'doc DirectiveList) -> Result<(), Stop> {
+        for directive in directives {
+            self.info.push_directive(&directive.name);
+            if enter!(self.enter_directive(directive)) {
+                self.arguments(&directive.arguments)?;
+                self.visitor.leave_directive(&self.info, directive)
+            }
+            self.info.pop_directive();
+        }
+        Ok(())
+    }
+
+    fn arguments(&mut self, arguments: &'doc [Node<Argument>]) -> Result<(), Stop> {
+        for argument in arguments {
+            self.info.push_argument(&argument.name);
+            if enter!(self.enter_argument(argument)) {
+                self.value(&argument.value)?;
+                self.visitor.leave_argument(&self.info, argument)
+            }
+            self.info.pop_argument();
+        }
+        Ok(())
+    }
+
+    /// Expects `self.info` to already have the expected type of this value
+    fn value(&mut self, value: &'doc Node<Value>) -> Result<(), Stop> {
+        if !enter!(self.enter_value(value)) {
+            return Ok(());
+        }
+        match value.as_ref() {
+            Value::List(items) => {
+                for item in items {
+                    self.info.push_input_type(self.info.list_item_type());
+                    self.value(item)?;
+                    self.info.pop_input_type();
+                }
+            }
+            Value::Object(fields) => {
+                for (name, field_value) in fields {
+                    self.info.push_input_type(self.info.input_field_type(name));
+                    self.value(field_value)?;
+                    self.info.pop_input_type();
+                }
+            }
+            Value::Null
+            | Value::Enum(_)
+            | Value::Variable(_)
+            | Value::String(_)
+            | Value::Float(_)
+            | Value::Int(_)
+            | Value::Boolean(_) => {}
+        }
+        self.visitor.leave_value(&self.info, value);
+        Ok(())
+    }
+}
+
+struct WalkerMut<'schema, 'v, V> {
+    info: TypeInfo<'schema>,
+    visitor: &'v mut V,
+}
+
+/// Pointer equality of nodes, to detect whether a visitor changed anything
+pub(crate) trait SameNodes {
+    fn same_nodes(&self, other: &Self) -> bool;
+}
+
+impl<T> SameNodes for Vec<Node<T>> {
+    fn same_nodes(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a.ptr_eq(b))
+    }
+}
+
+impl SameNodes for DirectiveList {
+    fn same_nodes(&self, other: &Self) -> bool {
+        self.0.same_nodes(&other.0)
+    }
+}
+
+impl SameNodes for Option<Node<Value>> {
+    fn same_nodes(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Some(a), Some(b)) => a.ptr_eq(b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl SameNodes for SelectionSet {
+    fn same_nodes(&self, other: &Self) -> bool {
+        self.ty == other.ty
+            && self.selections.len() == other.selections.len()
+            && self
+                .selections
+                .iter()
+                .zip(&other.selections)
+                .all(|pair| match pair {
+                    (Selection::Field(a), Selection::Field(b)) => a.ptr_eq(b),
+                    (Selection::FragmentSpread(a), Selection::FragmentSpread(b)) => a.ptr_eq(b),
+                    (Selection::InlineFragment(a), Selection::InlineFragment(b)) => a.ptr_eq(b),
+                    _ => false,
+                })
+    }
+}
+
+impl SameNodes for Value {
+    fn same_nodes(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::List(a), Value::List(b)) => a.same_nodes(b),
+            (Value::Object(a), Value::Object(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .zip(b)
+                        .all(|((name_a, a), (name_b, b))| name_a == name_b && a.ptr_eq(b))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Walks a copy of `$node.$part` with `$walk`,
+/// then only makes `$node` mutable if the copy was changed.
+/// Evaluates to the result of `$walk`.
+macro_rules! walk_part {
+    ($node: ident . $part: ident, |$copy: ident| $walk: expr) => {{
+        let mut $copy = $node.$part.clone();
+        let result = $walk;
+        if !$copy.same_nodes(&$node.$part) {
+            $node.make_mut().$part = $copy
+        }
+        result
+    }};
+}
+pub(crate) use walk_part;
+
+impl<V: VisitorMut> WalkerMut<'_, '_, V> {
+    fn document(&mut self, document: &mut ExecutableDocument) -> Result<(), Stop> {
+        let operations = &mut document.operations;
+        for operation in operations
+            .anonymous
+            .iter_mut()
+            .chain(operations.named.values_mut())
+        {
+            self.operation(operation)?
+        }
+        for fragment in document.fragments.values_mut() {
+            self.fragment(fragment)?
+        }
+        Ok(())
+    }
+
+    fn operation(&mut self, operation: &mut Node<Operation>) -> Result<(), Stop> {
+        self.info.push_selection_set(&operation.selection_set);
+        if enter!(self.enter_operation(operation)) {
+            // The visitor may have replaced the operation
+            self.info.pop_selection_set();
+            self.info.push_selection_set(&operation.selection_set);
+            walk_part!(operation.variables, |variables| {
+                variables
+                    .iter_mut()
+                    .try_for_each(|variable| self.variable_definition(variable))
+            })?;
+            walk_part!(operation.directives, |directives| self
+                .directives(&mut directives))?;
+            walk_part!(operation.selection_set, |selection_set| self
+                .selection_set(&mut selection_set))?;
+            self.visitor.leave_operation(&self.info, operation)
+        }
+        self.info.pop_selection_set();
+        Ok(())
+    }
+
+    fn fragment(&mut self, fragment: &mut Node<Fragment>) -> Result<(), Stop> {
+        self.info.push_selection_set(&fragment.selection_set);
+        if enter!(self.enter_fragment(fragment)) {
+            // The visitor may have replaced the fragment
+            self.info.pop_selection_set();
+            self.info.push_selection_set(&fragment.selection_set);
+            walk_part!(fragment.directives, |directives| self
+                .directives(&mut directives))?;
+            walk_part!(fragment.selection_set, |selection_set| self
+                .selection_set(&mut selection_set))?;
+            self.visitor.leave_fragment(&self.info, fragment)
+        }
+        self.info.pop_selection_set();
+        Ok(())
+    }
+
+    fn variable_definition(&mut self, variable: &mut Node<VariableDefinition>) -> Result<(), Stop> {
+        self.info.push_input_type(Some((*variable.ty).clone()));
+        if enter!(self.enter_variable_definition(variable)) {
+            // The visitor may have changed the type
+            self.info.pop_input_type();
+            self.info.push_input_type(Some((*variable.ty).clone()));
+            walk_part!(variable.default_value, |default_value| {
+                match &mut default_value {
+                    Some(value) => self.value(value),
+                    None => Ok(()),
+                }
+            })?;
+            walk_part!(variable.directives, |directives| self
+                .directives(&mut directives))?;
+            self.visitor.leave_variable_definition(&self.info, variable)
+        }
+        self.info.pop_input_type();
+        Ok(())
+    }
+
+    /// Expects `self.info` to already have the type of this selection set
+    fn selection_set(&mut self, selection_set: &mut SelectionSet) -> Result<(), Stop> {
+        if !enter!(self.enter_selection_set(selection_set)) {
+            return Ok(());
+        }
+        for selection in &mut selection_set.selections {
+            match selection {
+                Selection::Field(field) => self.field(field)?,
+                Selection::FragmentSpread(spread) => {
+                    if enter!(self.enter_fragment_spread(spread)) {
+                        walk_part!(spread.directives, |directives| self
+                            .directives(&mut directives))?;
+                        self.visitor.leave_fragment_spread(&self.info, spread)
+                    }
+                }
+                Selection::InlineFragment(inline) => {
+                    if enter!(self.enter_inline_fragment(inline)) {
+                        walk_part!(inline.directives, |directives| self
+                            .directives(&mut directives))?;
+                        self.info.push_selection_set(&inline.selection_set);
+                        let result = walk_part!(inline.selection_set, |selection_set| self
+                            .selection_set(&mut selection_set));
+                        self.info.pop_selection_set();
+                        result?;
+                        self.visitor.leave_inline_fragment(&self.info, inline)
+                    }
+                }
+            }
+        }
+        self.visitor.leave_selection_set(&self.info, selection_set);
+        Ok(())
+    }
+
+    fn field(&mut self, field: &mut Node<Field>) -> Result<(), Stop> {
+        self.info.push_field(&field.name);
+        if enter!(self.enter_field(field)) {
+            // The visitor may have renamed or replaced the field
+            self.info.pop_field();
+            self.info.push_field(&field.name);
+            walk_part!(field.arguments, |arguments| self.arguments(&mut arguments))?;
+            walk_part!(field.directives, |directives| self
+                .directives(&mut directives))?;
+            self.info.push_selection_set(&field.selection_set);
+            let result = walk_part!(field.selection_set, |selection_set| self
+                .selection_set(&mut selection_set));
+            self.info.pop_selection_set();
+            result?;
+            self.visitor.leave_field(&self.info, field)
+        }
+        self.info.pop_field();
+        Ok(())
+    }
+
+    fn directives(&mut self, directives: &mut DirectiveList) -> Result<(), Stop> {
+        for directive in directives.iter_mut() {
+            self.info.push_directive(&directive.name);
+            if enter!(self.enter_directive(directive)) {
+                // The visitor may have renamed or replaced the directive
+                self.info.pop_directive();
+                self.info.push_directive(&directive.name);
+                walk_part!(directive.arguments, |arguments| self
+                    .arguments(&mut arguments))?;
+                self.visitor.leave_directive(&self.info, directive)
+            }
+            self.info.pop_directive();
+        }
+        Ok(())
+    }
+
+    fn arguments(&mut self, arguments: &mut [Node<Argument>]) -> Result<(), Stop> {
+        for argument in arguments {
+            self.info.push_argument(&argument.name);
+            if enter!(self.enter_argument(argument)) {
+                // The visitor may have renamed or replaced the argument
+                self.info.pop_argument();
+                self.info.push_argument(&argument.name);
+                let mut value = argument.value.clone();
+                let result = self.value(&mut value);
+                if !value.ptr_eq(&argument.value) {
+                    argument.make_mut().value = value
+                }
+                result?;
+                self.visitor.leave_argument(&self.info, argument)
+            }
+            self.info.pop_argument();
+        }
+        Ok(())
+    }
+
+    /// Expects `self.info` to already have the expected type of this value
+    fn value(&mut self, value: &mut Node<Value>) -> Result<(), Stop> {
+        if !enter!(self.enter_value(value)) {
+            return Ok(());
+        }
+        if matches!(**value, Value::List(_) | Value::Object(_)) {
+            let mut copy = Value::clone(value);
+            let result = self.value_children(&mut copy);
+            if !copy.same_nodes(value) {
+                *value.make_mut() = copy
+            }
+            result?
+        }
+        self.visitor.leave_value(&self.info, value);
+        Ok(())
+    }
+
+    fn value_children(&mut self, value: &mut Value) -> Result<(), Stop> {
+        match value {
+            Value::List(items) => {
+                for item in items {
+                    self.info.push_input_type(self.info.list_item_type());
+                    let result = self.value(item);
+                    self.info.pop_input_type();
+                    result?
+                }
+            }
+            Value::Object(fields) => {
+                for (name, field_value) in fields {
+                    self.info.push_input_type(self.info.input_field_type(name));
+                    let result = self.value(field_value);
+                    self.info.pop_input_type();
+                    result?
+                }
+            }
+            Value::Null
+            | Value::Enum(_)
+            | Value::Variable(_)
+            | Value::String(_)
+            | Value::Float(_)
+            | Value::Int(_)
+            | Value::Boolean(_) => {}
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,358 @@
+//! Async equivalent of `engine` and `result_coercion`,
+//! calling [`AsyncResolver`]s and resolving sibling fields concurrently.
+
+use crate::collections::HashSet;
+use crate::collections::IndexMap;
+use crate::executable::Field;
+use crate::executable::Selection;
+use crate::execution::engine;
+use crate::execution::engine::collect_fields;
+use crate::execution::engine::try_nullify;
+use crate::execution::engine::ExecutionMode;
+use crate::execution::engine::LinkedPath;
+use crate::execution::engine::LinkedPathElement;
+use crate::execution::engine::PropagateNull;
+use crate::execution::input_coercion::coerce_argument_values;
+use crate::execution::resolver::AsyncResolvedValue;
+use crate::execution::resolver::AsyncResolver;
+use crate::execution::resolver::ResolverError;
+use crate::execution::result_coercion::complete_leaf_value;
+use crate::introspection::resolvers::IntrospectionRootResolver;
+use crate::response::GraphQLError;
+use crate::response::JsonMap;
+use crate::response::JsonValue;
+use crate::response::ResponseDataPathSegment;
+use crate::schema::ExtendedType;
+use crate::schema::FieldDefinition;
+use crate::schema::ObjectType;
+use crate::schema::Type;
+use crate::validation::SuspectedValidationBug;
+use crate::validation::Valid;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Schema;
+use futures::future::join_all;
+use futures::future::BoxFuture;
+use futures::StreamExt;
+
+/// <https://spec.graphql.org/October2021/#ExecuteSelectionSet()>
+///
+/// In [`ExecutionMode::Normal`], fields are resolved concurrently.
+/// Errors are still reported in the same order as with the sync engine.
+///
+/// `introspection` is only expected for the root selection set of a query.
+#[allow(clippy::too_many_arguments)] // yes it’s not a nice API but it’s internal
+pub(crate) fn execute_selection_set<'a>(
+    schema: &'a Valid<Schema>,
+    document: &'a Valid<ExecutableDocument>,
+    variable_values: &'a Valid<JsonMap>,
+    errors: &'a mut Vec<GraphQLError>,
+    path: LinkedPath<'a>,
+    mode: ExecutionMode,
+    introspection: Option<&'a IntrospectionRootResolver<'a>>,
+    object_type: &'a ObjectType,
+    object_value: &'a (dyn AsyncResolver + 'a),
+    selections: impl IntoIterator<Item = &'a Selection>,
+) -> BoxFuture<'a, Result<JsonMap, PropagateNull>> {
+    let mut grouped_field_set = IndexMap::default();
+    collect_fields(
+        schema,
+        document,
+        variable_values,
+        object_type,
+        &|field_name| object_value.skip_field(field_name),
+        selections,
+        &mut HashSet::default(),
+        &mut grouped_field_set,
+    );
+    Box::pin(async move {
+        let field_entries = grouped_field_set.iter().map(|(&response_key, fields)| {
+            let field_name = &fields[0].name;
+            let field_def = schema.type_field(&object_type.name, field_name).ok();
+            async move {
+                // TODO: Return a `validation_bug`` field error if `field_def` is `None`?
+                // See the corresponding comment in the sync engine.
+                let field_def = field_def?;
+                let mut field_errors = Vec::new();
+                let value = if field_name == "__typename" {
+                    Ok(JsonValue::from(object_type.name.as_str()))
+                } else {
+                    let field_path = LinkedPathElement {
+                        element: ResponseDataPathSegment::Field(response_key.clone()),
+                        next: path,
+                    };
+                    match introspection {
+                        Some(introspection)
+                            if matches!(field_name.as_str(), "__schema" | "__type") =>
+                        {
+                            engine::execute_field(
+                                schema,
+                                document,
+                                variable_values,
+                                &mut field_errors,
+                                Some(&field_path),
+                                ExecutionMode::Normal,
+                                introspection,
+                                field_def,
+                                fields,
+                            )
+                        }
+                        _ => {
+                            execute_field(
+                                schema,
+                                document,
+                                variable_values,
+                                &mut field_errors,
+                                Some(&field_path),
+                                object_value,
+                                field_def,
+                                fields,
+                            )
+                            .await
+                        }
+                    }
+                };
+                Some((response_key, value, field_errors))
+            }
+        });
+        let mut response_map = JsonMap::with_capacity(grouped_field_set.len());
+        let mut add_entry = |(response_key, value, field_errors): (&Name, _, _)| {
+            errors.extend(field_errors);
+            response_map.insert(response_key.as_str(), value?);
+            Ok(())
+        };
+        match mode {
+            ExecutionMode::Normal => {
+                for entry in join_all(field_entries).await.into_iter().flatten() {
+                    add_entry(entry)?
+                }
+            }
+            ExecutionMode::Sequential => {
+                for entry in field_entries {
+                    if let Some(entry) = entry.await {
+                        add_entry(entry)?
+                    }
+                }
+            }
+        }
+        Ok(response_map)
+    })
+}
+
+/// <https://spec.graphql.org/October2021/#ExecuteField()>
+#[allow(clippy::too_many_arguments)] // yes it’s not a nice API but it’s internal
+async fn execute_field(
+    schema: &Valid<Schema>,
+    document: &Valid<ExecutableDocument>,
+    variable_values: &Valid<JsonMap>,
+    errors: &mut Vec<GraphQLError>,
+    path: LinkedPath<'_>,
+    object_value: &(dyn AsyncResolver + '_),
+    field_def: &FieldDefinition,
+    fields: &[&Field],
+) -> Result<JsonValue, PropagateNull> {
+    let field = fields[0];
+    let argument_values = match coerce_argument_values(
+        schema,
+        document,
+        variable_values,
+        errors,
+        path,
+        field_def,
+        field,
+    ) {
+        Ok(argument_values) => argument_values,
+        Err(PropagateNull) => return try_nullify(&field_def.ty, Err(PropagateNull)),
+    };
+    let resolved_result = object_value
+        .resolve_field(&field.name, &argument_values)
+        .await;
+    let completed_result = match resolved_result {
+        Ok(resolved) => {
+            complete_value(
+                schema,
+                document,
+                variable_values,
+                errors,
+                path,
+                field.ty(),
+                resolved,
+                fields,
+            )
+            .await
+        }
+        Err(ResolverError { message }) => {
+            errors.push(GraphQLError::field_error(
+                format!("resolver error: {message}"),
+                path,
+                field.name.location(),
+                &document.sources,
+            ));
+            Err(PropagateNull)
+        }
+    };
+    try_nullify(&field_def.ty, completed_result)
+}
+
+/// <https://spec.graphql.org/October2021/#CompleteValue()>
+///
+/// Returns `Err` for a field error being propagated upwards to find a nullable place
+#[allow(clippy::too_many_arguments)] // yes it’s not a nice API but it’s internal
+fn complete_value<'a, 'b: 'a>(
+    schema: &'a Valid<Schema>,
+    document: &'a Valid<ExecutableDocument>,
+    variable_values: &'a Valid<JsonMap>,
+    errors: &'a mut Vec<GraphQLError>,
+    path: LinkedPath<'a>,
+    ty: &'a Type,
+    resolved: AsyncResolvedValue<'b>,
+    fields: &'a [&'a Field],
+) -> BoxFuture<'a, Result<JsonValue, PropagateNull>> {
+    Box::pin(async move {
+        let location = fields[0].name.location();
+        macro_rules! field_error {
+            ($($arg: tt)+) => {
+                {
+                    errors.push(GraphQLError::field_error(
+                        format!($($arg)+),
+                        path,
+                        location,
+                        &document.sources
+                    ));
+                    return Err(PropagateNull);
+                }
+            };
+        }
+        if let AsyncResolvedValue::Leaf(JsonValue::Null) = resolved {
+            if ty.is_non_null() {
+                field_error!("Non-null type {ty} resolved to null")
+            } else {
+                return Ok(JsonValue::Null);
+            }
+        }
+        if let AsyncResolvedValue::List(stream) = resolved {
+            match ty {
+                Type::Named(_) | Type::NonNullNamed(_) => {
+                    field_error!("Non-list type {ty} resolved to a list")
+                }
+                Type::List(inner_ty) | Type::NonNullList(inner_ty) => {
+                    let items: Vec<_> = stream.collect().await;
+                    let completed_items = join_all(items.into_iter().enumerate().map(
+                        |(index, inner_resolved)| {
+                            async move {
+                                let inner_path = LinkedPathElement {
+                                    element: ResponseDataPathSegment::ListIndex(index),
+                                    next: path,
+                                };
+                                let mut inner_errors = Vec::new();
+                                let inner_result = complete_value(
+                                    schema,
+                                    document,
+                                    variable_values,
+                                    &mut inner_errors,
+                                    Some(&inner_path),
+                                    inner_ty,
+                                    inner_resolved,
+                                    fields,
+                                )
+                                .await;
+                                // On field error, try to nullify that item
+                                (try_nullify(inner_ty, inner_result), inner_errors)
+                            }
+                        },
+                    ))
+                    .await;
+                    let mut completed_list = Vec::with_capacity(completed_items.len());
+                    for (inner_result, inner_errors) in completed_items {
+                        errors.extend(inner_errors);
+                        match inner_result {
+                            Ok(inner_value) => completed_list.push(inner_value),
+                            // If the item is non-null, try to nullify the list
+                            Err(PropagateNull) => return try_nullify(ty, Err(PropagateNull)),
+                        }
+                    }
+                    return Ok(completed_list.into());
+                }
+            }
+        }
+        let ty_name = match ty {
+            Type::List(_) | Type::NonNullList(_) => {
+                field_error!("List type {ty} resolved to an object")
+            }
+            Type::Named(name) | Type::NonNullNamed(name) => name,
+        };
+        let Some(ty_def) = schema.types.get(ty_name) else {
+            errors.push(
+                SuspectedValidationBug {
+                    message: format!("Undefined type {ty_name}"),
+                    location,
+                }
+                .into_field_error(&document.sources, path),
+            );
+            return Err(PropagateNull);
+        };
+        if let ExtendedType::InputObject(_) = ty_def {
+            errors.push(
+                SuspectedValidationBug {
+                    message: format!("Field with input object type {ty_name}"),
+                    location,
+                }
+                .into_field_error(&document.sources, path),
+            );
+            return Err(PropagateNull);
+        }
+        let resolved_obj = match resolved {
+            AsyncResolvedValue::List(_) => unreachable!(), // early return above
+            AsyncResolvedValue::Leaf(json_value) => {
+                match complete_leaf_value(ty_name, ty_def, json_value) {
+                    Ok(json_value) => return Ok(json_value),
+                    Err(message) => field_error!("{message}"),
+                }
+            }
+            AsyncResolvedValue::Object(resolved_obj) => resolved_obj,
+        };
+        let object_type = match ty_def {
+            ExtendedType::InputObject(_) => unreachable!(), // early return above
+            ExtendedType::Enum(_) | ExtendedType::Scalar(_) => {
+                field_error!(
+                    "Resolver returned a an object of type {}, expected {ty_name}",
+                    resolved_obj.type_name()
+                )
+            }
+            ExtendedType::Interface(_) | ExtendedType::Union(_) => {
+                let object_type_name = resolved_obj.type_name();
+                if let Some(def) = schema.get_object(object_type_name) {
+                    def
+                } else {
+                    field_error!(
+                        "Resolver returned an object of type {object_type_name} \
+                         not defined in the schema"
+                    )
+                }
+            }
+            ExtendedType::Object(def) => {
+                debug_assert_eq!(ty_name, resolved_obj.type_name());
+                def
+            }
+        };
+        // Only root fields of a mutation are executed sequentially
+        let mode = ExecutionMode::Normal;
+        let introspection = None;
+        execute_selection_set(
+            schema,
+            document,
+            variable_values,
+            errors,
+            path,
+            mode,
+            introspection,
+            object_type,
+            &*resolved_obj,
+            fields
+                .iter()
+                .flat_map(|field| &field.selection_set.selections),
+        )
+        .await
+        .map(JsonValue::Object)
+    })
+}
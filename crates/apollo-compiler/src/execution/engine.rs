@@ -29,7 +29,6 @@ pub(crate) enum ExecutionMode {
     /// Allowed to resolve fields in any order, including in parellel
     Normal,
     /// Top-level fields of a mutation operation must be executed in order
-    #[allow(unused)]
     Sequential,
 }
 
@@ -65,7 +64,7 @@ pub(crate) fn execute_selection_set<'a>(
         document,
         variable_values,
         object_type,
-        object_value,
+        &|field_name| object_value.skip_field(field_name),
         selections,
         &mut HashSet::default(),
         &mut grouped_field_set,
@@ -114,13 +113,16 @@ pub(crate) fn execute_selection_set<'a>(
 }
 
 /// <https://spec.graphql.org/October2021/#CollectFields()>
+///
+/// `skip_field` is [`Resolver::skip_field`][crate::execution::Resolver::skip_field]
+/// or its async equivalent
 #[allow(clippy::too_many_arguments)] // yes it’s not a nice API but it’s internal
-fn collect_fields<'a>(
+pub(crate) fn collect_fields<'a>(
     schema: &Schema,
     document: &'a ExecutableDocument,
     variable_values: &Valid<JsonMap>,
     object_type: &ObjectType,
-    object_value: &ObjectValue<'_>,
+    skip_field: &dyn Fn(&str) -> bool,
     selections: impl IntoIterator<Item = &'a Selection>,
     visited_fragments: &mut HashSet<&'a Name>,
     grouped_fields: &mut IndexMap<&'a Name, Vec<&'a Field>>,
@@ -133,7 +135,7 @@ fn collect_fields<'a>(
         }
         match selection {
             Selection::Field(field) => {
-                if !object_value.skip_field(&field.name) {
+                if !skip_field(&field.name) {
                     grouped_fields
                         .entry(field.response_key())
                         .or_default()
@@ -156,7 +158,7 @@ fn collect_fields<'a>(
                     document,
                     variable_values,
                     object_type,
-                    object_value,
+                    skip_field,
                     &fragment.selection_set.selections,
                     visited_fragments,
                     grouped_fields,
@@ -173,7 +175,7 @@ fn collect_fields<'a>(
                     document,
                     variable_values,
                     object_type,
-                    object_value,
+                    skip_field,
                     &inline.selection_set.selections,
                     visited_fragments,
                     grouped_fields,
@@ -200,7 +202,7 @@ fn does_fragment_type_apply(
     }
 }
 
-fn eval_if_arg(
+pub(crate) fn eval_if_arg(
     selection: &Selection,
     directive_name: &str,
     variable_values: &Valid<JsonMap>,
@@ -219,7 +221,7 @@ fn eval_if_arg(
 
 /// <https://spec.graphql.org/October2021/#ExecuteField()>
 #[allow(clippy::too_many_arguments)] // yes it’s not a nice API but it’s internal
-fn execute_field(
+pub(crate) fn execute_field(
     schema: &Valid<Schema>,
     document: &Valid<ExecutableDocument>,
     variable_values: &Valid<JsonMap>,
@@ -170,6 +170,26 @@ fn coerce_variable_value(
                         location: None,
                     });
                 }
+                if ty_def.is_one_of() {
+                    // https://spec.graphql.org/draft/#sec-OneOf-Input-Objects.Input-Coercion
+                    if object.len() != 1 {
+                        return Err(InputCoercionError::ValueError {
+                            message: format!(
+                                "OneOf input object {ty_name} must have exactly one field"
+                            ),
+                            location: None,
+                        });
+                    }
+                    if let Some((key, _)) = object.iter().find(|(_, value)| value.is_null()) {
+                        return Err(InputCoercionError::ValueError {
+                            message: format!(
+                                "null value for field {ty_name}.{} of OneOf input object",
+                                key.as_str()
+                            ),
+                            location: None,
+                        });
+                    }
+                }
                 let mut object = object.clone();
                 for (field_name, field_def) in &ty_def.fields {
                     if let Some(field_value) = object.get_mut(field_name.as_str()) {
@@ -442,6 +462,37 @@ fn coerce_argument_value(
                     ));
                     return Err(PropagateNull);
                 }
+                if ty_def.is_one_of() {
+                    // https://spec.graphql.org/draft/#sec-OneOf-Input-Objects.Input-Coercion
+                    let message = match object {
+                        [(key, field_value)] => {
+                            let is_null = if let Some(var_name) = field_value.as_variable() {
+                                variable_values
+                                    .get(var_name.as_str())
+                                    .is_none_or(|var_value| var_value.is_null())
+                            } else {
+                                field_value.is_null()
+                            };
+                            is_null.then(|| {
+                                format!(
+                                    "null value for field {ty_name}.{key} of OneOf input object"
+                                )
+                            })
+                        }
+                        _ => Some(format!(
+                            "OneOf input object {ty_name} must have exactly one field"
+                        )),
+                    };
+                    if let Some(message) = message {
+                        errors.push(GraphQLError::field_error(
+                            message,
+                            path,
+                            value.location(),
+                            &document.sources,
+                        ));
+                        return Err(PropagateNull);
+                    }
+                }
                 #[allow(clippy::map_identity)] // `map` converts `&(k, v)` to `(&k, &v)`
                 let object: HashMap<_, _> = object.iter().map(|(k, v)| (k, v)).collect();
                 let mut coerced_object = JsonMap::new();
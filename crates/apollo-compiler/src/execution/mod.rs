@@ -1,11 +1,200 @@
 //! APIs related to [executing a GraphQL request][execution]
 //! and returning a [GraphQL response][response]
 //!
+//! The main entry point is [`execute`],
+//! which runs an operation against [`Resolver`] implementations for the object values.
+//! [`execute_async`] does the same with [`AsyncResolver`] implementations,
+//! for resolvers that need to do I/O.
+//!
 //! [execution]: https://spec.graphql.org/October2021/#sec-Execution
 //! [response]: https://spec.graphql.org/October2021/#sec-Response
 
+use crate::executable::Operation;
+#[cfg(doc)]
+use crate::executable::OperationMap;
+use crate::execution::engine::execute_selection_set;
+use crate::execution::engine::ExecutionMode;
+use crate::execution::engine::PropagateNull;
+use crate::introspection::resolvers::IntrospectionRootResolver;
+use crate::introspection::resolvers::SchemaWithImplementersMap;
+#[cfg(doc)]
+use crate::request::coerce_variable_values;
+use crate::request::RequestError;
+use crate::response::ExecutionResponse;
+use crate::response::JsonMap;
+use crate::schema::ObjectType;
+use crate::validation::Valid;
+use crate::ExecutableDocument;
+use crate::Schema;
+
 #[macro_use]
 pub(crate) mod resolver;
+pub(crate) mod async_engine;
 pub(crate) mod engine;
 pub(crate) mod input_coercion;
 pub(crate) mod result_coercion;
+
+pub use self::resolver::AsyncResolvedValue;
+pub use self::resolver::AsyncResolver;
+pub use self::resolver::ResolvedValue;
+pub use self::resolver::Resolver;
+pub use self::resolver::ResolverError;
+
+/// Executes an operation and returns a response.
+///
+/// * Consider calling [`check_max_depth`][crate::introspection::check_max_depth]
+///   before this function
+/// * `operation` is expected to be from
+///   [`document.operations.get(operation_name)?`][OperationMap::get]
+/// * `variable_values` is expected to be from [`coerce_variable_values`]
+/// * `root` resolves the [root fields][Operation::root_fields] of the operation.
+///   Its [`type_name`][Resolver::type_name] is expected to be
+///   that of the root operation type.
+///   For a subscription operation, it represents a single event of the source stream.
+///
+/// Top-level fields of a mutation operation are executed serially, in document order.
+///
+/// Schema introspection meta-fields `__schema` and `__type` in a query operation
+/// are resolved by apollo-compiler and never passed to `root`.
+/// To disable introspection, reject operations that select them before calling this function.
+///
+/// Field errors are collected in [`ExecutionResponse::errors`].
+/// A request error is returned if execution could not start at all.
+pub fn execute(
+    schema: &Valid<Schema>,
+    document: &Valid<ExecutableDocument>,
+    operation: &Operation,
+    variable_values: &Valid<JsonMap>,
+    root: &dyn Resolver,
+) -> Result<ExecutionResponse, RequestError> {
+    let (root_operation_object_type_def, mode) = root_object_type_and_mode(schema, operation)?;
+    let implementers_map;
+    let initial_value = RootResolver {
+        introspection: if operation.operation_type.is_query() {
+            implementers_map = schema.implementers_map();
+            Some(IntrospectionRootResolver(SchemaWithImplementersMap {
+                schema,
+                implementers_map: &implementers_map,
+            }))
+        } else {
+            None
+        },
+        root,
+    };
+    let mut errors = Vec::new();
+    let path = None;
+    let data = execute_selection_set(
+        schema,
+        document,
+        variable_values,
+        &mut errors,
+        path,
+        mode,
+        root_operation_object_type_def,
+        &initial_value,
+        &operation.selection_set.selections,
+    )
+    // What `.ok()` below converts to `None` is a field error on a non-null field
+    // propagated all the way to the root, so that the response JSON should contain `"data": null`.
+    //
+    // No-op to witness the error type:
+    .inspect_err(|_: &PropagateNull| {})
+    .ok();
+    Ok(ExecutionResponse { data, errors })
+}
+
+/// Executes an operation and returns a response, calling async resolvers.
+///
+/// This is the async equivalent of [`execute`], with the same expectations for parameters.
+///
+/// Sibling fields are resolved concurrently,
+/// except top-level fields of a mutation operation which are executed serially, in document order.
+/// The response (including the order of errors) is the same as the one [`execute`]
+/// would produce with equivalent sync resolvers.
+pub async fn execute_async(
+    schema: &Valid<Schema>,
+    document: &Valid<ExecutableDocument>,
+    operation: &Operation,
+    variable_values: &Valid<JsonMap>,
+    root: &dyn AsyncResolver,
+) -> Result<ExecutionResponse, RequestError> {
+    let (root_operation_object_type_def, mode) = root_object_type_and_mode(schema, operation)?;
+    let implementers_map;
+    let introspection = if operation.operation_type.is_query() {
+        implementers_map = schema.implementers_map();
+        Some(IntrospectionRootResolver(SchemaWithImplementersMap {
+            schema,
+            implementers_map: &implementers_map,
+        }))
+    } else {
+        None
+    };
+    let mut errors = Vec::new();
+    let path = None;
+    let data = async_engine::execute_selection_set(
+        schema,
+        document,
+        variable_values,
+        &mut errors,
+        path,
+        mode,
+        introspection.as_ref(),
+        root_operation_object_type_def,
+        root,
+        &operation.selection_set.selections,
+    )
+    .await
+    // See comment in `execute`
+    .ok();
+    Ok(ExecutionResponse { data, errors })
+}
+
+fn root_object_type_and_mode<'a>(
+    schema: &'a Valid<Schema>,
+    operation: &Operation,
+) -> Result<(&'a ObjectType, ExecutionMode), RequestError> {
+    let object_type_name = operation.object_type();
+    let Some(root_operation_object_type_def) = schema.get_object(object_type_name) else {
+        return Err(RequestError {
+            message: "Undefined root operation type".to_owned(),
+            location: object_type_name.location(),
+            is_suspected_validation_bug: true,
+        });
+    };
+    let mode = if operation.operation_type.is_mutation() {
+        ExecutionMode::Sequential
+    } else {
+        ExecutionMode::Normal
+    };
+    Ok((root_operation_object_type_def, mode))
+}
+
+/// Dispatches schema introspection meta-fields to built-in resolvers,
+/// and everything else to the user-provided root resolver.
+struct RootResolver<'a> {
+    introspection: Option<IntrospectionRootResolver<'a>>,
+    root: &'a dyn Resolver,
+}
+
+impl Resolver for RootResolver<'_> {
+    fn type_name(&self) -> &'static str {
+        self.root.type_name()
+    }
+
+    fn resolve_field<'a>(
+        &'a self,
+        field_name: &'a str,
+        arguments: &'a JsonMap,
+    ) -> Result<ResolvedValue<'a>, ResolverError> {
+        match (field_name, &self.introspection) {
+            ("__schema" | "__type", Some(introspection)) => {
+                introspection.resolve_field(field_name, arguments)
+            }
+            _ => self.root.resolve_field(field_name, arguments),
+        }
+    }
+
+    fn skip_field(&self, field_name: &str) -> bool {
+        self.root.skip_field(field_name)
+    }
+}
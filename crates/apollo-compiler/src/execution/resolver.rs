@@ -1,14 +1,21 @@
 use crate::response::JsonMap;
+use futures::future::BoxFuture;
+use futures::stream::BoxStream;
+use futures::StreamExt;
 use serde_json_bytes::Value as JsonValue;
 
 /// A GraphQL object whose fields can be resolved during execution
 pub(crate) type ObjectValue<'a> = dyn Resolver + 'a;
 
-/// Abstraction for implementing field resolvers. Used through [`ObjectValue`].
+/// Abstraction for implementing field resolvers,
+/// as used by [`execute`][crate::execution::execute].
+///
+/// Each implementation represents one GraphQL object value:
+/// the root operation value, or a value returned by some other field resolver.
 ///
 /// Use the [`impl_resolver!`][crate::impl_resolver] macro to implement this trait
 /// with reduced boilerplate
-pub(crate) trait Resolver {
+pub trait Resolver {
     /// Returns the name of the concrete object type this resolver represents
     ///
     /// That name expected to be that of an object type defined in the schema.
@@ -34,10 +41,41 @@ pub(crate) trait Resolver {
     }
 }
 
-pub(crate) struct ResolverError {
+/// An error returned by [`Resolver::resolve_field`].
+///
+/// Execution turns it into a [field error](https://spec.graphql.org/October2021/#sec-Errors.Field-errors)
+/// in the response, with a path and location pointing to the relevant field.
+#[derive(Debug, Clone)]
+pub struct ResolverError {
     pub(crate) message: String,
 }
 
+impl ResolverError {
+    /// Creates an error with the message to show in the response
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+
+    /// Returns the message of the field error
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl From<String> for ResolverError {
+    fn from(message: String) -> Self {
+        Self { message }
+    }
+}
+
+impl From<&'_ str> for ResolverError {
+    fn from(message: &'_ str) -> Self {
+        Self::new(message)
+    }
+}
+
 /// Implements the [`Resolver`] trait with reduced boilerplate
 ///
 /// Define:
@@ -46,9 +84,33 @@ pub(crate) struct ResolverError {
 /// * The __typename string
 /// * One pseudo-method per field. Types are omitted in the signature for brevity.
 ///   - Takes two optional arguments: `&self` (which must be spelled something else because macros)
-///     and `args: `[`&JsonMap`][crate::JsonMap] for the field arguments.
+///     and `args: `[`&JsonMap`][crate::response::JsonMap] for the field arguments.
 ///     Field arguments are coerced according to their definition in the schema.
-///   - Returns `Result<ResolvedValue, String>`, `Err` it turned into a field error
+///   - Returns `Result<ResolvedValue, ResolverError>`, `Err` is turned into a field error
+///
+/// ```
+/// use apollo_compiler::execution::ResolvedValue;
+/// use apollo_compiler::impl_resolver;
+///
+/// struct Query {
+///     world: String,
+/// }
+///
+/// impl_resolver! {
+///     for Query:
+///
+///     __typename = "Query";
+///
+///     fn hello(&self_) {
+///         Ok(ResolvedValue::leaf(format!("Hello {}!", self_.world)))
+///     }
+///
+///     fn echo(&_self, args) {
+///         Ok(ResolvedValue::leaf(args["value"].clone()))
+///     }
+/// }
+/// ```
+#[macro_export]
 macro_rules! impl_resolver {
     (
         for $ty: ty:
@@ -60,7 +122,7 @@ macro_rules! impl_resolver {
         )*
 
     ) => {
-        impl $crate::execution::resolver::Resolver for $ty {
+        impl $crate::execution::Resolver for $ty {
             fn type_name(&self) -> &'static str {
                 $type_name
             }
@@ -70,8 +132,8 @@ macro_rules! impl_resolver {
                 field_name: &'a str,
                 arguments: &'a $crate::response::JsonMap,
             ) -> Result<
-                $crate::execution::resolver::ResolvedValue<'a>,
-                crate::execution::resolver::ResolverError
+                $crate::execution::ResolvedValue<'a>,
+                $crate::execution::ResolverError
             > {
                 let _allow_unused = arguments;
                 match field_name {
@@ -86,12 +148,10 @@ macro_rules! impl_resolver {
                             return $block
                         },
                     )*
-                    _ => Err(crate::execution::resolver::ResolverError {
-                        message: format!(
-                            "unexpected field name: {field_name} in type {}",
-                            self.type_name()
-                        )
-                    }),
+                    _ => Err($crate::execution::ResolverError::new(format!(
+                        "unexpected field name: {field_name} in type {}",
+                        $crate::execution::Resolver::type_name(self)
+                    ))),
                 }
             }
         }
@@ -99,7 +159,7 @@ macro_rules! impl_resolver {
 }
 
 /// The value of a resolved field
-pub(crate) enum ResolvedValue<'a> {
+pub enum ResolvedValue<'a> {
     /// * JSON null represents GraphQL null
     /// * A GraphQL enum value is represented as a JSON string
     /// * GraphQL built-in scalars are coerced according to their respective *Result Coercion* spec
@@ -115,22 +175,22 @@ pub(crate) enum ResolvedValue<'a> {
 
 impl<'a> ResolvedValue<'a> {
     /// Construct a null leaf resolved value
-    pub(crate) fn null() -> Self {
+    pub fn null() -> Self {
         Self::Leaf(JsonValue::Null)
     }
 
     /// Construct a leaf resolved value from something that is convertible to JSON
-    pub(crate) fn leaf(json: impl Into<JsonValue>) -> Self {
+    pub fn leaf(json: impl Into<JsonValue>) -> Self {
         Self::Leaf(json.into())
     }
 
     /// Construct an object resolved value from the resolver for that object
-    pub(crate) fn object(resolver: impl Resolver + 'a) -> Self {
+    pub fn object(resolver: impl Resolver + 'a) -> Self {
         Self::Object(Box::new(resolver))
     }
 
     /// Construct an object resolved value or null, from an optional resolver
-    pub(crate) fn opt_object(opt_resolver: Option<impl Resolver + 'a>) -> Self {
+    pub fn opt_object(opt_resolver: Option<impl Resolver + 'a>) -> Self {
         match opt_resolver {
             Some(resolver) => Self::Object(Box::new(resolver)),
             None => Self::null(),
@@ -138,7 +198,7 @@ impl<'a> ResolvedValue<'a> {
     }
 
     /// Construct a list resolved value from an iterator
-    pub(crate) fn list<I>(iter: I) -> Self
+    pub fn list<I>(iter: I) -> Self
     where
         I: IntoIterator<Item = Self>,
         I::IntoIter: 'a,
@@ -147,6 +207,90 @@ impl<'a> ResolvedValue<'a> {
     }
 }
 
+/// Abstraction for implementing field resolvers that may need to do I/O,
+/// as used by [`execute_async`][crate::execution::execute_async].
+///
+/// This is the async equivalent of [`Resolver`].
+/// Sibling fields may be resolved concurrently,
+/// so implementations should not assume any particular order of calls.
+pub trait AsyncResolver: Send + Sync {
+    /// Returns the name of the concrete object type this resolver represents
+    ///
+    /// That name expected to be that of an object type defined in the schema.
+    /// This is called when the schema indicates an abstract (interface or union) type.
+    fn type_name(&self) -> &'static str;
+
+    /// Resolves a field of this object with the given arguments
+    ///
+    /// The resolved is expected to match the type of the corresponding field definition
+    /// in the schema.
+    fn resolve_field<'a>(
+        &'a self,
+        field_name: &'a str,
+        arguments: &'a JsonMap,
+    ) -> BoxFuture<'a, Result<AsyncResolvedValue<'a>, ResolverError>>;
+
+    /// Returns true if this field should be skipped,
+    /// as if the corresponding selection has `@skip(if: true)`.
+    fn skip_field(&self, _field_name: &str) -> bool {
+        false
+    }
+}
+
+/// The value of a field resolved by an [`AsyncResolver`]
+pub enum AsyncResolvedValue<'a> {
+    /// * JSON null represents GraphQL null
+    /// * A GraphQL enum value is represented as a JSON string
+    /// * GraphQL built-in scalars are coerced according to their respective *Result Coercion* spec
+    /// * For custom scalars, any JSON value is passed through as-is (including array or object)
+    Leaf(JsonValue),
+
+    /// Expected where the GraphQL type is an object, interface, or union type
+    Object(Box<dyn AsyncResolver + 'a>),
+
+    /// Expected for GraphQL list types
+    List(BoxStream<'a, AsyncResolvedValue<'a>>),
+}
+
+impl<'a> AsyncResolvedValue<'a> {
+    /// Construct a null leaf resolved value
+    pub fn null() -> Self {
+        Self::Leaf(JsonValue::Null)
+    }
+
+    /// Construct a leaf resolved value from something that is convertible to JSON
+    pub fn leaf(json: impl Into<JsonValue>) -> Self {
+        Self::Leaf(json.into())
+    }
+
+    /// Construct an object resolved value from the resolver for that object
+    pub fn object(resolver: impl AsyncResolver + 'a) -> Self {
+        Self::Object(Box::new(resolver))
+    }
+
+    /// Construct an object resolved value or null, from an optional resolver
+    pub fn opt_object(opt_resolver: Option<impl AsyncResolver + 'a>) -> Self {
+        match opt_resolver {
+            Some(resolver) => Self::Object(Box::new(resolver)),
+            None => Self::null(),
+        }
+    }
+
+    /// Construct a list resolved value from an iterator
+    pub fn list<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Self>,
+        I::IntoIter: Send + 'a,
+    {
+        Self::List(futures::stream::iter(iter).boxed())
+    }
+
+    /// Construct a list resolved value from a stream, whose items may be produced asynchronously
+    pub fn stream(stream: impl futures::Stream<Item = Self> + Send + 'a) -> Self {
+        Self::List(stream.boxed())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::execution::resolver::ResolvedValue;
@@ -15,6 +15,7 @@ use crate::schema::Type;
 use crate::validation::SuspectedValidationBug;
 use crate::validation::Valid;
 use crate::ExecutableDocument;
+use crate::Name;
 use crate::Schema;
 
 /// <https://spec.graphql.org/October2021/#CompleteValue()>
@@ -115,71 +116,10 @@ pub(crate) fn complete_value<'a, 'b>(
     }
     let resolved_obj = match resolved {
         ResolvedValue::List(_) => unreachable!(), // early return above
-        ResolvedValue::Leaf(json_value) => {
-            match ty_def {
-                ExtendedType::InputObject(_) => unreachable!(), // early return above
-                ExtendedType::Object(_) | ExtendedType::Interface(_) | ExtendedType::Union(_) => {
-                    field_error!(
-                        "Resolver returned a leaf value \
-                         but expected an object for type {ty_name}"
-                    )
-                }
-                ExtendedType::Enum(enum_def) => {
-                    // https://spec.graphql.org/October2021/#sec-Enums.Result-Coercion
-                    if !json_value
-                        .as_str()
-                        .is_some_and(|str| enum_def.values.contains_key(str))
-                    {
-                        field_error!("Resolver returned {json_value}, expected enum {ty_name}")
-                    }
-                }
-                ExtendedType::Scalar(_) => match ty_name.as_str() {
-                    "Int" => {
-                        // https://spec.graphql.org/October2021/#sec-Int.Result-Coercion
-                        // > GraphQL services may coerce non-integer internal values to integers
-                        // > when reasonable without losing information
-                        //
-                        // We choose not to, to keep with Rust’s strong typing
-                        if let Some(int) = json_value.as_i64() {
-                            if i32::try_from(int).is_err() {
-                                field_error!("Resolver returned {json_value} which overflows Int")
-                            }
-                        } else {
-                            field_error!("Resolver returned {json_value}, expected Int")
-                        }
-                    }
-                    "Float" => {
-                        // https://spec.graphql.org/October2021/#sec-Float.Result-Coercion
-                        if !json_value.is_f64() {
-                            field_error!("Resolver returned {json_value}, expected Float")
-                        }
-                    }
-                    "String" => {
-                        // https://spec.graphql.org/October2021/#sec-String.Result-Coercion
-                        if !json_value.is_string() {
-                            field_error!("Resolver returned {json_value}, expected String")
-                        }
-                    }
-                    "Boolean" => {
-                        // https://spec.graphql.org/October2021/#sec-Boolean.Result-Coercion
-                        if !json_value.is_boolean() {
-                            field_error!("Resolver returned {json_value}, expected Boolean")
-                        }
-                    }
-                    "ID" => {
-                        // https://spec.graphql.org/October2021/#sec-ID.Result-Coercion
-                        if !(json_value.is_string() || json_value.is_i64()) {
-                            field_error!("Resolver returned {json_value}, expected ID")
-                        }
-                    }
-                    _ => {
-                        // Custom scalar: accept any JSON value (including an array or object,
-                        // despite this being a "leaf" as far as GraphQL resolution is concerned)
-                    }
-                },
-            };
-            return Ok(json_value);
-        }
+        ResolvedValue::Leaf(json_value) => match complete_leaf_value(ty_name, ty_def, json_value) {
+            Ok(json_value) => return Ok(json_value),
+            Err(message) => field_error!("{message}"),
+        },
         ResolvedValue::Object(resolved_obj) => resolved_obj,
     };
     let object_type = match ty_def {
@@ -221,3 +161,83 @@ pub(crate) fn complete_value<'a, 'b>(
     )
     .map(JsonValue::Object)
 }
+
+/// The leaf value part of <https://spec.graphql.org/October2021/#CompleteValue()>
+///
+/// Returns `Err` with the message of a field error
+pub(crate) fn complete_leaf_value(
+    ty_name: &Name,
+    ty_def: &ExtendedType,
+    json_value: JsonValue,
+) -> Result<JsonValue, String> {
+    macro_rules! leaf_error {
+        ($($arg: tt)+) => {
+            return Err(format!($($arg)+))
+        };
+    }
+    match ty_def {
+        ExtendedType::InputObject(_) => {
+            leaf_error!("Resolver returned a leaf value for input object type {ty_name}")
+        }
+        ExtendedType::Object(_) | ExtendedType::Interface(_) | ExtendedType::Union(_) => {
+            leaf_error!(
+                "Resolver returned a leaf value \
+                 but expected an object for type {ty_name}"
+            )
+        }
+        ExtendedType::Enum(enum_def) => {
+            // https://spec.graphql.org/October2021/#sec-Enums.Result-Coercion
+            if !json_value
+                .as_str()
+                .is_some_and(|str| enum_def.values.contains_key(str))
+            {
+                leaf_error!("Resolver returned {json_value}, expected enum {ty_name}")
+            }
+        }
+        ExtendedType::Scalar(_) => match ty_name.as_str() {
+            "Int" => {
+                // https://spec.graphql.org/October2021/#sec-Int.Result-Coercion
+                // > GraphQL services may coerce non-integer internal values to integers
+                // > when reasonable without losing information
+                //
+                // We choose not to, to keep with Rust’s strong typing
+                if let Some(int) = json_value.as_i64() {
+                    if i32::try_from(int).is_err() {
+                        leaf_error!("Resolver returned {json_value} which overflows Int")
+                    }
+                } else {
+                    leaf_error!("Resolver returned {json_value}, expected Int")
+                }
+            }
+            "Float" => {
+                // https://spec.graphql.org/October2021/#sec-Float.Result-Coercion
+                if !json_value.is_f64() {
+                    leaf_error!("Resolver returned {json_value}, expected Float")
+                }
+            }
+            "String" => {
+                // https://spec.graphql.org/October2021/#sec-String.Result-Coercion
+                if !json_value.is_string() {
+                    leaf_error!("Resolver returned {json_value}, expected String")
+                }
+            }
+            "Boolean" => {
+                // https://spec.graphql.org/October2021/#sec-Boolean.Result-Coercion
+                if !json_value.is_boolean() {
+                    leaf_error!("Resolver returned {json_value}, expected Boolean")
+                }
+            }
+            "ID" => {
+                // https://spec.graphql.org/October2021/#sec-ID.Result-Coercion
+                if !(json_value.is_string() || json_value.is_i64()) {
+                    leaf_error!("Resolver returned {json_value}, expected ID")
+                }
+            }
+            _ => {
+                // Custom scalar: accept any JSON value (including an array or object,
+                // despite this being a "leaf" as far as GraphQL resolution is concerned)
+            }
+        },
+    };
+    Ok(json_value)
+}
@@ -0,0 +1,174 @@
+/// Configuration for generating the full introspection query with [`full_query`][super::full_query]
+///
+/// Options correspond to those of `getIntrospectionQuery` in graphql-js,
+/// and default to the same values.
+/// Options for fields or arguments that not all servers support are disabled by default.
+#[derive(Debug, Clone)]
+pub struct FullQueryOptions {
+    descriptions: bool,
+    schema_description: bool,
+    specified_by_url: bool,
+    directive_is_repeatable: bool,
+    input_value_deprecation: bool,
+    one_of: bool,
+    type_ref_depth: usize,
+}
+
+impl Default for FullQueryOptions {
+    fn default() -> Self {
+        Self {
+            descriptions: true,
+            schema_description: false,
+            specified_by_url: false,
+            directive_is_repeatable: false,
+            input_value_deprecation: false,
+            one_of: false,
+            type_ref_depth: 7,
+        }
+    }
+}
+
+impl FullQueryOptions {
+    /// Create options with default configuration.
+    /// Use other methods to change the configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Configure whether to query `description` of types, fields, etc.
+    /// Enabled by default.
+    pub fn descriptions(mut self, value: bool) -> Self {
+        self.descriptions = value;
+        self
+    }
+
+    /// Configure whether to query `__Schema.description`.
+    /// Ignored if [`descriptions`][Self::descriptions] is disabled.
+    pub fn schema_description(mut self, value: bool) -> Self {
+        self.schema_description = value;
+        self
+    }
+
+    /// Configure whether to query `__Type.specifiedByURL` for custom scalars.
+    pub fn specified_by_url(mut self, value: bool) -> Self {
+        self.specified_by_url = value;
+        self
+    }
+
+    /// Configure whether to query `__Directive.isRepeatable`.
+    pub fn directive_is_repeatable(mut self, value: bool) -> Self {
+        self.directive_is_repeatable = value;
+        self
+    }
+
+    /// Configure whether to query deprecated arguments and input fields
+    /// with `includeDeprecated: true`, together with their
+    /// `__InputValue.isDeprecated` and `__InputValue.deprecationReason`.
+    pub fn input_value_deprecation(mut self, value: bool) -> Self {
+        self.input_value_deprecation = value;
+        self
+    }
+
+    /// Configure whether to query `__Type.isOneOf` for input object types.
+    pub fn one_of(mut self, value: bool) -> Self {
+        self.one_of = value;
+        self
+    }
+
+    /// Configure how many levels of `ofType` are queried for type references.
+    /// Defaults to 7.
+    ///
+    /// Wrapping types (list and non-null) each take one level,
+    /// so the default is enough for types like `[[[String!]!]!]!`.
+    pub fn type_ref_depth(mut self, value: usize) -> Self {
+        self.type_ref_depth = value;
+        self
+    }
+
+    pub(super) fn to_source(&self) -> String {
+        let description = if self.descriptions { "description" } else { "" };
+        let schema_description = if self.descriptions && self.schema_description {
+            "description"
+        } else {
+            ""
+        };
+        let specified_by_url = if self.specified_by_url {
+            "specifiedByURL"
+        } else {
+            ""
+        };
+        let is_repeatable = if self.directive_is_repeatable {
+            "isRepeatable"
+        } else {
+            ""
+        };
+        let is_one_of = if self.one_of { "isOneOf" } else { "" };
+        let (include_deprecated, input_deprecation) = if self.input_value_deprecation {
+            (
+                "(includeDeprecated: true)",
+                "isDeprecated deprecationReason",
+            )
+        } else {
+            ("", "")
+        };
+        let mut type_ref = String::from("kind name");
+        for _ in 0..self.type_ref_depth {
+            type_ref = format!("kind name ofType {{ {type_ref} }}");
+        }
+        format!(
+            "query IntrospectionQuery {{
+              __schema {{
+                {schema_description}
+                queryType {{ name }}
+                mutationType {{ name }}
+                subscriptionType {{ name }}
+                types {{ ...FullType }}
+                directives {{
+                  name
+                  {description}
+                  {is_repeatable}
+                  locations
+                  args{include_deprecated} {{ ...InputValue }}
+                }}
+              }}
+            }}
+
+            fragment FullType on __Type {{
+              kind
+              name
+              {description}
+              {specified_by_url}
+              {is_one_of}
+              fields(includeDeprecated: true) {{
+                name
+                {description}
+                args{include_deprecated} {{ ...InputValue }}
+                type {{ ...TypeRef }}
+                isDeprecated
+                deprecationReason
+              }}
+              inputFields{include_deprecated} {{ ...InputValue }}
+              interfaces {{ ...TypeRef }}
+              enumValues(includeDeprecated: true) {{
+                name
+                {description}
+                isDeprecated
+                deprecationReason
+              }}
+              possibleTypes {{ ...TypeRef }}
+            }}
+
+            fragment InputValue on __InputValue {{
+              name
+              {description}
+              type {{ ...TypeRef }}
+              defaultValue
+              {input_deprecation}
+            }}
+
+            fragment TypeRef on __Type {{
+              {type_ref}
+            }}"
+        )
+    }
+}
@@ -3,6 +3,7 @@
 //! portion of a query
 //!
 //! The main entry point is [`partial_execute`].
+//! [`full_query`] generates the standard introspection query for clients.
 
 use crate::collections::HashMap;
 use crate::executable::Operation;
@@ -18,12 +19,50 @@ use crate::response::ExecutionResponse;
 use crate::response::JsonMap;
 use crate::schema::Implementers;
 use crate::validation::Valid;
+use crate::validation::WithErrors;
 use crate::ExecutableDocument;
 use crate::Name;
 use crate::Schema;
 
+mod full_query;
 mod max_depth;
-mod resolvers;
+pub(crate) mod resolvers;
+
+pub use self::full_query::FullQueryOptions;
+
+/// Generates the full [schema introspection](https://spec.graphql.org/draft/#sec-Schema-Introspection)
+/// query, as sent by clients and tools to fetch everything needed to reconstruct a schema.
+///
+/// The response can be converted with [`Schema::from_introspection_json`].
+/// The query always passes [`check_max_depth`].
+///
+/// `schema` is used for validation and only needs a query root operation type.
+/// Validation errors are returned if it does not have one,
+/// or if `options` request an introspection field that is not supported
+/// by this version of apollo-compiler.
+///
+/// ```
+/// # use apollo_compiler::introspection;
+/// # use apollo_compiler::Schema;
+/// let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+/// let options = introspection::FullQueryOptions::new()
+///     .specified_by_url(true)
+///     .directive_is_repeatable(true);
+/// let document = introspection::full_query(&schema, &options).unwrap();
+/// let operation = document.operations.get(None).unwrap();
+/// introspection::check_max_depth(&document, operation).unwrap();
+/// ```
+#[allow(clippy::result_large_err)] // Typically not called very often
+pub fn full_query(
+    schema: &Valid<Schema>,
+    options: &FullQueryOptions,
+) -> Result<Valid<ExecutableDocument>, WithErrors<ExecutableDocument>> {
+    ExecutableDocument::parse_and_validate(
+        schema,
+        options.to_source(),
+        "introspection_query.graphql",
+    )
+}
 
 /// Check that the nesting level of some list fields does not exceed a fixed depth limit.
 ///
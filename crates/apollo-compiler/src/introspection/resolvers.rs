@@ -299,6 +299,13 @@ impl_resolver! {
             .and_then(|arg| arg.as_str())
         ))
     }
+
+    fn isOneOf(&self_) {
+        let schema::ExtendedType::InputObject(def) = self_.def else {
+            return Ok(ResolvedValue::null())
+        };
+        Ok(ResolvedValue::leaf(def.is_one_of()))
+    }
 }
 
 // Only used for non-null and list types
@@ -336,6 +343,7 @@ impl_resolver! {
     fn enumValues() { Ok(ResolvedValue::null()) }
     fn inputFields() { Ok(ResolvedValue::null()) }
     fn specifiedByURL() { Ok(ResolvedValue::null()) }
+    fn isOneOf() { Ok(ResolvedValue::null()) }
 }
 
 impl_resolver! {
@@ -5,12 +5,15 @@
 mod macros;
 pub mod ast;
 pub mod collections;
+pub mod completion;
 pub mod coordinate;
+pub mod cost;
 pub mod diagnostic;
 pub mod executable;
 #[macro_use]
-mod execution;
+pub mod execution;
 pub mod introspection;
+mod lookup;
 mod name;
 mod node;
 pub mod parser;
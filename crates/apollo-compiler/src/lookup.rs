@@ -0,0 +1,436 @@
+//! Finding the element at a source position, and references to schema elements
+
+use crate::ast;
+use crate::coordinate::DirectiveArgumentCoordinate;
+use crate::coordinate::DirectiveCoordinate;
+use crate::coordinate::FieldArgumentCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::coordinate::TypeCoordinate;
+use crate::executable;
+use crate::parser::FileId;
+use crate::parser::SourceSpan;
+use crate::schema::ExtendedType;
+use crate::schema::InputValueDefinition;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+
+/// An element of an executable document, returned by
+/// [`ExecutableDocument::element_at`][crate::ExecutableDocument::element_at]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ExecutableElement {
+    /// An operation, outside of any more specific element
+    Operation(Option<Name>),
+    /// A fragment definition or fragment spread
+    Fragment(Name),
+    /// A variable definition or variable usage
+    Variable(Name),
+    /// A field selection
+    Field {
+        /// The field definition in the schema, on the type where it is selected
+        coordinate: TypeAttributeCoordinate,
+        /// Response keys (alias or name) of the field and its parent fields,
+        /// from the root of its operation or fragment
+        path: Vec<Name>,
+    },
+    /// Any other schema element referenced in the document:
+    /// a type condition or variable type, an argument, a directive,
+    /// an input object field, or an enum value
+    Schema(SchemaCoordinate),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    /// The name of an element where it is defined
+    Definition,
+    /// The name of an element where it is used
+    Reference,
+    /// An entire node, such as a field definition including its arguments and directives
+    Enclosing,
+}
+
+struct Occurrence {
+    location: SourceSpan,
+    role: Role,
+    element: ExecutableElement,
+}
+
+/// Collects definitions of and references to elements, with their source location
+struct Occurrences<'a> {
+    schema: &'a Schema,
+    list: Vec<Occurrence>,
+}
+
+impl<'a> Occurrences<'a> {
+    fn new(schema: &'a Schema) -> Self {
+        Self {
+            schema,
+            list: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, location: Option<SourceSpan>, role: Role, element: ExecutableElement) {
+        if let Some(location) = location {
+            if location.file_id() != FileId::BUILT_IN {
+                self.list.push(Occurrence {
+                    location,
+                    role,
+                    element,
+                })
+            }
+        }
+    }
+
+    fn push_schema(
+        &mut self,
+        location: Option<SourceSpan>,
+        role: Role,
+        coordinate: impl Into<SchemaCoordinate>,
+    ) {
+        self.push(location, role, ExecutableElement::Schema(coordinate.into()))
+    }
+
+    fn named_type(&mut self, name: &Name) {
+        self.push_schema(
+            name.location(),
+            Role::Reference,
+            TypeCoordinate { ty: name.clone() },
+        )
+    }
+
+    fn directives<'b>(&mut self, directives: impl IntoIterator<Item = &'b Node<ast::Directive>>) {
+        for directive in directives {
+            let coordinate = DirectiveCoordinate {
+                directive: directive.name.clone(),
+            };
+            self.push_schema(directive.location(), Role::Enclosing, coordinate.clone());
+            self.push_schema(directive.name.location(), Role::Reference, coordinate);
+            let definition = self.schema.directive_definitions.get(&directive.name);
+            for argument in &directive.arguments {
+                let coordinate = DirectiveArgumentCoordinate {
+                    directive: directive.name.clone(),
+                    argument: argument.name.clone(),
+                };
+                self.push_schema(argument.location(), Role::Enclosing, coordinate.clone());
+                self.push_schema(argument.name.location(), Role::Reference, coordinate);
+                if let Some(definition) =
+                    definition.and_then(|def| def.argument_by_name(&argument.name))
+                {
+                    self.value(&definition.ty, &argument.value)
+                }
+            }
+        }
+    }
+
+    fn value(&mut self, ty: &ast::Type, value: &Node<ast::Value>) {
+        match value.as_ref() {
+            ast::Value::Variable(name) => self.push(
+                name.location(),
+                Role::Reference,
+                ExecutableElement::Variable(name.clone()),
+            ),
+            ast::Value::Enum(name) => {
+                if let Some(ExtendedType::Enum(enum_)) =
+                    self.schema.types.get(ty.inner_named_type())
+                {
+                    self.push_schema(
+                        name.location(),
+                        Role::Reference,
+                        type_attribute(&enum_.name, name),
+                    )
+                }
+            }
+            ast::Value::List(items) => {
+                for item in items {
+                    self.value(ty.item_type(), item)
+                }
+            }
+            ast::Value::Object(fields) => {
+                let Some(ExtendedType::InputObject(input_object)) =
+                    self.schema.types.get(ty.inner_named_type())
+                else {
+                    return;
+                };
+                for (name, value) in fields {
+                    self.push_schema(
+                        name.location(),
+                        Role::Reference,
+                        type_attribute(&input_object.name, name),
+                    );
+                    if let Some(definition) = input_object.fields.get(name) {
+                        self.value(&definition.ty, value)
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn input_value_definition(
+        &mut self,
+        definition: &Node<InputValueDefinition>,
+        coordinate: SchemaCoordinate,
+    ) {
+        self.push_schema(definition.location(), Role::Enclosing, coordinate.clone());
+        self.push_schema(definition.name.location(), Role::Definition, coordinate);
+        self.named_type(definition.ty.inner_named_type());
+        if let Some(default_value) = &definition.default_value {
+            self.value(&definition.ty, default_value)
+        }
+        self.directives(&definition.directives)
+    }
+
+    fn schema(&mut self) {
+        let schema = self.schema;
+        let definition = &schema.schema_definition;
+        self.directives(definition.directives.iter().map(|d| &**d));
+        for root in [
+            &definition.query,
+            &definition.mutation,
+            &definition.subscription,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            self.named_type(root)
+        }
+
+        for definition in schema.directive_definitions.values() {
+            let coordinate = DirectiveCoordinate {
+                directive: definition.name.clone(),
+            };
+            self.push_schema(definition.location(), Role::Enclosing, coordinate.clone());
+            self.push_schema(definition.name.location(), Role::Definition, coordinate);
+            for argument in &definition.arguments {
+                let coordinate = DirectiveArgumentCoordinate {
+                    directive: definition.name.clone(),
+                    argument: argument.name.clone(),
+                };
+                self.input_value_definition(argument, coordinate.into())
+            }
+        }
+
+        for (name, ty) in &schema.types {
+            let coordinate = TypeCoordinate { ty: name.clone() };
+            self.push_schema(ty.location(), Role::Enclosing, coordinate.clone());
+            self.push_schema(name_location(ty), Role::Definition, coordinate);
+            self.directives(ty.directives().iter().map(|d| &**d));
+            match ty {
+                ExtendedType::Scalar(_) => {}
+                ExtendedType::Object(_) | ExtendedType::Interface(_) => {
+                    let (implements_interfaces, fields) = match ty {
+                        ExtendedType::Object(ty) => (&ty.implements_interfaces, &ty.fields),
+                        ExtendedType::Interface(ty) => (&ty.implements_interfaces, &ty.fields),
+                        _ => unreachable!(),
+                    };
+                    for interface in implements_interfaces {
+                        self.named_type(interface)
+                    }
+                    for field in fields.values() {
+                        let coordinate = type_attribute(name, &field.name);
+                        self.push_schema(field.location(), Role::Enclosing, coordinate.clone());
+                        self.push_schema(field.name.location(), Role::Definition, coordinate);
+                        for argument in &field.arguments {
+                            let coordinate = FieldArgumentCoordinate {
+                                ty: name.clone(),
+                                field: field.name.clone(),
+                                argument: argument.name.clone(),
+                            };
+                            self.input_value_definition(argument, coordinate.into())
+                        }
+                        self.named_type(field.ty.inner_named_type());
+                        self.directives(&field.directives)
+                    }
+                }
+                ExtendedType::Union(ty) => {
+                    for member in &ty.members {
+                        self.named_type(member)
+                    }
+                }
+                ExtendedType::Enum(ty) => {
+                    for value in ty.values.values() {
+                        let coordinate = type_attribute(name, &value.value);
+                        self.push_schema(value.location(), Role::Enclosing, coordinate.clone());
+                        self.push_schema(value.value.location(), Role::Definition, coordinate);
+                        self.directives(&value.directives)
+                    }
+                }
+                ExtendedType::InputObject(ty) => {
+                    for field in ty.fields.values() {
+                        let coordinate = type_attribute(name, &field.name);
+                        self.input_value_definition(field, coordinate.into())
+                    }
+                }
+            }
+        }
+    }
+
+    fn executable_document(&mut self, document: &ExecutableDocument) {
+        for operation in document.operations.iter() {
+            let element = ExecutableElement::Operation(operation.name.clone());
+            self.push(operation.location(), Role::Enclosing, element.clone());
+            if let Some(name) = &operation.name {
+                self.push(name.location(), Role::Definition, element)
+            }
+            for variable in &operation.variables {
+                let element = ExecutableElement::Variable(variable.name.clone());
+                self.push(variable.location(), Role::Enclosing, element.clone());
+                self.push(variable.name.location(), Role::Definition, element);
+                self.named_type(variable.ty.inner_named_type());
+                if let Some(default_value) = &variable.default_value {
+                    self.value(&variable.ty, default_value)
+                }
+                self.directives(&variable.directives)
+            }
+            self.directives(&operation.directives);
+            self.selection_set(&operation.selection_set, &mut Vec::new())
+        }
+        for fragment in document.fragments.values() {
+            let element = ExecutableElement::Fragment(fragment.name.clone());
+            self.push(fragment.location(), Role::Enclosing, element.clone());
+            self.push(fragment.name.location(), Role::Definition, element);
+            self.named_type(fragment.type_condition());
+            self.directives(&fragment.directives);
+            self.selection_set(&fragment.selection_set, &mut Vec::new())
+        }
+    }
+
+    fn selection_set(&mut self, selection_set: &executable::SelectionSet, path: &mut Vec<Name>) {
+        for selection in &selection_set.selections {
+            match selection {
+                executable::Selection::Field(field) => {
+                    path.push(field.response_key().clone());
+                    let element = ExecutableElement::Field {
+                        coordinate: type_attribute(&selection_set.ty, &field.name),
+                        path: path.clone(),
+                    };
+                    self.push(field.location(), Role::Enclosing, element.clone());
+                    self.push(field.name.location(), Role::Reference, element);
+                    for argument in &field.arguments {
+                        let coordinate = FieldArgumentCoordinate {
+                            ty: selection_set.ty.clone(),
+                            field: field.name.clone(),
+                            argument: argument.name.clone(),
+                        };
+                        self.push_schema(argument.location(), Role::Enclosing, coordinate.clone());
+                        self.push_schema(argument.name.location(), Role::Reference, coordinate);
+                        if let Some(definition) = field.definition.argument_by_name(&argument.name)
+                        {
+                            self.value(&definition.ty, &argument.value)
+                        }
+                    }
+                    self.directives(&field.directives);
+                    self.selection_set(&field.selection_set, path);
+                    path.pop();
+                }
+                executable::Selection::FragmentSpread(spread) => {
+                    let element = ExecutableElement::Fragment(spread.fragment_name.clone());
+                    self.push(spread.location(), Role::Enclosing, element.clone());
+                    self.push(spread.fragment_name.location(), Role::Reference, element);
+                    self.directives(&spread.directives)
+                }
+                executable::Selection::InlineFragment(inline) => {
+                    if let Some(type_condition) = &inline.type_condition {
+                        self.named_type(type_condition)
+                    }
+                    self.directives(&inline.directives);
+                    self.selection_set(&inline.selection_set, path)
+                }
+            }
+        }
+    }
+
+    /// The innermost element at `offset`, including at the end of a name
+    fn element_at(self, file_id: FileId, offset: usize) -> Option<ExecutableElement> {
+        self.list
+            .into_iter()
+            .filter(|occurrence| {
+                let location = occurrence.location;
+                location.file_id() == file_id
+                    && location.offset() <= offset
+                    && offset <= location.end_offset()
+            })
+            .min_by_key(|occurrence| occurrence.location.node_len())
+            .map(|occurrence| occurrence.element)
+    }
+
+    fn references(self, coordinate: &SchemaCoordinate) -> Vec<SourceSpan> {
+        self.list
+            .into_iter()
+            .filter(|occurrence| occurrence.role == Role::Reference)
+            .filter(|occurrence| match (&occurrence.element, coordinate) {
+                (ExecutableElement::Schema(element), _) => element == coordinate,
+                (
+                    ExecutableElement::Field {
+                        coordinate: field, ..
+                    },
+                    SchemaCoordinate::TypeAttribute(coordinate),
+                ) => field == coordinate,
+                _ => false,
+            })
+            .map(|occurrence| occurrence.location)
+            .collect()
+    }
+}
+
+/// The location of the name in the main definition of a type
+fn name_location(ty: &ExtendedType) -> Option<SourceSpan> {
+    match ty {
+        ExtendedType::Scalar(ty) => ty.name.location(),
+        ExtendedType::Object(ty) => ty.name.location(),
+        ExtendedType::Interface(ty) => ty.name.location(),
+        ExtendedType::Union(ty) => ty.name.location(),
+        ExtendedType::Enum(ty) => ty.name.location(),
+        ExtendedType::InputObject(ty) => ty.name.location(),
+    }
+}
+
+fn type_attribute(ty: &Name, attribute: &Name) -> TypeAttributeCoordinate {
+    TypeAttributeCoordinate {
+        ty: ty.clone(),
+        attribute: attribute.clone(),
+    }
+}
+
+pub(crate) fn schema_element_at(
+    schema: &Schema,
+    file_id: FileId,
+    offset: usize,
+) -> Option<SchemaCoordinate> {
+    let mut occurrences = Occurrences::new(schema);
+    occurrences.schema();
+    match occurrences.element_at(file_id, offset)? {
+        ExecutableElement::Schema(coordinate) => Some(coordinate),
+        _ => None,
+    }
+}
+
+pub(crate) fn schema_references(schema: &Schema, coordinate: &SchemaCoordinate) -> Vec<SourceSpan> {
+    let mut occurrences = Occurrences::new(schema);
+    occurrences.schema();
+    occurrences.references(coordinate)
+}
+
+pub(crate) fn executable_element_at(
+    schema: &Schema,
+    document: &ExecutableDocument,
+    file_id: FileId,
+    offset: usize,
+) -> Option<ExecutableElement> {
+    let mut occurrences = Occurrences::new(schema);
+    occurrences.executable_document(document);
+    occurrences.element_at(file_id, offset)
+}
+
+pub(crate) fn executable_references(
+    schema: &Schema,
+    document: &ExecutableDocument,
+    coordinate: &SchemaCoordinate,
+) -> Vec<SourceSpan> {
+    let mut occurrences = Occurrences::new(schema);
+    occurrences.executable_document(document);
+    occurrences.references(coordinate)
+}
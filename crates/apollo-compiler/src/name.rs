@@ -156,6 +156,23 @@ impl Name {
         }
     }
 
+    /// A placeholder for a name missing because of a syntax error, such as `type { … }`.
+    ///
+    /// Parsing keeps partially-parsed definitions, fields, and types with this name
+    /// so that tooling can still work with the rest of an invalid document.
+    /// [`Schema`][crate::Schema] and [`ExecutableDocument`][crate::ExecutableDocument]
+    /// leave out definitions named with this placeholder,
+    /// so that references to a missing name do not resolve.
+    /// It starts with `__` as names reserved for introspection do,
+    /// so it cannot be confused with a name defined in a valid document.
+    pub const MISSING: Self = Self::new_static_unchecked("__missing");
+
+    /// Returns whether this is the [`MISSING`][Self::MISSING] placeholder added by parsing,
+    /// as opposed to `__missing` written in source text
+    pub(crate) fn is_missing_placeholder(&self) -> bool {
+        *self == Self::MISSING && self.location().is_none()
+    }
+
     /// Modifies the given name to add its location in a parsed source file
     pub fn with_location(mut self, location: SourceSpan) -> Self {
         debug_assert_eq!(location.text_range.len(), self.len.into());
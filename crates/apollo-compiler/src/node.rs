@@ -1,3 +1,4 @@
+use crate::ast::Comments;
 use crate::parser::FileId;
 use crate::parser::LineColumn;
 use crate::parser::SourceMap;
@@ -16,6 +17,8 @@ use triomphe::HeaderSlice;
 ///
 /// * In addition to `T`, contains an optional [`SourceSpan`].
 ///   This location notably allows diagnostics to point to relevant parts of parsed input files.
+/// * May also contain [`Comments`] from the source text around the node,
+///   which are emitted again by serialization.
 /// * Weak references are not supported.
 #[derive(serde::Deserialize)]
 #[serde(from = "T")]
@@ -24,6 +27,7 @@ pub struct Node<T: ?Sized>(triomphe::Arc<HeaderSlice<Header, T>>);
 #[derive(Clone)]
 struct Header {
     location: Option<SourceSpan>,
+    comments: Option<Box<Comments>>,
 }
 
 impl<T> Node<T> {
@@ -38,9 +42,20 @@ impl<T> Node<T> {
         Self::new_opt_location(node, None)
     }
 
+    pub(crate) fn with_parsed_comments(mut self, comments: Option<Comments>) -> Self {
+        if let Some(comments) = comments {
+            let inner = triomphe::Arc::get_mut(&mut self.0).expect("newly created node");
+            inner.header.comments = Some(Box::new(comments))
+        }
+        self
+    }
+
     pub(crate) fn new_opt_location(node: T, location: Option<SourceSpan>) -> Self {
         Self(triomphe::Arc::new(HeaderSlice {
-            header: Header { location },
+            header: Header {
+                location,
+                comments: None,
+            },
             slice: node,
         }))
     }
@@ -60,7 +75,10 @@ impl Node<str> {
 
     pub(crate) fn new_str_opt_location(node: &str, location: Option<SourceSpan>) -> Self {
         Self(triomphe::Arc::from_header_and_str(
-            Header { location },
+            Header {
+                location,
+                comments: None,
+            },
             node,
         ))
     }
@@ -89,8 +107,31 @@ impl<T: ?Sized> Node<T> {
     }
 
     /// Returns the given `node` at the same location as `self` (e.g. for a type conversion).
+    ///
+    /// [`Comments`] attached to `self` are also attached to the new node.
     pub fn same_location<U>(&self, node: U) -> Node<U> {
-        Node::new_opt_location(node, self.0.header.location)
+        Node(triomphe::Arc::new(HeaderSlice {
+            header: self.0.header.clone(),
+            slice: node,
+        }))
+    }
+
+    /// Returns `#` comments attached to this node, if any.
+    ///
+    /// Serialization emits comments of top-level definitions and of items
+    /// in curly-bracket-delimited lists such as fields, enum values, and selections.
+    pub fn comments(&self) -> Option<&Comments> {
+        self.0.header.comments.as_deref()
+    }
+
+    /// Returns a mutable reference to comments attached to this node,
+    /// cloning the node if necessary like [`make_mut`][Self::make_mut].
+    pub fn comments_mut(&mut self) -> &mut Comments
+    where
+        T: Clone,
+    {
+        let inner = triomphe::Arc::make_mut(&mut self.0);
+        inner.header.comments.get_or_insert_with(Default::default)
     }
 
     pub fn to_component(&self, origin: ComponentOrigin) -> Component<T> {
@@ -107,6 +148,10 @@ impl<T: ?Sized> Node<T> {
         triomphe::Arc::ptr_eq(&self.0, &other.0)
     }
 
+    pub(crate) fn as_ptr(&self) -> *const () {
+        triomphe::Arc::as_ptr(&self.0).cast()
+    }
+
     /// Returns a mutable reference to `T`, cloning it if necessary
     ///
     /// This is functionally equivalent to [`Arc::make_mut`][mm] from the standard library.
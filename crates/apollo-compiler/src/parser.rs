@@ -36,6 +36,13 @@ use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 use std::sync::OnceLock;
 
+/// The kind of a syntax error, returned by [`DiagnosticData::syntax_error_kind`].
+///
+/// [`DiagnosticData::syntax_error_kind`]: crate::validation::DiagnosticData::syntax_error_kind
+pub use apollo_parser::ErrorKind as SyntaxErrorKind;
+/// The kind of a lexical token, found in [`SyntaxErrorKind`].
+pub use apollo_parser::TokenKind;
+
 /// Configuration for parsing an input string as GraphQL syntax
 #[derive(Default, Debug, Clone)]
 pub struct Parser {
@@ -199,6 +206,7 @@ impl Parser {
             } else {
                 Details::SyntaxError {
                     message: parser_error.message().to_owned(),
+                    kind: parser_error.kind(),
                 }
             };
             errors.push(location, details)
@@ -1,8 +1,11 @@
 //! GraphQL [requests](https://spec.graphql.org/draft/#request)
 //!
-//! This exists primarily to support [`introspection::partial_execute`].
+//! This exists primarily to support [`execution::execute`]
+//! and [`introspection::partial_execute`].
 
 use crate::executable::Operation;
+#[cfg(doc)]
+use crate::execution;
 use crate::execution::input_coercion::InputCoercionError;
 #[cfg(doc)]
 use crate::introspection;
@@ -1,7 +1,10 @@
 //! GraphQL [responses](https://spec.graphql.org/draft/#sec-Response)
 //!
-//! This exists primarily to support [`introspection::partial_execute`].
+//! This exists primarily to support [`execution::execute`]
+//! and [`introspection::partial_execute`].
 
+#[cfg(doc)]
+use crate::execution;
 #[cfg(doc)]
 use crate::introspection;
 use crate::parser::LineColumn;
@@ -0,0 +1,594 @@
+//! Finding which operations of an executable document are affected by breaking schema changes
+
+use crate::ast::DirectiveList;
+use crate::ast::DirectiveLocation;
+use crate::ast::Value;
+use crate::collections::HashMap;
+use crate::collections::HashSet;
+use crate::coordinate::DirectiveCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::coordinate::TypeCoordinate;
+use crate::diagnostic::CliReport;
+use crate::diagnostic::Diagnostic;
+use crate::diagnostic::ToCliReport;
+use crate::executable::Argument;
+use crate::executable::Operation;
+use crate::executable::Selection;
+use crate::executable::SelectionSet;
+use crate::executable::VariableDefinition;
+use crate::parser::SourceMap;
+use crate::parser::SourceSpan;
+use crate::schema::ChangeKind;
+use crate::schema::ChangeSeverity;
+use crate::schema::InputValueDefinition;
+use crate::schema::SchemaChange;
+use crate::schema::SchemaDiff;
+use crate::schema::Type;
+use crate::validation::Valid;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+use std::fmt;
+
+/// An operation affected by breaking schema changes,
+/// returned by [`SchemaDiff::affected_operations`]
+#[derive(Debug, Clone)]
+pub struct AffectedOperation {
+    /// `None` for an anonymous operation
+    pub name: Option<Name>,
+    /// Location of the operation in the executable document
+    pub location: Option<SourceSpan>,
+    pub impacts: Vec<OperationImpact>,
+}
+
+/// A use in an operation (or in a fragment it uses) of a schema element that had a breaking change
+#[derive(Debug, Clone)]
+pub struct OperationImpact {
+    pub change: SchemaChange,
+    /// Location in the executable document of the affected field selection, argument,
+    /// variable definition, type condition, or directive
+    pub location: Option<SourceSpan>,
+}
+
+impl AffectedOperation {
+    /// Returns impacts as diagnostics, given the executable document’s source map
+    pub fn iter<'a>(
+        &'a self,
+        sources: &'a SourceMap,
+    ) -> impl DoubleEndedIterator<Item = Diagnostic<'a, OperationImpact>> + ExactSizeIterator {
+        self.impacts
+            .iter()
+            .map(move |impact| impact.to_diagnostic(sources))
+    }
+}
+
+impl fmt::Display for OperationImpact {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.change, f)
+    }
+}
+
+impl ToCliReport for OperationImpact {
+    fn location(&self) -> Option<SourceSpan> {
+        self.location
+    }
+
+    fn report(&self, report: &mut CliReport<'_>) {
+        report.with_label_opt(self.location, "affected by this change")
+    }
+}
+
+impl SchemaDiff {
+    /// Returns operations of `document` that may break with the new schema
+    /// because they use schema elements with breaking changes.
+    ///
+    /// * `old_schema` and `new_schema` are expected to be those passed to [`diff`][super::diff]
+    /// * `document` is expected to be valid against `old_schema`
+    ///
+    /// Operations are affected by:
+    ///
+    /// * Selecting a field that was removed or whose type changed incompatibly
+    /// * Selecting a field or using a directive that gained a required argument
+    /// * Passing an argument that was removed,
+    ///   or whose new type is incompatible with the value or variable passed
+    /// * Using a removed enum value or input field in a literal value
+    /// * Declaring a variable whose input type was removed or changed incompatibly
+    /// * Using a type condition on a type that was removed or changed kind,
+    ///   or on an interface that the parent type no longer implements
+    /// * Using a directive that was removed or is no longer allowed at that location
+    /// * Using an operation type whose root operation type was removed or changed
+    ///
+    /// Operations not affected are not returned.
+    pub fn affected_operations(
+        &self,
+        old_schema: &Valid<Schema>,
+        new_schema: &Valid<Schema>,
+        document: &Valid<ExecutableDocument>,
+    ) -> Vec<AffectedOperation> {
+        let mut breaking = HashMap::<_, Vec<_>>::default();
+        let mut added_required = HashMap::<_, Vec<_>>::default();
+        let mut root_operations = Vec::new();
+        let mut removed_interfaces = HashMap::<_, Vec<_>>::default();
+        for change in &self.changes {
+            if change.severity() != ChangeSeverity::Breaking {
+                continue;
+            }
+            match (&change.kind, &change.coordinate) {
+                (ChangeKind::ArgumentAdded { .. }, SchemaCoordinate::FieldArgument(coord)) => {
+                    added_required
+                        .entry(coord.field_coordinate().into())
+                        .or_insert_with(Vec::new)
+                        .push(change)
+                }
+                (ChangeKind::ArgumentAdded { .. }, SchemaCoordinate::DirectiveArgument(coord)) => {
+                    added_required
+                        .entry(coord.directive_coordinate().into())
+                        .or_insert_with(Vec::new)
+                        .push(change)
+                }
+                (ChangeKind::InputFieldAdded { .. }, SchemaCoordinate::TypeAttribute(coord)) => {
+                    added_required
+                        .entry(coord.type_coordinate().into())
+                        .or_insert_with(Vec::new)
+                        .push(change)
+                }
+                (ChangeKind::RootOperationTypeChanged { .. }, _) => root_operations.push(change),
+                (ChangeKind::InterfaceImplementationRemoved { interface }, _) => {
+                    removed_interfaces
+                        .entry(interface.clone())
+                        .or_insert_with(Vec::new)
+                        .push(change);
+                    breaking
+                        .entry(change.coordinate.clone())
+                        .or_insert_with(Vec::new)
+                        .push(change)
+                }
+                _ => breaking
+                    .entry(change.coordinate.clone())
+                    .or_insert_with(Vec::new)
+                    .push(change),
+            }
+        }
+        let checker = Checker {
+            old_schema,
+            new_schema,
+            document,
+            breaking,
+            added_required,
+            root_operations,
+            removed_interfaces,
+        };
+        document
+            .operations
+            .iter()
+            .filter_map(|operation| {
+                let mut state = OperationState {
+                    operation,
+                    impacts: Vec::new(),
+                    visited_fragments: HashSet::default(),
+                    visited_input_types: HashSet::default(),
+                };
+                checker.operation(&mut state);
+                (!state.impacts.is_empty()).then(|| AffectedOperation {
+                    name: operation.name.clone(),
+                    location: operation.location(),
+                    impacts: state.impacts,
+                })
+            })
+            .collect()
+    }
+}
+
+struct Checker<'a> {
+    old_schema: &'a Schema,
+    new_schema: &'a Schema,
+    document: &'a ExecutableDocument,
+    /// Breaking changes by coordinate, except those in `added_required`
+    breaking: HashMap<SchemaCoordinate, Vec<&'a SchemaChange>>,
+    /// Added required arguments and input fields,
+    /// by coordinate of the field, directive, or input object type that contains them
+    added_required: HashMap<SchemaCoordinate, Vec<&'a SchemaChange>>,
+    /// Breaking changes to root operation types
+    root_operations: Vec<&'a SchemaChange>,
+    /// Removed interface implementations, by name of the interface
+    removed_interfaces: HashMap<Name, Vec<&'a SchemaChange>>,
+}
+
+struct OperationState<'a> {
+    operation: &'a Node<Operation>,
+    impacts: Vec<OperationImpact>,
+    visited_fragments: HashSet<&'a Name>,
+    visited_input_types: HashSet<Name>,
+}
+
+impl<'a> Checker<'a> {
+    fn push(
+        &self,
+        state: &mut OperationState<'a>,
+        change: &SchemaChange,
+        location: Option<SourceSpan>,
+    ) {
+        state.impacts.push(OperationImpact {
+            change: change.clone(),
+            location,
+        })
+    }
+
+    /// Pushes all changes to `coordinate` in the given map
+    fn push_all(
+        &self,
+        state: &mut OperationState<'a>,
+        map: &HashMap<SchemaCoordinate, Vec<&'a SchemaChange>>,
+        coordinate: SchemaCoordinate,
+        location: Option<SourceSpan>,
+    ) {
+        for change in map.get(&coordinate).into_iter().flatten() {
+            self.push(state, change, location)
+        }
+    }
+
+    fn operation(&self, state: &mut OperationState<'a>) {
+        let operation = state.operation;
+        for change in &self.root_operations {
+            if let ChangeKind::RootOperationTypeChanged { operation_type, .. } = change.kind {
+                if operation_type == operation.operation_type {
+                    self.push(state, change, operation.location())
+                }
+            }
+        }
+        for variable in &operation.variables {
+            self.variable_definition(state, variable)
+        }
+        self.directives(
+            state,
+            &operation.directives,
+            operation.operation_type.into(),
+        );
+        self.selection_set(state, &operation.selection_set)
+    }
+
+    fn variable_definition(
+        &self,
+        state: &mut OperationState<'a>,
+        variable: &Node<VariableDefinition>,
+    ) {
+        let type_name = variable.ty.inner_named_type();
+        // Input types are visited once per variable, so that each affected variable is reported
+        state.visited_input_types.clear();
+        self.input_type(state, type_name, variable.location());
+        self.directives(
+            state,
+            &variable.directives,
+            DirectiveLocation::VariableDefinition,
+        );
+    }
+
+    /// Breaking changes to an input type that values provided for a variable may rely on
+    fn input_type(
+        &self,
+        state: &mut OperationState<'a>,
+        type_name: &Name,
+        location: Option<SourceSpan>,
+    ) {
+        if !state.visited_input_types.insert(type_name.clone()) {
+            return;
+        }
+        let type_coord = TypeCoordinate {
+            ty: type_name.clone(),
+        };
+        self.push_all(state, &self.breaking, type_coord.clone().into(), location);
+        self.push_all(
+            state,
+            &self.added_required,
+            type_coord.clone().into(),
+            location,
+        );
+        if let Some(def) = self.old_schema.get_input_object(type_name) {
+            for (field_name, field_def) in &def.fields {
+                let coord = type_coord.with_attribute(field_name.clone());
+                self.push_all(state, &self.breaking, coord.into(), location);
+                self.input_type(state, field_def.ty.inner_named_type(), location)
+            }
+        } else if let Some(def) = self.old_schema.get_enum(type_name) {
+            for value in def.values.keys() {
+                let coord = type_coord.with_attribute(value.clone());
+                self.push_all(state, &self.breaking, coord.into(), location);
+            }
+        }
+    }
+
+    fn selection_set(&self, state: &mut OperationState<'a>, selection_set: &'a SelectionSet) {
+        for selection in &selection_set.selections {
+            match selection {
+                Selection::Field(field) => {
+                    self.directives(state, &field.directives, DirectiveLocation::Field);
+                    if !field.name.starts_with("__") {
+                        let coord = TypeAttributeCoordinate {
+                            ty: selection_set.ty.clone(),
+                            attribute: field.name.clone(),
+                        };
+                        self.push_all(
+                            state,
+                            &self.breaking,
+                            coord.clone().into(),
+                            field.location(),
+                        );
+                        self.push_all(
+                            state,
+                            &self.added_required,
+                            coord.clone().into(),
+                            field.location(),
+                        );
+                        let new_arguments = self
+                            .new_schema
+                            .type_field(&selection_set.ty, &field.name)
+                            .ok()
+                            .map(|def| &def.arguments[..]);
+                        self.arguments(
+                            state,
+                            &|argument| coord.with_argument(argument).into(),
+                            &field.arguments,
+                            &field.definition.arguments,
+                            new_arguments,
+                        );
+                    }
+                    self.selection_set(state, &field.selection_set)
+                }
+                Selection::FragmentSpread(spread) => {
+                    self.directives(state, &spread.directives, DirectiveLocation::FragmentSpread);
+                    if !state.visited_fragments.insert(&spread.fragment_name) {
+                        continue;
+                    }
+                    let Some(fragment) = self.document.fragments.get(&spread.fragment_name) else {
+                        continue;
+                    };
+                    self.type_condition(
+                        state,
+                        &selection_set.ty,
+                        fragment.type_condition(),
+                        fragment.location(),
+                    );
+                    self.directives(
+                        state,
+                        &fragment.directives,
+                        DirectiveLocation::FragmentDefinition,
+                    );
+                    self.selection_set(state, &fragment.selection_set)
+                }
+                Selection::InlineFragment(inline) => {
+                    if let Some(type_condition) = &inline.type_condition {
+                        self.type_condition(
+                            state,
+                            &selection_set.ty,
+                            type_condition,
+                            inline.location(),
+                        )
+                    }
+                    self.directives(state, &inline.directives, DirectiveLocation::InlineFragment);
+                    self.selection_set(state, &inline.selection_set)
+                }
+            }
+        }
+    }
+
+    fn type_condition(
+        &self,
+        state: &mut OperationState<'a>,
+        parent_type: &Name,
+        type_condition: &Name,
+        location: Option<SourceSpan>,
+    ) {
+        let coord = TypeCoordinate {
+            ty: type_condition.clone(),
+        };
+        self.push_all(state, &self.breaking, coord.into(), location);
+        // A type condition on an interface may no longer apply to the parent type
+        for change in self
+            .removed_interfaces
+            .get(type_condition)
+            .into_iter()
+            .flatten()
+        {
+            let SchemaCoordinate::Type(implementer) = &change.coordinate else {
+                continue;
+            };
+            if implementer.ty == *parent_type
+                || self.old_schema.is_subtype(parent_type, &implementer.ty)
+            {
+                self.push(state, change, location)
+            }
+        }
+    }
+
+    fn directives(
+        &self,
+        state: &mut OperationState<'a>,
+        directives: &DirectiveList,
+        directive_location: DirectiveLocation,
+    ) {
+        for directive in directives {
+            let coord = DirectiveCoordinate {
+                directive: directive.name.clone(),
+            };
+            for change in self
+                .breaking
+                .get(&coord.clone().into())
+                .into_iter()
+                .flatten()
+            {
+                match &change.kind {
+                    ChangeKind::DirectiveLocationRemoved { location }
+                        if *location != directive_location => {}
+                    _ => self.push(state, change, directive.location()),
+                }
+            }
+            self.push_all(
+                state,
+                &self.added_required,
+                coord.clone().into(),
+                directive.location(),
+            );
+            let Some(old_def) = self.old_schema.directive_definitions.get(&directive.name) else {
+                continue;
+            };
+            let new_arguments = self
+                .new_schema
+                .directive_definitions
+                .get(&directive.name)
+                .map(|def| &def.arguments[..]);
+            self.arguments(
+                state,
+                &|argument| coord.with_argument(argument).into(),
+                &directive.arguments,
+                &old_def.arguments,
+                new_arguments,
+            )
+        }
+    }
+
+    fn arguments(
+        &self,
+        state: &mut OperationState<'a>,
+        coord: &dyn Fn(Name) -> SchemaCoordinate,
+        arguments: &[Node<Argument>],
+        old_definitions: &[Node<InputValueDefinition>],
+        new_definitions: Option<&[Node<InputValueDefinition>]>,
+    ) {
+        for argument in arguments {
+            let new_definition = new_definitions
+                .into_iter()
+                .flatten()
+                .find(|def| def.name == argument.name);
+            for change in self
+                .breaking
+                .get(&coord(argument.name.clone()))
+                .into_iter()
+                .flatten()
+            {
+                if let ChangeKind::ArgumentTypeChanged { old_type, new_type } = &change.kind {
+                    let has_default = new_definition.is_some_and(|def| def.default_value.is_some());
+                    if self.is_compatible(state, &argument.value, old_type, new_type, has_default) {
+                        continue;
+                    }
+                }
+                self.push(state, change, argument.location())
+            }
+            if let Some(old_definition) =
+                old_definitions.iter().find(|def| def.name == argument.name)
+            {
+                self.literal(state, &argument.value, &old_definition.ty)
+            }
+        }
+    }
+
+    /// Breaking changes to enum values and input fields used in a literal value
+    fn literal(&self, state: &mut OperationState<'a>, value: &Node<Value>, old_type: &Type) {
+        let type_name = old_type.inner_named_type();
+        match value.as_ref() {
+            Value::Enum(enum_value) => {
+                let coord = TypeAttributeCoordinate {
+                    ty: type_name.clone(),
+                    attribute: enum_value.clone(),
+                };
+                self.push_all(state, &self.breaking, coord.into(), value.location())
+            }
+            Value::List(items) => {
+                for item in items {
+                    self.literal(state, item, old_type.item_type())
+                }
+            }
+            Value::Object(fields) => {
+                let Some(old_def) = self.old_schema.get_input_object(type_name) else {
+                    return;
+                };
+                let type_coord = TypeCoordinate {
+                    ty: type_name.clone(),
+                };
+                self.push_all(
+                    state,
+                    &self.added_required,
+                    type_coord.clone().into(),
+                    value.location(),
+                );
+                let new_def = self.new_schema.get_input_object(type_name);
+                for (field_name, field_value) in fields {
+                    let coord = type_coord.with_attribute(field_name.clone());
+                    for change in self.breaking.get(&coord.into()).into_iter().flatten() {
+                        if let ChangeKind::InputFieldTypeChanged { old_type, new_type } =
+                            &change.kind
+                        {
+                            let has_default = new_def
+                                .and_then(|def| def.fields.get(field_name))
+                                .is_some_and(|def| def.default_value.is_some());
+                            if self.is_compatible(
+                                state,
+                                field_value,
+                                old_type,
+                                new_type,
+                                has_default,
+                            ) {
+                                continue;
+                            }
+                        }
+                        self.push(state, change, field_value.location())
+                    }
+                    if let Some(field_def) = old_def.fields.get(field_name) {
+                        self.literal(state, field_value, &field_def.ty)
+                    }
+                }
+            }
+            Value::Variable(_)
+            | Value::Null
+            | Value::String(_)
+            | Value::Float(_)
+            | Value::Int(_)
+            | Value::Boolean(_) => {}
+        }
+    }
+
+    /// Whether a value valid for `old_type` is still valid for `new_type`
+    fn is_compatible(
+        &self,
+        state: &OperationState<'a>,
+        value: &Value,
+        old_type: &Type,
+        new_type: &Type,
+        new_has_default: bool,
+    ) -> bool {
+        match value {
+            // <https://spec.graphql.org/draft/#IsVariableUsageAllowed()>
+            Value::Variable(name) => {
+                let Some(variable) = state
+                    .operation
+                    .variables
+                    .iter()
+                    .find(|variable| variable.name == *name)
+                else {
+                    return false;
+                };
+                let has_non_null_default = variable
+                    .default_value
+                    .as_ref()
+                    .is_some_and(|value| !value.is_null());
+                variable.ty.is_assignable_to(new_type)
+                    || (new_type.is_non_null()
+                        && (has_non_null_default || new_has_default)
+                        && variable.ty.is_assignable_to(&new_type.clone().nullable()))
+            }
+            Value::Null => !new_type.is_non_null(),
+            // Other literals stay valid if only nullability changed
+            _ => without_non_null(old_type) == without_non_null(new_type),
+        }
+    }
+}
+
+fn without_non_null(ty: &Type) -> Type {
+    match ty {
+        Type::Named(name) | Type::NonNullNamed(name) => Type::Named(name.clone()),
+        Type::List(inner) | Type::NonNullList(inner) => {
+            Type::List(Box::new(without_non_null(inner)))
+        }
+    }
+}
@@ -5,7 +5,6 @@ use std::fmt;
 use std::hash;
 use std::ops::Deref;
 use std::ops::DerefMut;
-use triomphe::Arc;
 
 /// A component of a type or `schema`, for example a field of an object type.
 ///
@@ -35,36 +34,37 @@ pub enum ComponentOrigin {
 /// even if they contain the same source location.
 #[derive(Debug, Clone, Eq)]
 pub struct ExtensionId {
-    arc: Arc<Option<SourceSpan>>,
+    /// Carries the location and comments of the extension
+    node: Node<()>,
 }
 
 impl ExtensionId {
     pub fn new<T>(extension: &Node<T>) -> Self {
         Self {
-            arc: Arc::new(extension.location()),
+            node: extension.same_location(()),
         }
     }
 
     /// If this extension was parsed from a source file, returns the file ID and source span
     /// (start and end byte offsets) within that file.
     pub fn location(&self) -> Option<SourceSpan> {
-        *self.arc
+        self.node.location()
     }
 
     pub fn same_location<T>(&self, node: T) -> Node<T> {
-        Node::new_opt_location(node, self.location())
+        self.node.same_location(node)
     }
 }
 
 impl PartialEq for ExtensionId {
     fn eq(&self, other: &Self) -> bool {
-        Arc::ptr_eq(&self.arc, &other.arc)
+        self.node.ptr_eq(&other.node)
     }
 }
 
 impl hash::Hash for ExtensionId {
     fn hash<H: hash::Hasher>(&self, state: &mut H) {
-        Arc::as_ptr(&self.arc).hash(state);
+        self.node.as_ptr().hash(state);
     }
 }
 
@@ -0,0 +1,878 @@
+//! Comparing two versions of a schema
+
+use crate::ast::DirectiveLocation;
+use crate::ast::OperationType;
+use crate::ast::Value;
+use crate::collections::IndexMap;
+use crate::collections::IndexSet;
+use crate::coordinate::DirectiveCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::TypeCoordinate;
+use crate::diagnostic::CliReport;
+use crate::diagnostic::Diagnostic;
+use crate::diagnostic::ToCliReport;
+use crate::parser::SourceMap;
+use crate::parser::SourceSpan;
+use crate::schema::Component;
+use crate::schema::ComponentName;
+use crate::schema::DirectiveDefinition;
+use crate::schema::EnumValueDefinition;
+use crate::schema::ExtendedType;
+use crate::schema::FieldDefinition;
+use crate::schema::InputValueDefinition;
+use crate::schema::Type;
+use crate::validation::Valid;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+use std::fmt;
+use std::sync::Arc;
+
+/// The result of [`diff`]: changes between two versions of a schema
+#[derive(Debug, Clone)]
+pub struct SchemaDiff {
+    /// Source files of both the old and new schema, for rendering diagnostics
+    pub sources: SourceMap,
+    pub changes: Vec<SchemaChange>,
+}
+
+/// A single difference between two versions of a schema
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaChange {
+    /// The schema element that changed.
+    /// For changes to a list of names like [`ChangeKind::UnionMemberRemoved`],
+    /// this is the coordinate of the type or directive that contains that list.
+    pub coordinate: SchemaCoordinate,
+    pub kind: ChangeKind,
+    /// Location in the old schema of the element that changed,
+    /// or of its parent if the element was added
+    pub old_location: Option<SourceSpan>,
+    /// Location in the new schema of the element that changed,
+    /// or of its parent if the element was removed
+    pub new_location: Option<SourceSpan>,
+}
+
+/// What changed about a schema element
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ChangeKind {
+    TypeAdded,
+    TypeRemoved,
+    /// For example, an object type became an interface type.
+    /// Kinds are described like `"an object type"`.
+    TypeKindChanged {
+        old_kind: &'static str,
+        new_kind: &'static str,
+    },
+    /// A field of an object or interface type was added
+    FieldAdded,
+    /// A field of an object or interface type was removed
+    FieldRemoved,
+    FieldTypeChanged {
+        old_type: Type,
+        new_type: Type,
+        /// Whether values of `new_type` are still valid for `old_type`,
+        /// including through interface implementations and union membership in the new schema.
+        /// For example, `Node` to `User!` where `User` implements `Node`.
+        compatible: bool,
+    },
+    /// An argument of a field or directive was added
+    ArgumentAdded {
+        required: bool,
+    },
+    /// An argument of a field or directive was removed
+    ArgumentRemoved,
+    ArgumentTypeChanged {
+        old_type: Type,
+        new_type: Type,
+    },
+    InputFieldAdded {
+        required: bool,
+    },
+    InputFieldRemoved,
+    InputFieldTypeChanged {
+        old_type: Type,
+        new_type: Type,
+    },
+    /// The default value of an argument or input field was added, removed, or changed
+    DefaultValueChanged {
+        old_value: Option<Node<Value>>,
+        new_value: Option<Node<Value>>,
+    },
+    EnumValueAdded,
+    EnumValueRemoved,
+    UnionMemberAdded {
+        member: Name,
+    },
+    UnionMemberRemoved {
+        member: Name,
+    },
+    InterfaceImplementationAdded {
+        interface: Name,
+    },
+    InterfaceImplementationRemoved {
+        interface: Name,
+    },
+    DirectiveAdded,
+    DirectiveRemoved,
+    DirectiveLocationAdded {
+        location: DirectiveLocation,
+    },
+    DirectiveLocationRemoved {
+        location: DirectiveLocation,
+    },
+    DirectiveRepeatableAdded,
+    DirectiveRepeatableRemoved,
+    /// A field, argument, input field, or enum value now has a `@deprecated` directive
+    DeprecationAdded,
+    /// A field, argument, input field, or enum value no longer has a `@deprecated` directive
+    DeprecationRemoved,
+    DescriptionChanged,
+    /// A root operation type was added, removed, or set to a different type.
+    /// The coordinate is that of the old type, or of the new type if there was none.
+    RootOperationTypeChanged {
+        operation_type: OperationType,
+        old_type: Option<Name>,
+        new_type: Option<Name>,
+    },
+}
+
+/// How a schema change may affect existing clients.
+///
+/// Ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ChangeSeverity {
+    /// The change does not affect existing operations
+    Safe,
+    /// Existing operations stay valid, but their results may change
+    /// in ways that clients do not expect.
+    /// For example, a new enum value could be returned to a client with an exhaustive `match`.
+    Dangerous,
+    /// Some existing operations may become invalid or fail at runtime
+    Breaking,
+}
+
+/// Compare two versions of a schema, for example to gate deployments.
+///
+/// Changes are classified by [`SchemaChange::severity`]:
+///
+/// ```
+/// use apollo_compiler::schema;
+/// use apollo_compiler::schema::ChangeSeverity;
+/// use apollo_compiler::Schema;
+///
+/// let old = Schema::parse_and_validate("type Query { a: Int, b: Int }", "old.graphql").unwrap();
+/// let new = Schema::parse_and_validate("type Query { a: Int! }", "new.graphql").unwrap();
+/// let diff = schema::diff(&old, &new);
+/// let summary: Vec<_> = diff
+///     .changes
+///     .iter()
+///     .map(|change| (change.severity(), change.to_string()))
+///     .collect();
+/// assert_eq!(summary, [
+///     (ChangeSeverity::Safe, "field `Query.a` changed type from `Int` to `Int!`".to_owned()),
+///     (ChangeSeverity::Breaking, "field `Query.b` was removed".to_owned()),
+/// ]);
+/// ```
+///
+/// Changes to built-in types are not reported.
+pub fn diff(old: &Valid<Schema>, new: &Valid<Schema>) -> SchemaDiff {
+    let mut sources = (*old.sources).clone();
+    sources.extend(new.sources.iter().map(|(id, file)| (*id, file.clone())));
+    let mut differ = Differ {
+        old,
+        new,
+        changes: Vec::new(),
+    };
+    differ.root_operations();
+    differ.types();
+    differ.directive_definitions();
+    SchemaDiff {
+        sources: Arc::new(sources),
+        changes: differ.changes,
+    }
+}
+
+impl SchemaDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Returns the highest severity among changes, or `None` if there are no changes
+    pub fn max_severity(&self) -> Option<ChangeSeverity> {
+        self.changes.iter().map(SchemaChange::severity).max()
+    }
+
+    pub fn has_breaking_changes(&self) -> bool {
+        self.max_severity() == Some(ChangeSeverity::Breaking)
+    }
+
+    /// Returns changes with the given severity
+    pub fn with_severity(
+        &self,
+        severity: ChangeSeverity,
+    ) -> impl Iterator<Item = &SchemaChange> + '_ {
+        self.changes
+            .iter()
+            .filter(move |change| change.severity() == severity)
+    }
+
+    /// Returns changes as diagnostics that can be rendered with labels in both schemas
+    pub fn iter(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = Diagnostic<'_, SchemaChange>> + ExactSizeIterator {
+        self.changes
+            .iter()
+            .map(|change| change.to_diagnostic(&self.sources))
+    }
+}
+
+impl fmt::Display for SchemaDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for diagnostic in self.iter() {
+            fmt::Display::fmt(&diagnostic, f)?
+        }
+        Ok(())
+    }
+}
+
+impl SchemaChange {
+    pub fn severity(&self) -> ChangeSeverity {
+        use ChangeKind::*;
+        match &self.kind {
+            TypeRemoved
+            | TypeKindChanged { .. }
+            | FieldRemoved
+            | ArgumentRemoved
+            | InputFieldRemoved
+            | EnumValueRemoved
+            | UnionMemberRemoved { .. }
+            | InterfaceImplementationRemoved { .. }
+            | DirectiveRemoved
+            | DirectiveLocationRemoved { .. }
+            | DirectiveRepeatableRemoved => ChangeSeverity::Breaking,
+            RootOperationTypeChanged { old_type, .. } => {
+                if old_type.is_none() {
+                    ChangeSeverity::Safe
+                } else {
+                    ChangeSeverity::Breaking
+                }
+            }
+            // Output types can be made more specific, such as nullable to non-null
+            FieldTypeChanged { compatible, .. } => {
+                if *compatible {
+                    ChangeSeverity::Safe
+                } else {
+                    ChangeSeverity::Breaking
+                }
+            }
+            // Input types can be made more permissive, such as non-null to nullable
+            ArgumentTypeChanged { old_type, new_type }
+            | InputFieldTypeChanged { old_type, new_type } => {
+                if old_type.is_assignable_to(new_type) {
+                    ChangeSeverity::Safe
+                } else {
+                    ChangeSeverity::Breaking
+                }
+            }
+            ArgumentAdded { required: true } | InputFieldAdded { required: true } => {
+                ChangeSeverity::Breaking
+            }
+            ArgumentAdded { required: false }
+                if matches!(self.coordinate, SchemaCoordinate::DirectiveArgument(_)) =>
+            {
+                ChangeSeverity::Safe
+            }
+            ArgumentAdded { required: false }
+            | InputFieldAdded { required: false }
+            | DefaultValueChanged { .. }
+            | EnumValueAdded
+            | UnionMemberAdded { .. }
+            | InterfaceImplementationAdded { .. } => ChangeSeverity::Dangerous,
+            TypeAdded
+            | FieldAdded
+            | DirectiveAdded
+            | DirectiveLocationAdded { .. }
+            | DirectiveRepeatableAdded
+            | DeprecationAdded
+            | DeprecationRemoved
+            | DescriptionChanged => ChangeSeverity::Safe,
+        }
+    }
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ChangeKind::*;
+        let coord = &self.coordinate;
+        match &self.kind {
+            TypeAdded => write!(f, "type `{coord}` was added"),
+            TypeRemoved => write!(f, "type `{coord}` was removed"),
+            TypeKindChanged { old_kind, new_kind } => {
+                write!(f, "`{coord}` changed from {old_kind} to {new_kind}")
+            }
+            FieldAdded => write!(f, "field `{coord}` was added"),
+            FieldRemoved => write!(f, "field `{coord}` was removed"),
+            FieldTypeChanged {
+                old_type, new_type, ..
+            } => {
+                write!(
+                    f,
+                    "field `{coord}` changed type from `{old_type}` to `{new_type}`"
+                )
+            }
+            ArgumentAdded { required } => {
+                let required = if *required { "required" } else { "optional" };
+                write!(f, "{required} argument `{coord}` was added")
+            }
+            ArgumentRemoved => write!(f, "argument `{coord}` was removed"),
+            ArgumentTypeChanged { old_type, new_type } => {
+                write!(
+                    f,
+                    "argument `{coord}` changed type from `{old_type}` to `{new_type}`"
+                )
+            }
+            InputFieldAdded { required } => {
+                let required = if *required { "required" } else { "optional" };
+                write!(f, "{required} input field `{coord}` was added")
+            }
+            InputFieldRemoved => write!(f, "input field `{coord}` was removed"),
+            InputFieldTypeChanged { old_type, new_type } => {
+                write!(
+                    f,
+                    "input field `{coord}` changed type from `{old_type}` to `{new_type}`"
+                )
+            }
+            DefaultValueChanged {
+                old_value,
+                new_value,
+            } => match (old_value, new_value) {
+                (None, Some(new)) => write!(f, "default value `{new}` was added to `{coord}`"),
+                (Some(old), None) => write!(f, "default value `{old}` was removed from `{coord}`"),
+                (Some(old), Some(new)) => {
+                    write!(
+                        f,
+                        "default value of `{coord}` changed from `{old}` to `{new}`"
+                    )
+                }
+                (None, None) => write!(f, "default value of `{coord}` changed"),
+            },
+            EnumValueAdded => write!(f, "enum value `{coord}` was added"),
+            EnumValueRemoved => write!(f, "enum value `{coord}` was removed"),
+            UnionMemberAdded { member } => {
+                write!(f, "`{member}` was added to union `{coord}`")
+            }
+            UnionMemberRemoved { member } => {
+                write!(f, "`{member}` was removed from union `{coord}`")
+            }
+            InterfaceImplementationAdded { interface } => {
+                write!(f, "`{coord}` now implements interface `{interface}`")
+            }
+            InterfaceImplementationRemoved { interface } => {
+                write!(f, "`{coord}` no longer implements interface `{interface}`")
+            }
+            DirectiveAdded => write!(f, "directive `{coord}` was added"),
+            DirectiveRemoved => write!(f, "directive `{coord}` was removed"),
+            DirectiveLocationAdded { location } => {
+                write!(f, "location {location} was added to directive `{coord}`")
+            }
+            DirectiveLocationRemoved { location } => {
+                write!(
+                    f,
+                    "location {location} was removed from directive `{coord}`"
+                )
+            }
+            DirectiveRepeatableAdded => write!(f, "directive `{coord}` is now repeatable"),
+            DirectiveRepeatableRemoved => {
+                write!(f, "directive `{coord}` is no longer repeatable")
+            }
+            DeprecationAdded => write!(f, "`{coord}` was deprecated"),
+            DeprecationRemoved => write!(f, "`{coord}` is no longer deprecated"),
+            DescriptionChanged => write!(f, "description of `{coord}` changed"),
+            RootOperationTypeChanged {
+                operation_type,
+                old_type,
+                new_type,
+            } => match (old_type, new_type) {
+                (None, Some(new)) => {
+                    write!(f, "root {operation_type} operation type `{new}` was added")
+                }
+                (Some(old), None) => {
+                    write!(
+                        f,
+                        "root {operation_type} operation type `{old}` was removed"
+                    )
+                }
+                (Some(old), Some(new)) => write!(
+                    f,
+                    "root {operation_type} operation type changed from `{old}` to `{new}`"
+                ),
+                (None, None) => write!(f, "root {operation_type} operation type changed"),
+            },
+        }
+    }
+}
+
+impl ToCliReport for SchemaChange {
+    fn location(&self) -> Option<SourceSpan> {
+        self.new_location.or(self.old_location)
+    }
+
+    fn report(&self, report: &mut CliReport<'_>) {
+        report.with_label_opt(self.old_location, "in the old schema");
+        report.with_label_opt(self.new_location, "in the new schema");
+        if self.severity() == ChangeSeverity::Breaking {
+            report.with_note("this is a breaking change");
+        }
+    }
+}
+
+impl fmt::Display for ChangeSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Safe => "safe",
+            Self::Dangerous => "dangerous",
+            Self::Breaking => "breaking",
+        })
+    }
+}
+
+struct Differ<'a> {
+    old: &'a Schema,
+    new: &'a Schema,
+    changes: Vec<SchemaChange>,
+}
+
+/// Old and new locations
+type Locations = (Option<SourceSpan>, Option<SourceSpan>);
+
+impl Differ<'_> {
+    fn push(
+        &mut self,
+        coordinate: impl Into<SchemaCoordinate>,
+        kind: ChangeKind,
+        (old_location, new_location): Locations,
+    ) {
+        self.changes.push(SchemaChange {
+            coordinate: coordinate.into(),
+            kind,
+            old_location,
+            new_location,
+        })
+    }
+
+    fn root_operations(&mut self) {
+        let locations = (
+            self.old.schema_definition.location(),
+            self.new.schema_definition.location(),
+        );
+        for operation_type in [
+            OperationType::Query,
+            OperationType::Mutation,
+            OperationType::Subscription,
+        ] {
+            let old_type = self.old.root_operation(operation_type);
+            let new_type = self.new.root_operation(operation_type);
+            if old_type == new_type {
+                continue;
+            }
+            let Some(ty) = old_type.or(new_type) else {
+                continue;
+            };
+            let kind = ChangeKind::RootOperationTypeChanged {
+                operation_type,
+                old_type: old_type.cloned(),
+                new_type: new_type.cloned(),
+            };
+            self.push(TypeCoordinate { ty: ty.clone() }, kind, locations)
+        }
+    }
+
+    fn types(&mut self) {
+        for (name, old_ty) in &self.old.types {
+            if old_ty.is_built_in() {
+                continue;
+            }
+            let coord = TypeCoordinate { ty: name.clone() };
+            match self.new.types.get(name) {
+                Some(new_ty) => self.type_definition(coord, old_ty, new_ty),
+                None => self.push(coord, ChangeKind::TypeRemoved, (old_ty.location(), None)),
+            }
+        }
+        for (name, new_ty) in &self.new.types {
+            if !new_ty.is_built_in() && !self.old.types.contains_key(name) {
+                let coord = TypeCoordinate { ty: name.clone() };
+                self.push(coord, ChangeKind::TypeAdded, (None, new_ty.location()))
+            }
+        }
+    }
+
+    fn type_definition(&mut self, coord: TypeCoordinate, old: &ExtendedType, new: &ExtendedType) {
+        let locations = (old.location(), new.location());
+        match (old, new) {
+            (ExtendedType::Scalar(old), ExtendedType::Scalar(new)) => {
+                self.description(coord, &old.description, &new.description, locations)
+            }
+            (ExtendedType::Object(old), ExtendedType::Object(new)) => {
+                self.description(coord.clone(), &old.description, &new.description, locations);
+                self.interfaces(
+                    &coord,
+                    &old.implements_interfaces,
+                    &new.implements_interfaces,
+                    locations,
+                );
+                self.fields(&coord, &old.fields, &new.fields, locations);
+            }
+            (ExtendedType::Interface(old), ExtendedType::Interface(new)) => {
+                self.description(coord.clone(), &old.description, &new.description, locations);
+                self.interfaces(
+                    &coord,
+                    &old.implements_interfaces,
+                    &new.implements_interfaces,
+                    locations,
+                );
+                self.fields(&coord, &old.fields, &new.fields, locations);
+            }
+            (ExtendedType::Union(old), ExtendedType::Union(new)) => {
+                self.description(coord.clone(), &old.description, &new.description, locations);
+                for member in old.members.difference(&new.members) {
+                    let kind = ChangeKind::UnionMemberRemoved {
+                        member: member.name.clone(),
+                    };
+                    self.push(coord.clone(), kind, (member.location(), locations.1))
+                }
+                for member in new.members.difference(&old.members) {
+                    let kind = ChangeKind::UnionMemberAdded {
+                        member: member.name.clone(),
+                    };
+                    self.push(coord.clone(), kind, (locations.0, member.location()))
+                }
+            }
+            (ExtendedType::Enum(old), ExtendedType::Enum(new)) => {
+                self.description(coord.clone(), &old.description, &new.description, locations);
+                self.enum_values(&coord, &old.values, &new.values, locations)
+            }
+            (ExtendedType::InputObject(old), ExtendedType::InputObject(new)) => {
+                self.description(coord.clone(), &old.description, &new.description, locations);
+                self.input_fields(&coord, &old.fields, &new.fields, locations)
+            }
+            _ => {
+                let kind = ChangeKind::TypeKindChanged {
+                    old_kind: old.describe(),
+                    new_kind: new.describe(),
+                };
+                self.push(coord, kind, locations)
+            }
+        }
+    }
+
+    fn interfaces(
+        &mut self,
+        coord: &TypeCoordinate,
+        old: &IndexSet<ComponentName>,
+        new: &IndexSet<ComponentName>,
+        locations: Locations,
+    ) {
+        for interface in old.difference(new) {
+            let kind = ChangeKind::InterfaceImplementationRemoved {
+                interface: interface.name.clone(),
+            };
+            self.push(coord.clone(), kind, (interface.location(), locations.1))
+        }
+        for interface in new.difference(old) {
+            let kind = ChangeKind::InterfaceImplementationAdded {
+                interface: interface.name.clone(),
+            };
+            self.push(coord.clone(), kind, (locations.0, interface.location()))
+        }
+    }
+
+    fn fields(
+        &mut self,
+        type_coord: &TypeCoordinate,
+        old: &IndexMap<Name, Component<FieldDefinition>>,
+        new: &IndexMap<Name, Component<FieldDefinition>>,
+        locations: Locations,
+    ) {
+        for (name, old_field) in old {
+            let coord = type_coord.with_attribute(name.clone());
+            let Some(new_field) = new.get(name) else {
+                let kind = ChangeKind::FieldRemoved;
+                self.push(coord, kind, (old_field.location(), locations.1));
+                continue;
+            };
+            let field_locations = (old_field.location(), new_field.location());
+            if old_field.ty != new_field.ty {
+                let kind = ChangeKind::FieldTypeChanged {
+                    old_type: old_field.ty.clone(),
+                    new_type: new_field.ty.clone(),
+                    compatible: self
+                        .new
+                        .is_valid_implementation_field_type(&new_field.ty, &old_field.ty),
+                };
+                self.push(coord.clone(), kind, field_locations)
+            }
+            self.description(
+                coord.clone(),
+                &old_field.description,
+                &new_field.description,
+                field_locations,
+            );
+            self.deprecation(
+                coord.clone(),
+                old_field.directives.has("deprecated"),
+                new_field.directives.has("deprecated"),
+                field_locations,
+            );
+            self.arguments(
+                |argument| coord.with_argument(argument).into(),
+                &old_field.arguments,
+                &new_field.arguments,
+                field_locations,
+            )
+        }
+        for (name, new_field) in new {
+            if !old.contains_key(name) {
+                let coord = type_coord.with_attribute(name.clone());
+                let kind = ChangeKind::FieldAdded;
+                self.push(coord, kind, (locations.0, new_field.location()))
+            }
+        }
+    }
+
+    fn arguments(
+        &mut self,
+        coord: impl Fn(Name) -> SchemaCoordinate,
+        old: &[Node<InputValueDefinition>],
+        new: &[Node<InputValueDefinition>],
+        locations: Locations,
+    ) {
+        for old_arg in old {
+            let arg_coord = coord(old_arg.name.clone());
+            match new.iter().find(|new_arg| new_arg.name == old_arg.name) {
+                Some(new_arg) => self.input_value(arg_coord, true, old_arg, new_arg),
+                None => self.push(
+                    arg_coord,
+                    ChangeKind::ArgumentRemoved,
+                    (old_arg.location(), locations.1),
+                ),
+            }
+        }
+        for new_arg in new {
+            if !old.iter().any(|old_arg| old_arg.name == new_arg.name) {
+                let kind = ChangeKind::ArgumentAdded {
+                    required: new_arg.is_required(),
+                };
+                self.push(
+                    coord(new_arg.name.clone()),
+                    kind,
+                    (locations.0, new_arg.location()),
+                )
+            }
+        }
+    }
+
+    fn input_fields(
+        &mut self,
+        type_coord: &TypeCoordinate,
+        old: &IndexMap<Name, Component<InputValueDefinition>>,
+        new: &IndexMap<Name, Component<InputValueDefinition>>,
+        locations: Locations,
+    ) {
+        for (name, old_field) in old {
+            let coord = type_coord.with_attribute(name.clone());
+            match new.get(name) {
+                Some(new_field) => self.input_value(coord.into(), false, old_field, new_field),
+                None => self.push(
+                    coord,
+                    ChangeKind::InputFieldRemoved,
+                    (old_field.location(), locations.1),
+                ),
+            }
+        }
+        for (name, new_field) in new {
+            if !old.contains_key(name) {
+                let kind = ChangeKind::InputFieldAdded {
+                    required: new_field.is_required(),
+                };
+                let coord = type_coord.with_attribute(name.clone());
+                self.push(coord, kind, (locations.0, new_field.location()))
+            }
+        }
+    }
+
+    /// Compares an argument (if `is_argument`) or an input field
+    fn input_value(
+        &mut self,
+        coord: SchemaCoordinate,
+        is_argument: bool,
+        old: &Node<InputValueDefinition>,
+        new: &Node<InputValueDefinition>,
+    ) {
+        let locations = (old.location(), new.location());
+        if old.ty != new.ty {
+            let old_type = (*old.ty).clone();
+            let new_type = (*new.ty).clone();
+            let kind = if is_argument {
+                ChangeKind::ArgumentTypeChanged { old_type, new_type }
+            } else {
+                ChangeKind::InputFieldTypeChanged { old_type, new_type }
+            };
+            self.push(coord.clone(), kind, locations)
+        }
+        if old.default_value != new.default_value {
+            let kind = ChangeKind::DefaultValueChanged {
+                old_value: old.default_value.clone(),
+                new_value: new.default_value.clone(),
+            };
+            let locations = (
+                old.default_value.as_ref().and_then(|v| v.location()),
+                new.default_value.as_ref().and_then(|v| v.location()),
+            );
+            self.push(coord.clone(), kind, locations)
+        }
+        self.description(coord.clone(), &old.description, &new.description, locations);
+        self.deprecation(
+            coord,
+            old.directives.has("deprecated"),
+            new.directives.has("deprecated"),
+            locations,
+        )
+    }
+
+    fn enum_values(
+        &mut self,
+        type_coord: &TypeCoordinate,
+        old: &IndexMap<Name, Component<EnumValueDefinition>>,
+        new: &IndexMap<Name, Component<EnumValueDefinition>>,
+        locations: Locations,
+    ) {
+        for (name, old_value) in old {
+            let coord = type_coord.with_attribute(name.clone());
+            let Some(new_value) = new.get(name) else {
+                let kind = ChangeKind::EnumValueRemoved;
+                self.push(coord, kind, (old_value.location(), locations.1));
+                continue;
+            };
+            let value_locations = (old_value.location(), new_value.location());
+            self.description(
+                coord.clone(),
+                &old_value.description,
+                &new_value.description,
+                value_locations,
+            );
+            self.deprecation(
+                coord,
+                old_value.directives.has("deprecated"),
+                new_value.directives.has("deprecated"),
+                value_locations,
+            )
+        }
+        for (name, new_value) in new {
+            if !old.contains_key(name) {
+                let coord = type_coord.with_attribute(name.clone());
+                let kind = ChangeKind::EnumValueAdded;
+                self.push(coord, kind, (locations.0, new_value.location()))
+            }
+        }
+    }
+
+    fn directive_definitions(&mut self) {
+        for (name, old_def) in &self.old.directive_definitions {
+            let coord = DirectiveCoordinate {
+                directive: name.clone(),
+            };
+            match self.new.directive_definitions.get(name) {
+                Some(new_def) => self.directive_definition(coord, old_def, new_def),
+                None => self.push(
+                    coord,
+                    ChangeKind::DirectiveRemoved,
+                    (old_def.location(), None),
+                ),
+            }
+        }
+        for (name, new_def) in &self.new.directive_definitions {
+            if !self.old.directive_definitions.contains_key(name) {
+                let coord = DirectiveCoordinate {
+                    directive: name.clone(),
+                };
+                self.push(
+                    coord,
+                    ChangeKind::DirectiveAdded,
+                    (None, new_def.location()),
+                )
+            }
+        }
+    }
+
+    fn directive_definition(
+        &mut self,
+        coord: DirectiveCoordinate,
+        old: &Node<DirectiveDefinition>,
+        new: &Node<DirectiveDefinition>,
+    ) {
+        let locations = (old.location(), new.location());
+        self.description(coord.clone(), &old.description, &new.description, locations);
+        match (old.repeatable, new.repeatable) {
+            (true, false) => self.push(
+                coord.clone(),
+                ChangeKind::DirectiveRepeatableRemoved,
+                locations,
+            ),
+            (false, true) => self.push(
+                coord.clone(),
+                ChangeKind::DirectiveRepeatableAdded,
+                locations,
+            ),
+            _ => {}
+        }
+        for &location in &old.locations {
+            if !new.locations.contains(&location) {
+                let kind = ChangeKind::DirectiveLocationRemoved { location };
+                self.push(coord.clone(), kind, locations)
+            }
+        }
+        for &location in &new.locations {
+            if !old.locations.contains(&location) {
+                let kind = ChangeKind::DirectiveLocationAdded { location };
+                self.push(coord.clone(), kind, locations)
+            }
+        }
+        self.arguments(
+            |argument| coord.with_argument(argument).into(),
+            &old.arguments,
+            &new.arguments,
+            locations,
+        )
+    }
+
+    fn description(
+        &mut self,
+        coord: impl Into<SchemaCoordinate>,
+        old: &Option<Node<str>>,
+        new: &Option<Node<str>>,
+        locations: Locations,
+    ) {
+        if old != new {
+            self.push(coord, ChangeKind::DescriptionChanged, locations)
+        }
+    }
+
+    fn deprecation(
+        &mut self,
+        coord: impl Into<SchemaCoordinate>,
+        old_is_deprecated: bool,
+        new_is_deprecated: bool,
+        locations: Locations,
+    ) {
+        match (old_is_deprecated, new_is_deprecated) {
+            (false, true) => self.push(coord, ChangeKind::DeprecationAdded, locations),
+            (true, false) => self.push(coord, ChangeKind::DeprecationRemoved, locations),
+            _ => {}
+        }
+    }
+}
@@ -109,6 +109,14 @@ impl SchemaBuilder {
         executable_definitions_are_errors: bool,
     ) {
         for definition in &document.definitions {
+            if definition
+                .name()
+                .is_some_and(|name| name.is_missing_placeholder())
+            {
+                // Already a syntax error. Leave it out so that references to a missing name
+                // do not resolve to an unrelated definition.
+                continue;
+            }
             macro_rules! type_definition {
                 ($def: ident, $Type: ident, is_scalar = $is_scalar: literal) => {
                     match self.schema.types.entry($def.name.clone()) {
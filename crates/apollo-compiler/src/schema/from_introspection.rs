@@ -0,0 +1,462 @@
+use crate::ast;
+use crate::ast::Value;
+use crate::collections::IndexMap;
+use crate::collections::IndexSet;
+use crate::name;
+use crate::parser::Parser;
+use crate::response::JsonMap;
+use crate::response::JsonValue;
+use crate::schema::Component;
+use crate::schema::ComponentName;
+use crate::schema::Directive;
+use crate::schema::DirectiveDefinition;
+use crate::schema::DirectiveList;
+use crate::schema::DirectiveLocation;
+use crate::schema::EnumType;
+use crate::schema::EnumValueDefinition;
+use crate::schema::ExtendedType;
+use crate::schema::FieldDefinition;
+use crate::schema::InputObjectType;
+use crate::schema::InputValueDefinition;
+use crate::schema::InterfaceType;
+use crate::schema::ObjectType;
+use crate::schema::ScalarType;
+use crate::schema::Type;
+use crate::schema::UnionType;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+
+/// Error type of [`Schema::from_introspection_json`]:
+/// the JSON value does not have the expected shape of an introspection response.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid introspection response at `{path}`: {message}")]
+pub struct IntrospectionJsonError {
+    /// Where in the JSON value the error was found, like `__schema.types[4].fields[0].type`
+    pub path: String,
+    pub message: String,
+}
+
+type Result<T, E = IntrospectionJsonError> = std::result::Result<T, E>;
+
+/// A JSON object together with its path from the root, for error messages
+#[derive(Clone, Copy)]
+struct Object<'a> {
+    map: &'a JsonMap,
+    path: &'a str,
+}
+
+impl<'a> Object<'a> {
+    fn new(value: &'a JsonValue, path: &'a str) -> Result<Self> {
+        match value.as_object() {
+            Some(map) => Ok(Self { map, path }),
+            None => Err(error(path, "expected an object")),
+        }
+    }
+
+    fn child_path(&self, key: &str) -> String {
+        if self.path.is_empty() {
+            key.to_owned()
+        } else {
+            format!("{}.{key}", self.path)
+        }
+    }
+
+    /// Returns `None` for a missing key or `null`
+    fn opt(&self, key: &str) -> Option<&'a JsonValue> {
+        self.map.get(key).filter(|value| !value.is_null())
+    }
+
+    fn opt_str(&self, key: &str) -> Result<Option<&'a str>> {
+        match self.opt(key) {
+            None => Ok(None),
+            Some(value) => match value.as_str() {
+                Some(str) => Ok(Some(str)),
+                None => Err(error(&self.child_path(key), "expected a string")),
+            },
+        }
+    }
+
+    fn str(&self, key: &str) -> Result<&'a str> {
+        self.opt_str(key)?
+            .ok_or_else(|| error(&self.child_path(key), "expected a string"))
+    }
+
+    fn name(&self, key: &str) -> Result<Name> {
+        let str = self.str(key)?;
+        Name::new(str).map_err(|_| error(&self.child_path(key), "expected a valid GraphQL name"))
+    }
+
+    fn opt_name(&self, key: &str) -> Result<Option<Name>> {
+        match self.opt(key) {
+            None => Ok(None),
+            Some(value) => Object::new(value, &self.child_path(key))?
+                .name("name")
+                .map(Some),
+        }
+    }
+
+    fn bool(&self, key: &str) -> Result<bool> {
+        match self.opt(key) {
+            None => Ok(false),
+            Some(value) => value
+                .as_bool()
+                .ok_or_else(|| error(&self.child_path(key), "expected a boolean")),
+        }
+    }
+
+    fn description(&self) -> Result<Option<Node<str>>> {
+        Ok(self.opt_str("description")?.map(Node::from))
+    }
+
+    /// Calls `f` for each item of an array. A missing key or `null` is treated as empty.
+    fn each(&self, key: &str, mut f: impl FnMut(&JsonValue, &str) -> Result<()>) -> Result<()> {
+        let Some(value) = self.opt(key) else {
+            return Ok(());
+        };
+        let path = self.child_path(key);
+        let Some(array) = value.as_array() else {
+            return Err(error(&path, "expected an array"));
+        };
+        for (index, item) in array.iter().enumerate() {
+            f(item, &format!("{path}[{index}]"))?
+        }
+        Ok(())
+    }
+
+    fn each_object(&self, key: &str, mut f: impl FnMut(Object<'_>) -> Result<()>) -> Result<()> {
+        self.each(key, |item, path| f(Object::new(item, path)?))
+    }
+}
+
+fn error(path: &str, message: impl Into<String>) -> IntrospectionJsonError {
+    IntrospectionJsonError {
+        path: path.to_owned(),
+        message: message.into(),
+    }
+}
+
+pub(crate) fn schema_from_introspection_json(json: &JsonValue) -> Result<Schema> {
+    // Accept a full GraphQL response, its `data`, or the `__schema` object itself
+    let mut path = String::new();
+    let mut value = json;
+    for key in ["data", "__schema"] {
+        if let Some(inner) = value.as_object().and_then(|map| map.get(key)) {
+            path = if path.is_empty() {
+                key.to_owned()
+            } else {
+                format!("{path}.{key}")
+            };
+            value = inner;
+        }
+    }
+    let json_schema = Object::new(value, &path)?;
+
+    let mut schema = Schema::new();
+    let schema_def = schema.schema_definition.make_mut();
+    schema_def.description = json_schema.description()?;
+    schema_def.query = json_schema.opt_name("queryType")?.map(ComponentName::from);
+    schema_def.mutation = json_schema
+        .opt_name("mutationType")?
+        .map(ComponentName::from);
+    schema_def.subscription = json_schema
+        .opt_name("subscriptionType")?
+        .map(ComponentName::from);
+
+    json_schema.each_object("types", |json_type| {
+        let name = json_type.name("name")?;
+        if name.starts_with("__") {
+            // Schema-introspection types are always included in a `Schema`
+            return Ok(());
+        }
+        if schema.types.get(&name).is_some_and(|def| def.is_built_in()) {
+            // Built-in scalars are always included in a `Schema`
+            return Ok(());
+        }
+        let def = type_definition(json_type, name.clone())?;
+        schema.types.insert(name, def);
+        Ok(())
+    })?;
+
+    json_schema.each_object("directives", |json_directive| {
+        let name = json_directive.name("name")?;
+        if schema.directive_definitions.contains_key(&name) {
+            // Built-in directives are always included in a `Schema`
+            return Ok(());
+        }
+        let mut locations = Vec::new();
+        json_directive.each("locations", |location, path| {
+            locations.push(directive_location(location, path)?);
+            Ok(())
+        })?;
+        let def = DirectiveDefinition {
+            description: json_directive.description()?,
+            name: name.clone(),
+            arguments: input_values(json_directive, "args")?
+                .into_iter()
+                .map(Node::new)
+                .collect(),
+            repeatable: json_directive.bool("isRepeatable")?,
+            locations,
+        };
+        schema.directive_definitions.insert(name, Node::new(def));
+        Ok(())
+    })?;
+    Ok(schema)
+}
+
+fn type_definition(json_type: Object<'_>, name: Name) -> Result<ExtendedType> {
+    let description = json_type.description()?;
+    let kind = json_type.str("kind")?;
+    Ok(match kind {
+        "SCALAR" => {
+            let mut directives = DirectiveList::new();
+            if let Some(url) = json_type.opt_str("specifiedByURL")? {
+                directives.push(Directive {
+                    name: name!("specifiedBy"),
+                    arguments: vec![(name!("url"), url).into()],
+                });
+            }
+            ScalarType {
+                description,
+                name,
+                directives,
+            }
+            .into()
+        }
+        "OBJECT" => ObjectType {
+            description,
+            name,
+            implements_interfaces: interfaces(json_type)?,
+            directives: Default::default(),
+            fields: fields(json_type)?,
+        }
+        .into(),
+        "INTERFACE" => InterfaceType {
+            description,
+            name,
+            implements_interfaces: interfaces(json_type)?,
+            directives: Default::default(),
+            fields: fields(json_type)?,
+        }
+        .into(),
+        "UNION" => {
+            let mut members = IndexSet::default();
+            json_type.each_object("possibleTypes", |member| {
+                members.insert(member.name("name")?.into());
+                Ok(())
+            })?;
+            UnionType {
+                description,
+                name,
+                directives: Default::default(),
+                members,
+            }
+            .into()
+        }
+        "ENUM" => {
+            let mut values = IndexMap::default();
+            json_type.each_object("enumValues", |json_value| {
+                let value = json_value.name("name")?;
+                let def = EnumValueDefinition {
+                    description: json_value.description()?,
+                    value: value.clone(),
+                    directives: deprecated(json_value)?,
+                };
+                values.insert(value, Component::new(def));
+                Ok(())
+            })?;
+            EnumType {
+                description,
+                name,
+                directives: Default::default(),
+                values,
+            }
+            .into()
+        }
+        "INPUT_OBJECT" => {
+            let mut directives = DirectiveList::new();
+            if json_type.bool("isOneOf")? {
+                directives.push(Directive::new(name!("oneOf")));
+            }
+            InputObjectType {
+                description,
+                name,
+                directives,
+                fields: input_values(json_type, "inputFields")?
+                    .into_iter()
+                    .map(|def| (def.name.clone(), Component::new(def)))
+                    .collect(),
+            }
+            .into()
+        }
+        _ => {
+            return Err(error(
+                &json_type.child_path("kind"),
+                format!("unexpected kind {kind} for a named type"),
+            ))
+        }
+    })
+}
+
+fn interfaces(json_type: Object<'_>) -> Result<IndexSet<ComponentName>> {
+    let mut interfaces = IndexSet::default();
+    json_type.each_object("interfaces", |interface| {
+        interfaces.insert(interface.name("name")?.into());
+        Ok(())
+    })?;
+    Ok(interfaces)
+}
+
+fn fields(json_type: Object<'_>) -> Result<IndexMap<Name, Component<FieldDefinition>>> {
+    let mut fields = IndexMap::default();
+    json_type.each_object("fields", |json_field| {
+        let name = json_field.name("name")?;
+        let def = FieldDefinition {
+            description: json_field.description()?,
+            name: name.clone(),
+            arguments: input_values(json_field, "args")?
+                .into_iter()
+                .map(Node::new)
+                .collect(),
+            ty: type_ref(json_field)?,
+            directives: deprecated(json_field)?,
+        };
+        fields.insert(name, Component::new(def));
+        Ok(())
+    })?;
+    Ok(fields)
+}
+
+fn input_values(parent: Object<'_>, key: &str) -> Result<Vec<InputValueDefinition>> {
+    let mut defs = Vec::new();
+    parent.each_object(key, |json_input_value| {
+        let default_value = match json_input_value.opt_str("defaultValue")? {
+            Some(source) => Some(parse_value(
+                source,
+                &json_input_value.child_path("defaultValue"),
+            )?),
+            None => None,
+        };
+        defs.push(InputValueDefinition {
+            description: json_input_value.description()?,
+            name: json_input_value.name("name")?,
+            ty: Node::new(type_ref(json_input_value)?),
+            default_value,
+            directives: deprecated(json_input_value)?,
+        });
+        Ok(())
+    })?;
+    Ok(defs)
+}
+
+/// Converts `isDeprecated` and `deprecationReason` to a `@deprecated` directive
+fn deprecated(json_object: Object<'_>) -> Result<ast::DirectiveList> {
+    let mut directives = ast::DirectiveList::new();
+    if json_object.bool("isDeprecated")? {
+        // Explicit `null` if there is no reason, which differs from the argument's default value
+        let reason: Node<Value> = match json_object.opt_str("deprecationReason")? {
+            Some(reason) => reason.into(),
+            None => Value::Null.into(),
+        };
+        directives.push(Directive {
+            name: name!("deprecated"),
+            arguments: vec![(name!("reason"), reason).into()],
+        })
+    }
+    Ok(directives)
+}
+
+/// Converts a `__Type` reference with nested `ofType` to a [`Type`]
+fn type_ref(parent: Object<'_>) -> Result<Type> {
+    let path = parent.child_path("type");
+    let json_type = Object::new(parent.opt("type").unwrap_or(&JsonValue::Null), &path)?;
+    type_ref_inner(json_type)
+}
+
+fn type_ref_inner(json_type: Object<'_>) -> Result<Type> {
+    let kind = json_type.str("kind")?;
+    let of_type = || {
+        let path = json_type.child_path("ofType");
+        let json_of_type = Object::new(json_type.opt("ofType").unwrap_or(&JsonValue::Null), &path)?;
+        type_ref_inner(json_of_type)
+    };
+    match kind {
+        "NON_NULL" => match of_type()? {
+            Type::Named(name) => Ok(Type::NonNullNamed(name)),
+            Type::List(inner) => Ok(Type::NonNullList(inner)),
+            Type::NonNullNamed(_) | Type::NonNullList(_) => Err(error(
+                &json_type.child_path("ofType"),
+                "non-null of a non-null type",
+            )),
+        },
+        "LIST" => Ok(Type::List(Box::new(of_type()?))),
+        _ => Ok(Type::Named(json_type.name("name")?)),
+    }
+}
+
+fn directive_location(json: &JsonValue, path: &str) -> Result<DirectiveLocation> {
+    let location = json
+        .as_str()
+        .ok_or_else(|| error(path, "expected a string"))?;
+    let all = [
+        DirectiveLocation::Query,
+        DirectiveLocation::Mutation,
+        DirectiveLocation::Subscription,
+        DirectiveLocation::Field,
+        DirectiveLocation::FragmentDefinition,
+        DirectiveLocation::FragmentSpread,
+        DirectiveLocation::InlineFragment,
+        DirectiveLocation::VariableDefinition,
+        DirectiveLocation::Schema,
+        DirectiveLocation::Scalar,
+        DirectiveLocation::Object,
+        DirectiveLocation::FieldDefinition,
+        DirectiveLocation::ArgumentDefinition,
+        DirectiveLocation::Interface,
+        DirectiveLocation::Union,
+        DirectiveLocation::Enum,
+        DirectiveLocation::EnumValue,
+        DirectiveLocation::InputObject,
+        DirectiveLocation::InputFieldDefinition,
+    ];
+    all.into_iter()
+        .find(|candidate| candidate.name() == location)
+        .ok_or_else(|| error(path, format!("unknown directive location {location}")))
+}
+
+/// Parses the GraphQL syntax of a default value, as found in `__InputValue.defaultValue`.
+///
+/// There is no parser entry point for a standalone value,
+/// so this parses it as the default value of an input field in a synthetic document.
+fn parse_value(source: &str, path: &str) -> Result<Node<Value>> {
+    let document = Parser::new()
+        .parse_ast(
+            format!("input X {{ x: X = {source} }}"),
+            "defaultValue.graphql",
+        )
+        .map_err(|_| error(path, "invalid GraphQL syntax for a value"))?;
+    let value = match document.definitions.as_slice() {
+        [ast::Definition::InputObjectTypeDefinition(def)] => match def.fields.as_slice() {
+            [field] => field.default_value.as_ref(),
+            _ => None,
+        },
+        _ => None,
+    };
+    let value = value.ok_or_else(|| error(path, "invalid GraphQL syntax for a value"))?;
+    Ok(without_location(value))
+}
+
+/// Rebuild nodes without source location, since the synthetic document is not kept
+fn without_location(value: &Node<Value>) -> Node<Value> {
+    Node::new(match value.as_ref() {
+        Value::List(items) => Value::List(items.iter().map(without_location).collect()),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(name, value)| (name.clone(), without_location(value)))
+                .collect(),
+        ),
+        other => other.clone(),
+    })
+}
@@ -65,11 +65,14 @@ use crate::ast;
 use crate::collections::HashMap;
 use crate::collections::IndexMap;
 use crate::collections::IndexSet;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::SchemaCoordinateLookup;
 use crate::name;
 use crate::parser::FileId;
 use crate::parser::Parser;
 use crate::parser::SourceSpan;
 use crate::ty;
+use crate::validation::lint::LintConfig;
 use crate::validation::DiagnosticList;
 use crate::validation::Valid;
 use crate::validation::WithErrors;
@@ -78,16 +81,31 @@ use crate::Node;
 use std::path::Path;
 use std::sync::OnceLock;
 
+mod affected_operations;
 mod component;
+mod diff;
 mod from_ast;
+mod from_introspection;
+mod prune;
 mod serialize;
 pub(crate) mod validation;
+pub(crate) mod visit;
 
+pub use self::affected_operations::AffectedOperation;
+pub use self::affected_operations::OperationImpact;
 pub use self::component::Component;
 pub use self::component::ComponentName;
 pub use self::component::ComponentOrigin;
 pub use self::component::ExtensionId;
+pub use self::diff::diff;
+pub use self::diff::ChangeKind;
+pub use self::diff::ChangeSeverity;
+pub use self::diff::SchemaChange;
+pub use self::diff::SchemaDiff;
 pub use self::from_ast::SchemaBuilder;
+pub use self::from_introspection::IntrospectionJsonError;
+pub use self::visit::Visitor;
+pub use self::visit::VisitorMut;
 pub use crate::ast::Directive;
 pub use crate::ast::DirectiveDefinition;
 pub use crate::ast::DirectiveLocation;
@@ -440,6 +458,46 @@ impl Schema {
         SchemaBuilder::new()
     }
 
+    /// Reconstruct a schema from the JSON result of an introspection query,
+    /// such as the response to the standard full introspection query.
+    ///
+    /// `json` can be a full GraphQL response, its `data` object, or the `__schema` object.
+    /// Types, fields, arguments, default values, deprecations, `specifiedByURL`,
+    /// and directive definitions are reconstructed from whatever the query selected.
+    ///
+    /// Introspection does not expose directive applications other than the above,
+    /// nor extensions or source locations.
+    /// The returned schema is not validated: consider calling [`validate`][Self::validate].
+    ///
+    /// ```
+    /// use apollo_compiler::response::JsonValue;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let json: JsonValue = serde_json::from_str(r#"{
+    ///     "__schema": {
+    ///         "queryType": { "name": "Query" },
+    ///         "types": [{
+    ///             "kind": "OBJECT",
+    ///             "name": "Query",
+    ///             "fields": [{
+    ///                 "name": "hello",
+    ///                 "args": [],
+    ///                 "type": { "kind": "SCALAR", "name": "String" }
+    ///             }],
+    ///             "interfaces": []
+    ///         }],
+    ///         "directives": []
+    ///     }
+    /// }"#).unwrap();
+    /// let schema = Schema::from_introspection_json(&json).unwrap();
+    /// assert_eq!(schema.to_string(), "type Query {\n  hello: String\n}\n");
+    /// ```
+    pub fn from_introspection_json(
+        json: &crate::response::JsonValue,
+    ) -> Result<Self, IntrospectionJsonError> {
+        from_introspection::schema_from_introspection_json(json)
+    }
+
     #[allow(clippy::result_large_err)] // Typically not called very often
     pub fn validate(mut self) -> Result<Valid<Self>, WithErrors<Self>> {
         let mut errors = DiagnosticList::new(self.sources.clone());
@@ -447,6 +505,137 @@ impl Schema {
         errors.into_valid_result(self)
     }
 
+    /// Check the schema for [lints][crate::validation::lint]:
+    /// problems that do not make it invalid, such as unused types.
+    ///
+    /// Returns warnings and, for rules configured as [`Deny`][crate::validation::lint::LintLevel::Deny],
+    /// errors. Use [`DiagnosticList::has_errors`] to tell them apart.
+    pub fn lint(&self, config: &LintConfig) -> DiagnosticList {
+        let mut diagnostics = DiagnosticList::new(self.sources.clone());
+        crate::validation::lint::lint_schema(&mut diagnostics, self, config);
+        diagnostics
+    }
+
+    /// Returns the innermost schema element defined or referenced at a byte offset of a source file,
+    /// such as a field definition, an argument definition, a type named in a field type,
+    /// or an applied directive.
+    ///
+    /// An offset at the end of a name is considered part of that name.
+    pub fn element_at(&self, file_id: FileId, offset: usize) -> Option<SchemaCoordinate> {
+        crate::lookup::schema_element_at(self, file_id, offset)
+    }
+
+    /// Returns the locations of names referencing a schema element in this schema,
+    /// not including the definition of the element itself.
+    ///
+    /// For example, references to the type coordinate `User` include field types,
+    /// union members, and implemented interfaces named `User`.
+    /// Use [`ExecutableDocument::references`][crate::ExecutableDocument::references]
+    /// to find references in executable documents.
+    pub fn references(&self, coordinate: &SchemaCoordinate) -> Vec<SourceSpan> {
+        crate::lookup::schema_references(self, coordinate)
+    }
+
+    /// Returns a copy of this schema without the types and directive definitions
+    /// that are not reachable from root operation types or from `keep`.
+    ///
+    /// A type is reachable if it is used by a reachable field, argument, or input field,
+    /// if it is a member of a reachable union type,
+    /// or if it is an object type implementing a reachable interface.
+    /// Directive definitions are reachable if they are applied to reachable elements
+    /// or if they can be used in executable documents.
+    /// Built-in types and directives are always kept.
+    /// Coordinates in `keep` mark the type or directive they point to as reachable.
+    ///
+    /// Object and interface types stop implementing interfaces that are removed.
+    ///
+    /// The result is validated.
+    #[allow(clippy::result_large_err)] // Typically not called very often
+    pub fn prune(&self, keep: &[SchemaCoordinate]) -> Result<Valid<Self>, WithErrors<Self>> {
+        prune::prune(self, keep).validate()
+    }
+
+    /// Returns a copy of this schema without the elements for which `hide` returns true.
+    ///
+    /// `hide` is called with the coordinate of each type, field, argument, input field,
+    /// enum value, and directive definition that is not built-in,
+    /// except those nested in an element that is hidden.
+    ///
+    /// To keep the schema valid, references to removed elements are fixed up:
+    ///
+    /// * Fields, arguments, and input fields of a removed type are removed,
+    ///   as well as arguments and input fields with a default value that refers
+    ///   to a removed enum value or input field
+    /// * Union types stop including removed members
+    /// * Object and interface types stop implementing interfaces
+    ///   whose fields or arguments they do not have anymore,
+    ///   or whose field types are not valid for their own fields anymore
+    /// * Applications of removed directives are removed,
+    ///   as well as those with an argument value that is not valid anymore
+    /// * Types without fields, members, or values are removed
+    /// * Root operations whose type is removed are removed
+    ///
+    /// Types that are not reachable anymore are kept.
+    /// Call [`prune`][Self::prune] on the result to remove them.
+    ///
+    /// The result is validated.
+    ///
+    /// ```
+    /// use apollo_compiler::coordinate::SchemaCoordinateLookup;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let input = r#"
+    ///   directive @internal on OBJECT | FIELD_DEFINITION
+    ///   type Query {
+    ///     me: User
+    ///     audit: AuditLog
+    ///   }
+    ///   type User {
+    ///     name: String
+    ///     passwordHash: String @internal
+    ///   }
+    ///   type AuditLog @internal {
+    ///     entries: [String]
+    ///   }
+    /// "#;
+    /// let schema = Schema::parse_and_validate(input, "schema.graphql").unwrap();
+    /// let public = schema
+    ///     .filter(|_, element| match element {
+    ///         SchemaCoordinateLookup::Type(ty) => ty.directives().has("internal"),
+    ///         SchemaCoordinateLookup::Field(field) => field.directives.has("internal"),
+    ///         _ => false,
+    ///     })
+    ///     .unwrap()
+    ///     .prune(&[])
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     public.to_string(),
+    ///     "type Query {\n  me: User\n}\n\ntype User {\n  name: String\n}\n"
+    /// );
+    /// ```
+    #[allow(clippy::result_large_err)] // Typically not called very often
+    pub fn filter(
+        &self,
+        hide: impl FnMut(&SchemaCoordinate, SchemaCoordinateLookup<'_>) -> bool,
+    ) -> Result<Valid<Self>, WithErrors<Self>> {
+        prune::filter(self, hide).validate()
+    }
+
+    /// Traverse type system definitions depth-first, calling [`Visitor`] methods for each node.
+    /// Built-in definitions are not visited.
+    pub fn walk<'doc>(&'doc self, visitor: &mut impl Visitor<'doc>) {
+        visit::walk_schema(self, visitor)
+    }
+
+    /// Traverse type system definitions depth-first, calling [`VisitorMut`] methods for each node.
+    /// Built-in definitions are not visited.
+    ///
+    /// The schema is typically not [`Valid`] anymore after modifications.
+    /// Consider calling [`validate`][Self::validate] afterwards.
+    pub fn walk_mut(&mut self, visitor: &mut impl VisitorMut) {
+        visit::walk_schema_mut(self, visitor)
+    }
+
     /// Returns the type with the given name, if it is a scalar type
     pub fn get_scalar(&self, name: &str) -> Option<&Node<ScalarType>> {
         if let Some(ExtendedType::Scalar(ty)) = self.types.get(name) {
@@ -613,6 +802,31 @@ impl Schema {
         })
     }
 
+    /// Whether a field of type `field_type` can be used where `implemented_type` is expected,
+    /// such as a field implementing an interface field or an output field made more specific.
+    ///
+    /// Implementation of spec function
+    /// [_IsValidImplementationFieldType()_](https://spec.graphql.org/draft/#IsValidImplementationFieldType()).
+    pub(crate) fn is_valid_implementation_field_type(
+        &self,
+        field_type: &Type,
+        implemented_type: &Type,
+    ) -> bool {
+        match (field_type, implemented_type) {
+            (Type::Named(_) | Type::List(_), Type::NonNullNamed(_) | Type::NonNullList(_)) => false,
+            (Type::NonNullList(item), Type::NonNullList(implemented_item))
+            | (Type::List(item) | Type::NonNullList(item), Type::List(implemented_item)) => {
+                self.is_valid_implementation_field_type(item, implemented_item)
+            }
+            (
+                Type::Named(name) | Type::NonNullNamed(name),
+                Type::Named(implemented) | Type::NonNullNamed(implemented),
+            ) => name == implemented || self.is_subtype(implemented, name),
+            // List and non-list
+            _ => false,
+        }
+    }
+
     /// Returns whether the type `ty` is defined as is an input type
     ///
     /// <https://spec.graphql.org/October2021/#sec-Input-and-Output-Types>
@@ -982,6 +1196,14 @@ impl InputObjectType {
             .collect()
     }
 
+    /// Returns whether this is a [OneOf input object](https://spec.graphql.org/draft/#sec-OneOf-Input-Objects),
+    /// with the `@oneOf` directive applied.
+    ///
+    /// A value for a OneOf input object must have exactly one field, whose value is not null.
+    pub fn is_one_of(&self) -> bool {
+        self.directives.has("oneOf")
+    }
+
     serialize_method!();
 }
 
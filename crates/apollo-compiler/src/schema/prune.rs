@@ -0,0 +1,527 @@
+use crate::ast::Directive;
+use crate::ast::DirectiveLocation;
+use crate::ast::Type;
+use crate::ast::Value;
+use crate::collections::HashMap;
+use crate::collections::IndexMap;
+use crate::collections::IndexSet;
+use crate::coordinate::DirectiveCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::SchemaCoordinateLookup;
+use crate::coordinate::TypeCoordinate;
+use crate::schema::Component;
+use crate::schema::ComponentName;
+use crate::schema::DirectiveDefinition;
+use crate::schema::DirectiveList;
+use crate::schema::ExtendedType;
+use crate::schema::FieldDefinition;
+use crate::schema::Implementers;
+use crate::schema::InputValueDefinition;
+use crate::schema::SchemaDefinition;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+
+struct Reachable<'a> {
+    schema: &'a Schema,
+    implementers: HashMap<Name, Implementers>,
+    types: IndexSet<Name>,
+    directives: IndexSet<Name>,
+}
+
+/// Removes types and directive definitions not reachable from root operations or `keep`,
+/// and implementations of removed interfaces
+pub(crate) fn prune(schema: &Schema, keep: &[SchemaCoordinate]) -> Schema {
+    let mut reachable = Reachable {
+        schema,
+        implementers: schema.implementers_map(),
+        types: IndexSet::default(),
+        directives: IndexSet::default(),
+    };
+    for (_, root) in schema.schema_definition.iter_root_operations() {
+        reachable.add_type(root)
+    }
+    for directive in schema.schema_definition.directives.iter() {
+        reachable.add_directive(&directive.name)
+    }
+    for (name, ty) in &schema.types {
+        if ty.is_built_in() {
+            reachable.add_type(name)
+        }
+    }
+    for (name, definition) in &schema.directive_definitions {
+        // Directives that can be used in executable documents are part of the API
+        if definition.is_built_in() || definition.locations.iter().any(is_executable) {
+            reachable.add_directive(name)
+        }
+    }
+    for coordinate in keep {
+        match coordinate {
+            SchemaCoordinate::Type(coordinate) => reachable.add_type(&coordinate.ty),
+            SchemaCoordinate::TypeAttribute(coordinate) => reachable.add_type(&coordinate.ty),
+            SchemaCoordinate::FieldArgument(coordinate) => reachable.add_type(&coordinate.ty),
+            SchemaCoordinate::Directive(coordinate) => {
+                reachable.add_directive(&coordinate.directive)
+            }
+            SchemaCoordinate::DirectiveArgument(coordinate) => {
+                reachable.add_directive(&coordinate.directive)
+            }
+        }
+    }
+    reachable.traverse();
+
+    let mut result = schema.clone();
+    result
+        .types
+        .retain(|name, _| reachable.types.contains(name));
+    result
+        .directive_definitions
+        .retain(|name, _| reachable.directives.contains(name));
+    for ty in result.types.values_mut() {
+        let implements_interfaces = match ty {
+            ExtendedType::Object(def) => &def.implements_interfaces,
+            ExtendedType::Interface(def) => &def.implements_interfaces,
+            _ => continue,
+        };
+        if implements_interfaces
+            .iter()
+            .all(|interface| reachable.types.contains(&interface.name))
+        {
+            continue;
+        }
+        let implements_interfaces = match ty {
+            ExtendedType::Object(def) => &mut def.make_mut().implements_interfaces,
+            ExtendedType::Interface(def) => &mut def.make_mut().implements_interfaces,
+            _ => continue,
+        };
+        implements_interfaces.retain(|interface| reachable.types.contains(&interface.name))
+    }
+    result
+}
+
+fn is_executable(location: &DirectiveLocation) -> bool {
+    matches!(
+        location,
+        DirectiveLocation::Query
+            | DirectiveLocation::Mutation
+            | DirectiveLocation::Subscription
+            | DirectiveLocation::Field
+            | DirectiveLocation::FragmentDefinition
+            | DirectiveLocation::FragmentSpread
+            | DirectiveLocation::InlineFragment
+            | DirectiveLocation::VariableDefinition
+    )
+}
+
+impl Reachable<'_> {
+    fn add_type(&mut self, name: &Name) {
+        if self.schema.types.contains_key(name) {
+            self.types.insert(name.clone());
+        }
+    }
+
+    fn add_directive(&mut self, name: &Name) {
+        if self.schema.directive_definitions.contains_key(name) {
+            self.directives.insert(name.clone());
+        }
+    }
+
+    /// Adds everything referenced by reachable types and directives, until nothing new is found
+    fn traverse(&mut self) {
+        let schema = self.schema;
+        let mut type_index = 0;
+        let mut directive_index = 0;
+        loop {
+            if let Some(name) = self.types.get_index(type_index).cloned() {
+                type_index += 1;
+                self.type_references(&schema.types[&name]);
+            } else if let Some(name) = self.directives.get_index(directive_index).cloned() {
+                directive_index += 1;
+                for argument in &schema.directive_definitions[&name].arguments {
+                    self.input_value_references(argument)
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn type_references(&mut self, ty: &ExtendedType) {
+        for directive in ty.directives().iter() {
+            self.add_directive(&directive.name)
+        }
+        match ty {
+            ExtendedType::Scalar(_) => {}
+            ExtendedType::Object(def) => self.field_references(&def.fields),
+            ExtendedType::Interface(def) => {
+                self.field_references(&def.fields);
+                // Objects implementing a reachable interface can be returned by its fields
+                if let Some(implementers) = self.implementers.get(&def.name) {
+                    for object in implementers.objects.clone() {
+                        self.add_type(&object)
+                    }
+                }
+            }
+            ExtendedType::Union(def) => {
+                for member in &def.members {
+                    self.add_type(member)
+                }
+            }
+            ExtendedType::Enum(def) => {
+                for value in def.values.values() {
+                    for directive in value.directives.iter() {
+                        self.add_directive(&directive.name)
+                    }
+                }
+            }
+            ExtendedType::InputObject(def) => {
+                for field in def.fields.values() {
+                    self.input_value_references(field)
+                }
+            }
+        }
+    }
+
+    fn field_references(&mut self, fields: &IndexMap<Name, Component<FieldDefinition>>) {
+        for field in fields.values() {
+            self.add_type(field.ty.inner_named_type());
+            for directive in field.directives.iter() {
+                self.add_directive(&directive.name)
+            }
+            for argument in &field.arguments {
+                self.input_value_references(argument)
+            }
+        }
+    }
+
+    fn input_value_references(&mut self, definition: &InputValueDefinition) {
+        self.add_type(definition.ty.inner_named_type());
+        for directive in definition.directives.iter() {
+            self.add_directive(&directive.name)
+        }
+    }
+}
+
+/// Removes elements for which `hide` returns true,
+/// then removes or updates elements that refer to removed elements
+pub(crate) fn filter(
+    schema: &Schema,
+    mut hide: impl FnMut(&SchemaCoordinate, SchemaCoordinateLookup<'_>) -> bool,
+) -> Schema {
+    let mut result = schema.clone();
+    result.directive_definitions.retain(|name, definition| {
+        definition.is_built_in()
+            || !hide(
+                &DirectiveCoordinate {
+                    directive: name.clone(),
+                }
+                .into(),
+                SchemaCoordinateLookup::Directive(definition),
+            )
+    });
+    for (name, definition) in &mut result.directive_definitions {
+        if definition.is_built_in() {
+            continue;
+        }
+        let coordinate = DirectiveCoordinate {
+            directive: name.clone(),
+        };
+        definition.make_mut().arguments.retain(|argument| {
+            !hide(
+                &coordinate.with_argument(argument.name.clone()).into(),
+                SchemaCoordinateLookup::Argument(argument),
+            )
+        })
+    }
+    result.types.retain(|name, ty| {
+        ty.is_built_in()
+            || !hide(
+                &TypeCoordinate { ty: name.clone() }.into(),
+                SchemaCoordinateLookup::Type(ty),
+            )
+    });
+    for (name, ty) in &mut result.types {
+        if ty.is_built_in() {
+            continue;
+        }
+        let coordinate = TypeCoordinate { ty: name.clone() };
+        match ty {
+            ExtendedType::Scalar(_) | ExtendedType::Union(_) => {}
+            ExtendedType::Object(def) => {
+                hide_fields(&coordinate, &mut def.make_mut().fields, &mut hide)
+            }
+            ExtendedType::Interface(def) => {
+                hide_fields(&coordinate, &mut def.make_mut().fields, &mut hide)
+            }
+            ExtendedType::Enum(def) => def.make_mut().values.retain(|value_name, value| {
+                !hide(
+                    &coordinate.with_attribute(value_name.clone()).into(),
+                    SchemaCoordinateLookup::EnumValue(value),
+                )
+            }),
+            ExtendedType::InputObject(def) => def.make_mut().fields.retain(|field_name, field| {
+                !hide(
+                    &coordinate.with_attribute(field_name.clone()).into(),
+                    SchemaCoordinateLookup::InputField(field),
+                )
+            }),
+        }
+    }
+
+    // Removing an element can make others invalid, which are then removed too
+    loop {
+        let snapshot = result.clone();
+        let mut fixer = Fixer {
+            schema: &snapshot,
+            changed: false,
+        };
+        fixer.schema_definition(result.schema_definition.make_mut());
+        for definition in result.directive_definitions.values_mut() {
+            fixer.directive_definition(definition)
+        }
+        result.types.retain(|_, ty| fixer.ty(ty));
+        if !fixer.changed {
+            break;
+        }
+    }
+    result
+}
+
+fn hide_fields(
+    coordinate: &TypeCoordinate,
+    fields: &mut IndexMap<Name, Component<FieldDefinition>>,
+    hide: &mut impl FnMut(&SchemaCoordinate, SchemaCoordinateLookup<'_>) -> bool,
+) {
+    fields.retain(|name, field| {
+        !hide(
+            &coordinate.with_attribute(name.clone()).into(),
+            SchemaCoordinateLookup::Field(field),
+        )
+    });
+    for (name, field) in fields {
+        let field_coordinate = coordinate.with_attribute(name.clone());
+        field.make_mut().arguments.retain(|argument| {
+            !hide(
+                &field_coordinate.with_argument(argument.name.clone()).into(),
+                SchemaCoordinateLookup::Argument(argument),
+            )
+        })
+    }
+}
+
+/// Removes references to elements that do not exist in `schema`.
+///
+/// Each pass reads the snapshot from before the pass,
+/// so that all decisions in a pass are consistent with each other.
+struct Fixer<'a> {
+    schema: &'a Schema,
+    changed: bool,
+}
+
+impl Fixer<'_> {
+    /// Returns `keep`, recording a change if false
+    fn keep(&mut self, keep: bool) -> bool {
+        if !keep {
+            self.changed = true
+        }
+        keep
+    }
+
+    fn schema_definition(&mut self, definition: &mut SchemaDefinition) {
+        for root in [
+            &mut definition.query,
+            &mut definition.mutation,
+            &mut definition.subscription,
+        ] {
+            if root
+                .as_ref()
+                .is_some_and(|name| self.schema.get_object(name).is_none())
+            {
+                *root = None;
+                self.changed = true
+            }
+        }
+        self.component_directives(&mut definition.directives)
+    }
+
+    fn directive_definition(&mut self, definition: &mut Node<DirectiveDefinition>) {
+        if definition.is_built_in() {
+            return;
+        }
+        definition
+            .make_mut()
+            .arguments
+            .retain_mut(|argument| self.input_value(argument))
+    }
+
+    /// Returns whether to keep the type
+    fn ty(&mut self, ty: &mut ExtendedType) -> bool {
+        if ty.is_built_in() {
+            return true;
+        }
+        let keep = match ty {
+            ExtendedType::Scalar(def) => {
+                self.component_directives(&mut def.make_mut().directives);
+                true
+            }
+            ExtendedType::Object(def) => {
+                let def = def.make_mut();
+                self.component_directives(&mut def.directives);
+                def.fields.retain(|_, field| self.field(field));
+                let name = &def.name;
+                def.implements_interfaces
+                    .retain(|interface| self.implementation(name, interface));
+                !def.fields.is_empty()
+            }
+            ExtendedType::Interface(def) => {
+                let def = def.make_mut();
+                self.component_directives(&mut def.directives);
+                def.fields.retain(|_, field| self.field(field));
+                let name = &def.name;
+                def.implements_interfaces
+                    .retain(|interface| self.implementation(name, interface));
+                !def.fields.is_empty()
+            }
+            ExtendedType::Union(def) => {
+                let def = def.make_mut();
+                self.component_directives(&mut def.directives);
+                def.members.retain(|member| {
+                    let keep = self.schema.get_object(member).is_some();
+                    self.keep(keep)
+                });
+                !def.members.is_empty()
+            }
+            ExtendedType::Enum(def) => {
+                let def = def.make_mut();
+                self.component_directives(&mut def.directives);
+                for value in def.values.values_mut() {
+                    self.directives(&mut value.make_mut().directives)
+                }
+                !def.values.is_empty()
+            }
+            ExtendedType::InputObject(def) => {
+                let def = def.make_mut();
+                self.component_directives(&mut def.directives);
+                def.fields.retain(|_, field| self.input_value(field));
+                !def.fields.is_empty()
+            }
+        };
+        self.keep(keep)
+    }
+
+    fn field(&mut self, field: &mut Component<FieldDefinition>) -> bool {
+        if !self.schema.types.contains_key(field.ty.inner_named_type()) {
+            return self.keep(false);
+        }
+        let field = field.make_mut();
+        field
+            .arguments
+            .retain_mut(|argument| self.input_value(argument));
+        self.directives(&mut field.directives);
+        true
+    }
+
+    /// Returns whether to keep an argument or input field
+    fn input_value(&mut self, definition: &mut Node<InputValueDefinition>) -> bool {
+        if !self
+            .schema
+            .types
+            .contains_key(definition.ty.inner_named_type())
+        {
+            return self.keep(false);
+        }
+        if let Some(default_value) = &definition.default_value {
+            if !self.value_is_valid(&definition.ty, default_value) {
+                return self.keep(false);
+            }
+        }
+        self.directives(&mut definition.make_mut().directives);
+        true
+    }
+
+    /// Returns whether `implementer` can still implement `interface`
+    fn implementation(&mut self, implementer: &Name, interface: &ComponentName) -> bool {
+        let Some(interface) = self.schema.get_interface(interface) else {
+            return self.keep(false);
+        };
+        let (fields, implements_interfaces) = match self.schema.types.get(implementer) {
+            Some(ExtendedType::Object(def)) => (&def.fields, &def.implements_interfaces),
+            Some(ExtendedType::Interface(def)) => (&def.fields, &def.implements_interfaces),
+            _ => return self.keep(false),
+        };
+        let fields_implemented = interface.fields.values().all(|interface_field| {
+            fields.get(&interface_field.name).is_some_and(|field| {
+                self.schema
+                    .is_valid_implementation_field_type(&field.ty, &interface_field.ty)
+                    && interface_field
+                        .arguments
+                        .iter()
+                        .all(|argument| field.argument_by_name(&argument.name).is_some())
+            })
+        });
+        let transitive_implemented = interface
+            .implements_interfaces
+            .iter()
+            .all(|transitive| implements_interfaces.contains(transitive));
+        self.keep(fields_implemented && transitive_implemented)
+    }
+
+    fn component_directives(&mut self, directives: &mut DirectiveList) {
+        directives
+            .0
+            .retain_mut(|directive| self.directive(&mut directive.node))
+    }
+
+    fn directives(&mut self, directives: &mut crate::ast::DirectiveList) {
+        directives.retain_mut(|directive| self.directive(directive))
+    }
+
+    /// Returns whether to keep a directive application.
+    /// Removes arguments that are not defined anymore.
+    fn directive(&mut self, directive: &mut Node<Directive>) -> bool {
+        let Some(definition) = self.schema.directive_definitions.get(&directive.name) else {
+            return self.keep(false);
+        };
+        if directive
+            .arguments
+            .iter()
+            .any(|argument| definition.argument_by_name(&argument.name).is_none())
+        {
+            directive
+                .make_mut()
+                .arguments
+                .retain(|argument| definition.argument_by_name(&argument.name).is_some());
+            self.changed = true
+        }
+        let valid = definition.arguments.iter().all(|argument_definition| {
+            match directive.specified_argument_by_name(&argument_definition.name) {
+                Some(value) => self.value_is_valid(&argument_definition.ty, value),
+                None => !argument_definition.is_required(),
+            }
+        });
+        self.keep(valid)
+    }
+
+    /// Returns whether `value` only refers to enum values and input fields that exist
+    fn value_is_valid(&self, ty: &Type, value: &Value) -> bool {
+        match value {
+            Value::Null | Value::Variable(_) => return true,
+            Value::List(items) => return items.iter().all(|item| self.value_is_valid(ty, item)),
+            _ => {}
+        }
+        match (self.schema.types.get(ty.inner_named_type()), value) {
+            (None, _) => false,
+            (Some(ExtendedType::Enum(def)), Value::Enum(value)) => def.values.contains_key(value),
+            (Some(ExtendedType::InputObject(def)), Value::Object(fields)) => {
+                fields.iter().all(|(name, value)| {
+                    def.fields
+                        .get(name)
+                        .is_some_and(|field| self.value_is_valid(&field.ty, value))
+                }) && def.fields.values().all(|field| {
+                    !field.is_required() || fields.iter().any(|(name, _)| *name == field.name)
+                })
+            }
+            (Some(_), _) => true,
+        }
+    }
+}
@@ -103,13 +103,13 @@ impl ExtendedType {
     fn to_ast(&self) -> impl Iterator<Item = ast::Definition> + '_ {
         match self {
             ExtendedType::Scalar(ty) => {
-                Box::new(ty.to_ast(ty.location())) as Box<dyn Iterator<Item = _>>
+                Box::new(ty.to_ast(Some(ty))) as Box<dyn Iterator<Item = _>>
             }
-            ExtendedType::Object(ty) => Box::new(ty.to_ast(ty.location())) as _,
-            ExtendedType::Interface(ty) => Box::new(ty.to_ast(ty.location())) as _,
-            ExtendedType::Union(ty) => Box::new(ty.to_ast(ty.location())) as _,
-            ExtendedType::Enum(ty) => Box::new(ty.to_ast(ty.location())) as _,
-            ExtendedType::InputObject(ty) => Box::new(ty.to_ast(ty.location())) as _,
+            ExtendedType::Object(ty) => Box::new(ty.to_ast(Some(ty))) as _,
+            ExtendedType::Interface(ty) => Box::new(ty.to_ast(Some(ty))) as _,
+            ExtendedType::Union(ty) => Box::new(ty.to_ast(Some(ty))) as _,
+            ExtendedType::Enum(ty) => Box::new(ty.to_ast(Some(ty))) as _,
+            ExtendedType::InputObject(ty) => Box::new(ty.to_ast(Some(ty))) as _,
         }
     }
 
@@ -126,13 +126,16 @@ impl ExtendedType {
 }
 
 impl ScalarType {
-    fn to_ast(&self, location: Option<SourceSpan>) -> impl Iterator<Item = ast::Definition> + '_ {
+    fn to_ast<'a>(
+        &'a self,
+        node: Option<&Node<Self>>,
+    ) -> impl Iterator<Item = ast::Definition> + 'a {
         let def = ast::ScalarTypeDefinition {
             description: self.description.clone(),
             name: self.name.clone(),
             directives: ast::DirectiveList(components(&self.directives, None)),
         };
-        std::iter::once(Node::new_opt_location(def, location).into()).chain(
+        std::iter::once(same_location_opt(node, def).into()).chain(
             self.extensions().into_iter().map(move |ext| {
                 ast::Definition::ScalarTypeExtension(ext.same_location(ast::ScalarTypeExtension {
                     name: self.name.clone(),
@@ -150,7 +153,10 @@ impl ScalarType {
 }
 
 impl ObjectType {
-    fn to_ast(&self, location: Option<SourceSpan>) -> impl Iterator<Item = ast::Definition> + '_ {
+    fn to_ast<'a>(
+        &'a self,
+        node: Option<&Node<Self>>,
+    ) -> impl Iterator<Item = ast::Definition> + 'a {
         let def = ast::ObjectTypeDefinition {
             description: self.description.clone(),
             name: self.name.clone(),
@@ -158,7 +164,7 @@ impl ObjectType {
             directives: ast::DirectiveList(components(&self.directives, None)),
             fields: components(self.fields.values(), None),
         };
-        std::iter::once(Node::new_opt_location(def, location).into()).chain(
+        std::iter::once(same_location_opt(node, def).into()).chain(
             self.extensions().into_iter().map(move |ext| {
                 ast::Definition::ObjectTypeExtension(ext.same_location(ast::ObjectTypeExtension {
                     name: self.name.clone(),
@@ -178,7 +184,10 @@ impl ObjectType {
 }
 
 impl InterfaceType {
-    fn to_ast(&self, location: Option<SourceSpan>) -> impl Iterator<Item = ast::Definition> + '_ {
+    fn to_ast<'a>(
+        &'a self,
+        node: Option<&Node<Self>>,
+    ) -> impl Iterator<Item = ast::Definition> + 'a {
         let def = ast::InterfaceTypeDefinition {
             description: self.description.clone(),
             name: self.name.clone(),
@@ -186,7 +195,7 @@ impl InterfaceType {
             directives: ast::DirectiveList(components(&self.directives, None)),
             fields: components(self.fields.values(), None),
         };
-        std::iter::once(Node::new_opt_location(def, location).into()).chain(
+        std::iter::once(same_location_opt(node, def).into()).chain(
             self.extensions().into_iter().map(move |ext| {
                 ast::Definition::InterfaceTypeExtension(ext.same_location(
                     ast::InterfaceTypeExtension {
@@ -208,14 +217,17 @@ impl InterfaceType {
 }
 
 impl UnionType {
-    fn to_ast(&self, location: Option<SourceSpan>) -> impl Iterator<Item = ast::Definition> + '_ {
+    fn to_ast<'a>(
+        &'a self,
+        node: Option<&Node<Self>>,
+    ) -> impl Iterator<Item = ast::Definition> + 'a {
         let def = ast::UnionTypeDefinition {
             description: self.description.clone(),
             name: self.name.clone(),
             directives: ast::DirectiveList(components(&self.directives, None)),
             members: names(&self.members, None),
         };
-        std::iter::once(Node::new_opt_location(def, location).into()).chain(
+        std::iter::once(same_location_opt(node, def).into()).chain(
             self.extensions().into_iter().map(move |ext| {
                 ast::Definition::UnionTypeExtension(ext.same_location(ast::UnionTypeExtension {
                     name: self.name.clone(),
@@ -234,14 +246,17 @@ impl UnionType {
 }
 
 impl EnumType {
-    fn to_ast(&self, location: Option<SourceSpan>) -> impl Iterator<Item = ast::Definition> + '_ {
+    fn to_ast<'a>(
+        &'a self,
+        node: Option<&Node<Self>>,
+    ) -> impl Iterator<Item = ast::Definition> + 'a {
         let def = ast::EnumTypeDefinition {
             description: self.description.clone(),
             name: self.name.clone(),
             directives: ast::DirectiveList(components(&self.directives, None)),
             values: components(self.values.values(), None),
         };
-        std::iter::once(Node::new_opt_location(def, location).into()).chain(
+        std::iter::once(same_location_opt(node, def).into()).chain(
             self.extensions().into_iter().map(move |ext| {
                 ast::Definition::EnumTypeExtension(ext.same_location(ast::EnumTypeExtension {
                     name: self.name.clone(),
@@ -260,14 +275,17 @@ impl EnumType {
 }
 
 impl InputObjectType {
-    fn to_ast(&self, location: Option<SourceSpan>) -> impl Iterator<Item = ast::Definition> + '_ {
+    fn to_ast<'a>(
+        &'a self,
+        node: Option<&Node<Self>>,
+    ) -> impl Iterator<Item = ast::Definition> + 'a {
         let def = ast::InputObjectTypeDefinition {
             description: self.description.clone(),
             name: self.name.clone(),
             directives: ast::DirectiveList(components(&self.directives, None)),
             fields: components(self.fields.values(), None),
         };
-        std::iter::once(Node::new_opt_location(def, location).into()).chain(
+        std::iter::once(same_location_opt(node, def).into()).chain(
             self.extensions().into_iter().map(move |ext| {
                 ast::Definition::InputObjectTypeExtension(ext.same_location(
                     ast::InputObjectTypeExtension {
@@ -298,6 +316,14 @@ fn components<'a, T: 'a>(
         .collect()
 }
 
+/// Copy location and comments from `node` if available
+fn same_location_opt<T, U>(node: Option<&Node<T>>, new: U) -> Node<U> {
+    match node {
+        Some(node) => node.same_location(new),
+        None => Node::new(new),
+    }
+}
+
 fn names(names: &IndexSet<ComponentName>, ext: Option<&ExtensionId>) -> Vec<Name> {
     names
         .iter()
@@ -0,0 +1,967 @@
+//! Depth-first traversal of type system definitions
+
+use crate::ast;
+use crate::ast::Argument;
+use crate::ast::Definition;
+use crate::collections::IndexMap;
+use crate::collections::IndexSet;
+use crate::executable::visit::walk_part;
+use crate::executable::visit::SameNodes;
+use crate::executable::visit::Stop;
+use crate::executable::VisitControl;
+use crate::schema::Component;
+use crate::schema::ComponentName;
+use crate::schema::Directive;
+use crate::schema::DirectiveDefinition;
+use crate::schema::DirectiveList;
+use crate::schema::EnumValueDefinition;
+use crate::schema::ExtendedType;
+use crate::schema::FieldDefinition;
+use crate::schema::InputValueDefinition;
+use crate::schema::NamedType;
+use crate::schema::SchemaDefinition;
+use crate::schema::Type;
+use crate::schema::Value;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+
+/// Read-only visitor for [`Schema::walk`] and [`ast::Document::walk`].
+///
+/// All methods have default implementations that do nothing,
+/// so implementations only need to define the methods they’re interested in.
+/// `enter_*` methods are called before visiting children, `leave_*` methods after.
+/// They return [`VisitControl`] to skip children or stop the traversal.
+///
+/// Walking a [`Schema`] visits its schema definition, then directive definitions,
+/// then types. Built-in definitions are not visited.
+/// Walking an [`ast::Document`] visits type system definitions and extensions
+/// in document order. Executable definitions are skipped:
+/// see [`executable::Visitor`][crate::executable::Visitor] for those.
+///
+/// # Example
+///
+/// ```
+/// use apollo_compiler::schema::FieldDefinition;
+/// use apollo_compiler::schema::Visitor;
+/// use apollo_compiler::executable::VisitControl;
+/// use apollo_compiler::Node;
+/// use apollo_compiler::Schema;
+///
+/// #[derive(Default)]
+/// struct DeprecatedFields(Vec<String>);
+///
+/// impl<'doc> Visitor<'doc> for DeprecatedFields {
+///     fn enter_field_definition(&mut self, field: &'doc Node<FieldDefinition>) -> VisitControl {
+///         if field.directives.has("deprecated") {
+///             self.0.push(field.name.to_string())
+///         }
+///         VisitControl::Continue
+///     }
+/// }
+///
+/// let schema = Schema::parse(
+///     "type Query { a: Int @deprecated, b: Int } interface I { c: Int @deprecated }",
+///     "schema.graphql",
+/// ).unwrap();
+/// let mut visitor = DeprecatedFields::default();
+/// schema.walk(&mut visitor);
+/// assert_eq!(visitor.0, ["a", "c"]);
+/// ```
+#[allow(unused_variables)]
+pub trait Visitor<'doc> {
+    /// Called for type system definitions and extensions of an [`ast::Document`]
+    fn enter_definition(&mut self, definition: &'doc Definition) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_definition(&mut self, definition: &'doc Definition) {}
+
+    /// Called for the schema definition of a [`Schema`]
+    fn enter_schema_definition(
+        &mut self,
+        definition: &'doc Node<SchemaDefinition>,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_schema_definition(&mut self, definition: &'doc Node<SchemaDefinition>) {}
+
+    /// Called for directive definitions of a [`Schema`]
+    fn enter_directive_definition(
+        &mut self,
+        definition: &'doc Node<DirectiveDefinition>,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_directive_definition(&mut self, definition: &'doc Node<DirectiveDefinition>) {}
+
+    /// Called for types of a [`Schema`]
+    fn enter_type(&mut self, ty: &'doc ExtendedType) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_type(&mut self, ty: &'doc ExtendedType) {}
+
+    fn enter_field_definition(&mut self, field: &'doc Node<FieldDefinition>) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_field_definition(&mut self, field: &'doc Node<FieldDefinition>) {}
+
+    /// Called for arguments of fields and directive definitions, and for input fields
+    fn enter_input_value_definition(
+        &mut self,
+        definition: &'doc Node<InputValueDefinition>,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_input_value_definition(&mut self, definition: &'doc Node<InputValueDefinition>) {}
+
+    fn enter_enum_value_definition(
+        &mut self,
+        definition: &'doc Node<EnumValueDefinition>,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_enum_value_definition(&mut self, definition: &'doc Node<EnumValueDefinition>) {}
+
+    fn enter_directive(&mut self, directive: &'doc Node<Directive>) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_directive(&mut self, directive: &'doc Node<Directive>) {}
+
+    fn enter_argument(&mut self, argument: &'doc Node<Argument>) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_argument(&mut self, argument: &'doc Node<Argument>) {}
+
+    /// Called for values of directive arguments and default values,
+    /// and recursively for list items and input object fields
+    fn enter_value(&mut self, value: &'doc Node<Value>) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_value(&mut self, value: &'doc Node<Value>) {}
+
+    /// Called for each reference to a type by name:
+    /// types of fields, arguments, and input fields, implemented interfaces,
+    /// union members, and root operation types
+    fn named_type(&mut self, name: &'doc NamedType) {}
+}
+
+/// Mutable visitor for [`Schema::walk_mut`] and [`ast::Document::walk_mut`].
+///
+/// This has the same methods as [`Visitor`] but with mutable references,
+/// allowing to modify or replace nodes.
+/// If an `enter_*` method replaces its node, the children of the new node are visited.
+///
+/// When walking a [`Schema`], renaming a type, directive definition, field, enum value,
+/// or input field also updates its key in the containing map.
+/// References to it by name are not updated:
+/// use [`named_type`][Self::named_type] to rename them too.
+///
+/// Children are visited on copies, and a node is only made mutable with [`Node::make_mut`]
+/// when the visitor modified it or one of its descendants.
+/// Unmodified nodes stay shared with other documents.
+#[allow(unused_variables)]
+pub trait VisitorMut {
+    /// Called for type system definitions and extensions of an [`ast::Document`]
+    fn enter_definition(&mut self, definition: &mut Definition) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_definition(&mut self, definition: &mut Definition) {}
+
+    /// Called for the schema definition of a [`Schema`]
+    fn enter_schema_definition(&mut self, definition: &mut Node<SchemaDefinition>) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_schema_definition(&mut self, definition: &mut Node<SchemaDefinition>) {}
+
+    /// Called for directive definitions of a [`Schema`]
+    fn enter_directive_definition(
+        &mut self,
+        definition: &mut Node<DirectiveDefinition>,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_directive_definition(&mut self, definition: &mut Node<DirectiveDefinition>) {}
+
+    /// Called for types of a [`Schema`]
+    fn enter_type(&mut self, ty: &mut ExtendedType) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_type(&mut self, ty: &mut ExtendedType) {}
+
+    fn enter_field_definition(&mut self, field: &mut Node<FieldDefinition>) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_field_definition(&mut self, field: &mut Node<FieldDefinition>) {}
+
+    /// Called for arguments of fields and directive definitions, and for input fields
+    fn enter_input_value_definition(
+        &mut self,
+        definition: &mut Node<InputValueDefinition>,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_input_value_definition(&mut self, definition: &mut Node<InputValueDefinition>) {}
+
+    fn enter_enum_value_definition(
+        &mut self,
+        definition: &mut Node<EnumValueDefinition>,
+    ) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_enum_value_definition(&mut self, definition: &mut Node<EnumValueDefinition>) {}
+
+    fn enter_directive(&mut self, directive: &mut Node<Directive>) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_directive(&mut self, directive: &mut Node<Directive>) {}
+
+    fn enter_argument(&mut self, argument: &mut Node<Argument>) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_argument(&mut self, argument: &mut Node<Argument>) {}
+
+    /// Called for values of directive arguments and default values,
+    /// and recursively for list items and input object fields
+    fn enter_value(&mut self, value: &mut Node<Value>) -> VisitControl {
+        VisitControl::Continue
+    }
+
+    fn leave_value(&mut self, value: &mut Node<Value>) {}
+
+    /// Called for each reference to a type by name:
+    /// types of fields, arguments, and input fields, implemented interfaces,
+    /// union members, and root operation types
+    fn named_type(&mut self, name: &mut NamedType) {}
+}
+
+/// Calls an `enter_*` method, returning early if the traversal should not continue
+macro_rules! enter {
+    ($walker: ident . $method: ident ( $node: expr )) => {
+        match $walker.visitor.$method($node) {
+            VisitControl::Continue => true,
+            VisitControl::SkipChildren => false,
+            VisitControl::Stop => return Err(Stop),
+        }
+    };
+}
+
+pub(crate) fn walk_schema<'doc>(schema: &'doc Schema, visitor: &mut impl Visitor<'doc>) {
+    let _: Result<(), Stop> = Walker { visitor }.schema(schema);
+}
+
+pub(crate) fn walk_schema_mut(schema: &mut Schema, visitor: &mut impl VisitorMut) {
+    let _: Result<(), Stop> = WalkerMut { visitor }.schema(schema);
+}
+
+pub(crate) fn walk_document<'doc>(document: &'doc ast::Document, visitor: &mut impl Visitor<'doc>) {
+    let _: Result<(), Stop> = Walker { visitor }.document(document);
+}
+
+pub(crate) fn walk_document_mut(document: &mut ast::Document, visitor: &mut impl VisitorMut) {
+    let _: Result<(), Stop> = WalkerMut { visitor }.document(document);
+}
+
+struct Walker<'v, V> {
+    visitor: &'v mut V,
+}
+
+impl<'doc, V: Visitor<'doc>> Walker<'_, V> {
+    fn schema(&mut self, schema: &'doc Schema) -> Result<(), Stop> {
+        let definition = &schema.schema_definition;
+        if enter!(self.enter_schema_definition(definition)) {
+            self.directives(definition.directives.iter().map(|d| &d.node))?;
+            let roots = [
+                &definition.query,
+                &definition.mutation,
+                &definition.subscription,
+            ];
+            for root in roots.into_iter().flatten() {
+                self.visitor.named_type(root)
+            }
+            self.visitor.leave_schema_definition(definition)
+        }
+        for definition in schema.directive_definitions.values() {
+            if definition.is_built_in() {
+                continue;
+            }
+            if enter!(self.enter_directive_definition(definition)) {
+                self.input_value_definitions(&definition.arguments)?;
+                self.visitor.leave_directive_definition(definition)
+            }
+        }
+        for ty in schema.types.values() {
+            if !ty.is_built_in() {
+                self.ty(ty)?
+            }
+        }
+        Ok(())
+    }
+
+    fn ty(&mut self, ty: &'doc ExtendedType) -> Result<(), Stop> {
+        if !enter!(self.enter_type(ty)) {
+            return Ok(());
+        }
+        match ty {
+            ExtendedType::Scalar(def) => self.component_directives(&def.directives)?,
+            ExtendedType::Object(def) => {
+                self.component_names(&def.implements_interfaces);
+                self.component_directives(&def.directives)?;
+                self.field_definitions(def.fields.values().map(|c| &c.node))?
+            }
+            ExtendedType::Interface(def) => {
+                self.component_names(&def.implements_interfaces);
+                self.component_directives(&def.directives)?;
+                self.field_definitions(def.fields.values().map(|c| &c.node))?
+            }
+            ExtendedType::Union(def) => {
+                self.component_directives(&def.directives)?;
+                self.component_names(&def.members)
+            }
+            ExtendedType::Enum(def) => {
+                self.component_directives(&def.directives)?;
+                self.enum_value_definitions(def.values.values().map(|c| &c.node))?
+            }
+            ExtendedType::InputObject(def) => {
+                self.component_directives(&def.directives)?;
+                self.input_value_definitions(def.fields.values().map(|c| &c.node))?
+            }
+        }
+        self.visitor.leave_type(ty);
+        Ok(())
+    }
+
+    fn document(&mut self, document: &'doc ast::Document) -> Result<(), Stop> {
+        for definition in &document.definitions {
+            if definition.is_executable_definition() {
+                continue;
+            }
+            if !enter!(self.enter_definition(definition)) {
+                continue;
+            }
+            match definition {
+                Definition::OperationDefinition(_) | Definition::FragmentDefinition(_) => {}
+                Definition::DirectiveDefinition(def) => {
+                    self.input_value_definitions(&def.arguments)?
+                }
+                Definition::SchemaDefinition(def) => {
+                    self.directives(&def.directives)?;
+                    self.root_operations(&def.root_operations)
+                }
+                Definition::SchemaExtension(def) => {
+                    self.directives(&def.directives)?;
+                    self.root_operations(&def.root_operations)
+                }
+                Definition::ScalarTypeDefinition(def) => self.directives(&def.directives)?,
+                Definition::ScalarTypeExtension(def) => self.directives(&def.directives)?,
+                Definition::ObjectTypeDefinition(def) => {
+                    self.named_types(&def.implements_interfaces);
+                    self.directives(&def.directives)?;
+                    self.field_definitions(&def.fields)?
+                }
+                Definition::ObjectTypeExtension(def) => {
+                    self.named_types(&def.implements_interfaces);
+                    self.directives(&def.directives)?;
+                    self.field_definitions(&def.fields)?
+                }
+                Definition::InterfaceTypeDefinition(def) => {
+                    self.named_types(&def.implements_interfaces);
+                    self.directives(&def.directives)?;
+                    self.field_definitions(&def.fields)?
+                }
+                Definition::InterfaceTypeExtension(def) => {
+                    self.named_types(&def.implements_interfaces);
+                    self.directives(&def.directives)?;
+                    self.field_definitions(&def.fields)?
+                }
+                Definition::UnionTypeDefinition(def) => {
+                    self.directives(&def.directives)?;
+                    self.named_types(&def.members)
+                }
+                Definition::UnionTypeExtension(def) => {
+                    self.directives(&def.directives)?;
+                    self.named_types(&def.members)
+                }
+                Definition::EnumTypeDefinition(def) => {
+                    self.directives(&def.directives)?;
+                    self.enum_value_definitions(&def.values)?
+                }
+                Definition::EnumTypeExtension(def) => {
+                    self.directives(&def.directives)?;
+                    self.enum_value_definitions(&def.values)?
+                }
+                Definition::InputObjectTypeDefinition(def) => {
+                    self.directives(&def.directives)?;
+                    self.input_value_definitions(&def.fields)?
+                }
+                Definition::InputObjectTypeExtension(def) => {
+                    self.directives(&def.directives)?;
+                    self.input_value_definitions(&def.fields)?
+                }
+            }
+            self.visitor.leave_definition(definition)
+        }
+        Ok(())
+    }
+
+    fn root_operations(&mut self, root_operations: &'doc [Node<(ast::OperationType, NamedType)>]) {
+        for root in root_operations {
+            self.visitor.named_type(&root.1)
+        }
+    }
+
+    fn named_types(&mut self, names: &'doc [NamedType]) {
+        for name in names {
+            self.visitor.named_type(name)
+        }
+    }
+
+    fn component_names(&mut self, names: &'doc IndexSet<ComponentName>) {
+        for name in names {
+            self.visitor.named_type(name)
+        }
+    }
+
+    fn field_definitions(
+        &mut self,
+        fields: impl IntoIterator<Item = &'doc Node<FieldDefinition>>,
+    ) -> Result<(), Stop> {
+        for field in fields {
+            if enter!(self.enter_field_definition(field)) {
+                self.input_value_definitions(&field.arguments)?;
+                self.visitor.named_type(field.ty.inner_named_type());
+                self.directives(&field.directives)?;
+                self.visitor.leave_field_definition(field)
+            }
+        }
+        Ok(())
+    }
+
+    fn input_value_definitions(
+        &mut self,
+        definitions: impl IntoIterator<Item = &'doc Node<InputValueDefinition>>,
+    ) -> Result<(), Stop> {
+        for definition in definitions {
+            if enter!(self.enter_input_value_definition(definition)) {
+                self.visitor.named_type(definition.ty.inner_named_type());
+                if let Some(value) = &definition.default_value {
+                    self.value(value)?
+                }
+                self.directives(&definition.directives)?;
+                self.visitor.leave_input_value_definition(definition)
+            }
+        }
+        Ok(())
+    }
+
+    fn enum_value_definitions(
+        &mut self,
+        definitions: impl IntoIterator<Item = &'doc Node<EnumValueDefinition>>,
+    ) -> Result<(), Stop> {
+        for definition in definitions {
+            if enter!(self.enter_enum_value_definition(definition)) {
+                self.directives(&definition.directives)?;
+                self.visitor.leave_enum_value_definition(definition)
+            }
+        }
+        Ok(())
+    }
+
+    fn component_directives(&mut self, directives: &'doc DirectiveList) -> Result<(), Stop> {
+        self.directives(directives.iter().map(|d| &d.node))
+    }
+
+    fn directives(
+        &mut self,
+        directives: impl IntoIterator<Item = &'doc Node<Directive>>,
+    ) -> Result<(), Stop> {
+        for directive in directives {
+            if enter!(self.enter_directive(directive)) {
+                for argument in &directive.arguments {
+                    if enter!(self.enter_argument(argument)) {
+                        self.value(&argument.value)?;
+                        self.visitor.leave_argument(argument)
+                    }
+                }
+                self.visitor.leave_directive(directive)
+            }
+        }
+        Ok(())
+    }
+
+    fn value(&mut self, value: &'doc Node<Value>) -> Result<(), Stop> {
+        if !enter!(self.enter_value(value)) {
+            return Ok(());
+        }
+        match value.as_ref() {
+            Value::List(items) => {
+                for item in items {
+                    self.value(item)?
+                }
+            }
+            Value::Object(fields) => {
+                for (_name, field_value) in fields {
+                    self.value(field_value)?
+                }
+            }
+            Value::Null
+            | Value::Enum(_)
+            | Value::Variable(_)
+            | Value::String(_)
+            | Value::Float(_)
+            | Value::Int(_)
+            | Value::Boolean(_) => {}
+        }
+        self.visitor.leave_value(value);
+        Ok(())
+    }
+}
+
+struct WalkerMut<'v, V> {
+    visitor: &'v mut V,
+}
+
+impl SameNodes for DirectiveList {
+    fn same_nodes(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .zip(other.iter())
+                .all(|(a, b)| a.origin == b.origin && a.node.ptr_eq(&b.node))
+    }
+}
+
+impl<T> SameNodes for IndexMap<Name, Component<T>> {
+    fn same_nodes(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self.iter().zip(other).all(|((key_a, a), (key_b, b))| {
+                key_a == key_b && a.origin == b.origin && a.node.ptr_eq(&b.node)
+            })
+    }
+}
+
+impl SameNodes for IndexSet<ComponentName> {
+    fn same_nodes(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a == b)
+    }
+}
+
+impl SameNodes for Vec<Name> {
+    fn same_nodes(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+/// The innermost named type, after unwrapping any non-null or list markers
+fn inner_named_type_mut(ty: &mut Type) -> &mut NamedType {
+    match ty {
+        Type::Named(name) | Type::NonNullNamed(name) => name,
+        Type::List(inner) | Type::NonNullList(inner) => inner_named_type_mut(inner),
+    }
+}
+
+/// Updates the keys of `map` for entries whose name changed
+fn rekey<T>(map: &mut IndexMap<Name, T>, name: impl Fn(&T) -> &Name) {
+    if map.iter().any(|(key, value)| key != name(value)) {
+        *map = std::mem::take(map)
+            .into_iter()
+            .map(|(_, value)| (name(&value).clone(), value))
+            .collect()
+    }
+}
+
+impl<V: VisitorMut> WalkerMut<'_, V> {
+    /// Returns the new name if the visitor renamed this type reference
+    fn renamed(&mut self, name: &NamedType) -> Option<NamedType> {
+        let mut copy = name.clone();
+        self.visitor.named_type(&mut copy);
+        (copy != *name).then_some(copy)
+    }
+
+    fn schema(&mut self, schema: &mut Schema) -> Result<(), Stop> {
+        self.schema_definition(&mut schema.schema_definition)?;
+        let result = schema
+            .directive_definitions
+            .values_mut()
+            .filter(|definition| !definition.is_built_in())
+            .try_for_each(|definition| self.directive_definition(definition));
+        rekey(&mut schema.directive_definitions, |def| &def.name);
+        result?;
+        let result = schema
+            .types
+            .values_mut()
+            .filter(|ty| !ty.is_built_in())
+            .try_for_each(|ty| self.ty(ty));
+        rekey(&mut schema.types, |ty| ty.name());
+        result
+    }
+
+    fn schema_definition(&mut self, definition: &mut Node<SchemaDefinition>) -> Result<(), Stop> {
+        if !enter!(self.enter_schema_definition(definition)) {
+            return Ok(());
+        }
+        walk_part!(definition.directives, |directives| self
+            .component_directives(&mut directives))?;
+        let renamed = [
+            &definition.query,
+            &definition.mutation,
+            &definition.subscription,
+        ]
+        .map(|root| root.as_ref().and_then(|root| self.renamed(root)));
+        if renamed.iter().any(Option::is_some) {
+            let definition = definition.make_mut();
+            let roots = [
+                &mut definition.query,
+                &mut definition.mutation,
+                &mut definition.subscription,
+            ];
+            for (root, renamed) in roots.into_iter().zip(renamed) {
+                if let (Some(root), Some(renamed)) = (root, renamed) {
+                    root.name = renamed
+                }
+            }
+        }
+        self.visitor.leave_schema_definition(definition);
+        Ok(())
+    }
+
+    fn directive_definition(
+        &mut self,
+        definition: &mut Node<DirectiveDefinition>,
+    ) -> Result<(), Stop> {
+        if enter!(self.enter_directive_definition(definition)) {
+            walk_part!(definition.arguments, |arguments| self
+                .input_value_definitions(arguments.iter_mut()))?;
+            self.visitor.leave_directive_definition(definition)
+        }
+        Ok(())
+    }
+
+    fn ty(&mut self, ty: &mut ExtendedType) -> Result<(), Stop> {
+        if !enter!(self.enter_type(ty)) {
+            return Ok(());
+        }
+        match ty {
+            ExtendedType::Scalar(def) => walk_part!(def.directives, |directives| self
+                .component_directives(&mut directives))?,
+            ExtendedType::Object(def) => {
+                walk_part!(def.implements_interfaces, |names| self
+                    .component_names(&mut names));
+                walk_part!(def.directives, |directives| self
+                    .component_directives(&mut directives))?;
+                walk_part!(def.fields, |fields| self.component_fields(&mut fields))?
+            }
+            ExtendedType::Interface(def) => {
+                walk_part!(def.implements_interfaces, |names| self
+                    .component_names(&mut names));
+                walk_part!(def.directives, |directives| self
+                    .component_directives(&mut directives))?;
+                walk_part!(def.fields, |fields| self.component_fields(&mut fields))?
+            }
+            ExtendedType::Union(def) => {
+                walk_part!(def.directives, |directives| self
+                    .component_directives(&mut directives))?;
+                walk_part!(def.members, |names| self.component_names(&mut names))
+            }
+            ExtendedType::Enum(def) => {
+                walk_part!(def.directives, |directives| self
+                    .component_directives(&mut directives))?;
+                walk_part!(def.values, |values| {
+                    let result =
+                        self.enum_value_definitions(values.values_mut().map(|c| &mut c.node));
+                    rekey(&mut values, |value| &value.value);
+                    result
+                })?
+            }
+            ExtendedType::InputObject(def) => {
+                walk_part!(def.directives, |directives| self
+                    .component_directives(&mut directives))?;
+                walk_part!(def.fields, |fields| {
+                    let result =
+                        self.input_value_definitions(fields.values_mut().map(|c| &mut c.node));
+                    rekey(&mut fields, |field| &field.name);
+                    result
+                })?
+            }
+        }
+        self.visitor.leave_type(ty);
+        Ok(())
+    }
+
+    fn component_fields(
+        &mut self,
+        fields: &mut IndexMap<Name, Component<FieldDefinition>>,
+    ) -> Result<(), Stop> {
+        let result = self.field_definitions(fields.values_mut().map(|c| &mut c.node));
+        rekey(fields, |field| &field.name);
+        result
+    }
+
+    fn component_names(&mut self, names: &mut IndexSet<ComponentName>) {
+        let renamed: Vec<_> = names.iter().map(|name| self.renamed(name)).collect();
+        if renamed.iter().all(Option::is_none) {
+            return;
+        }
+        *names = names
+            .iter()
+            .zip(renamed)
+            .map(|(name, renamed)| match renamed {
+                Some(renamed) => renamed.to_component(name.origin.clone()),
+                None => name.clone(),
+            })
+            .collect()
+    }
+
+    fn document(&mut self, document: &mut ast::Document) -> Result<(), Stop> {
+        for definition in &mut document.definitions {
+            if definition.is_executable_definition() {
+                continue;
+            }
+            if !enter!(self.enter_definition(definition)) {
+                continue;
+            }
+            match definition {
+                Definition::OperationDefinition(_) | Definition::FragmentDefinition(_) => {}
+                Definition::DirectiveDefinition(def) => {
+                    walk_part!(def.arguments, |arguments| self
+                        .input_value_definitions(arguments.iter_mut()))?
+                }
+                Definition::SchemaDefinition(def) => {
+                    walk_part!(def.directives, |directives| self
+                        .directives(&mut directives))?;
+                    walk_part!(def.root_operations, |roots| self
+                        .root_operations(&mut roots))
+                }
+                Definition::SchemaExtension(def) => {
+                    walk_part!(def.directives, |directives| self
+                        .directives(&mut directives))?;
+                    walk_part!(def.root_operations, |roots| self
+                        .root_operations(&mut roots))
+                }
+                Definition::ScalarTypeDefinition(def) => {
+                    walk_part!(def.directives, |directives| self
+                        .directives(&mut directives))?
+                }
+                Definition::ScalarTypeExtension(def) => {
+                    walk_part!(def.directives, |directives| self
+                        .directives(&mut directives))?
+                }
+                Definition::ObjectTypeDefinition(def) => {
+                    walk_part!(def.implements_interfaces, |names| self
+                        .named_types(&mut names));
+                    walk_part!(def.directives, |directives| self
+                        .directives(&mut directives))?;
+                    walk_part!(def.fields, |fields| self
+                        .field_definitions(fields.iter_mut()))?
+                }
+                Definition::ObjectTypeExtension(def) => {
+                    walk_part!(def.implements_interfaces, |names| self
+                        .named_types(&mut names));
+                    walk_part!(def.directives, |directives| self
+                        .directives(&mut directives))?;
+                    walk_part!(def.fields, |fields| self
+                        .field_definitions(fields.iter_mut()))?
+                }
+                Definition::InterfaceTypeDefinition(def) => {
+                    walk_part!(def.implements_interfaces, |names| self
+                        .named_types(&mut names));
+                    walk_part!(def.directives, |directives| self
+                        .directives(&mut directives))?;
+                    walk_part!(def.fields, |fields| self
+                        .field_definitions(fields.iter_mut()))?
+                }
+                Definition::InterfaceTypeExtension(def) => {
+                    walk_part!(def.implements_interfaces, |names| self
+                        .named_types(&mut names));
+                    walk_part!(def.directives, |directives| self
+                        .directives(&mut directives))?;
+                    walk_part!(def.fields, |fields| self
+                        .field_definitions(fields.iter_mut()))?
+                }
+                Definition::UnionTypeDefinition(def) => {
+                    walk_part!(def.directives, |directives| self
+                        .directives(&mut directives))?;
+                    walk_part!(def.members, |names| self.named_types(&mut names))
+                }
+                Definition::UnionTypeExtension(def) => {
+                    walk_part!(def.directives, |directives| self
+                        .directives(&mut directives))?;
+                    walk_part!(def.members, |names| self.named_types(&mut names))
+                }
+                Definition::EnumTypeDefinition(def) => {
+                    walk_part!(def.directives, |directives| self
+                        .directives(&mut directives))?;
+                    walk_part!(def.values, |values| self
+                        .enum_value_definitions(values.iter_mut()))?
+                }
+                Definition::EnumTypeExtension(def) => {
+                    walk_part!(def.directives, |directives| self
+                        .directives(&mut directives))?;
+                    walk_part!(def.values, |values| self
+                        .enum_value_definitions(values.iter_mut()))?
+                }
+                Definition::InputObjectTypeDefinition(def) => {
+                    walk_part!(def.directives, |directives| self
+                        .directives(&mut directives))?;
+                    walk_part!(def.fields, |fields| self
+                        .input_value_definitions(fields.iter_mut()))?
+                }
+                Definition::InputObjectTypeExtension(def) => {
+                    walk_part!(def.directives, |directives| self
+                        .directives(&mut directives))?;
+                    walk_part!(def.fields, |fields| self
+                        .input_value_definitions(fields.iter_mut()))?
+                }
+            }
+            self.visitor.leave_definition(definition)
+        }
+        Ok(())
+    }
+
+    fn root_operations(&mut self, root_operations: &mut [Node<(ast::OperationType, NamedType)>]) {
+        for root in root_operations {
+            if let Some(renamed) = self.renamed(&root.1) {
+                root.make_mut().1 = renamed
+            }
+        }
+    }
+
+    fn named_types(&mut self, names: &mut [NamedType]) {
+        for name in names {
+            self.visitor.named_type(name)
+        }
+    }
+
+    fn field_definitions<'a>(
+        &mut self,
+        fields: impl IntoIterator<Item = &'a mut Node<FieldDefinition>>,
+    ) -> Result<(), Stop> {
+        for field in fields {
+            if enter!(self.enter_field_definition(field)) {
+                walk_part!(field.arguments, |arguments| self
+                    .input_value_definitions(arguments.iter_mut()))?;
+                if let Some(renamed) = self.renamed(field.ty.inner_named_type()) {
+                    *inner_named_type_mut(&mut field.make_mut().ty) = renamed
+                }
+                walk_part!(field.directives, |directives| self
+                    .directives(&mut directives))?;
+                self.visitor.leave_field_definition(field)
+            }
+        }
+        Ok(())
+    }
+
+    fn input_value_definitions<'a>(
+        &mut self,
+        definitions: impl IntoIterator<Item = &'a mut Node<InputValueDefinition>>,
+    ) -> Result<(), Stop> {
+        for definition in definitions {
+            if enter!(self.enter_input_value_definition(definition)) {
+                if let Some(renamed) = self.renamed(definition.ty.inner_named_type()) {
+                    *inner_named_type_mut(definition.make_mut().ty.make_mut()) = renamed
+                }
+                walk_part!(definition.default_value, |default_value| {
+                    match &mut default_value {
+                        Some(value) => self.value(value),
+                        None => Ok(()),
+                    }
+                })?;
+                walk_part!(definition.directives, |directives| self
+                    .directives(&mut directives))?;
+                self.visitor.leave_input_value_definition(definition)
+            }
+        }
+        Ok(())
+    }
+
+    fn enum_value_definitions<'a>(
+        &mut self,
+        definitions: impl IntoIterator<Item = &'a mut Node<EnumValueDefinition>>,
+    ) -> Result<(), Stop> {
+        for definition in definitions {
+            if enter!(self.enter_enum_value_definition(definition)) {
+                walk_part!(definition.directives, |directives| self
+                    .directives(&mut directives))?;
+                self.visitor.leave_enum_value_definition(definition)
+            }
+        }
+        Ok(())
+    }
+
+    fn component_directives(&mut self, directives: &mut DirectiveList) -> Result<(), Stop> {
+        directives
+            .iter_mut()
+            .try_for_each(|directive| self.directive(&mut directive.node))
+    }
+
+    fn directives(&mut self, directives: &mut ast::DirectiveList) -> Result<(), Stop> {
+        directives
+            .iter_mut()
+            .try_for_each(|directive| self.directive(directive))
+    }
+
+    fn directive(&mut self, directive: &mut Node<Directive>) -> Result<(), Stop> {
+        if enter!(self.enter_directive(directive)) {
+            walk_part!(directive.arguments, |arguments| {
+                arguments
+                    .iter_mut()
+                    .try_for_each(|argument| self.argument(argument))
+            })?;
+            self.visitor.leave_directive(directive)
+        }
+        Ok(())
+    }
+
+    fn argument(&mut self, argument: &mut Node<Argument>) -> Result<(), Stop> {
+        if enter!(self.enter_argument(argument)) {
+            let mut value = argument.value.clone();
+            let result = self.value(&mut value);
+            if !value.ptr_eq(&argument.value) {
+                argument.make_mut().value = value
+            }
+            result?;
+            self.visitor.leave_argument(argument)
+        }
+        Ok(())
+    }
+
+    fn value(&mut self, value: &mut Node<Value>) -> Result<(), Stop> {
+        if !enter!(self.enter_value(value)) {
+            return Ok(());
+        }
+        if matches!(**value, Value::List(_) | Value::Object(_)) {
+            let mut copy = Value::clone(value);
+            let result = match &mut copy {
+                Value::List(items) => items.iter_mut().try_for_each(|item| self.value(item)),
+                Value::Object(fields) => fields
+                    .iter_mut()
+                    .try_for_each(|(_name, field_value)| self.value(field_value)),
+                _ => Ok(()),
+            };
+            if !copy.same_nodes(value) {
+                *value.make_mut() = copy
+            }
+            result?
+        }
+        self.visitor.leave_value(value);
+        Ok(())
+    }
+}
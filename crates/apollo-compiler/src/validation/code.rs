@@ -0,0 +1,504 @@
+use super::diagnostics;
+use super::Details;
+use super::DiagnosticData;
+use crate::executable::BuildError as ExecutableBuildError;
+use crate::parser::SyntaxErrorKind;
+use crate::schema::BuildError as SchemaBuildError;
+use serde::Deserialize;
+use serde::Serialize;
+use std::fmt;
+
+macro_rules! diagnostic_codes {
+    ($( $(#[doc = $doc: literal])* $name: ident = $number: literal, )+) => {
+        /// A stable code identifying the kind of a [`DiagnosticData`],
+        /// for tools that handle diagnostics programmatically.
+        ///
+        /// Codes are displayed as `GQL` followed by four digits, such as `GQL0301`,
+        /// and each has a name matching its variant, such as `UniqueVariable`.
+        /// Once assigned, the number and name of a code do not change.
+        /// New codes may be added in future versions.
+        ///
+        /// Numbers are grouped by the phase that reports the diagnostic:
+        ///
+        /// * `GQL00xx`: syntax errors
+        /// * `GQL01xx`: errors building a [`Schema`][crate::Schema]
+        /// * `GQL02xx`: errors building an [`ExecutableDocument`][crate::ExecutableDocument]
+        /// * `GQL03xx`: validation errors
+        /// * `GQL04xx`: limits reached during validation
+        /// * `GQL05xx`: [lints][crate::validation::lint], reported as warnings by default
+        /// * `GQL06xx`: errors reported by [custom rules][crate::validation::Validator]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #[non_exhaustive]
+        #[repr(u16)]
+        pub enum DiagnosticCode {
+            $(
+                $(#[doc = $doc])*
+                $name = $number,
+            )+
+        }
+
+        impl DiagnosticCode {
+            /// All diagnostic codes, in increasing order.
+            pub const ALL: &'static [Self] = &[$(Self::$name),+];
+
+            /// Returns the name of this code, such as `"UniqueVariable"`.
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Self::$name => stringify!($name),)+
+                }
+            }
+
+            /// Returns a one-sentence description of this code,
+            /// such as `"Multiple variables with the same name."`.
+            pub fn description(self) -> &'static str {
+                match self {
+                    $(Self::$name => concat!($($doc),*).trim_ascii_start(),)+
+                }
+            }
+        }
+    };
+}
+
+diagnostic_codes! {
+    /// A character that cannot start a token.
+    UnexpectedCharacter = 1,
+    /// A string value without closing quotes.
+    UnterminatedString = 2,
+    /// A line break in a string value that is not a block string.
+    UnexpectedLineTerminator = 3,
+    /// An invalid escape sequence in a string value.
+    InvalidEscapeSequence = 4,
+    /// A malformed number.
+    InvalidNumber = 5,
+    /// One or two dots that are not part of a `...` spread.
+    UnterminatedSpread = 6,
+    /// A missing token, such as a closing brace or a name.
+    ExpectedToken = 7,
+    /// A token that is not valid at this position.
+    UnexpectedToken = 8,
+    /// The input ended where more tokens were required.
+    UnexpectedEof = 9,
+    /// A syntax error of another kind.
+    SyntaxError = 10,
+    /// The parser reached its token limit or recursion limit.
+    ParserLimit = 11,
+
+    /// A schema document contains an operation or fragment.
+    ExecutableDefinition = 101,
+    /// Multiple `schema` definitions.
+    SchemaDefinitionCollision = 102,
+    /// Multiple definitions of a directive with the same name.
+    DirectiveDefinitionCollision = 103,
+    /// Multiple definitions of a type with the same name.
+    TypeDefinitionCollision = 104,
+    /// A definition of a built-in scalar type.
+    BuiltInScalarTypeRedefinition = 105,
+    /// A schema extension without a schema definition.
+    OrphanSchemaExtension = 106,
+    /// A type extension for an undefined type.
+    OrphanTypeExtension = 107,
+    /// A type extension of a different kind than the type it extends.
+    TypeExtensionKindMismatch = 108,
+    /// Multiple definitions of the same root operation type.
+    DuplicateRootOperation = 109,
+    /// An object type implements the same interface multiple times.
+    DuplicateImplementsInterfaceInObject = 110,
+    /// An interface implements the same interface multiple times.
+    DuplicateImplementsInterfaceInInterface = 111,
+    /// Multiple fields with the same name in an object type.
+    ObjectFieldNameCollision = 112,
+    /// Multiple fields with the same name in an interface.
+    InterfaceFieldNameCollision = 113,
+    /// Multiple values with the same name in an enum type.
+    EnumValueNameCollision = 114,
+    /// A union type has the same member type multiple times.
+    UnionMemberNameCollision = 115,
+    /// Multiple fields with the same name in an input object type.
+    InputFieldNameCollision = 116,
+
+    /// An executable document contains a type system definition.
+    TypeSystemDefinition = 201,
+    /// An anonymous operation in a document with multiple operations.
+    AmbiguousAnonymousOperation = 202,
+    /// Multiple operations with the same name.
+    OperationNameCollision = 203,
+    /// Multiple fragments with the same name.
+    FragmentNameCollision = 204,
+    /// An operation whose root operation type is not defined in the schema.
+    UndefinedRootOperation = 205,
+    /// A named fragment whose type condition is not defined in the schema.
+    UndefinedTypeInNamedFragmentTypeCondition = 206,
+    /// An inline fragment whose type condition is not defined in the schema.
+    UndefinedTypeInInlineFragmentTypeCondition = 207,
+    /// A selection set on a field of scalar type.
+    SubselectionOnScalarType = 208,
+    /// A selection set on a field of enum type.
+    SubselectionOnEnumType = 209,
+    /// A selected field that is not defined on the parent type.
+    UndefinedField = 210,
+    /// A subscription that selects more than one root field.
+    SubscriptionUsesMultipleFields = 211,
+    /// A subscription that selects an introspection field at the root.
+    SubscriptionUsesIntrospection = 212,
+    /// A subscription with `@skip` or `@include` on a root field.
+    SubscriptionUsesConditionalSelection = 213,
+    /// Selections with the same response key and different types.
+    ConflictingFieldType = 214,
+    /// Selections with the same response key and different arguments.
+    ConflictingFieldArgument = 215,
+    /// Selections with the same response key and different fields.
+    ConflictingFieldName = 216,
+
+    /// Multiple variables with the same name.
+    UniqueVariable = 301,
+    /// The same argument is provided multiple times.
+    UniqueArgument = 302,
+    /// Multiple arguments or input fields with the same name.
+    UniqueInputValue = 303,
+    /// An argument that is not defined.
+    UndefinedArgument = 304,
+    /// A reference to an undefined type.
+    UndefinedDefinition = 305,
+    /// A reference to an undefined directive.
+    UndefinedDirective = 306,
+    /// A reference to an undefined variable.
+    UndefinedVariable = 307,
+    /// A reference to an undefined fragment.
+    UndefinedFragment = 308,
+    /// An enum value that is not defined.
+    UndefinedEnumValue = 309,
+    /// An input object field that is not defined.
+    UndefinedInputValue = 310,
+    /// A type is missing a field of an interface it implements.
+    MissingInterfaceField = 311,
+    /// A required argument is not provided.
+    RequiredArgument = 312,
+    /// A required input object field is not provided.
+    RequiredField = 313,
+    /// A type does not declare interfaces implemented by its interfaces.
+    TransitiveImplementedInterfaces = 314,
+    /// A field whose type is not an output type.
+    OutputType = 315,
+    /// An argument or input field whose type is not an input type.
+    InputType = 316,
+    /// A variable whose type is not an input type.
+    VariableInputType = 317,
+    /// The schema does not define a query root operation type.
+    QueryRootOperationType = 318,
+    /// A variable that is never used.
+    UnusedVariable = 319,
+    /// A root operation type that is not an object type.
+    RootOperationObjectType = 320,
+    /// A union member type that is not an object type.
+    UnionMemberObjectType = 321,
+    /// A directive used in a location it does not support.
+    UnsupportedLocation = 322,
+    /// A value that does not match the expected type.
+    UnsupportedValueType = 323,
+    /// An integer value outside of the 32-bit signed range.
+    IntCoercionError = 324,
+    /// A float value that is not finite.
+    FloatCoercionError = 325,
+    /// A non-repeatable directive used multiple times in the same location.
+    UniqueDirective = 326,
+    /// A field of composite type without a selection set.
+    MissingSubselection = 327,
+    /// A fragment whose type condition is not a composite type.
+    InvalidFragmentTarget = 328,
+    /// A fragment spread that can never apply to the parent type.
+    InvalidFragmentSpread = 329,
+    /// A fragment that is never used.
+    UnusedFragment = 330,
+    /// A variable used where its type is not allowed.
+    DisallowedVariableUsage = 331,
+    /// A directive definition that references itself.
+    RecursiveDirectiveDefinition = 332,
+    /// An interface that implements itself.
+    RecursiveInterfaceDefinition = 333,
+    /// An input object type that references itself through non-null fields.
+    RecursiveInputObjectDefinition = 334,
+    /// A fragment that spreads itself.
+    RecursiveFragmentDefinition = 335,
+    /// Too much nesting in a type or value.
+    DeeplyNestedType = 336,
+    /// Too much recursion while validating a definition.
+    RecursionError = 337,
+    /// An object type or interface without fields.
+    EmptyFieldSet = 338,
+    /// An enum type without values.
+    EmptyValueSet = 339,
+    /// A union type without member types.
+    EmptyMemberSet = 340,
+    /// An input object type without fields.
+    EmptyInputValueSet = 341,
+    /// A definition with a name that is reserved for introspection.
+    ReservedName = 342,
+    /// A non-null field in a OneOf input object.
+    OneOfInputObjectNonNullField = 343,
+    /// A field with a default value in a OneOf input object.
+    OneOfInputObjectFieldDefaultValue = 344,
+    /// A OneOf input object value that does not have exactly one field.
+    OneOfInputObjectFieldCount = 345,
+    /// A null field in a OneOf input object value.
+    OneOfInputObjectNullField = 346,
+    /// A nullable variable used as a field of a OneOf input object value.
+    OneOfInputObjectNullableVariable = 347,
+
+    /// The recursion limit was reached while validating an executable document.
+    RecursionLimitError = 401,
+
+    /// A use of a deprecated field, argument, input field, or enum value.
+    DeprecatedUsage = 501,
+    /// A type that cannot be reached from a root operation type.
+    UnusedType = 502,
+    /// A definition without a description.
+    MissingDescription = 503,
+    /// A name that does not follow naming conventions.
+    NamingConvention = 504,
+    /// A field whose type is a nullable list of nullable items.
+    NullableListItems = 505,
+
+    /// A diagnostic reported by a custom validation rule.
+    CustomRule = 601,
+}
+
+impl DiagnosticCode {
+    /// Returns the number of this code, such as `301` for `GQL0301`.
+    pub fn number(self) -> u16 {
+        self as u16
+    }
+}
+
+/// Formats as `GQL` followed by the four-digit number, such as `GQL0301`.
+impl fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "GQL{:04}", self.number())
+    }
+}
+
+/// How serious a diagnostic is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Severity {
+    /// The document is invalid.
+    Error,
+    /// The document is valid, but likely has a problem.
+    Warning,
+}
+
+impl Severity {
+    /// Returns `"error"` or `"warning"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl DiagnosticData {
+    /// Returns the stable code for this diagnostic.
+    pub fn code(&self) -> DiagnosticCode {
+        match &self.details {
+            Details::ParserLimit { .. } => DiagnosticCode::ParserLimit,
+            Details::SyntaxError { kind, .. } => match kind {
+                SyntaxErrorKind::UnexpectedCharacter => DiagnosticCode::UnexpectedCharacter,
+                SyntaxErrorKind::UnterminatedString => DiagnosticCode::UnterminatedString,
+                SyntaxErrorKind::UnexpectedLineTerminator => {
+                    DiagnosticCode::UnexpectedLineTerminator
+                }
+                SyntaxErrorKind::InvalidEscapeSequence => DiagnosticCode::InvalidEscapeSequence,
+                SyntaxErrorKind::InvalidNumber => DiagnosticCode::InvalidNumber,
+                SyntaxErrorKind::UnterminatedSpread => DiagnosticCode::UnterminatedSpread,
+                SyntaxErrorKind::ExpectedToken { .. } => DiagnosticCode::ExpectedToken,
+                SyntaxErrorKind::UnexpectedToken { .. } => DiagnosticCode::UnexpectedToken,
+                SyntaxErrorKind::UnexpectedEof => DiagnosticCode::UnexpectedEof,
+                SyntaxErrorKind::LimitExceeded => DiagnosticCode::ParserLimit,
+                _ => DiagnosticCode::SyntaxError,
+            },
+            Details::SchemaBuildError(error) => match error {
+                SchemaBuildError::ExecutableDefinition { .. } => {
+                    DiagnosticCode::ExecutableDefinition
+                }
+                SchemaBuildError::SchemaDefinitionCollision { .. } => {
+                    DiagnosticCode::SchemaDefinitionCollision
+                }
+                SchemaBuildError::DirectiveDefinitionCollision { .. } => {
+                    DiagnosticCode::DirectiveDefinitionCollision
+                }
+                SchemaBuildError::TypeDefinitionCollision { .. } => {
+                    DiagnosticCode::TypeDefinitionCollision
+                }
+                SchemaBuildError::BuiltInScalarTypeRedefinition => {
+                    DiagnosticCode::BuiltInScalarTypeRedefinition
+                }
+                SchemaBuildError::OrphanSchemaExtension => DiagnosticCode::OrphanSchemaExtension,
+                SchemaBuildError::OrphanTypeExtension { .. } => DiagnosticCode::OrphanTypeExtension,
+                SchemaBuildError::TypeExtensionKindMismatch { .. } => {
+                    DiagnosticCode::TypeExtensionKindMismatch
+                }
+                SchemaBuildError::DuplicateRootOperation { .. } => {
+                    DiagnosticCode::DuplicateRootOperation
+                }
+                SchemaBuildError::DuplicateImplementsInterfaceInObject { .. } => {
+                    DiagnosticCode::DuplicateImplementsInterfaceInObject
+                }
+                SchemaBuildError::DuplicateImplementsInterfaceInInterface { .. } => {
+                    DiagnosticCode::DuplicateImplementsInterfaceInInterface
+                }
+                SchemaBuildError::ObjectFieldNameCollision { .. } => {
+                    DiagnosticCode::ObjectFieldNameCollision
+                }
+                SchemaBuildError::InterfaceFieldNameCollision { .. } => {
+                    DiagnosticCode::InterfaceFieldNameCollision
+                }
+                SchemaBuildError::EnumValueNameCollision { .. } => {
+                    DiagnosticCode::EnumValueNameCollision
+                }
+                SchemaBuildError::UnionMemberNameCollision { .. } => {
+                    DiagnosticCode::UnionMemberNameCollision
+                }
+                SchemaBuildError::InputFieldNameCollision { .. } => {
+                    DiagnosticCode::InputFieldNameCollision
+                }
+            },
+            Details::ExecutableBuildError(error) => match error {
+                ExecutableBuildError::TypeSystemDefinition { .. } => {
+                    DiagnosticCode::TypeSystemDefinition
+                }
+                ExecutableBuildError::AmbiguousAnonymousOperation => {
+                    DiagnosticCode::AmbiguousAnonymousOperation
+                }
+                ExecutableBuildError::OperationNameCollision { .. } => {
+                    DiagnosticCode::OperationNameCollision
+                }
+                ExecutableBuildError::FragmentNameCollision { .. } => {
+                    DiagnosticCode::FragmentNameCollision
+                }
+                ExecutableBuildError::UndefinedRootOperation { .. } => {
+                    DiagnosticCode::UndefinedRootOperation
+                }
+                ExecutableBuildError::UndefinedTypeInNamedFragmentTypeCondition { .. } => {
+                    DiagnosticCode::UndefinedTypeInNamedFragmentTypeCondition
+                }
+                ExecutableBuildError::UndefinedTypeInInlineFragmentTypeCondition { .. } => {
+                    DiagnosticCode::UndefinedTypeInInlineFragmentTypeCondition
+                }
+                ExecutableBuildError::SubselectionOnScalarType { .. } => {
+                    DiagnosticCode::SubselectionOnScalarType
+                }
+                ExecutableBuildError::SubselectionOnEnumType { .. } => {
+                    DiagnosticCode::SubselectionOnEnumType
+                }
+                ExecutableBuildError::UndefinedField { .. } => DiagnosticCode::UndefinedField,
+                ExecutableBuildError::SubscriptionUsesMultipleFields { .. } => {
+                    DiagnosticCode::SubscriptionUsesMultipleFields
+                }
+                ExecutableBuildError::SubscriptionUsesIntrospection { .. } => {
+                    DiagnosticCode::SubscriptionUsesIntrospection
+                }
+                ExecutableBuildError::SubscriptionUsesConditionalSelection { .. } => {
+                    DiagnosticCode::SubscriptionUsesConditionalSelection
+                }
+                ExecutableBuildError::ConflictingFieldType(_) => {
+                    DiagnosticCode::ConflictingFieldType
+                }
+                ExecutableBuildError::ConflictingFieldArgument(_) => {
+                    DiagnosticCode::ConflictingFieldArgument
+                }
+                ExecutableBuildError::ConflictingFieldName(_) => {
+                    DiagnosticCode::ConflictingFieldName
+                }
+            },
+            Details::CompilerDiagnostic(diagnostic) => {
+                use diagnostics::DiagnosticData::*;
+                match diagnostic {
+                    UniqueVariable { .. } => DiagnosticCode::UniqueVariable,
+                    UniqueArgument { .. } => DiagnosticCode::UniqueArgument,
+                    UniqueInputValue { .. } => DiagnosticCode::UniqueInputValue,
+                    UndefinedArgument { .. } => DiagnosticCode::UndefinedArgument,
+                    UndefinedDefinition { .. } => DiagnosticCode::UndefinedDefinition,
+                    UndefinedDirective { .. } => DiagnosticCode::UndefinedDirective,
+                    UndefinedVariable { .. } => DiagnosticCode::UndefinedVariable,
+                    UndefinedFragment { .. } => DiagnosticCode::UndefinedFragment,
+                    UndefinedEnumValue { .. } => DiagnosticCode::UndefinedEnumValue,
+                    UndefinedInputValue { .. } => DiagnosticCode::UndefinedInputValue,
+                    MissingInterfaceField { .. } => DiagnosticCode::MissingInterfaceField,
+                    RequiredArgument { .. } => DiagnosticCode::RequiredArgument,
+                    RequiredField { .. } => DiagnosticCode::RequiredField,
+                    TransitiveImplementedInterfaces { .. } => {
+                        DiagnosticCode::TransitiveImplementedInterfaces
+                    }
+                    OutputType { .. } => DiagnosticCode::OutputType,
+                    InputType { .. } => DiagnosticCode::InputType,
+                    VariableInputType { .. } => DiagnosticCode::VariableInputType,
+                    QueryRootOperationType => DiagnosticCode::QueryRootOperationType,
+                    UnusedVariable { .. } => DiagnosticCode::UnusedVariable,
+                    RootOperationObjectType { .. } => DiagnosticCode::RootOperationObjectType,
+                    UnionMemberObjectType { .. } => DiagnosticCode::UnionMemberObjectType,
+                    UnsupportedLocation { .. } => DiagnosticCode::UnsupportedLocation,
+                    UnsupportedValueType { .. } => DiagnosticCode::UnsupportedValueType,
+                    IntCoercionError { .. } => DiagnosticCode::IntCoercionError,
+                    FloatCoercionError { .. } => DiagnosticCode::FloatCoercionError,
+                    UniqueDirective { .. } => DiagnosticCode::UniqueDirective,
+                    MissingSubselection { .. } => DiagnosticCode::MissingSubselection,
+                    InvalidFragmentTarget { .. } => DiagnosticCode::InvalidFragmentTarget,
+                    InvalidFragmentSpread { .. } => DiagnosticCode::InvalidFragmentSpread,
+                    UnusedFragment { .. } => DiagnosticCode::UnusedFragment,
+                    DisallowedVariableUsage { .. } => DiagnosticCode::DisallowedVariableUsage,
+                    RecursiveDirectiveDefinition { .. } => {
+                        DiagnosticCode::RecursiveDirectiveDefinition
+                    }
+                    RecursiveInterfaceDefinition { .. } => {
+                        DiagnosticCode::RecursiveInterfaceDefinition
+                    }
+                    RecursiveInputObjectDefinition { .. } => {
+                        DiagnosticCode::RecursiveInputObjectDefinition
+                    }
+                    RecursiveFragmentDefinition { .. } => {
+                        DiagnosticCode::RecursiveFragmentDefinition
+                    }
+                    DeeplyNestedType { .. } => DiagnosticCode::DeeplyNestedType,
+                    RecursionError { .. } => DiagnosticCode::RecursionError,
+                    EmptyFieldSet { .. } => DiagnosticCode::EmptyFieldSet,
+                    EmptyValueSet { .. } => DiagnosticCode::EmptyValueSet,
+                    EmptyMemberSet { .. } => DiagnosticCode::EmptyMemberSet,
+                    EmptyInputValueSet { .. } => DiagnosticCode::EmptyInputValueSet,
+                    ReservedName { .. } => DiagnosticCode::ReservedName,
+                    OneOfInputObjectNonNullField { .. } => {
+                        DiagnosticCode::OneOfInputObjectNonNullField
+                    }
+                    OneOfInputObjectFieldDefaultValue { .. } => {
+                        DiagnosticCode::OneOfInputObjectFieldDefaultValue
+                    }
+                    OneOfInputObjectFieldCount { .. } => DiagnosticCode::OneOfInputObjectFieldCount,
+                    OneOfInputObjectNullField { .. } => DiagnosticCode::OneOfInputObjectNullField,
+                    OneOfInputObjectNullableVariable { .. } => {
+                        DiagnosticCode::OneOfInputObjectNullableVariable
+                    }
+                }
+            }
+            Details::RecursionLimitError => DiagnosticCode::RecursionLimitError,
+            Details::Lint { lint, .. } => lint.code(),
+            Details::Custom { .. } => DiagnosticCode::CustomRule,
+        }
+    }
+
+    /// Returns how serious this diagnostic is.
+    ///
+    /// Validation errors are always [`Severity::Error`].
+    /// The severity of lints depends on the configured [`LintLevel`][super::lint::LintLevel].
+    pub fn severity(&self) -> Severity {
+        match &self.details {
+            Details::Lint { severity, .. } => *severity,
+            _ => Severity::Error,
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use crate::ast;
+use crate::coordinate::DirectiveArgumentCoordinate;
+use crate::coordinate::FieldArgumentCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::executable;
+use crate::parser::SourceSpan;
+use crate::schema;
+use crate::schema::Component;
+use crate::validation::value::ValueDefinitions;
+use crate::validation::DiagnosticList;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+use std::fmt;
+
+/// A use in an executable document of a schema element marked `@deprecated`.
+///
+/// Returned by [`ExecutableDocument::deprecated_usages`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DeprecatedUsage {
+    /// What kind of schema element is used
+    pub kind: DeprecatedUsageKind,
+    /// The deprecated schema element, such as `Query.oldField` or `Query.field(oldArgument:)`
+    pub coordinate: SchemaCoordinate,
+    /// The `reason` argument of `@deprecated`, or its default value `"No longer supported"`
+    pub reason: Option<String>,
+    /// Where the element is used in the executable document
+    pub location: Option<SourceSpan>,
+    /// Where the `@deprecated` directive is applied in the schema
+    pub deprecated_location: Option<SourceSpan>,
+}
+
+/// The kind of schema element in a [`DeprecatedUsage`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DeprecatedUsageKind {
+    /// A field selection
+    Field,
+    /// An argument of a field or directive
+    Argument,
+    /// A field of an input object value
+    InputField,
+    /// An enum value literal
+    EnumValue,
+}
+
+impl DeprecatedUsageKind {
+    /// Returns a description of the kind, such as `"enum value"`.
+    pub fn describe(self) -> &'static str {
+        match self {
+            Self::Field => "field",
+            Self::Argument => "argument",
+            Self::InputField => "input field",
+            Self::EnumValue => "enum value",
+        }
+    }
+}
+
+impl fmt::Display for DeprecatedUsageKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.describe())
+    }
+}
+
+/// Collects deprecated usages, resolving definitions with field and value validation code.
+struct DeprecatedUsages<'a> {
+    schema: &'a Schema,
+    usages: Vec<DeprecatedUsage>,
+}
+
+impl ValueDefinitions for DeprecatedUsages<'_> {
+    fn enum_value(
+        &mut self,
+        enum_: &Node<schema::EnumType>,
+        value: &Node<ast::Value>,
+        definition: &Component<ast::EnumValueDefinition>,
+    ) {
+        self.check(
+            DeprecatedUsageKind::EnumValue,
+            TypeAttributeCoordinate {
+                ty: enum_.name.clone(),
+                attribute: definition.value.clone(),
+            },
+            &definition.directives,
+            value.location(),
+        )
+    }
+
+    fn input_field(
+        &mut self,
+        input_object: &Node<schema::InputObjectType>,
+        name: &Name,
+        definition: &Component<ast::InputValueDefinition>,
+    ) {
+        self.check(
+            DeprecatedUsageKind::InputField,
+            TypeAttributeCoordinate {
+                ty: input_object.name.clone(),
+                attribute: definition.name.clone(),
+            },
+            &definition.directives,
+            name.location(),
+        )
+    }
+}
+
+impl DeprecatedUsages<'_> {
+    fn check(
+        &mut self,
+        kind: DeprecatedUsageKind,
+        coordinate: impl Into<SchemaCoordinate>,
+        directives: &ast::DirectiveList,
+        location: Option<SourceSpan>,
+    ) {
+        let Some(deprecated) = directives.get("deprecated") else {
+            return;
+        };
+        let reason = deprecated
+            .argument_by_name("reason", self.schema)
+            .ok()
+            .and_then(|reason| reason.as_str())
+            .map(ToOwned::to_owned);
+        self.usages.push(DeprecatedUsage {
+            kind,
+            coordinate: coordinate.into(),
+            reason,
+            location,
+            deprecated_location: deprecated.location(),
+        })
+    }
+
+    fn directives(&mut self, directives: &ast::DirectiveList) {
+        for directive in directives {
+            let Some(definition) = self.schema.directive_definitions.get(&directive.name) else {
+                continue;
+            };
+            for argument in &directive.arguments {
+                let Some(argument_definition) = definition.argument_by_name(&argument.name) else {
+                    continue;
+                };
+                self.check(
+                    DeprecatedUsageKind::Argument,
+                    DirectiveArgumentCoordinate {
+                        directive: directive.name.clone(),
+                        argument: argument.name.clone(),
+                    },
+                    &argument_definition.directives,
+                    argument.location(),
+                );
+                self.value(&argument_definition.ty, &argument.value);
+            }
+        }
+    }
+
+    fn value(&mut self, ty: &Node<ast::Type>, value: &Node<ast::Value>) {
+        // Type errors are reported by validation, only the resolved definitions matter here
+        let mut type_errors = DiagnosticList::new(Default::default());
+        let schema = self.schema;
+        super::value::check_value(&mut type_errors, schema, ty, value, &[], self)
+    }
+
+    fn selection_set(&mut self, selection_set: &executable::SelectionSet) {
+        for selection in &selection_set.selections {
+            match selection {
+                executable::Selection::Field(field) => self.field(&selection_set.ty, field),
+                executable::Selection::FragmentSpread(spread) => {
+                    self.directives(&spread.directives)
+                }
+                executable::Selection::InlineFragment(inline) => {
+                    self.directives(&inline.directives);
+                    self.selection_set(&inline.selection_set)
+                }
+            }
+        }
+    }
+
+    fn field(&mut self, parent_type: &ast::NamedType, field: &Node<executable::Field>) {
+        if let Ok(definition) = self.schema.type_field(parent_type, &field.name) {
+            self.check(
+                DeprecatedUsageKind::Field,
+                TypeAttributeCoordinate {
+                    ty: parent_type.clone(),
+                    attribute: field.name.clone(),
+                },
+                &definition.directives,
+                field.location(),
+            );
+            for (argument, argument_definition) in
+                super::field::argument_definitions(definition, field)
+            {
+                let Some(argument_definition) = argument_definition else {
+                    continue;
+                };
+                self.check(
+                    DeprecatedUsageKind::Argument,
+                    FieldArgumentCoordinate {
+                        ty: parent_type.clone(),
+                        field: field.name.clone(),
+                        argument: argument.name.clone(),
+                    },
+                    &argument_definition.directives,
+                    argument.location(),
+                );
+                self.value(&argument_definition.ty, &argument.value);
+            }
+        }
+        self.directives(&field.directives);
+        self.selection_set(&field.selection_set)
+    }
+}
+
+pub(crate) fn find_deprecated_usages(
+    schema: &Schema,
+    document: &ExecutableDocument,
+) -> Vec<DeprecatedUsage> {
+    let mut finder = DeprecatedUsages {
+        schema,
+        usages: Vec::new(),
+    };
+    for operation in document.operations.iter() {
+        for variable in &operation.variables {
+            finder.directives(&variable.directives);
+            if let Some(default_value) = &variable.default_value {
+                finder.value(&variable.ty, default_value)
+            }
+        }
+        finder.directives(&operation.directives);
+        finder.selection_set(&operation.selection_set)
+    }
+    for fragment in document.fragments.values() {
+        finder.directives(&fragment.directives);
+        finder.selection_set(&fragment.selection_set)
+    }
+    finder.usages
+}
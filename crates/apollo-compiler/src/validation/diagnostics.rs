@@ -292,6 +292,32 @@ pub(crate) enum DiagnosticData {
         "{describe} cannot be named `{name}` as names starting with two underscores are reserved"
     )]
     ReservedName { name: Name, describe: &'static str },
+    #[error("field `{coordinate}` of a OneOf input object must be nullable")]
+    OneOfInputObjectNonNullField {
+        coordinate: TypeAttributeCoordinate,
+        ty: Node<Type>,
+    },
+    #[error("field `{coordinate}` of a OneOf input object cannot have a default value")]
+    OneOfInputObjectFieldDefaultValue {
+        coordinate: TypeAttributeCoordinate,
+        default_value_location: Option<SourceSpan>,
+    },
+    #[error("OneOf input object `{name}` must specify exactly one field")]
+    OneOfInputObjectFieldCount {
+        name: Name,
+        definition_location: Option<SourceSpan>,
+    },
+    #[error("field `{coordinate}` of a OneOf input object must not be null")]
+    OneOfInputObjectNullField { coordinate: TypeAttributeCoordinate },
+    #[error(
+        "variable `${variable}` must be non-nullable to be used for field `{coordinate}` of a OneOf input object"
+    )]
+    OneOfInputObjectNullableVariable {
+        variable: Name,
+        variable_type: Node<Type>,
+        variable_location: Option<SourceSpan>,
+        coordinate: TypeAttributeCoordinate,
+    },
 }
 
 impl DiagnosticData {
@@ -721,6 +747,49 @@ impl DiagnosticData {
             DiagnosticData::ReservedName { name, .. } => {
                 report.with_label_opt(name.location(), "Pick a different name here");
             }
+            DiagnosticData::OneOfInputObjectNonNullField { ty, .. } => {
+                report.with_label_opt(
+                    ty.location(),
+                    format_args!("remove the `!` to make `{ty}` nullable"),
+                );
+            }
+            DiagnosticData::OneOfInputObjectFieldDefaultValue {
+                default_value_location,
+                ..
+            } => {
+                report.with_label_opt(main_location, "field of a OneOf input object");
+                report.with_label_opt(*default_value_location, "default value defined here");
+            }
+            DiagnosticData::OneOfInputObjectFieldCount {
+                name,
+                definition_location,
+            } => {
+                report.with_label_opt(
+                    main_location,
+                    format_args!("exactly one field of `{name}` must be provided"),
+                );
+                report.with_label_opt(*definition_location, "input object defined here");
+            }
+            DiagnosticData::OneOfInputObjectNullField { .. } => {
+                report.with_label_opt(main_location, "null value provided here");
+            }
+            DiagnosticData::OneOfInputObjectNullableVariable {
+                variable,
+                variable_type,
+                variable_location,
+                ..
+            } => {
+                report.with_label_opt(
+                    *variable_location,
+                    format_args!(
+                        "variable `${variable}` of type `{variable_type}` is declared here"
+                    ),
+                );
+                report.with_label_opt(
+                    main_location,
+                    format_args!("variable `${variable}` used here"),
+                );
+            }
         }
     }
 
@@ -7,6 +7,7 @@ use crate::coordinate::DirectiveCoordinate;
 use crate::schema;
 use crate::schema::validation::BuiltInScalars;
 use crate::validation::diagnostics::DiagnosticData;
+use crate::validation::DiagnosticCode;
 use crate::validation::DiagnosticList;
 use crate::validation::RecursionGuard;
 use crate::validation::RecursionStack;
@@ -159,6 +160,12 @@ pub(crate) fn validate_directive_definition(
     // references itself directly.
     //
     // Returns Recursive Definition error.
+    if !diagnostics.should_check(&[
+        DiagnosticCode::RecursiveDirectiveDefinition,
+        DiagnosticCode::DeeplyNestedType,
+    ]) {
+        return;
+    }
     match FindRecursiveDirective::check(schema, def) {
         Ok(_) => {}
         Err(CycleError::Recursed(trace)) => {
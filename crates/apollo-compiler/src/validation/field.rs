@@ -41,11 +41,7 @@ pub(crate) fn validate_field(
     };
 
     if let Ok(field_definition) = schema.type_field(against_type, &field.name) {
-        for argument in &field.arguments {
-            let arg_definition = field_definition
-                .arguments
-                .iter()
-                .find(|val| val.name == argument.name);
+        for (argument, arg_definition) in argument_definitions(field_definition, field) {
             if let Some(arg_definition) = arg_definition {
                 if super::variable::validate_variable_usage(
                     diagnostics,
@@ -131,6 +127,25 @@ pub(crate) fn validate_field(
     }
 }
 
+/// Pairs each argument of a field selection with its definition, if there is one
+pub(crate) fn argument_definitions<'a>(
+    field_definition: &'a ast::FieldDefinition,
+    field: &'a executable::Field,
+) -> impl Iterator<
+    Item = (
+        &'a Node<ast::Argument>,
+        Option<&'a Node<ast::InputValueDefinition>>,
+    ),
+> {
+    field.arguments.iter().map(|argument| {
+        let definition = field_definition
+            .arguments
+            .iter()
+            .find(|val| val.name == argument.name);
+        (argument, definition)
+    })
+}
+
 pub(crate) fn validate_field_definition(
     diagnostics: &mut DiagnosticList,
     schema: &crate::Schema,
@@ -9,6 +9,7 @@ use crate::schema::Implementers;
 use crate::validation::diagnostics::DiagnosticData;
 use crate::validation::variable::walk_selections_with_deduped_fragments;
 use crate::validation::CycleError;
+use crate::validation::DiagnosticCode;
 use crate::validation::DiagnosticList;
 use crate::validation::OperationValidationContext;
 use crate::validation::RecursionGuard;
@@ -23,7 +24,7 @@ use std::borrow::Cow;
 /// Given a type definition, find all the type names that can be used for fragment spreading.
 ///
 /// Spec: https://spec.graphql.org/October2021/#GetPossibleTypes()
-fn get_possible_types<'a>(
+pub(crate) fn get_possible_types<'a>(
     type_definition: &schema::ExtendedType,
     implementers_map: &'a HashMap<Name, Implementers>,
 ) -> Cow<'a, IndexSet<NamedType>> {
@@ -242,7 +243,12 @@ pub(crate) fn validate_fragment_definition(
     let has_type_error = diagnostics.len() > previous;
 
     let previous = diagnostics.len();
-    validate_fragment_cycles(diagnostics, document, fragment);
+    if diagnostics.should_check(&[
+        DiagnosticCode::RecursiveFragmentDefinition,
+        DiagnosticCode::DeeplyNestedType,
+    ]) {
+        validate_fragment_cycles(diagnostics, document, fragment);
+    }
     let has_cycles = diagnostics.len() > previous;
 
     if !has_type_error && !has_cycles {
@@ -1,9 +1,11 @@
 use crate::ast;
 use crate::collections::HashMap;
+use crate::coordinate::TypeAttributeCoordinate;
 use crate::schema::validation::BuiltInScalars;
 use crate::schema::InputObjectType;
 use crate::validation::diagnostics::DiagnosticData;
 use crate::validation::CycleError;
+use crate::validation::DiagnosticCode;
 use crate::validation::DiagnosticList;
 use crate::validation::RecursionGuard;
 use crate::validation::RecursionStack;
@@ -80,23 +82,28 @@ pub(crate) fn validate_input_object_definition(
         Default::default(),
     );
 
-    match FindRecursiveInputValue::check(schema, input_object) {
-        Ok(_) => {}
-        Err(CycleError::Recursed(trace)) => diagnostics.push(
-            input_object.location(),
-            DiagnosticData::RecursiveInputObjectDefinition {
-                name: input_object.name.clone(),
-                trace,
-            },
-        ),
-        Err(CycleError::Limit(_)) => {
-            diagnostics.push(
+    if diagnostics.should_check(&[
+        DiagnosticCode::RecursiveInputObjectDefinition,
+        DiagnosticCode::DeeplyNestedType,
+    ]) {
+        match FindRecursiveInputValue::check(schema, input_object) {
+            Ok(_) => {}
+            Err(CycleError::Recursed(trace)) => diagnostics.push(
                 input_object.location(),
-                DiagnosticData::DeeplyNestedType {
+                DiagnosticData::RecursiveInputObjectDefinition {
                     name: input_object.name.clone(),
-                    describe_type: "input object",
+                    trace,
                 },
-            );
+            ),
+            Err(CycleError::Limit(_)) => {
+                diagnostics.push(
+                    input_object.location(),
+                    DiagnosticData::DeeplyNestedType {
+                        name: input_object.name.clone(),
+                        describe_type: "input object",
+                    },
+                );
+            }
         }
     }
 
@@ -117,6 +124,35 @@ pub(crate) fn validate_input_object_definition(
         "an input object field",
     );
 
+    // Fields of a OneOf input object must be nullable and not have default values
+    // https://spec.graphql.org/draft/#sec-Input-Objects.Type-Validation
+    if input_object.is_one_of() {
+        for field in &fields {
+            let coordinate = TypeAttributeCoordinate {
+                ty: input_object.name.clone(),
+                attribute: field.name.clone(),
+            };
+            if field.ty.is_non_null() {
+                diagnostics.push(
+                    field.location(),
+                    DiagnosticData::OneOfInputObjectNonNullField {
+                        coordinate: coordinate.clone(),
+                        ty: field.ty.clone(),
+                    },
+                );
+            }
+            if let Some(default_value) = &field.default_value {
+                diagnostics.push(
+                    field.location(),
+                    DiagnosticData::OneOfInputObjectFieldDefaultValue {
+                        coordinate,
+                        default_value_location: default_value.location(),
+                    },
+                );
+            }
+        }
+    }
+
     // validate there is at least one input value on the input object type
     // https://spec.graphql.org/draft/#sel-HAHhBXDBABAB5BvgD
     if input_object.fields.is_empty() {
@@ -0,0 +1,141 @@
+use super::DiagnosticData;
+use super::DiagnosticList;
+use super::Severity;
+use crate::diagnostic::Color;
+use crate::diagnostic::Diagnostic;
+use crate::diagnostic::ToCliReport;
+use crate::parser::LineColumn;
+use crate::parser::SourceMap;
+use crate::parser::SourceSpan;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// A diagnostic in a stable JSON format, for tools that process diagnostics
+/// without parsing human-readable text.
+///
+/// Unlike [`Diagnostic::to_json`], which follows the shape of GraphQL response errors,
+/// this includes the [code][super::DiagnosticCode] and [severity][Severity] of the diagnostic,
+/// and all locations it refers to.
+/// Fields may be added in future versions, but existing fields keep their meaning.
+///
+/// Returned by [`DiagnosticList::to_json_diagnostics`].
+///
+/// ```json
+/// {
+///   "code": "GQL0301",
+///   "name": "UniqueVariable",
+///   "severity": "error",
+///   "message": "the variable `$a` is declared multiple times",
+///   "location": {
+///     "file": "query.graphql",
+///     "start": { "line": 1, "column": 16 },
+///     "end": { "line": 1, "column": 23 },
+///     "offset": 15,
+///     "length": 7
+///   },
+///   "labels": [
+///     {
+///       "location": {
+///         "file": "query.graphql",
+///         "start": { "line": 1, "column": 7 },
+///         "end": { "line": 1, "column": 14 },
+///         "offset": 6,
+///         "length": 7
+///       },
+///       "message": "previous definition of `$a` here"
+///     }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonDiagnostic {
+    /// The stable [code][super::DiagnosticCode] of this diagnostic, such as `"GQL0301"`
+    pub code: String,
+    /// The name of the code, such as `"UniqueVariable"`
+    pub name: String,
+    pub severity: Severity,
+    /// The human-readable message, which can change between versions
+    pub message: String,
+    /// The main location of the diagnostic
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<JsonLocation>,
+    /// Messages attached to the main location and to related locations
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<JsonLabel>,
+    /// A suggestion on how to fix the problem
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help: Option<String>,
+}
+
+/// A source location in a [`JsonDiagnostic`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonLocation {
+    /// The path of the source file, as given when parsing it
+    pub file: String,
+    pub start: LineColumn,
+    pub end: LineColumn,
+    /// The offset of the start of the location in the source file, in UTF-8 bytes
+    pub offset: usize,
+    /// The length of the location, in UTF-8 bytes
+    pub length: usize,
+}
+
+/// A message attached to a source location in a [`JsonDiagnostic`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonLabel {
+    pub location: JsonLocation,
+    pub message: String,
+}
+
+impl JsonLocation {
+    fn new(location: SourceSpan, sources: &SourceMap) -> Option<Self> {
+        let file = sources.get(&location.file_id())?;
+        let range = location.line_column_range(sources)?;
+        Some(Self {
+            file: file.path().display().to_string(),
+            start: range.start,
+            end: range.end,
+            offset: location.offset(),
+            length: location.node_len(),
+        })
+    }
+}
+
+impl Diagnostic<'_, DiagnosticData> {
+    /// Convert this diagnostic to a stable JSON format.
+    pub fn to_json_diagnostic(&self) -> JsonDiagnostic {
+        let report = self.to_report(Color::Never);
+        let code = self.error.code();
+        JsonDiagnostic {
+            code: code.to_string(),
+            name: code.name().to_owned(),
+            severity: self.error.severity(),
+            message: self.error.to_string(),
+            location: self
+                .error
+                .location()
+                .and_then(|location| JsonLocation::new(location, self.sources)),
+            labels: report
+                .labels
+                .into_iter()
+                .filter_map(|(location, message)| {
+                    Some(JsonLabel {
+                        location: JsonLocation::new(location, self.sources)?,
+                        message,
+                    })
+                })
+                .collect(),
+            help: report.help,
+        }
+    }
+}
+
+impl DiagnosticList {
+    /// Convert all diagnostics to a stable JSON format.
+    /// The returned list can be serialized with [`serde`].
+    pub fn to_json_diagnostics(&self) -> Vec<JsonDiagnostic> {
+        self.iter()
+            .map(|diagnostic| diagnostic.to_json_diagnostic())
+            .collect()
+    }
+}
@@ -0,0 +1,629 @@
+//! Lints report problems in schemas and executable documents that are valid,
+//! but likely unintended or against common conventions.
+//!
+//! Each [`LintRule`] has a [`LintLevel`] configured in a [`LintConfig`]:
+//! allowed lints are not checked, other lints are reported as warnings or as errors.
+//! Lints are not part of validation: call [`Schema::lint`] or [`ExecutableDocument::lint`]
+//! to get a [`DiagnosticList`] that can be printed like validation errors.
+//!
+//! ```
+//! use apollo_compiler::validation::lint::LintConfig;
+//! use apollo_compiler::validation::lint::LintRule;
+//! use apollo_compiler::Schema;
+//!
+//! let input = r#"
+//!   type Query { user: User }
+//!   type User { first_name: String }
+//!   type Unused { id: ID }
+//! "#;
+//! let schema = Schema::parse_and_validate(input, "schema.graphql").unwrap();
+//!
+//! let config = LintConfig::new().deny(LintRule::NamingConvention);
+//! let diagnostics = schema.lint(&config);
+//! assert_eq!(diagnostics.len(), 2);
+//! assert!(diagnostics.has_errors());
+//! ```
+//!
+//! [`Schema::lint`]: crate::Schema::lint
+//! [`ExecutableDocument::lint`]: crate::ExecutableDocument::lint
+
+use super::DeprecatedUsage;
+use super::DeprecatedUsageKind;
+use super::Details;
+use super::DiagnosticCode;
+use super::DiagnosticList;
+use super::Severity;
+use crate::ast::Type;
+use crate::collections::HashMap;
+use crate::collections::HashSet;
+use crate::coordinate::DirectiveCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::coordinate::TypeCoordinate;
+use crate::diagnostic::CliReport;
+use crate::parser::SourceSpan;
+use crate::schema::ExtendedType;
+use crate::schema::FieldDefinition;
+use crate::schema::InputValueDefinition;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+use std::fmt;
+
+/// How a [`LintRule`] is reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintLevel {
+    /// Do not check this rule.
+    Allow,
+    /// Report with [`Severity::Warning`].
+    Warn,
+    /// Report with [`Severity::Error`].
+    Deny,
+}
+
+/// A lint that can be configured in a [`LintConfig`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum LintRule {
+    /// An executable document uses a field, argument, input field or enum value
+    /// marked `@deprecated`.
+    /// See [`ExecutableDocument::deprecated_usages`][crate::ExecutableDocument::deprecated_usages].
+    /// Warns by default.
+    DeprecatedUsage,
+    /// A schema type that cannot be reached from a root operation type or directive argument.
+    /// Warns by default.
+    UnusedType,
+    /// A schema definition, field, argument or enum value without a description.
+    /// Allowed by default.
+    MissingDescription,
+    /// A name that does not follow common conventions:
+    /// `PascalCase` types, `camelCase` fields, arguments and directives,
+    /// and `SCREAMING_SNAKE_CASE` enum values.
+    /// Warns by default.
+    NamingConvention,
+    /// A field whose type is a nullable list of nullable items, such as `[String]`.
+    /// Allowed by default.
+    NullableListItems,
+}
+
+/// The level of each [`LintRule`], for [`Schema::lint`] and [`ExecutableDocument::lint`].
+///
+/// Rules that are not configured use their [default level][LintRule::default_level].
+#[derive(Debug, Clone, Default)]
+pub struct LintConfig {
+    levels: HashMap<LintRule, LintLevel>,
+}
+
+impl LintRule {
+    /// All lint rules.
+    pub const ALL: &'static [Self] = &[
+        Self::DeprecatedUsage,
+        Self::UnusedType,
+        Self::MissingDescription,
+        Self::NamingConvention,
+        Self::NullableListItems,
+    ];
+
+    /// Returns the diagnostic code of lints reported for this rule.
+    pub fn code(self) -> DiagnosticCode {
+        match self {
+            Self::DeprecatedUsage => DiagnosticCode::DeprecatedUsage,
+            Self::UnusedType => DiagnosticCode::UnusedType,
+            Self::MissingDescription => DiagnosticCode::MissingDescription,
+            Self::NamingConvention => DiagnosticCode::NamingConvention,
+            Self::NullableListItems => DiagnosticCode::NullableListItems,
+        }
+    }
+
+    /// Returns the name of this rule, such as `"UnusedType"`.
+    pub fn name(self) -> &'static str {
+        self.code().name()
+    }
+
+    /// Returns the rule with the given [name][Self::name], such as from a configuration file.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|rule| rule.name() == name)
+    }
+
+    /// Returns the level used when a [`LintConfig`] does not configure this rule.
+    pub fn default_level(self) -> LintLevel {
+        match self {
+            Self::DeprecatedUsage => LintLevel::Warn,
+            Self::UnusedType => LintLevel::Warn,
+            Self::MissingDescription => LintLevel::Allow,
+            Self::NamingConvention => LintLevel::Warn,
+            Self::NullableListItems => LintLevel::Allow,
+        }
+    }
+}
+
+impl fmt::Display for LintRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl LintConfig {
+    /// Returns a configuration with the default level for every rule.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the level of a rule.
+    pub fn level(mut self, rule: LintRule, level: LintLevel) -> Self {
+        self.levels.insert(rule, level);
+        self
+    }
+
+    /// Do not check a rule.
+    pub fn allow(self, rule: LintRule) -> Self {
+        self.level(rule, LintLevel::Allow)
+    }
+
+    /// Report a rule as a warning.
+    pub fn warn(self, rule: LintRule) -> Self {
+        self.level(rule, LintLevel::Warn)
+    }
+
+    /// Report a rule as an error.
+    pub fn deny(self, rule: LintRule) -> Self {
+        self.level(rule, LintLevel::Deny)
+    }
+
+    /// Returns the configured level of a rule, or its default level.
+    pub fn level_of(&self, rule: LintRule) -> LintLevel {
+        self.levels
+            .get(&rule)
+            .copied()
+            .unwrap_or_else(|| rule.default_level())
+    }
+}
+
+/// Structured data about a lint.
+#[derive(Debug, thiserror::Error, Clone, Hash, PartialEq, Eq)]
+pub(crate) enum LintDiagnostic {
+    #[error("the {} `{}` is deprecated", .0.kind, .0.coordinate)]
+    DeprecatedUsage(DeprecatedUsage),
+    #[error("the type `{name}` is never used")]
+    UnusedType { name: Name },
+    #[error("{describe} `{coordinate}` does not have a description")]
+    MissingDescription {
+        describe: &'static str,
+        coordinate: SchemaCoordinate,
+    },
+    #[error("{describe} `{coordinate}` should be {case}")]
+    NamingConvention {
+        describe: &'static str,
+        coordinate: SchemaCoordinate,
+        name: Name,
+        case: Case,
+    },
+    #[error("the field `{coordinate}` has a nullable list of nullable items type `{ty}`")]
+    NullableListItems {
+        coordinate: TypeAttributeCoordinate,
+        ty: Type,
+    },
+}
+
+/// A naming convention
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub(crate) enum Case {
+    Pascal,
+    Camel,
+    ScreamingSnake,
+}
+
+impl LintDiagnostic {
+    fn rule(&self) -> LintRule {
+        match self {
+            Self::DeprecatedUsage(_) => LintRule::DeprecatedUsage,
+            Self::UnusedType { .. } => LintRule::UnusedType,
+            Self::MissingDescription { .. } => LintRule::MissingDescription,
+            Self::NamingConvention { .. } => LintRule::NamingConvention,
+            Self::NullableListItems { .. } => LintRule::NullableListItems,
+        }
+    }
+
+    pub(crate) fn code(&self) -> DiagnosticCode {
+        self.rule().code()
+    }
+
+    pub(crate) fn report(&self, main_location: Option<SourceSpan>, report: &mut CliReport) {
+        match self {
+            Self::DeprecatedUsage(usage) => {
+                if usage.kind == DeprecatedUsageKind::Field {
+                    report.with_label_opt(main_location, "deprecated field selected here");
+                } else {
+                    report.with_label_opt(
+                        main_location,
+                        format_args!("deprecated {} used here", usage.kind),
+                    );
+                }
+                report.with_label_opt(usage.deprecated_location, "deprecated here");
+                if let Some(reason) = &usage.reason {
+                    report.with_note(format_args!("reason for deprecation: {reason}"));
+                }
+            }
+            Self::UnusedType { .. } => {
+                report.with_label_opt(main_location, "not reachable from a root operation type");
+                report.with_help("remove this type, or use it in a field, argument, or union");
+            }
+            Self::MissingDescription { .. } => {
+                report.with_label_opt(main_location, "add a description here");
+            }
+            Self::NamingConvention { name, case, .. } => {
+                report.with_label_opt(main_location, format_args!("not {case}"));
+                let renamed = case.convert(name);
+                if case.matches(&renamed) {
+                    report.with_help(format_args!("consider renaming to `{renamed}`"));
+                }
+            }
+            Self::NullableListItems { ty, .. } => {
+                report.with_label_opt(main_location, "both the list and its items can be null");
+                if let Type::List(item) = ty {
+                    let suggestion = Type::List(Box::new(item.as_ref().clone().non_null()));
+                    report.with_help(format_args!(
+                        "if list items are never null, consider using `{suggestion}`"
+                    ));
+                }
+            }
+        }
+    }
+}
+
+impl Case {
+    fn matches(self, name: &str) -> bool {
+        let mut chars = name.chars();
+        let Some(first) = chars.next() else {
+            return true;
+        };
+        match self {
+            Self::Pascal => first.is_ascii_uppercase() && !name.contains('_'),
+            Self::Camel => first.is_ascii_lowercase() && !name.contains('_'),
+            Self::ScreamingSnake => !name.chars().any(|c| c.is_ascii_lowercase()),
+        }
+    }
+
+    /// Split a name into words on underscores and on case changes,
+    /// then join the words according to this convention.
+    fn convert(self, name: &str) -> String {
+        let chars: Vec<char> = name.chars().collect();
+        let mut words = Vec::new();
+        let mut word = String::new();
+        for (i, &c) in chars.iter().enumerate() {
+            if c == '_' {
+                words.push(std::mem::take(&mut word));
+                continue;
+            }
+            let previous = i.checked_sub(1).map(|i| chars[i]);
+            let next = chars.get(i + 1);
+            let starts_word = c.is_ascii_uppercase()
+                && (previous.is_some_and(|p| p.is_ascii_lowercase() || p.is_ascii_digit())
+                    || previous.is_some_and(|p| p.is_ascii_uppercase())
+                        && next.is_some_and(|n| n.is_ascii_lowercase()));
+            if starts_word {
+                words.push(std::mem::take(&mut word));
+            }
+            word.push(c)
+        }
+        words.push(word);
+        let mut result = String::new();
+        for (i, word) in words.iter().filter(|w| !w.is_empty()).enumerate() {
+            match self {
+                Self::ScreamingSnake => {
+                    if i > 0 {
+                        result.push('_')
+                    }
+                    result.push_str(&word.to_ascii_uppercase())
+                }
+                Self::Camel if i == 0 => result.push_str(&word.to_ascii_lowercase()),
+                Self::Pascal | Self::Camel => {
+                    let mut chars = word.chars();
+                    if let Some(first) = chars.next() {
+                        result.push(first.to_ascii_uppercase());
+                        result.push_str(&chars.as_str().to_ascii_lowercase())
+                    }
+                }
+            }
+        }
+        result
+    }
+}
+
+impl fmt::Display for Case {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Pascal => "PascalCase",
+            Self::Camel => "camelCase",
+            Self::ScreamingSnake => "SCREAMING_SNAKE_CASE",
+        })
+    }
+}
+
+struct Linter<'a> {
+    diagnostics: &'a mut DiagnosticList,
+    config: &'a LintConfig,
+}
+
+impl Linter<'_> {
+    fn enabled(&self, rule: LintRule) -> bool {
+        self.config.level_of(rule) != LintLevel::Allow
+    }
+
+    fn push(&mut self, location: Option<SourceSpan>, lint: LintDiagnostic) {
+        let severity = match self.config.level_of(lint.rule()) {
+            LintLevel::Allow => return,
+            LintLevel::Warn => Severity::Warning,
+            LintLevel::Deny => Severity::Error,
+        };
+        self.diagnostics
+            .push(location, Details::Lint { severity, lint })
+    }
+
+    fn check_description(
+        &mut self,
+        description: Option<&Node<str>>,
+        describe: &'static str,
+        coordinate: impl Into<SchemaCoordinate>,
+        location: Option<SourceSpan>,
+    ) {
+        if description.is_none() {
+            let coordinate = coordinate.into();
+            self.push(
+                location,
+                LintDiagnostic::MissingDescription {
+                    describe,
+                    coordinate,
+                },
+            )
+        }
+    }
+
+    fn check_case(
+        &mut self,
+        name: &Name,
+        case: Case,
+        describe: &'static str,
+        coordinate: impl Into<SchemaCoordinate>,
+    ) {
+        // Names starting with `__` are reserved, which is a validation error
+        if !name.starts_with("__") && !case.matches(name) {
+            self.push(
+                name.location(),
+                LintDiagnostic::NamingConvention {
+                    describe,
+                    coordinate: coordinate.into(),
+                    name: name.clone(),
+                    case,
+                },
+            )
+        }
+    }
+
+    fn check_field(&mut self, type_name: &Name, field: &FieldDefinition) {
+        let coordinate = TypeAttributeCoordinate {
+            ty: type_name.clone(),
+            attribute: field.name.clone(),
+        };
+        self.check_description(
+            field.description.as_ref(),
+            "field",
+            coordinate.clone(),
+            field.name.location(),
+        );
+        self.check_case(&field.name, Case::Camel, "field", coordinate.clone());
+        for argument in &field.arguments {
+            let coordinate = coordinate.with_argument(argument.name.clone());
+            self.check_description(
+                argument.description.as_ref(),
+                "argument",
+                coordinate.clone(),
+                argument.name.location(),
+            );
+            self.check_case(&argument.name, Case::Camel, "argument", coordinate);
+        }
+        if let Type::List(item) = &field.ty {
+            if !item.is_non_null() {
+                self.push(
+                    field.name.location(),
+                    LintDiagnostic::NullableListItems {
+                        coordinate,
+                        ty: field.ty.clone(),
+                    },
+                )
+            }
+        }
+    }
+
+    fn check_input_field(&mut self, type_name: &Name, field: &InputValueDefinition) {
+        let coordinate = TypeAttributeCoordinate {
+            ty: type_name.clone(),
+            attribute: field.name.clone(),
+        };
+        self.check_description(
+            field.description.as_ref(),
+            "input field",
+            coordinate.clone(),
+            field.name.location(),
+        );
+        self.check_case(&field.name, Case::Camel, "input field", coordinate);
+    }
+
+    fn check_unused_types(&mut self, schema: &Schema) {
+        let implementers = schema.implementers_map();
+        let mut reachable = HashSet::default();
+        let mut queue: Vec<&Name> = schema
+            .schema_definition
+            .iter_root_operations()
+            .map(|(_, name)| &name.name)
+            .collect();
+        for definition in schema.directive_definitions.values() {
+            queue.extend(
+                definition
+                    .arguments
+                    .iter()
+                    .map(|argument| argument.ty.inner_named_type()),
+            )
+        }
+        while let Some(name) = queue.pop() {
+            if !reachable.insert(name) {
+                continue;
+            }
+            match schema.types.get(name) {
+                Some(ExtendedType::Object(ty)) => {
+                    for field in ty.fields.values() {
+                        queue.push(field.ty.inner_named_type());
+                        queue.extend(field.arguments.iter().map(|a| a.ty.inner_named_type()));
+                    }
+                    queue.extend(ty.implements_interfaces.iter().map(|i| &i.name));
+                }
+                Some(ExtendedType::Interface(ty)) => {
+                    for field in ty.fields.values() {
+                        queue.push(field.ty.inner_named_type());
+                        queue.extend(field.arguments.iter().map(|a| a.ty.inner_named_type()));
+                    }
+                    queue.extend(ty.implements_interfaces.iter().map(|i| &i.name));
+                    if let Some(implementers) = implementers.get(name) {
+                        queue.extend(implementers.objects.iter());
+                        queue.extend(implementers.interfaces.iter());
+                    }
+                }
+                Some(ExtendedType::Union(ty)) => {
+                    queue.extend(ty.members.iter().map(|member| &member.name))
+                }
+                Some(ExtendedType::InputObject(ty)) => {
+                    queue.extend(ty.fields.values().map(|field| field.ty.inner_named_type()))
+                }
+                Some(ExtendedType::Scalar(_)) | Some(ExtendedType::Enum(_)) | None => {}
+            }
+        }
+        for (name, ty) in &schema.types {
+            if !ty.is_built_in() && !reachable.contains(name) {
+                self.push(
+                    name.location(),
+                    LintDiagnostic::UnusedType { name: name.clone() },
+                )
+            }
+        }
+    }
+
+    fn check_schema(&mut self, schema: &Schema) {
+        if self.enabled(LintRule::UnusedType) {
+            self.check_unused_types(schema)
+        }
+        for (name, ty) in &schema.types {
+            if ty.is_built_in() {
+                continue;
+            }
+            let coordinate = TypeCoordinate { ty: name.clone() };
+            self.check_description(
+                ty.description(),
+                "type",
+                coordinate.clone(),
+                name.location(),
+            );
+            self.check_case(name, Case::Pascal, "type", coordinate);
+            match ty {
+                ExtendedType::Object(ty) => {
+                    for field in ty.fields.values() {
+                        self.check_field(name, field)
+                    }
+                }
+                ExtendedType::Interface(ty) => {
+                    for field in ty.fields.values() {
+                        self.check_field(name, field)
+                    }
+                }
+                ExtendedType::InputObject(ty) => {
+                    for field in ty.fields.values() {
+                        self.check_input_field(name, field)
+                    }
+                }
+                ExtendedType::Enum(ty) => {
+                    for value in ty.values.values() {
+                        let coordinate = TypeAttributeCoordinate {
+                            ty: name.clone(),
+                            attribute: value.value.clone(),
+                        };
+                        self.check_description(
+                            value.description.as_ref(),
+                            "enum value",
+                            coordinate.clone(),
+                            value.value.location(),
+                        );
+                        self.check_case(
+                            &value.value,
+                            Case::ScreamingSnake,
+                            "enum value",
+                            coordinate,
+                        );
+                    }
+                }
+                ExtendedType::Scalar(_) | ExtendedType::Union(_) => {}
+            }
+        }
+        for definition in schema.directive_definitions.values() {
+            if definition.is_built_in() {
+                continue;
+            }
+            let coordinate = DirectiveCoordinate {
+                directive: definition.name.clone(),
+            };
+            self.check_description(
+                definition.description.as_ref(),
+                "directive",
+                coordinate.clone(),
+                definition.name.location(),
+            );
+            self.check_case(
+                &definition.name,
+                Case::Camel,
+                "directive",
+                coordinate.clone(),
+            );
+            for argument in &definition.arguments {
+                let coordinate = coordinate.with_argument(argument.name.clone());
+                self.check_description(
+                    argument.description.as_ref(),
+                    "argument",
+                    coordinate.clone(),
+                    argument.name.location(),
+                );
+                self.check_case(&argument.name, Case::Camel, "argument", coordinate);
+            }
+        }
+    }
+
+    fn check_executable(&mut self, schema: &Schema, document: &ExecutableDocument) {
+        if !self.enabled(LintRule::DeprecatedUsage) {
+            return;
+        }
+        for usage in super::deprecated::find_deprecated_usages(schema, document) {
+            self.push(usage.location, LintDiagnostic::DeprecatedUsage(usage))
+        }
+    }
+}
+
+pub(crate) fn lint_schema(diagnostics: &mut DiagnosticList, schema: &Schema, config: &LintConfig) {
+    Linter {
+        diagnostics,
+        config,
+    }
+    .check_schema(schema);
+    diagnostics.sort()
+}
+
+pub(crate) fn lint_executable_document(
+    diagnostics: &mut DiagnosticList,
+    schema: &Schema,
+    document: &ExecutableDocument,
+    config: &LintConfig,
+) {
+    Linter {
+        diagnostics,
+        config,
+    }
+    .check_executable(schema, document);
+    diagnostics.sort()
+}
@@ -2,6 +2,8 @@
 //! and other kinds of errors.
 
 pub(crate) mod argument;
+mod code;
+pub(crate) mod deprecated;
 pub(crate) mod diagnostics;
 pub(crate) mod directive;
 pub(crate) mod enum_;
@@ -9,8 +11,12 @@ pub(crate) mod field;
 pub(crate) mod fragment;
 pub(crate) mod input_object;
 pub(crate) mod interface;
+mod json;
+pub mod lint;
 pub(crate) mod object;
 pub(crate) mod operation;
+mod rule;
+pub mod sarif;
 pub(crate) mod scalar;
 pub(crate) mod schema;
 pub(crate) mod selection;
@@ -18,11 +24,24 @@ pub(crate) mod union_;
 pub(crate) mod value;
 pub(crate) mod variable;
 
+pub use self::code::DiagnosticCode;
+pub use self::code::Severity;
+pub use self::deprecated::DeprecatedUsage;
+pub use self::deprecated::DeprecatedUsageKind;
+pub use self::json::JsonDiagnostic;
+pub use self::json::JsonLabel;
+pub use self::json::JsonLocation;
+pub use self::rule::ExecutableValidationRule;
+pub use self::rule::RuleDiagnostics;
+pub use self::rule::SchemaValidationRule;
+pub use self::rule::Validator;
+
 use crate::collections::HashMap;
 use crate::collections::HashSet;
 use crate::collections::IndexSet;
 use crate::coordinate::SchemaCoordinate;
 use crate::diagnostic::CliReport;
+use crate::diagnostic::Color;
 use crate::diagnostic::Diagnostic;
 use crate::diagnostic::ToCliReport;
 use crate::executable::BuildError as ExecutableBuildError;
@@ -32,6 +51,7 @@ use crate::executable::ConflictingFieldType;
 use crate::executable::VariableDefinition;
 use crate::parser::SourceMap;
 use crate::parser::SourceSpan;
+use crate::parser::SyntaxErrorKind;
 use crate::response::GraphQLError;
 use crate::schema::BuildError as SchemaBuildError;
 use crate::schema::Implementers;
@@ -119,9 +139,10 @@ impl<T: fmt::Display> fmt::Display for Valid<T> {
     }
 }
 
-/// Shared context with things that may be used throughout executable validation.
+/// Shared context with things that may be used throughout executable validation,
+/// including by [custom rules][ExecutableValidationRule].
 #[derive(Debug)]
-pub(crate) struct ExecutableValidationContext<'a> {
+pub struct ExecutableValidationContext<'a> {
     /// When None, rules that require a schema to validate are disabled.
     schema: Option<&'a Schema>,
     /// `schema.implementers_map()` is expensive to compute. This caches it for reuse.
@@ -137,12 +158,12 @@ impl<'a> ExecutableValidationContext<'a> {
     }
 
     /// Returns the schema to validate against, if any.
-    pub(crate) fn schema(&self) -> Option<&'a Schema> {
+    pub fn schema(&self) -> Option<&'a Schema> {
         self.schema
     }
 
     /// Returns a cached reference to the implementers map.
-    pub(crate) fn implementers_map(&self) -> &HashMap<Name, Implementers> {
+    pub fn implementers_map(&self) -> &HashMap<Name, Implementers> {
         self.implementers_map.get_or_init(|| {
             self.schema
                 .map(|schema| schema.implementers_map())
@@ -151,7 +172,7 @@ impl<'a> ExecutableValidationContext<'a> {
     }
 
     /// Returns a context for operation validation.
-    pub(crate) fn operation_context<'o>(
+    pub fn operation_context<'o>(
         &'o self,
         variables: &'o [Node<VariableDefinition>],
     ) -> OperationValidationContext<'o> {
@@ -165,7 +186,7 @@ impl<'a> ExecutableValidationContext<'a> {
 
 /// Shared context when validating things inside an operation.
 #[derive(Debug)]
-pub(crate) struct OperationValidationContext<'a> {
+pub struct OperationValidationContext<'a> {
     /// Parent context. Using a reference so the `OnceLock` is shared between all operation
     /// contexts.
     executable: &'a ExecutableValidationContext<'a>,
@@ -175,14 +196,20 @@ pub(crate) struct OperationValidationContext<'a> {
 }
 
 impl<'a> OperationValidationContext<'a> {
-    pub(crate) fn schema(&self) -> Option<&'a Schema> {
+    /// Returns the schema to validate against, if any.
+    pub fn schema(&self) -> Option<&'a Schema> {
         self.executable.schema
     }
 
     /// Returns a cached reference to the implementers map.
-    pub(crate) fn implementers_map(&self) -> &HashMap<Name, Implementers> {
+    pub fn implementers_map(&self) -> &HashMap<Name, Implementers> {
         self.executable.implementers_map()
     }
+
+    /// Returns the variables defined by the operation.
+    pub fn variables(&self) -> &'a [Node<VariableDefinition>] {
+        self.variables
+    }
 }
 
 /// A conversion failed with some errors, but also resulted in a partial document.
@@ -234,6 +261,9 @@ pub(crate) struct SuspectedValidationBug {
 pub struct DiagnosticList {
     pub(crate) sources: SourceMap,
     diagnostics_data: Vec<DiagnosticData>,
+    /// Codes of built-in checks not to run, from [`Validator::disable`].
+    /// Boxed to keep `Result<_, WithErrors<_>>` small.
+    disabled: Option<Box<HashSet<DiagnosticCode>>>,
 }
 
 // TODO(@goto-bus-stop) Can/should this be non-pub?
@@ -249,7 +279,10 @@ pub(crate) enum Details {
     #[error("{message}")]
     ParserLimit { message: String },
     #[error("syntax error: {message}")]
-    SyntaxError { message: String },
+    SyntaxError {
+        message: String,
+        kind: SyntaxErrorKind,
+    },
     #[error("{0}")]
     SchemaBuildError(SchemaBuildError),
     #[error("{0}")]
@@ -259,9 +292,37 @@ pub(crate) enum Details {
     CompilerDiagnostic(diagnostics::DiagnosticData),
     #[error("too much recursion")]
     RecursionLimitError,
+    #[error("{lint}")]
+    Lint {
+        severity: Severity,
+        lint: lint::LintDiagnostic,
+    },
+    #[error("{message}")]
+    Custom { rule: String, message: String },
 }
 
 impl DiagnosticData {
+    /// Returns the kind of a syntax error, or `None` for other diagnostics.
+    ///
+    /// Reaching the parser’s recursion or token limit is reported as
+    /// [`SyntaxErrorKind::LimitExceeded`].
+    pub fn syntax_error_kind(&self) -> Option<SyntaxErrorKind> {
+        match &self.details {
+            Details::SyntaxError { kind, .. } => Some(*kind),
+            Details::ParserLimit { .. } => Some(SyntaxErrorKind::LimitExceeded),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the [custom rule][ExecutableValidationRule] that reported
+    /// this diagnostic, or `None` for built-in diagnostics.
+    pub fn custom_rule(&self) -> Option<&str> {
+        match &self.details {
+            Details::Custom { rule, .. } => Some(rule),
+            _ => None,
+        }
+    }
+
     /// Returns the internal error name for an (operation) validation error.
     /// This is meant for debugging apollo-rs, not for public consumption.
     #[doc(hidden)]
@@ -312,6 +373,11 @@ impl DiagnosticData {
                     EmptyMemberSet { .. } => "EmptyMemberSet",
                     EmptyInputValueSet { .. } => "EmptyInputValueSet",
                     ReservedName { .. } => "ReservedName",
+                    OneOfInputObjectNonNullField { .. } => "OneOfInputObjectNonNullField",
+                    OneOfInputObjectFieldDefaultValue { .. } => "OneOfInputObjectFieldDefaultValue",
+                    OneOfInputObjectFieldCount { .. } => "OneOfInputObjectFieldCount",
+                    OneOfInputObjectNullField { .. } => "OneOfInputObjectNullField",
+                    OneOfInputObjectNullableVariable { .. } => "OneOfInputObjectNullableVariable",
                 })
             }
             Details::ExecutableBuildError(error) => Some(match error {
@@ -510,6 +576,26 @@ impl DiagnosticData {
                     EmptyMemberSet { .. } => None,
                     EmptyInputValueSet { .. } => None,
                     ReservedName { .. } => None,
+                    OneOfInputObjectNonNullField { coordinate, .. } => {
+                        Some(format!("OneOf input field {coordinate} must be nullable."))
+                    }
+                    OneOfInputObjectFieldDefaultValue { coordinate, .. } => Some(format!(
+                        "OneOf input field {coordinate} cannot have a default value."
+                    )),
+                    OneOfInputObjectFieldCount { name, .. } => Some(format!(
+                        r#"OneOf Input Object "{name}" must specify exactly one key."#
+                    )),
+                    OneOfInputObjectNullField { coordinate } => {
+                        Some(format!(r#"Field "{coordinate}" must be non-null."#))
+                    }
+                    OneOfInputObjectNullableVariable {
+                        variable,
+                        coordinate,
+                        ..
+                    } => Some(format!(
+                        r#"Variable "${variable}" must be non-nullable to be used for OneOf Input Object "{}"."#,
+                        coordinate.ty
+                    )),
                 }
             }
             Details::ExecutableBuildError(error) => match error {
@@ -638,11 +724,23 @@ impl ToCliReport for DiagnosticData {
         self.location
     }
 
+    fn to_report<'s>(&self, sources: &'s SourceMap, color: Color) -> CliReport<'s> {
+        let mut report =
+            CliReport::builder_with_severity(sources, self.location, self.severity(), color);
+        report.with_message(self);
+        self.report(&mut report);
+        report
+    }
+
     fn report(&self, report: &mut CliReport) {
         if let Details::CompilerDiagnostic(diagnostic) = &self.details {
             diagnostic.report(self.location, report);
             return;
         }
+        if let Details::Lint { lint, .. } = &self.details {
+            lint.report(self.location, report);
+            return;
+        }
 
         // Every case should also have a label at the main location
         // (preferably saying something not completely redundant with the main message)
@@ -650,7 +748,7 @@ impl ToCliReport for DiagnosticData {
         // Labels are always optional because locations are always optional,
         // so essential information should be in the main message.
         match &self.details {
-            Details::CompilerDiagnostic(_) => unreachable!(),
+            Details::CompilerDiagnostic(_) | Details::Lint { .. } => unreachable!(),
             Details::ParserLimit { message, .. } => report.with_label_opt(self.location, message),
             Details::SyntaxError { message, .. } => report.with_label_opt(self.location, message),
             Details::SchemaBuildError(err) => match err {
@@ -944,6 +1042,9 @@ impl ToCliReport for DiagnosticData {
                 }
             },
             Details::RecursionLimitError => {}
+            Details::Custom { rule, .. } => {
+                report.with_label_opt(self.location, format_args!("reported by the `{rule}` rule"))
+            }
         }
     }
 }
@@ -971,9 +1072,25 @@ impl DiagnosticList {
         Self {
             sources,
             diagnostics_data: Vec::new(),
+            disabled: None,
         }
     }
 
+    /// Skip built-in checks that can only report diagnostics with these codes
+    pub(crate) fn disable(&mut self, codes: &HashSet<DiagnosticCode>) {
+        if !codes.is_empty() {
+            self.disabled.get_or_insert_default().extend(codes)
+        }
+    }
+
+    /// Returns whether to run a built-in check that can report any of these codes
+    pub(crate) fn should_check(&self, codes: &[DiagnosticCode]) -> bool {
+        let Some(disabled) = &self.disabled else {
+            return true;
+        };
+        codes.iter().any(|code| !disabled.contains(code))
+    }
+
     pub fn is_empty(&self) -> bool {
         self.diagnostics_data.is_empty()
     }
@@ -982,6 +1099,15 @@ impl DiagnosticList {
         self.diagnostics_data.len()
     }
 
+    /// Returns whether any diagnostic in this list has [`Severity::Error`].
+    ///
+    /// This is `false` for a list that only contains warnings.
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics_data
+            .iter()
+            .any(|data| data.severity() == Severity::Error)
+    }
+
     pub fn iter(
         &self,
     ) -> impl DoubleEndedIterator<Item = Diagnostic<'_, DiagnosticData>> + ExactSizeIterator {
@@ -997,6 +1123,10 @@ impl DiagnosticList {
         })
     }
 
+    pub(crate) fn retain(&mut self, keep: impl FnMut(&DiagnosticData) -> bool) {
+        self.diagnostics_data.retain(keep)
+    }
+
     /// Concatenate an `other` list of diagnostics into `self`, and sort them together.
     pub fn merge(&mut self, other: Self) {
         if !Arc::ptr_eq(&self.sources, &other.sources) {
@@ -3,6 +3,7 @@ use crate::executable;
 use crate::validation::diagnostics::DiagnosticData;
 use crate::validation::DepthCounter;
 use crate::validation::DepthGuard;
+use crate::validation::DiagnosticCode;
 use crate::validation::DiagnosticList;
 use crate::validation::ExecutableValidationContext;
 use crate::validation::RecursionLimitError;
@@ -164,7 +165,12 @@ pub(crate) fn validate_operation(
         &operation.variables,
     );
 
-    super::variable::validate_unused_variables(diagnostics, document, operation);
+    if diagnostics.should_check(&[
+        DiagnosticCode::UnusedVariable,
+        DiagnosticCode::RecursionError,
+    ]) {
+        super::variable::validate_unused_variables(diagnostics, document, operation);
+    }
     super::selection::validate_selection_set(
         diagnostics,
         document,
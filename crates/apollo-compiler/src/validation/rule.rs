@@ -0,0 +1,259 @@
+use super::Details;
+use super::DiagnosticCode;
+use super::DiagnosticList;
+use super::ExecutableValidationContext;
+use super::OperationValidationContext;
+use super::Valid;
+use super::WithErrors;
+use crate::collections::HashSet;
+use crate::collections::IndexMap;
+use crate::executable::Operation;
+use crate::parser::SourceSpan;
+use crate::ExecutableDocument;
+use crate::Node;
+use crate::Schema;
+use std::fmt;
+use std::sync::Arc;
+
+/// A custom rule run by a [`Validator`] after built-in executable document validation.
+///
+/// Implement either [`validate`][Self::validate] to check a whole document,
+/// or [`validate_operation`][Self::validate_operation] to check each operation.
+///
+/// ```
+/// use apollo_compiler::executable::Operation;
+/// use apollo_compiler::executable::Selection;
+/// use apollo_compiler::validation::ExecutableValidationRule;
+/// use apollo_compiler::validation::OperationValidationContext;
+/// use apollo_compiler::validation::RuleDiagnostics;
+/// use apollo_compiler::Node;
+///
+/// struct NoRootAliases;
+///
+/// impl ExecutableValidationRule for NoRootAliases {
+///     fn name(&self) -> &str {
+///         "NoRootAliases"
+///     }
+///
+///     fn validate_operation(
+///         &self,
+///         operation: &Node<Operation>,
+///         _context: &OperationValidationContext<'_>,
+///         diagnostics: &mut RuleDiagnostics<'_>,
+///     ) {
+///         for selection in &operation.selection_set.selections {
+///             if let Selection::Field(field) = selection {
+///                 if let Some(alias) = &field.alias {
+///                     diagnostics.push(alias.location(), format!("root field `{}` is aliased", field.name));
+///                 }
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub trait ExecutableValidationRule: Send + Sync {
+    /// The name of this rule, returned by
+    /// [`DiagnosticData::custom_rule`][super::DiagnosticData::custom_rule]
+    /// for diagnostics it reports.
+    fn name(&self) -> &str;
+
+    /// Check a document.
+    ///
+    /// The default implementation calls [`validate_operation`][Self::validate_operation]
+    /// for each operation.
+    fn validate(
+        &self,
+        document: &ExecutableDocument,
+        context: &ExecutableValidationContext<'_>,
+        diagnostics: &mut RuleDiagnostics<'_>,
+    ) {
+        for operation in document.operations.iter() {
+            let operation_context = context.operation_context(&operation.variables);
+            self.validate_operation(operation, &operation_context, diagnostics)
+        }
+    }
+
+    /// Check an operation. Does nothing by default.
+    fn validate_operation(
+        &self,
+        operation: &Node<Operation>,
+        context: &OperationValidationContext<'_>,
+        diagnostics: &mut RuleDiagnostics<'_>,
+    ) {
+        let _ = (operation, context, diagnostics);
+    }
+}
+
+/// A custom rule run by a [`Validator`] after built-in schema validation.
+pub trait SchemaValidationRule: Send + Sync {
+    /// The name of this rule, returned by
+    /// [`DiagnosticData::custom_rule`][super::DiagnosticData::custom_rule]
+    /// for diagnostics it reports.
+    fn name(&self) -> &str;
+
+    /// Check a schema.
+    fn validate(&self, schema: &Schema, diagnostics: &mut RuleDiagnostics<'_>);
+}
+
+/// Collects errors reported by a custom rule
+/// into the same [`DiagnosticList`] as built-in validation errors.
+///
+/// They have the [`DiagnosticCode::CustomRule`] code.
+pub struct RuleDiagnostics<'a> {
+    rule: &'a str,
+    list: &'a mut DiagnosticList,
+}
+
+impl RuleDiagnostics<'_> {
+    /// Report a validation error.
+    pub fn push(&mut self, location: Option<SourceSpan>, message: impl Into<String>) {
+        self.list.push(
+            location,
+            Details::Custom {
+                rule: self.rule.to_owned(),
+                message: message.into(),
+            },
+        )
+    }
+}
+
+/// Validates schemas and executable documents with a customized set of rules.
+///
+/// Starts with all built-in rules, like [`Schema::validate`] and [`ExecutableDocument::validate`].
+/// Built-in rules can be disabled by [diagnostic code][DiagnosticCode],
+/// and custom rules can be added.
+///
+/// Disabling rules can make a `Valid<_>` result violate the GraphQL specification.
+/// Like with [`Valid::assume_valid`], the caller takes responsibility
+/// that other APIs taking `Valid<_>` parameters can handle this.
+///
+/// ```
+/// use apollo_compiler::validation::DiagnosticCode;
+/// use apollo_compiler::validation::Validator;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let validator = Validator::new().disable(DiagnosticCode::UnusedFragment);
+/// let schema = Schema::parse("type Query { name: String }", "schema.graphql").unwrap();
+/// let schema = validator.validate_schema(schema).unwrap();
+///
+/// let input = "{ name }  fragment Unused on Query { name }";
+/// let document = ExecutableDocument::parse(&schema, input, "query.graphql").unwrap();
+/// assert!(validator.validate_executable(&schema, document).is_ok());
+/// ```
+#[derive(Clone, Default)]
+pub struct Validator {
+    disabled: HashSet<DiagnosticCode>,
+    executable_rules: Vec<Arc<dyn ExecutableValidationRule>>,
+    schema_rules: Vec<Arc<dyn SchemaValidationRule>>,
+}
+
+impl Validator {
+    /// Returns a validator with all built-in rules and no custom rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Do not report diagnostics with the given code from built-in rules.
+    ///
+    /// Built-in checks that can only report disabled codes are not run at all.
+    pub fn disable(mut self, code: DiagnosticCode) -> Self {
+        self.disabled.insert(code);
+        self
+    }
+
+    /// Add a custom rule for executable documents.
+    pub fn executable_rule(mut self, rule: impl ExecutableValidationRule + 'static) -> Self {
+        self.executable_rules.push(Arc::new(rule));
+        self
+    }
+
+    /// Add a custom rule for schemas.
+    pub fn schema_rule(mut self, rule: impl SchemaValidationRule + 'static) -> Self {
+        self.schema_rules.push(Arc::new(rule));
+        self
+    }
+
+    /// Validate a schema, like [`Schema::validate`] with the configured rules.
+    ///
+    /// Custom rules run after built-in rules, with the same schema.
+    #[allow(clippy::result_large_err)] // Typically not called very often
+    pub fn validate_schema(&self, mut schema: Schema) -> Result<Valid<Schema>, WithErrors<Schema>> {
+        let mut errors = DiagnosticList::new(schema.sources.clone());
+        errors.disable(&self.disabled);
+        crate::schema::validation::validate_schema(&mut errors, &mut schema);
+        self.remove_disabled(&mut errors);
+        for rule in &self.schema_rules {
+            let mut diagnostics = RuleDiagnostics {
+                rule: rule.name(),
+                list: &mut errors,
+            };
+            rule.validate(&schema, &mut diagnostics)
+        }
+        errors.into_valid_result(schema)
+    }
+
+    /// Validate an executable document against a schema,
+    /// like [`ExecutableDocument::validate`] with the configured rules.
+    ///
+    /// Custom rules share a context with built-in rules,
+    /// so the [implementers map][ExecutableValidationContext::implementers_map]
+    /// is computed at most once.
+    #[allow(clippy::result_large_err)] // Typically not called very often
+    pub fn validate_executable(
+        &self,
+        schema: &Valid<Schema>,
+        document: ExecutableDocument,
+    ) -> Result<Valid<ExecutableDocument>, WithErrors<ExecutableDocument>> {
+        let mut sources = IndexMap::clone(&schema.sources);
+        sources.extend(document.sources.iter().map(|(k, v)| (*k, v.clone())));
+        let mut errors = DiagnosticList::new(Arc::new(sources));
+        errors.disable(&self.disabled);
+        let context = ExecutableValidationContext::new(Some(schema));
+        crate::executable::validation::validate_executable_document_with_context(
+            &mut errors,
+            &context,
+            schema,
+            &document,
+        );
+        self.remove_disabled(&mut errors);
+        for rule in &self.executable_rules {
+            let mut diagnostics = RuleDiagnostics {
+                rule: rule.name(),
+                list: &mut errors,
+            };
+            rule.validate(&document, &context, &mut diagnostics)
+        }
+        errors.into_valid_result(document)
+    }
+
+    fn remove_disabled(&self, errors: &mut DiagnosticList) {
+        if !self.disabled.is_empty() {
+            errors.retain(|diagnostic| !self.disabled.contains(&diagnostic.code()))
+        }
+    }
+}
+
+impl fmt::Debug for Validator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Validator")
+            .field("disabled", &self.disabled)
+            .field(
+                "executable_rules",
+                &self
+                    .executable_rules
+                    .iter()
+                    .map(|rule| rule.name())
+                    .collect::<Vec<_>>(),
+            )
+            .field(
+                "schema_rules",
+                &self
+                    .schema_rules
+                    .iter()
+                    .map(|rule| rule.name())
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
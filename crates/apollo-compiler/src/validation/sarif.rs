@@ -0,0 +1,273 @@
+//! Types for the [SARIF 2.1.0] format, returned by [`DiagnosticList::to_sarif`].
+//!
+//! SARIF is a JSON format for the output of static analysis tools,
+//! supported by code scanning and code review services to annotate source files.
+//! These types cover the part of the format used by apollo-compiler,
+//! and can be serialized with [`serde`].
+//!
+//! ```
+//! use apollo_compiler::Schema;
+//!
+//! let input = "type Query { a: Undefined }";
+//! let errors = Schema::parse_and_validate(input, "schema.graphql")
+//!     .unwrap_err()
+//!     .errors;
+//! let log = errors.to_sarif();
+//! let result = &log.runs[0].results[0];
+//! assert_eq!(result.rule_id, "GQL0305");
+//! assert_eq!(result.level, "error");
+//! ```
+//!
+//! [SARIF 2.1.0]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use super::DiagnosticCode;
+use super::DiagnosticList;
+use super::JsonLocation;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The top-level SARIF object
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Log {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub version: String,
+    pub runs: Vec<Run>,
+}
+
+/// A run of a tool over a set of files
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Run {
+    pub tool: Tool,
+    /// How column numbers are counted, `"unicodeCodePoints"` for apollo-compiler
+    pub column_kind: String,
+    pub results: Vec<SarifResult>,
+}
+
+/// The tool that produced a [`Run`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tool {
+    pub driver: ToolComponent,
+}
+
+/// The name and version of a tool, and the rules it checks
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolComponent {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub information_uri: Option<String>,
+    /// The rules of results in the run, one for each [`DiagnosticCode`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<ReportingDescriptor>,
+}
+
+/// A rule, identified by a [`DiagnosticCode`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportingDescriptor {
+    /// The code, such as `"GQL0301"`
+    pub id: String,
+    /// The name of the code, such as `"UniqueVariable"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_description: Option<Message>,
+}
+
+/// A diagnostic. Named `result` in the SARIF specification.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    /// The code, such as `"GQL0301"`
+    pub rule_id: String,
+    /// The index of the rule in [`ToolComponent::rules`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_index: Option<usize>,
+    /// `"error"` or `"warning"`
+    pub level: String,
+    pub message: Message,
+    /// The main location
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locations: Vec<Location>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related_locations: Vec<Location>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Message {
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    /// Identifies a related location within a result
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<usize>,
+    pub physical_location: PhysicalLocation,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<Message>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhysicalLocation {
+    pub artifact_location: ArtifactLocation,
+    pub region: Region,
+}
+
+/// A source file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactLocation {
+    /// The path of the source file as a URI reference
+    pub uri: String,
+}
+
+/// A range within a source file.
+///
+/// Lines and columns start at 1. The end column is the one after the last character.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Region {
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    /// In UTF-8 bytes
+    pub byte_offset: usize,
+    /// In UTF-8 bytes
+    pub byte_length: usize,
+}
+
+impl DiagnosticList {
+    /// Convert all diagnostics to a [SARIF 2.1.0][sarif] log, with one run.
+    /// The log can be serialized with [`serde`].
+    ///
+    /// Each diagnostic becomes a result whose rule is its [`DiagnosticCode`].
+    /// Labels at the main location are included in the message of the location,
+    /// and other labels become related locations.
+    /// File paths are converted to URIs: relative paths stay relative.
+    ///
+    /// [sarif]: crate::validation::sarif
+    pub fn to_sarif(&self) -> Log {
+        let mut codes: Vec<DiagnosticCode> = self.iter().map(|d| d.error.code()).collect();
+        codes.sort();
+        codes.dedup();
+        let results = self
+            .iter()
+            .map(|diagnostic| {
+                let code = diagnostic.error.code();
+                let json = diagnostic.to_json_diagnostic();
+                let mut location = json.location.map(|location| Location {
+                    id: None,
+                    physical_location: PhysicalLocation::new(&location),
+                    message: None,
+                });
+                let mut related_locations = Vec::new();
+                for label in json.labels {
+                    match &mut location {
+                        Some(location)
+                            if location.message.is_none()
+                                && location.physical_location
+                                    == PhysicalLocation::new(&label.location) =>
+                        {
+                            location.message = Some(Message {
+                                text: label.message,
+                            })
+                        }
+                        _ => related_locations.push(Location {
+                            id: Some(related_locations.len() + 1),
+                            physical_location: PhysicalLocation::new(&label.location),
+                            message: Some(Message {
+                                text: label.message,
+                            }),
+                        }),
+                    }
+                }
+                let mut text = json.message;
+                if let Some(help) = json.help {
+                    text = format!("{text}\n\n{help}")
+                }
+                SarifResult {
+                    rule_id: json.code,
+                    rule_index: codes.binary_search(&code).ok(),
+                    level: json.severity.as_str().to_owned(),
+                    message: Message { text },
+                    locations: location.into_iter().collect(),
+                    related_locations,
+                }
+            })
+            .collect();
+        let rules = codes
+            .into_iter()
+            .map(|code| ReportingDescriptor {
+                id: code.to_string(),
+                name: Some(code.name().to_owned()),
+                short_description: Some(Message {
+                    text: code.description().to_owned(),
+                }),
+            })
+            .collect();
+        Log {
+            schema: "https://json.schemastore.org/sarif-2.1.0.json".to_owned(),
+            version: "2.1.0".to_owned(),
+            runs: vec![Run {
+                tool: Tool {
+                    driver: ToolComponent {
+                        name: env!("CARGO_PKG_NAME").to_owned(),
+                        version: Some(env!("CARGO_PKG_VERSION").to_owned()),
+                        information_uri: Some(env!("CARGO_PKG_REPOSITORY").to_owned()),
+                        rules,
+                    },
+                },
+                column_kind: "unicodeCodePoints".to_owned(),
+                results,
+            }],
+        }
+    }
+}
+
+impl PhysicalLocation {
+    fn new(location: &JsonLocation) -> Self {
+        Self {
+            artifact_location: ArtifactLocation {
+                uri: path_to_uri(&location.file),
+            },
+            region: Region {
+                start_line: location.start.line,
+                start_column: location.start.column,
+                end_line: location.end.line,
+                end_column: location.end.column,
+                byte_offset: location.offset,
+                byte_length: location.length,
+            },
+        }
+    }
+}
+
+/// Convert a file path to a URI reference, percent-encoding characters as needed.
+///
+/// Absolute paths become `file:` URIs, relative paths stay relative references.
+fn path_to_uri(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let mut uri = String::new();
+    let bytes = path.as_bytes();
+    if bytes.first() == Some(&b'/') {
+        uri.push_str("file://")
+    } else if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+        // Windows drive letter
+        uri.push_str("file:///")
+    }
+    for &byte in bytes {
+        if byte.is_ascii_alphanumeric() || b"-._~/:@!$&'()*+,;=".contains(&byte) {
+            uri.push(byte as char)
+        } else {
+            uri.push_str(&format!("%{byte:02X}"))
+        }
+    }
+    uri
+}
@@ -190,6 +190,21 @@ fn same_name_and_arguments(
     Ok(())
 }
 
+/// Returns whether two fields with the same response key can be merged,
+/// with the same rules as validation.
+/// Used to merge fields when normalizing a document.
+pub(crate) fn can_merge_fields(
+    parent_type: &NamedType,
+    field_a: &Node<executable::Field>,
+    field_b: &Node<executable::Field>,
+) -> bool {
+    same_name_and_arguments(
+        FieldSelection::new(parent_type, field_a),
+        FieldSelection::new(parent_type, field_b),
+    )
+    .is_ok()
+}
+
 /// Compare two input values, with two special cases for objects: assuming no duplicate keys,
 /// and order-independence.
 fn same_value(left: &ast::Value, right: &ast::Value) -> bool {
@@ -201,10 +216,13 @@ fn same_value(left: &ast::Value, right: &ast::Value) -> bool {
         (ast::Value::Float(left), ast::Value::Float(right)) => left == right,
         (ast::Value::Int(left), ast::Value::Int(right)) => left == right,
         (ast::Value::Boolean(left), ast::Value::Boolean(right)) => left == right,
-        (ast::Value::List(left), ast::Value::List(right)) => left
-            .iter()
-            .zip(right.iter())
-            .all(|(left, right)| same_value(left, right)),
+        (ast::Value::List(left), ast::Value::List(right)) => {
+            left.len() == right.len()
+                && left
+                    .iter()
+                    .zip(right.iter())
+                    .all(|(left, right)| same_value(left, right))
+        }
         (ast::Value::Object(left), ast::Value::Object(right)) if left.len() == right.len() => {
             // This check could miss out on keys that exist in `right`, but not in `left`, if `left` contains duplicate keys.
             // We assume that that doesn't happen. GraphQL does not support duplicate keys and
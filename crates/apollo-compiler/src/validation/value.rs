@@ -1,10 +1,34 @@
 use crate::ast;
 use crate::coordinate::TypeAttributeCoordinate;
 use crate::schema;
+use crate::schema::Component;
 use crate::validation::diagnostics::DiagnosticData;
 use crate::validation::DiagnosticList;
+use crate::Name;
 use crate::Node;
 
+/// Receives the schema definitions that parts of a value resolve to
+/// while [`check_value`] type-checks it.
+pub(crate) trait ValueDefinitions {
+    fn enum_value(
+        &mut self,
+        _enum: &Node<schema::EnumType>,
+        _value: &Node<ast::Value>,
+        _definition: &Component<ast::EnumValueDefinition>,
+    ) {
+    }
+
+    fn input_field(
+        &mut self,
+        _input_object: &Node<schema::InputObjectType>,
+        _name: &Name,
+        _definition: &Component<ast::InputValueDefinition>,
+    ) {
+    }
+}
+
+impl ValueDefinitions for () {}
+
 fn unsupported_type(
     diagnostics: &mut DiagnosticList,
     value: &Node<ast::Value>,
@@ -36,6 +60,19 @@ pub(crate) fn value_of_correct_type(
     ty: &Node<ast::Type>,
     arg_value: &Node<ast::Value>,
     var_defs: &[Node<ast::VariableDefinition>],
+) {
+    check_value(diagnostics, schema, ty, arg_value, var_defs, &mut ())
+}
+
+/// Like [`value_of_correct_type`], also reporting the enum values and input object fields
+/// that the value resolves to
+pub(crate) fn check_value(
+    diagnostics: &mut DiagnosticList,
+    schema: &crate::Schema,
+    ty: &Node<ast::Type>,
+    arg_value: &Node<ast::Value>,
+    var_defs: &[Node<ast::VariableDefinition>],
+    definitions: &mut impl ValueDefinitions,
 ) {
     let Some(type_definition) = schema.types.get(ty.inner_named_type()) else {
         return;
@@ -161,7 +198,9 @@ pub(crate) fn value_of_correct_type(
                 // Accept enum values as input for custom scalars
             }
             schema::ExtendedType::Enum(enum_) => {
-                if !enum_.values.contains_key(value) {
+                if let Some(definition) = enum_.values.get(value) {
+                    definitions.enum_value(enum_, arg_value, definition)
+                } else {
                     diagnostics.push(
                         value.location(),
                         DiagnosticData::UndefinedEnumValue {
@@ -192,7 +231,7 @@ pub(crate) fn value_of_correct_type(
                 let item_type = ty.same_location(ty.item_type().clone());
                 if type_definition.is_input_type() {
                     for v in li {
-                        value_of_correct_type(diagnostics, schema, &item_type, v, var_defs);
+                        check_value(diagnostics, schema, &item_type, v, var_defs, definitions);
                     }
                 } else {
                     unsupported_type(diagnostics, arg_value, &item_type);
@@ -247,10 +286,47 @@ pub(crate) fn value_of_correct_type(
 
                     let used_val = obj.iter().find(|(obj_name, ..)| obj_name == input_name);
 
-                    if let Some((_, v)) = used_val {
-                        value_of_correct_type(diagnostics, schema, ty, v, var_defs);
+                    if let Some((name, v)) = used_val {
+                        definitions.input_field(input_obj, name, f);
+                        check_value(diagnostics, schema, ty, v, var_defs, definitions);
                     }
-                })
+                });
+
+                // A value for a OneOf input object must have exactly one field,
+                // and that field must not be null.
+                // https://spec.graphql.org/draft/#sec-OneOf-Input-Objects
+                if input_obj.is_one_of() {
+                    if let [(name, value)] = obj.as_slice() {
+                        let coordinate = TypeAttributeCoordinate {
+                            ty: input_obj.name.clone(),
+                            attribute: name.clone(),
+                        };
+                        match &**value {
+                            ast::Value::Null => diagnostics.push(
+                                value.location(),
+                                DiagnosticData::OneOfInputObjectNullField { coordinate },
+                            ),
+                            ast::Value::Variable(var_name) => {
+                                super::variable::validate_one_of_variable_usage(
+                                    diagnostics,
+                                    var_defs,
+                                    var_name,
+                                    value,
+                                    coordinate,
+                                )
+                            }
+                            _ => {}
+                        }
+                    } else {
+                        diagnostics.push(
+                            arg_value.location(),
+                            DiagnosticData::OneOfInputObjectFieldCount {
+                                name: input_obj.name.clone(),
+                                definition_location: input_obj.location(),
+                            },
+                        );
+                    }
+                }
             }
             _ => unsupported_type(diagnostics, arg_value, ty),
         },
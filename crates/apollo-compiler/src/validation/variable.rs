@@ -1,6 +1,7 @@
 use crate::ast;
 use crate::collections::HashMap;
 use crate::collections::HashSet;
+use crate::coordinate::TypeAttributeCoordinate;
 use crate::executable;
 use crate::validation::diagnostics::DiagnosticData;
 use crate::validation::value::value_of_correct_type;
@@ -294,6 +295,31 @@ pub(crate) fn validate_variable_usage(
     Ok(())
 }
 
+/// A variable used as the value of a field of a OneOf input object must be non-nullable,
+/// as a null value for that field is never allowed.
+pub(crate) fn validate_one_of_variable_usage(
+    diagnostics: &mut DiagnosticList,
+    var_defs: &[Node<ast::VariableDefinition>],
+    var_name: &Name,
+    var_usage: &Node<ast::Value>,
+    coordinate: TypeAttributeCoordinate,
+) {
+    // If the variable is not defined, we raise an error in `value.rs`
+    if let Some(var_def) = var_defs.iter().find(|v| v.name == *var_name) {
+        if !var_def.ty.is_non_null() {
+            diagnostics.push(
+                var_usage.location(),
+                DiagnosticData::OneOfInputObjectNullableVariable {
+                    variable: var_def.name.clone(),
+                    variable_type: var_def.ty.clone(),
+                    variable_location: var_def.location(),
+                    coordinate,
+                },
+            );
+        }
+    }
+}
+
 fn is_variable_usage_allowed(
     variable_def: &ast::VariableDefinition,
     variable_usage: &ast::InputValueDefinition,
@@ -0,0 +1,153 @@
+use apollo_compiler::ast;
+use apollo_compiler::name;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Node;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SDL: &str = r#"
+# The root query type
+# (hand-written)
+type Query {
+  # Look up a user
+  user(id: ID!): User # nullable if not found
+  status: Status
+}
+
+type User {
+  name: String, # display name
+}
+
+enum Status {
+  # Everything is fine
+  OK
+  LEGACY # to be removed
+}
+"#;
+
+#[test]
+fn ast_round_trip_with_edits() {
+    let mut doc = ast::Document::parse(SDL, "schema.graphql").unwrap();
+    for def in &mut doc.definitions {
+        match def {
+            ast::Definition::ObjectTypeDefinition(def) if def.name == "User" => {
+                def.make_mut().fields.push(Node::new(ast::FieldDefinition {
+                    description: None,
+                    name: name!("email"),
+                    arguments: Vec::new(),
+                    ty: ast::Type::Named(name!("String")),
+                    directives: Default::default(),
+                }));
+            }
+            ast::Definition::EnumTypeDefinition(def) => {
+                let value = def.make_mut().values.last_mut().unwrap();
+                value
+                    .make_mut()
+                    .directives
+                    .push(Node::new(ast::Directive::new(name!("deprecated"))));
+            }
+            _ => {}
+        }
+    }
+    let expected = expect![[r#"
+        # The root query type
+        # (hand-written)
+        type Query {
+          # Look up a user
+          user(id: ID!): User # nullable if not found
+          status: Status
+        }
+
+        type User {
+          name: String # display name
+          email: String
+        }
+
+        enum Status {
+          # Everything is fine
+          OK
+          LEGACY @deprecated # to be removed
+        }
+    "#]];
+    expected.assert_eq(&doc.to_string());
+
+    let comments = doc.definitions[0].comments().unwrap();
+    assert_eq!(
+        comments.leading,
+        [" The root query type", " (hand-written)"]
+    );
+    assert_eq!(comments.trailing, None);
+
+    // Comments are skipped when serializing to a single line
+    let expected = expect![[
+        r#"type Query { user(id: ID!): User status: Status } type User { name: String email: String } enum Status { OK LEGACY @deprecated }"#
+    ]];
+    expected.assert_eq(&doc.serialize().no_indent().to_string());
+}
+
+#[test]
+fn schema_and_executable_keep_comments() {
+    let schema = Schema::parse_and_validate(SDL, "schema.graphql").unwrap();
+    let expected = expect![[r#"
+        # The root query type
+        # (hand-written)
+        type Query {
+          # Look up a user
+          user(id: ID!): User # nullable if not found
+          status: Status
+        }
+
+        type User {
+          name: String # display name
+        }
+
+        enum Status {
+          # Everything is fine
+          OK
+          LEGACY # to be removed
+        }
+    "#]];
+    expected.assert_eq(&schema.to_string());
+
+    let query = r#"
+        # Fetch the current status
+        query GetStatus {
+          status # top-level field
+          # The user with a fixed ID
+          user(id: "1") {
+            name
+          }
+        }
+    "#;
+    let doc = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+    let expected = expect![[r#"
+        # Fetch the current status
+        query GetStatus {
+          status # top-level field
+          # The user with a fixed ID
+          user(id: "1") {
+            name
+          }
+        }
+    "#]];
+    expected.assert_eq(&doc.to_string());
+}
+
+#[test]
+fn programmatic_comments() {
+    let mut doc = ast::Document::parse("type Query { a: Int }", "schema.graphql").unwrap();
+    let ast::Definition::ObjectTypeDefinition(def) = &mut doc.definitions[0] else {
+        panic!("expected an object type")
+    };
+    def.comments_mut()
+        .leading
+        .push(" Added by a tool".to_owned());
+    def.make_mut().fields[0].comments_mut().trailing = Some(" TODO".to_owned());
+    let expected = expect![[r#"
+        # Added by a tool
+        type Query {
+          a: Int # TODO
+        }
+    "#]];
+    expected.assert_eq(&doc.to_string());
+}
@@ -0,0 +1,218 @@
+use apollo_compiler::completion::complete;
+use apollo_compiler::completion::CompletionItem;
+use apollo_compiler::completion::CompletionKind;
+use apollo_compiler::validation::Valid;
+use apollo_compiler::Schema;
+
+const SCHEMA: &str = r#"
+type Query {
+  user(id: ID!, role: Role = ADMIN): User
+  search(filter: Filter, roles: [Role]): [Result]
+  node: Node
+}
+
+type Mutation {
+  addUser(name: String): User
+}
+
+interface Node {
+  id: ID!
+}
+
+type User implements Node {
+  id: ID!
+  name: String
+  legacyName: String @deprecated
+}
+
+type Group implements Node {
+  id: ID!
+  members: [User]
+}
+
+union Result = User | Group
+
+enum Role {
+  ADMIN
+  GUEST
+}
+
+input Filter {
+  role: Role
+  name: String
+}
+
+directive @tag(name: String) repeatable on FIELD | QUERY
+directive @mutationOnly on MUTATION
+"#;
+
+fn schema() -> Valid<Schema> {
+    Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap()
+}
+
+/// Completes at the position of `|` in `source`
+fn complete_at(source: &str) -> Vec<CompletionItem> {
+    let offset = source.find('|').unwrap();
+    let source = source.replacen('|', "", 1);
+    complete(&schema(), &source, offset)
+}
+
+fn labels(source: &str) -> Vec<String> {
+    complete_at(source)
+        .into_iter()
+        .map(|item| item.label.to_string())
+        .collect()
+}
+
+#[test]
+fn fields() {
+    let expected = ["user", "search", "node", "__typename"];
+    assert_eq!(labels("{ | }"), expected);
+    assert_eq!(labels("query { us| }"), expected);
+    assert_eq!(labels("query { user { id } | }"), expected);
+    assert_eq!(labels("{ user(id: 1) { id } |"), expected);
+    assert_eq!(labels("{ me: | }"), expected);
+    assert_eq!(labels("mutation { | }"), ["addUser", "__typename"]);
+
+    let items = complete_at("{ user(id: 1) { na|");
+    let names: Vec<_> = items.iter().map(|item| item.label.as_str()).collect();
+    assert_eq!(names, ["id", "name", "legacyName", "__typename"]);
+    assert_eq!(items[1].kind, CompletionKind::Field);
+    assert_eq!(items[1].detail.as_deref(), Some("String"));
+    assert!(!items[1].deprecated);
+    assert!(items[2].deprecated);
+
+    assert_eq!(labels("{ node { | } }"), ["id", "__typename"]);
+    assert_eq!(labels("{ search { | } }"), ["__typename"]);
+    assert_eq!(
+        labels("{ search { ... on Group { | } } }"),
+        ["id", "members", "__typename"]
+    );
+    assert_eq!(
+        labels("fragment F on User { friends: | }"),
+        ["id", "name", "legacyName", "__typename"]
+    );
+    // Unknown parent field
+    assert_eq!(labels("{ unknown { | } }"), Vec::<String>::new());
+}
+
+#[test]
+fn arguments() {
+    assert_eq!(labels("{ user(|) }"), ["id", "role"]);
+    assert_eq!(labels("{ user(i|) }"), ["id", "role"]);
+    assert_eq!(labels("{ user(id: 1, |"), ["role"]);
+    assert_eq!(labels("{ user(id: 1 |) { id } }"), ["role"]);
+    assert_eq!(labels("{ user @tag(|) }"), ["name"]);
+
+    let items = complete_at("{ search(|) }");
+    assert_eq!(items[0].kind, CompletionKind::Argument);
+    assert_eq!(items[0].detail.as_deref(), Some("Filter"));
+}
+
+#[test]
+fn enum_values() {
+    let expected = ["ADMIN", "GUEST"];
+    assert_eq!(labels("{ user(role: |) }"), expected);
+    assert_eq!(labels("{ user(id: 1, role: GU|"), expected);
+    assert_eq!(labels("{ search(roles: [|]) }"), expected);
+    assert_eq!(labels("{ search(roles: [ADMIN, |"), expected);
+    assert_eq!(labels("{ search(filter: { role: | }) }"), expected);
+    assert_eq!(labels("query($role: Role = |) { __typename }"), expected);
+    assert_eq!(
+        complete_at("{ user(role: |) }")[0].kind,
+        CompletionKind::EnumValue
+    );
+    // Not an enum
+    assert_eq!(labels("{ user(id: |) }"), Vec::<String>::new());
+}
+
+#[test]
+fn input_fields() {
+    assert_eq!(labels("{ search(filter: { | }) }"), ["role", "name"]);
+    assert_eq!(labels("{ search(filter: { role: ADMIN, | }) }"), ["name"]);
+    let items = complete_at("{ search(filter: {|");
+    assert_eq!(items[0].kind, CompletionKind::InputField);
+    assert_eq!(items[0].detail.as_deref(), Some("Role"));
+}
+
+#[test]
+fn variables() {
+    let source = "query Q($id: ID!, $role: Role) { user(id: $|) }";
+    let items = complete_at(source);
+    let names: Vec<_> = items.iter().map(|item| item.label.as_str()).collect();
+    assert_eq!(names, ["id", "role"]);
+    assert_eq!(items[0].kind, CompletionKind::Variable);
+    assert_eq!(items[0].detail.as_deref(), Some("ID!"));
+
+    let source = "query Q($id: ID!) { user(id: $i|";
+    assert_eq!(complete_at(source).len(), 1);
+    // Defining a variable
+    assert_eq!(labels("query Q($|) { __typename }"), Vec::<String>::new());
+}
+
+#[test]
+fn fragments() {
+    let document = r#"
+        { node { ...| } }
+        fragment OnUser on User { id }
+        fragment OnNode on Node { id }
+        fragment OnResult on Result { __typename }
+        fragment OnQuery on Query { __typename }
+    "#;
+    let items = complete_at(document);
+    let names: Vec<_> = items.iter().map(|item| item.label.as_str()).collect();
+    assert_eq!(names, ["OnUser", "OnNode", "OnResult"]);
+    assert_eq!(items[0].kind, CompletionKind::Fragment);
+    assert_eq!(items[0].detail.as_deref(), Some("User"));
+
+    let document = "{ ...| }  fragment OnQuery on Query { __typename }";
+    assert_eq!(labels(document), ["OnQuery"]);
+    // A fragment cannot spread itself
+    let document = "fragment A on User { ...| }  fragment B on User { id }";
+    assert_eq!(labels(document), ["B"]);
+}
+
+#[test]
+fn directives() {
+    let items = complete_at("{ user @| }");
+    let names: Vec<_> = items.iter().map(|item| item.label.as_str()).collect();
+    assert_eq!(names, ["skip", "include", "tag"]);
+    assert_eq!(items[0].kind, CompletionKind::Directive);
+
+    assert_eq!(labels("{ user @skip(if: true) @| }"), ["include", "tag"]);
+    assert_eq!(labels("{ user @tag @| }"), ["skip", "include", "tag"]);
+    assert_eq!(labels("query @| { __typename }"), ["tag"]);
+    assert_eq!(labels("mutation @mu| { __typename }"), ["mutationOnly"]);
+    assert_eq!(labels("{ ...F @| }"), ["skip", "include"]);
+    assert_eq!(labels("fragment F on User @| { id }"), Vec::<String>::new());
+    assert_eq!(
+        labels("query($v: Int @|) { __typename }"),
+        Vec::<String>::new()
+    );
+}
+
+#[test]
+fn out_of_range() {
+    let schema = schema();
+    assert!(complete(&schema, "{ }", 10).is_empty());
+    assert!(complete(&schema, "{ é }", 3).is_empty());
+    assert!(complete(&schema, "", 0).is_empty());
+}
+
+#[test]
+fn text_not_lexed() {
+    let schema = schema();
+    // Unterminated string at the cursor
+    assert_eq!(labels(r#"{ user(a: "x|"#), Vec::<String>::new());
+    for (source, offsets) in [
+        (r#"{ user(a: "x"#, &[11, 12][..]),
+        ("{ user { name } } .", &[19]),
+        ("query { user } ~~", &[16, 17]),
+        ("{ user 1.e }", &[10, 11, 12]),
+    ] {
+        for &offset in offsets {
+            // Does not panic
+            complete(&schema, source, offset);
+        }
+    }
+}
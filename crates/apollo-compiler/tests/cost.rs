@@ -0,0 +1,217 @@
+use apollo_compiler::cost;
+use apollo_compiler::cost::CostOptions;
+use apollo_compiler::cost::OperationCost;
+use apollo_compiler::request::coerce_variable_values;
+use apollo_compiler::response::JsonMap;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+
+const SCHEMA: &str = r#"
+directive @cost(weight: Int!) on
+  | ARGUMENT_DEFINITION | ENUM | FIELD_DEFINITION | INPUT_FIELD_DEFINITION | OBJECT | SCALAR
+directive @listSize(
+  assumedSize: Int,
+  slicingArguments: [String!],
+  sizedFields: [String!],
+  requireOneSlicingArgument: Boolean = true
+) on FIELD_DEFINITION
+
+type Query {
+  user(id: ID!): User
+  users(first: Int, last: Int): [User] @listSize(slicingArguments: ["first", "last"])
+  search(filter: Filter, limit: Int = 5): UserConnection
+    @listSize(slicingArguments: ["limit"], sizedFields: ["edges"])
+  tags: [String]
+}
+
+type User {
+  id: ID!
+  name: String
+  friends: [User]
+  avatar: Image @cost(weight: 5)
+  banner: Image
+}
+
+type Image @cost(weight: 3) {
+  url: String
+}
+
+type UserConnection {
+  edges: [UserEdge]
+  total: Int
+}
+
+type UserEdge {
+  node: User
+}
+
+input Filter {
+  name: String
+  expensive: Boolean @cost(weight: 20)
+}
+"#;
+
+fn analyze(query: &str, variables: serde_json_bytes::Value) -> OperationCost {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let document = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let variables: JsonMap = variables.as_object().unwrap().clone();
+    let variables = coerce_variable_values(&schema, operation, &variables).unwrap();
+    cost::analyze(
+        &schema,
+        &document,
+        operation,
+        &variables,
+        &CostOptions::new(),
+    )
+}
+
+#[test]
+fn depth_and_counts() {
+    let query = "{ user(id: 1) { id me: name friends { friends { name } } } tags a: tags }";
+    assert_eq!(
+        analyze(query, serde_json_bytes::json!({})),
+        OperationCost {
+            max_depth: 4,
+            field_count: 8,
+            alias_count: 2,
+            root_field_count: 3,
+            // user: 1 + friends: 10 × (1 + 10 × 1)
+            cost: 111.0,
+        }
+    );
+}
+
+#[test]
+fn slicing_arguments() {
+    let query = "query($n: Int) { users(first: $n, last: 3) { name } }";
+    assert_eq!(analyze(query, serde_json_bytes::json!({"n": 7})).cost, 7.0);
+    assert_eq!(analyze(query, serde_json_bytes::json!({})).cost, 3.0);
+
+    let query = "{ search(limit: 2) { edges { node { name } } } }";
+    // search: 1 + edges: 2 × (1 + node: 1)
+    assert_eq!(analyze(query, serde_json_bytes::json!({})).cost, 5.0);
+    // Default value of `limit`
+    let query = "{ search { total edges { node { name } } } }";
+    assert_eq!(analyze(query, serde_json_bytes::json!({})).cost, 11.0);
+}
+
+#[test]
+fn cost_directive() {
+    let query = r#"{
+      user(id: 1) { avatar { url } banner { url } }
+      search(filter: { name: "a", expensive: true }) { total }
+    }"#;
+    // user: 1 + 5 + 3, search: 1 + 20
+    assert_eq!(analyze(query, serde_json_bytes::json!({})).cost, 30.0);
+}
+
+#[test]
+fn fragments_and_conditions() {
+    let query = r#"
+      query($skip: Boolean!) {
+        user(id: 1) { ...F }
+        users(first: 2) { ...F }
+        tags @include(if: false)
+        other: user(id: 2) @skip(if: $skip) { id }
+      }
+      fragment F on User { friends { id } }
+    "#;
+    assert_eq!(
+        analyze(query, serde_json_bytes::json!({"skip": true})),
+        OperationCost {
+            max_depth: 3,
+            field_count: 6,
+            alias_count: 0,
+            root_field_count: 2,
+            // user: 1 + 10, users: 2 × (1 + 10)
+            cost: 33.0,
+        }
+    );
+    let measured = analyze(query, serde_json_bytes::json!({"skip": false}));
+    assert_eq!(measured.field_count, 8);
+    assert_eq!(measured.alias_count, 1);
+    assert_eq!(measured.root_field_count, 3);
+}
+
+#[test]
+fn check_limits() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let query = "{ user(id: 1) { friends { friends { name } } } a: tags b: tags }";
+    let document = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let variables = coerce_variable_values(&schema, operation, &JsonMap::new()).unwrap();
+    let check = |options: CostOptions| match cost::check(
+        &schema, &document, operation, &variables, &options,
+    ) {
+        Ok(measured) => format!("ok: {}", measured.cost),
+        Err(error) => error.message().to_string(),
+    };
+    assert_eq!(check(CostOptions::new().max_depth(4)), "ok: 111");
+    assert_eq!(
+        check(CostOptions::new().max_depth(3)),
+        "Maximum depth exceeded: 4 > 3"
+    );
+    assert_eq!(
+        check(CostOptions::new().max_field_count(5)),
+        "Maximum field count exceeded: 6 > 5"
+    );
+    assert_eq!(
+        check(CostOptions::new().max_alias_count(1)),
+        "Maximum alias count exceeded: 2 > 1"
+    );
+    assert_eq!(
+        check(CostOptions::new().max_root_field_count(2)),
+        "Maximum root field count exceeded: 3 > 2"
+    );
+    assert_eq!(
+        check(CostOptions::new().max_cost(100.0)),
+        "Maximum cost exceeded: 111 > 100"
+    );
+    assert_eq!(
+        check(CostOptions::new().default_list_size(2).max_cost(100.0)),
+        "ok: 7"
+    );
+}
+
+fn check(query: &str, options: CostOptions) -> String {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let document = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let variables = coerce_variable_values(&schema, operation, &JsonMap::new()).unwrap();
+    match cost::check(&schema, &document, operation, &variables, &options) {
+        Ok(measured) => format!("ok: {}", measured.cost),
+        Err(error) => error.message().to_string(),
+    }
+}
+
+#[test]
+fn negative_list_size() {
+    let query = "{ a: users(first: 100) { name } b: users(first: -1000) { name } }";
+    assert_eq!(analyze(query, serde_json_bytes::json!({})).cost, 100.0);
+    assert_eq!(
+        check(query, CostOptions::new().max_cost(99.0)),
+        "Maximum cost exceeded: 100 > 99"
+    );
+}
+
+#[test]
+fn require_one_slicing_argument() {
+    assert_eq!(
+        check("{ users(last: 2) { name } }", CostOptions::new()),
+        "ok: 2"
+    );
+    // Default value of `limit`
+    assert_eq!(check("{ search { total } }", CostOptions::new()), "ok: 1");
+    assert_eq!(
+        check("{ users { name } }", CostOptions::new()),
+        "Expected exactly one slicing argument on field `users`, found 0"
+    );
+    assert_eq!(
+        check("{ users(first: 2, last: 3) { name } }", CostOptions::new()),
+        "Expected exactly one slicing argument on field `users`, found 2"
+    );
+    // Measuring does not require it
+    let query = "{ users(first: 2, last: 3) { name } }";
+    assert_eq!(analyze(query, serde_json_bytes::json!({})).cost, 3.0);
+}
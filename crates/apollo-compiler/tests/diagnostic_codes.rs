@@ -0,0 +1,272 @@
+use apollo_compiler::validation::DiagnosticCode;
+use apollo_compiler::validation::Severity;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+#[test]
+fn codes_are_unique_and_sorted() {
+    for pair in DiagnosticCode::ALL.windows(2) {
+        assert!(pair[0] < pair[1], "{:?} >= {:?}", pair[0], pair[1]);
+        assert!(pair[0].number() < pair[1].number());
+    }
+    for code in DiagnosticCode::ALL {
+        assert!(!code.description().is_empty(), "{code:?}");
+        assert_eq!(code.to_string(), format!("GQL{:04}", code.number()));
+    }
+}
+
+#[test]
+fn diagnostic_code() {
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let errors = ExecutableDocument::parse_and_validate(
+        &schema,
+        "query($a: Int, $a: Int) { a }",
+        "query.graphql",
+    )
+    .unwrap_err()
+    .errors;
+    let diagnostic = errors.iter().next().unwrap();
+    let code = diagnostic.error.code();
+    assert_eq!(code, DiagnosticCode::UniqueVariable);
+    assert_eq!(code.to_string(), "GQL0301");
+    assert_eq!(code.name(), "UniqueVariable");
+    assert_eq!(diagnostic.error.severity(), Severity::Error);
+
+    let errors = Schema::parse_and_validate("type Query { a: Int ", "schema.graphql")
+        .unwrap_err()
+        .errors;
+    let codes: Vec<_> = errors.iter().map(|d| d.error.code()).collect();
+    assert_eq!(codes, [DiagnosticCode::ExpectedToken]);
+}
+
+#[test]
+fn json_diagnostics() {
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let errors = ExecutableDocument::parse_and_validate(
+        &schema,
+        "query($a: Int, $a: Int) { a }",
+        "query.graphql",
+    )
+    .unwrap_err()
+    .errors;
+    let json = serde_json::to_string_pretty(&errors.to_json_diagnostics()).unwrap();
+    expect![[r#"
+        [
+          {
+            "code": "GQL0301",
+            "name": "UniqueVariable",
+            "severity": "error",
+            "message": "the variable `$a` is declared multiple times",
+            "location": {
+              "file": "query.graphql",
+              "start": {
+                "line": 1,
+                "column": 16
+              },
+              "end": {
+                "line": 1,
+                "column": 23
+              },
+              "offset": 15,
+              "length": 7
+            },
+            "labels": [
+              {
+                "location": {
+                  "file": "query.graphql",
+                  "start": {
+                    "line": 1,
+                    "column": 7
+                  },
+                  "end": {
+                    "line": 1,
+                    "column": 14
+                  },
+                  "offset": 6,
+                  "length": 7
+                },
+                "message": "previous definition of `$a` here"
+              },
+              {
+                "location": {
+                  "file": "query.graphql",
+                  "start": {
+                    "line": 1,
+                    "column": 16
+                  },
+                  "end": {
+                    "line": 1,
+                    "column": 23
+                  },
+                  "offset": 15,
+                  "length": 7
+                },
+                "message": "`$a` defined again here"
+              }
+            ]
+          },
+          {
+            "code": "GQL0319",
+            "name": "UnusedVariable",
+            "severity": "error",
+            "message": "unused variable: `$a`",
+            "location": {
+              "file": "query.graphql",
+              "start": {
+                "line": 1,
+                "column": 16
+              },
+              "end": {
+                "line": 1,
+                "column": 18
+              },
+              "offset": 15,
+              "length": 2
+            },
+            "labels": [
+              {
+                "location": {
+                  "file": "query.graphql",
+                  "start": {
+                    "line": 1,
+                    "column": 16
+                  },
+                  "end": {
+                    "line": 1,
+                    "column": 18
+                  },
+                  "offset": 15,
+                  "length": 2
+                },
+                "message": "variable is never used"
+              }
+            ]
+          }
+        ]"#]]
+    .assert_eq(&json);
+}
+
+#[test]
+fn sarif() {
+    let input = "type Query { a: Undefined, b: Int, b: Int }";
+    let errors = Schema::parse_and_validate(input, "dir/schema file.graphql")
+        .unwrap_err()
+        .errors;
+    let mut log = errors.to_sarif();
+    log.runs[0].tool.driver.version = Some("[version]".into());
+    let json = serde_json::to_string_pretty(&log).unwrap();
+    expect![[r#"
+        {
+          "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+          "version": "2.1.0",
+          "runs": [
+            {
+              "tool": {
+                "driver": {
+                  "name": "apollo-compiler",
+                  "version": "[version]",
+                  "informationUri": "https://github.com/apollographql/apollo-rs",
+                  "rules": [
+                    {
+                      "id": "GQL0112",
+                      "name": "ObjectFieldNameCollision",
+                      "shortDescription": {
+                        "text": "Multiple fields with the same name in an object type."
+                      }
+                    },
+                    {
+                      "id": "GQL0305",
+                      "name": "UndefinedDefinition",
+                      "shortDescription": {
+                        "text": "A reference to an undefined type."
+                      }
+                    }
+                  ]
+                }
+              },
+              "columnKind": "unicodeCodePoints",
+              "results": [
+                {
+                  "ruleId": "GQL0305",
+                  "ruleIndex": 1,
+                  "level": "error",
+                  "message": {
+                    "text": "cannot find type `Undefined` in this document"
+                  },
+                  "locations": [
+                    {
+                      "physicalLocation": {
+                        "artifactLocation": {
+                          "uri": "dir/schema%20file.graphql"
+                        },
+                        "region": {
+                          "startLine": 1,
+                          "startColumn": 17,
+                          "endLine": 1,
+                          "endColumn": 26,
+                          "byteOffset": 16,
+                          "byteLength": 9
+                        }
+                      },
+                      "message": {
+                        "text": "not found in this scope"
+                      }
+                    }
+                  ]
+                },
+                {
+                  "ruleId": "GQL0112",
+                  "ruleIndex": 0,
+                  "level": "error",
+                  "message": {
+                    "text": "duplicate definitions for the `b` field of object type `Query`"
+                  },
+                  "locations": [
+                    {
+                      "physicalLocation": {
+                        "artifactLocation": {
+                          "uri": "dir/schema%20file.graphql"
+                        },
+                        "region": {
+                          "startLine": 1,
+                          "startColumn": 36,
+                          "endLine": 1,
+                          "endColumn": 42,
+                          "byteOffset": 35,
+                          "byteLength": 6
+                        }
+                      },
+                      "message": {
+                        "text": "`b` redefined here"
+                      }
+                    }
+                  ],
+                  "relatedLocations": [
+                    {
+                      "id": 1,
+                      "physicalLocation": {
+                        "artifactLocation": {
+                          "uri": "dir/schema%20file.graphql"
+                        },
+                        "region": {
+                          "startLine": 1,
+                          "startColumn": 28,
+                          "endLine": 1,
+                          "endColumn": 29,
+                          "byteOffset": 27,
+                          "byteLength": 1
+                        }
+                      },
+                      "message": {
+                        "text": "previous definition of `b` here"
+                      }
+                    }
+                  ]
+                }
+              ]
+            }
+          ]
+        }"#]]
+    .assert_eq(&json);
+}
@@ -0,0 +1,518 @@
+use apollo_compiler::execution;
+use apollo_compiler::execution::AsyncResolvedValue;
+use apollo_compiler::execution::AsyncResolver;
+use apollo_compiler::execution::ResolvedValue;
+use apollo_compiler::execution::Resolver;
+use apollo_compiler::execution::ResolverError;
+use apollo_compiler::impl_resolver;
+use apollo_compiler::request::coerce_variable_values;
+use apollo_compiler::response::JsonMap;
+use apollo_compiler::validation::Valid;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+use futures::channel::oneshot;
+use futures::executor::block_on;
+use futures::future::BoxFuture;
+use std::cell::RefCell;
+use std::sync::Mutex;
+
+const SCHEMA: &str = r#"
+    type Query {
+        hello(name: String = "World"): String!
+        animals: [Animal!]!
+        failing: Int
+        failingNonNull: Int!
+    }
+
+    type Mutation {
+        push(value: Int!): [Int!]!
+    }
+
+    interface Animal {
+        name: String!
+    }
+
+    type Dog implements Animal {
+        name: String!
+        barks: Boolean
+    }
+
+    type Cat implements Animal {
+        name: String!
+        lives: Int
+    }
+"#;
+
+struct Query;
+
+struct Dog(&'static str);
+
+struct Cat(&'static str, i32);
+
+impl_resolver! {
+    for Query:
+
+    __typename = "Query";
+
+    fn hello(&_self, args) {
+        let name = args["name"].as_str().unwrap();
+        Ok(ResolvedValue::leaf(format!("Hello {name}!")))
+    }
+
+    fn animals() {
+        Ok(ResolvedValue::list([
+            ResolvedValue::object(Dog("Rex")),
+            ResolvedValue::object(Cat("Tom", 9)),
+        ]))
+    }
+
+    fn failing() {
+        Err(ResolverError::new("nope"))
+    }
+
+    fn failingNonNull() {
+        Err("still nope".into())
+    }
+}
+
+impl_resolver! {
+    for Dog:
+
+    __typename = "Dog";
+
+    fn name(&self_) {
+        Ok(ResolvedValue::leaf(self_.0))
+    }
+
+    fn barks() {
+        Ok(ResolvedValue::leaf(true))
+    }
+}
+
+impl_resolver! {
+    for Cat:
+
+    __typename = "Cat";
+
+    fn name(&self_) {
+        Ok(ResolvedValue::leaf(self_.0))
+    }
+
+    fn lives(&self_) {
+        Ok(ResolvedValue::leaf(self_.1))
+    }
+}
+
+fn execute(schema: &Valid<Schema>, query: &str, variables: JsonMap, root: &dyn Resolver) -> String {
+    let document = ExecutableDocument::parse_and_validate(schema, query, "query.graphql").unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let variables = coerce_variable_values(schema, operation, &variables).unwrap();
+    let response = execution::execute(schema, &document, operation, &variables, root).unwrap();
+    serde_json::to_string_pretty(&response).unwrap()
+}
+
+#[test]
+fn test_query() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let query = r#"
+        query($name: String) {
+            __typename
+            default: hello
+            hello(name: $name)
+            animals {
+                __typename
+                name
+                ... on Dog { barks }
+                ... on Cat { lives }
+            }
+            failing
+        }
+    "#;
+    let variables = [("name".into(), "Alice".into())].into_iter().collect();
+    let response = execute(&schema, query, variables, &Query);
+    expect![[r#"
+        {
+          "errors": [
+            {
+              "message": "resolver error: nope",
+              "locations": [
+                {
+                  "line": 12,
+                  "column": 13
+                }
+              ],
+              "path": [
+                "failing"
+              ]
+            }
+          ],
+          "data": {
+            "__typename": "Query",
+            "default": "Hello World!",
+            "hello": "Hello Alice!",
+            "animals": [
+              {
+                "__typename": "Dog",
+                "name": "Rex",
+                "barks": true
+              },
+              {
+                "__typename": "Cat",
+                "name": "Tom",
+                "lives": 9
+              }
+            ],
+            "failing": null
+          }
+        }"#]]
+    .assert_eq(&response);
+}
+
+#[test]
+fn test_null_propagation_to_root() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let query = "{ hello failingNonNull }";
+    let response = execute(&schema, query, Default::default(), &Query);
+    expect![[r#"
+        {
+          "errors": [
+            {
+              "message": "resolver error: still nope",
+              "locations": [
+                {
+                  "line": 1,
+                  "column": 9
+                }
+              ],
+              "path": [
+                "failingNonNull"
+              ]
+            }
+          ],
+          "data": null
+        }"#]]
+    .assert_eq(&response);
+}
+
+#[test]
+fn test_introspection() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let query = r#"
+        {
+            hello
+            __type(name: "Animal") {
+                possibleTypes { name }
+            }
+        }
+    "#;
+    let response = execute(&schema, query, Default::default(), &Query);
+    expect![[r#"
+        {
+          "data": {
+            "hello": "Hello World!",
+            "__type": {
+              "possibleTypes": [
+                {
+                  "name": "Dog"
+                },
+                {
+                  "name": "Cat"
+                }
+              ]
+            }
+          }
+        }"#]]
+    .assert_eq(&response);
+}
+
+#[test]
+fn test_mutation() {
+    struct Mutation {
+        log: RefCell<Vec<i64>>,
+    }
+
+    impl_resolver! {
+        for Mutation:
+
+        __typename = "Mutation";
+
+        fn push(&self_, args) {
+            let mut log = self_.log.borrow_mut();
+            log.push(args["value"].as_i64().unwrap());
+            Ok(ResolvedValue::list(log.clone().into_iter().map(ResolvedValue::leaf)))
+        }
+    }
+
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let query = r#"
+        mutation {
+            first: push(value: 1)
+            second: push(value: 2)
+            third: push(value: 3)
+        }
+    "#;
+    let root = Mutation {
+        log: Default::default(),
+    };
+    let response = execute(&schema, query, Default::default(), &root);
+    expect![[r#"
+        {
+          "data": {
+            "first": [
+              1
+            ],
+            "second": [
+              1,
+              2
+            ],
+            "third": [
+              1,
+              2,
+              3
+            ]
+          }
+        }"#]]
+    .assert_eq(&response);
+}
+
+struct AsyncQuery {
+    ping: Mutex<Option<oneshot::Sender<&'static str>>>,
+    pong: Mutex<Option<oneshot::Receiver<&'static str>>>,
+}
+
+struct AsyncDog(&'static str);
+
+impl AsyncResolver for AsyncQuery {
+    fn type_name(&self) -> &'static str {
+        "Query"
+    }
+
+    fn resolve_field<'a>(
+        &'a self,
+        field_name: &'a str,
+        arguments: &'a JsonMap,
+    ) -> BoxFuture<'a, Result<AsyncResolvedValue<'a>, ResolverError>> {
+        Box::pin(async move {
+            match field_name {
+                "hello" => {
+                    // Only completes if `animals` is resolved concurrently
+                    let receiver = self.pong.lock().unwrap().take().unwrap();
+                    let from = receiver.await.unwrap();
+                    let name = arguments["name"].as_str().unwrap();
+                    Ok(AsyncResolvedValue::leaf(format!(
+                        "Hello {name} from {from}!"
+                    )))
+                }
+                "animals" => {
+                    let sender = self.ping.lock().unwrap().take().unwrap();
+                    sender.send("animals").unwrap();
+                    Ok(AsyncResolvedValue::list([
+                        AsyncResolvedValue::object(AsyncDog("Rex")),
+                        AsyncResolvedValue::object(AsyncDog("Fido")),
+                    ]))
+                }
+                "failing" => Err(ResolverError::new("nope")),
+                _ => Err(format!("unexpected field {field_name}").into()),
+            }
+        })
+    }
+}
+
+impl AsyncResolver for AsyncDog {
+    fn type_name(&self) -> &'static str {
+        "Dog"
+    }
+
+    fn resolve_field<'a>(
+        &'a self,
+        field_name: &'a str,
+        _arguments: &'a JsonMap,
+    ) -> BoxFuture<'a, Result<AsyncResolvedValue<'a>, ResolverError>> {
+        Box::pin(async move {
+            match field_name {
+                "name" => Ok(AsyncResolvedValue::leaf(self.0)),
+                // Not a Boolean: field error
+                "barks" => Ok(AsyncResolvedValue::leaf("woof")),
+                _ => Err(format!("unexpected field {field_name}").into()),
+            }
+        })
+    }
+}
+
+fn new_async_query() -> AsyncQuery {
+    let (sender, receiver) = oneshot::channel();
+    AsyncQuery {
+        ping: Mutex::new(Some(sender)),
+        pong: Mutex::new(Some(receiver)),
+    }
+}
+
+fn execute_async(
+    schema: &Valid<Schema>,
+    query: &str,
+    variables: JsonMap,
+    root: &dyn AsyncResolver,
+) -> String {
+    let document = ExecutableDocument::parse_and_validate(schema, query, "query.graphql").unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let variables = coerce_variable_values(schema, operation, &variables).unwrap();
+    let future = execution::execute_async(schema, &document, operation, &variables, root);
+    fn assert_send<T: Send>(value: T) -> T {
+        value
+    }
+    let response = block_on(assert_send(future)).unwrap();
+    serde_json::to_string_pretty(&response).unwrap()
+}
+
+#[test]
+fn test_async_query() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let query = r#"
+        {
+            __typename
+            hello
+            animals {
+                __typename
+                name
+                ... on Dog { barks }
+            }
+            failing
+            __type(name: "Animal") {
+                possibleTypes { name }
+            }
+        }
+    "#;
+    let response = execute_async(&schema, query, Default::default(), &new_async_query());
+    expect![[r#"
+        {
+          "errors": [
+            {
+              "message": "Resolver returned \"woof\", expected Boolean",
+              "locations": [
+                {
+                  "line": 8,
+                  "column": 30
+                }
+              ],
+              "path": [
+                "animals",
+                0,
+                "barks"
+              ]
+            },
+            {
+              "message": "Resolver returned \"woof\", expected Boolean",
+              "locations": [
+                {
+                  "line": 8,
+                  "column": 30
+                }
+              ],
+              "path": [
+                "animals",
+                1,
+                "barks"
+              ]
+            },
+            {
+              "message": "resolver error: nope",
+              "locations": [
+                {
+                  "line": 10,
+                  "column": 13
+                }
+              ],
+              "path": [
+                "failing"
+              ]
+            }
+          ],
+          "data": {
+            "__typename": "Query",
+            "hello": "Hello World from animals!",
+            "animals": [
+              {
+                "__typename": "Dog",
+                "name": "Rex",
+                "barks": null
+              },
+              {
+                "__typename": "Dog",
+                "name": "Fido",
+                "barks": null
+              }
+            ],
+            "failing": null,
+            "__type": {
+              "possibleTypes": [
+                {
+                  "name": "Dog"
+                },
+                {
+                  "name": "Cat"
+                }
+              ]
+            }
+          }
+        }"#]]
+    .assert_eq(&response);
+}
+
+#[test]
+fn test_async_mutation() {
+    struct AsyncMutation {
+        log: Mutex<Vec<i64>>,
+    }
+
+    impl AsyncResolver for AsyncMutation {
+        fn type_name(&self) -> &'static str {
+            "Mutation"
+        }
+
+        fn resolve_field<'a>(
+            &'a self,
+            _field_name: &'a str,
+            arguments: &'a JsonMap,
+        ) -> BoxFuture<'a, Result<AsyncResolvedValue<'a>, ResolverError>> {
+            Box::pin(async move {
+                let mut log = self.log.lock().unwrap();
+                log.push(arguments["value"].as_i64().unwrap());
+                Ok(AsyncResolvedValue::list(
+                    log.clone().into_iter().map(AsyncResolvedValue::leaf),
+                ))
+            })
+        }
+    }
+
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let query = r#"
+        mutation {
+            first: push(value: 1)
+            second: push(value: 2)
+            third: push(value: 3)
+        }
+    "#;
+    let root = AsyncMutation {
+        log: Default::default(),
+    };
+    let response = execute_async(&schema, query, Default::default(), &root);
+    expect![[r#"
+        {
+          "data": {
+            "first": [
+              1
+            ],
+            "second": [
+              1,
+              2
+            ],
+            "third": [
+              1,
+              2,
+              3
+            ]
+          }
+        }"#]]
+    .assert_eq(&response);
+}
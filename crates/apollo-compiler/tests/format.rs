@@ -0,0 +1,95 @@
+use apollo_compiler::ast::Document;
+use expect_test::expect;
+
+const INPUT: &str = r#"
+query Search($term: String!, $first: Int = 10, $after: String, $filter: SearchFilter) {
+  search(term: $term, first: $first, after: $after, filter: $filter) { id }
+}
+
+# Root type
+type Query {
+  "Search everything, with a description long enough to be wrapped onto multiple lines"
+  search(term: String!, first: Int = 10, after: String, filter: SearchFilter): [Result!]! @cost(weight: 10) @auth
+  node(id: ID!): Node # by global ID
+}
+
+enum Sort { NEWEST OLDEST RELEVANCE @deprecated }
+
+directive @auth on FIELD_DEFINITION
+"#;
+
+#[test]
+fn format_wraps_long_lines() {
+    let doc = Document::parse(INPUT, "input.graphql").unwrap();
+    let expected = expect![[r#"
+        query Search(
+          $term: String!,
+          $first: Int = 10,
+          $after: String,
+          $filter: SearchFilter,
+        ) {
+          search(term: $term, first: $first, after: $after, filter: $filter) {
+            id
+          }
+        }
+
+        # Root type
+        type Query {
+          """
+          Search everything, with a description long enough to be wrapped onto multiple lines
+          """
+          search(
+            term: String!,
+            first: Int = 10,
+            after: String,
+            filter: SearchFilter,
+          ): [Result!]! @cost(weight: 10) @auth
+          node(id: ID!): Node # by global ID
+        }
+
+        enum Sort {
+          NEWEST
+          OLDEST
+          RELEVANCE @deprecated
+        }
+
+        directive @auth on FIELD_DEFINITION
+    "#]];
+    expected.assert_eq(&doc.format().to_string());
+}
+
+#[test]
+fn format_sorted() {
+    let doc = Document::parse(INPUT, "input.graphql").unwrap();
+    let expected = expect![[r#"
+        directive @auth on FIELD_DEFINITION
+
+        # Root type
+        type Query {
+          node(id: ID!): Node # by global ID
+          """Search everything, with a description long enough to be wrapped onto multiple lines"""
+          search(term: String!, first: Int = 10, after: String, filter: SearchFilter): [Result!]! @auth @cost(weight: 10)
+        }
+
+        enum Sort {
+          NEWEST
+          OLDEST
+          RELEVANCE @deprecated
+        }
+
+        query Search($term: String!, $first: Int = 10, $after: String, $filter: SearchFilter) {
+          search(term: $term, first: $first, after: $after, filter: $filter) {
+            id
+          }
+        }
+    "#]];
+    let formatted = doc
+        .format()
+        .max_line_width(200)
+        .sort_definitions(true)
+        .sort_fields(true)
+        .sort_enum_values(true)
+        .sort_directives(true)
+        .to_string();
+    expected.assert_eq(&formatted);
+}
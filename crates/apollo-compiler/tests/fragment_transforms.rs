@@ -0,0 +1,297 @@
+use apollo_compiler::executable::Selection;
+use apollo_compiler::parser::SourceSpan;
+use apollo_compiler::validation::Valid;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+type Query {
+  user(id: ID!): User
+  me: User
+  node: Node
+}
+
+interface Node {
+  id: ID!
+}
+
+type User implements Node {
+  id: ID!
+  name: String
+  friends: [User]
+  group: Group
+}
+
+type Group implements Node {
+  id: ID!
+  title: String
+}
+"#;
+
+fn schema() -> Valid<Schema> {
+    Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap()
+}
+
+fn location(selection: &Selection) -> Option<SourceSpan> {
+    match selection {
+        Selection::Field(field) => field.location(),
+        Selection::FragmentSpread(spread) => spread.location(),
+        Selection::InlineFragment(inline) => inline.location(),
+    }
+}
+
+#[test]
+fn inline_fragment_spreads() {
+    let schema = schema();
+    let source = r#"
+        query GetUser($id: ID!, $withGroup: Boolean!) {
+          user(id: $id) {
+            ...UserFields
+            ...GroupFields @include(if: $withGroup)
+          }
+          node {
+            ...UserFields
+          }
+        }
+
+        fragment UserFields on User {
+          id
+          name
+          friends { ...FriendFields }
+        }
+
+        fragment FriendFields on User {
+          name
+        }
+
+        fragment GroupFields on User {
+          group { title }
+        }
+    "#;
+    let document =
+        ExecutableDocument::parse_and_validate(&schema, source, "query.graphql").unwrap();
+    let inlined = document.inline_fragments(&schema).unwrap();
+    let expected = expect![[r#"
+        query GetUser($id: ID!, $withGroup: Boolean!) {
+          user(id: $id) {
+            ... {
+              id
+              name
+              friends {
+                ... {
+                  name
+                }
+              }
+            }
+            ... @include(if: $withGroup) {
+              group {
+                title
+              }
+            }
+          }
+          node {
+            ... on User {
+              id
+              name
+              friends {
+                ... {
+                  name
+                }
+              }
+            }
+          }
+        }
+    "#]];
+    expected.assert_eq(&inlined.to_string());
+
+    // Inline fragments keep the location of the spread they replace
+    let Selection::Field(user) = &document.operations.named["GetUser"]
+        .selection_set
+        .selections[0]
+    else {
+        panic!("expected a field")
+    };
+    let Selection::Field(inlined_user) =
+        &inlined.operations.named["GetUser"].selection_set.selections[0]
+    else {
+        panic!("expected a field")
+    };
+    assert!(location(&user.selection_set.selections[0]).is_some());
+    assert_eq!(
+        location(&inlined_user.selection_set.selections[0]),
+        location(&user.selection_set.selections[0]),
+    );
+}
+
+#[test]
+fn extract_repeated_selection_sets() {
+    let schema = schema();
+    let source = r#"
+        query GetUser($id: ID!) {
+          user(id: $id) {
+            id
+            name
+            group { id title }
+          }
+        }
+
+        query GetMe {
+          me {
+            id
+            name
+            group { id title }
+          }
+          node {
+            id
+            ... on User { group { id title } }
+          }
+        }
+    "#;
+    let document =
+        ExecutableDocument::parse_and_validate(&schema, source, "query.graphql").unwrap();
+    let extracted = document.extract_fragments(&schema).unwrap();
+    let expected = expect![[r#"
+        query GetUser($id: ID!) {
+          user(id: $id) {
+            ...UserFields
+          }
+        }
+
+        query GetMe {
+          me {
+            ...UserFields
+          }
+          node {
+            id
+            ... on User {
+              group {
+                ...GroupFields
+              }
+            }
+          }
+        }
+
+        fragment UserFields on User {
+          id
+          name
+          group {
+            ...GroupFields
+          }
+        }
+
+        fragment GroupFields on Group {
+          id
+          title
+        }
+    "#]];
+    expected.assert_eq(&extracted.to_string());
+
+    // Extracted selections keep their location
+    let Selection::Field(user) = &document.operations.named["GetUser"]
+        .selection_set
+        .selections[0]
+    else {
+        panic!("expected a field")
+    };
+    assert!(location(&user.selection_set.selections[0]).is_some());
+    assert_eq!(
+        location(&extracted.fragments["UserFields"].selection_set.selections[0]),
+        location(&user.selection_set.selections[0]),
+    );
+
+    // Inlining extracted fragments restores the original selections
+    let roundtrip = extracted.inline_fragments(&schema).unwrap();
+    let expected = expect![[r#"
+        query GetUser($id: ID!) {
+          user(id: $id) {
+            ... {
+              id
+              name
+              group {
+                ... {
+                  id
+                  title
+                }
+              }
+            }
+          }
+        }
+
+        query GetMe {
+          me {
+            ... {
+              id
+              name
+              group {
+                ... {
+                  id
+                  title
+                }
+              }
+            }
+          }
+          node {
+            id
+            ... on User {
+              group {
+                ... {
+                  id
+                  title
+                }
+              }
+            }
+          }
+        }
+    "#]];
+    expected.assert_eq(&roundtrip.to_string());
+}
+
+#[test]
+fn extracted_fragment_names_do_not_conflict() {
+    let schema = schema();
+    let source = r#"
+        {
+          user(id: 1) { ...UserFields }
+          me { id name }
+          node { ... on User { friends { id name } } }
+        }
+
+        fragment UserFields on User {
+          id
+        }
+    "#;
+    let document =
+        ExecutableDocument::parse_and_validate(&schema, source, "query.graphql").unwrap();
+    let extracted = document.extract_fragments(&schema).unwrap();
+    let expected = expect![[r#"
+        {
+          user(id: 1) {
+            ...UserFields
+          }
+          me {
+            ...UserFields2
+          }
+          node {
+            ... on User {
+              friends {
+                ...UserFields2
+              }
+            }
+          }
+        }
+
+        fragment UserFields on User {
+          id
+        }
+
+        fragment UserFields2 on User {
+          id
+          name
+        }
+    "#]];
+    expected.assert_eq(&extracted.to_string());
+
+    // Nothing is repeated anymore
+    assert_eq!(extracted.extract_fragments(&schema).unwrap(), extracted);
+}
@@ -226,3 +226,296 @@ fn built_in_scalars() {
     assert!(valid_after_mutation.types.contains_key("String"));
     assert!(valid_after_mutation.types.contains_key("Boolean"));
 }
+
+#[test]
+fn from_introspection_json_round_trip() {
+    let sdl = r#"
+        "The schema"
+        schema {
+          query: TheQuery
+          mutation: TheMutation
+        }
+
+        directive @tag(name: String! = "default" @deprecated(reason: "Use names")) repeatable on FIELD_DEFINITION | OBJECT
+
+        "Root query type"
+        type TheQuery implements I {
+          id: ID!
+          ints(filter: In = {a: 2, b: [1, 2], c: null, e: NEW}): [[Int!]]! @deprecated(reason: "…")
+          union: U @deprecated
+          old(arg: Int @deprecated): Int
+        }
+
+        type TheMutation {
+          do(input: In!): Boolean
+        }
+
+        interface I {
+          id: ID!
+        }
+
+        input In {
+          a: Int!
+          b: [Int] @deprecated(reason: null)
+          c: String = "with \"quotes\""
+          e: E = OLD
+        }
+
+        union U = TheQuery | T
+
+        type T {
+          enum: E
+        }
+
+        enum E {
+          "The new one"
+          NEW
+          OLD @deprecated(reason: "Too old")
+        }
+    "#;
+    let schema = Schema::parse_and_validate(sdl, "schema.graphql").unwrap();
+    let document = ExecutableDocument::parse_and_validate(
+        &schema,
+        include_str!("../test_data/introspection/introspect_full_schema.graphql"),
+        "query.graphql",
+    )
+    .unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let variables = coerce_variable_values(&schema, operation, &Default::default()).unwrap();
+    let response = introspection::partial_execute(
+        &schema,
+        &schema.implementers_map(),
+        &document,
+        operation,
+        &variables,
+    )
+    .unwrap();
+    let json = serde_json_bytes::json!({ "data": response.data });
+
+    let rebuilt = Schema::from_introspection_json(&json).unwrap();
+    // Differences with the original schema are inherent to introspection:
+    // * `introspect_full_schema.graphql` does not query the schema description
+    //   nor `isRepeatable`
+    // * Default values of `@deprecated(reason:)` are made explicit
+    let expected = expect![[r#"
+        schema {
+          query: TheQuery
+          mutation: TheMutation
+        }
+
+        directive @tag(
+          name: String! = "default" @deprecated(reason: "Use names"),
+        ) on FIELD_DEFINITION | OBJECT
+
+        """Root query type"""
+        type TheQuery implements I {
+          id: ID!
+          ints(filter: In = {a: 2, b: [1, 2], c: null, e: NEW}): [[Int!]]! @deprecated(reason: "…")
+          union: U @deprecated(reason: "No longer supported")
+          old(
+            arg: Int @deprecated(reason: "No longer supported"),
+          ): Int
+        }
+
+        type TheMutation {
+          do(input: In!): Boolean
+        }
+
+        interface I {
+          id: ID!
+        }
+
+        input In {
+          a: Int!
+          b: [Int] @deprecated(reason: null)
+          c: String = "with \"quotes\""
+          e: E = OLD
+        }
+
+        union U = TheQuery | T
+
+        type T {
+          enum: E
+        }
+
+        enum E {
+          """The new one"""
+          NEW
+          OLD @deprecated(reason: "Too old")
+        }
+    "#]];
+    expected.assert_eq(&rebuilt.to_string());
+    let rebuilt = rebuilt.validate().unwrap();
+    assert_eq!(rebuilt.types.len(), schema.types.len());
+}
+
+#[test]
+fn from_introspection_json_optional_fields() {
+    let json = serde_json_bytes::json!({
+        "__schema": {
+            "description": "Described",
+            "queryType": { "name": "Query" },
+            "types": [
+                {
+                    "kind": "OBJECT",
+                    "name": "Query",
+                    "fields": [
+                        {
+                            "name": "url",
+                            "args": [],
+                            "type": { "kind": "SCALAR", "name": "Url" },
+                        },
+                    ],
+                    "interfaces": [],
+                },
+                {
+                    "kind": "SCALAR",
+                    "name": "Url",
+                    "specifiedByURL": "https://url.spec.whatwg.org/",
+                },
+                { "kind": "SCALAR", "name": "String" },
+            ],
+            "directives": [
+                {
+                    "name": "skip",
+                    "locations": ["FIELD"],
+                    "args": [],
+                },
+                {
+                    "name": "tag",
+                    "locations": ["FIELD_DEFINITION"],
+                    "args": [],
+                    "isRepeatable": true,
+                },
+            ],
+        }
+    });
+    let schema = Schema::from_introspection_json(&json).unwrap();
+    let expected = expect![[r#"
+        """Described"""
+        schema {
+          query: Query
+        }
+
+        directive @tag repeatable on FIELD_DEFINITION
+
+        type Query {
+          url: Url
+        }
+
+        scalar Url @specifiedBy(url: "https://url.spec.whatwg.org/")
+    "#]];
+    expected.assert_eq(&schema.to_string());
+    schema.validate().unwrap();
+
+    let invalid = serde_json_bytes::json!({
+        "__schema": {
+            "types": [{ "kind": "OBJECT", "name": "Query", "fields": [{ "name": "f" }] }],
+        }
+    });
+    let error = Schema::from_introspection_json(&invalid).unwrap_err();
+    expect![
+        "invalid introspection response at `__schema.types[0].fields[0].type`: expected an object"
+    ]
+    .assert_eq(&error.to_string());
+}
+
+#[test]
+fn full_query() {
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let options = introspection::FullQueryOptions::new().input_value_deprecation(true);
+    let document = introspection::full_query(&schema, &options).unwrap();
+    // Same query as the test data, except for blank lines between definitions
+    assert_eq!(
+        document.to_string().replace("\n\n", "\n"),
+        include_str!("../test_data/introspection/introspect_full_schema.graphql")
+    );
+
+    let options = introspection::FullQueryOptions::new()
+        .descriptions(false)
+        .specified_by_url(true)
+        .directive_is_repeatable(true)
+        .type_ref_depth(2);
+    let document = introspection::full_query(&schema, &options).unwrap();
+    let operation = document.operations.get(None).unwrap();
+    introspection::check_max_depth(&document, operation).unwrap();
+    expect![[r#"
+        query IntrospectionQuery {
+          __schema {
+            queryType {
+              name
+            }
+            mutationType {
+              name
+            }
+            subscriptionType {
+              name
+            }
+            types {
+              ...FullType
+            }
+            directives {
+              name
+              isRepeatable
+              locations
+              args {
+                ...InputValue
+              }
+            }
+          }
+        }
+
+        fragment FullType on __Type {
+          kind
+          name
+          specifiedByURL
+          fields(includeDeprecated: true) {
+            name
+            args {
+              ...InputValue
+            }
+            type {
+              ...TypeRef
+            }
+            isDeprecated
+            deprecationReason
+          }
+          inputFields {
+            ...InputValue
+          }
+          interfaces {
+            ...TypeRef
+          }
+          enumValues(includeDeprecated: true) {
+            name
+            isDeprecated
+            deprecationReason
+          }
+          possibleTypes {
+            ...TypeRef
+          }
+        }
+
+        fragment InputValue on __InputValue {
+          name
+          type {
+            ...TypeRef
+          }
+          defaultValue
+        }
+
+        fragment TypeRef on __Type {
+          kind
+          name
+          ofType {
+            kind
+            name
+            ofType {
+              kind
+              name
+            }
+          }
+        }
+    "#]]
+    .assert_eq(&document.to_string());
+}
@@ -0,0 +1,209 @@
+use apollo_compiler::validation::lint::LintConfig;
+use apollo_compiler::validation::lint::LintLevel;
+use apollo_compiler::validation::lint::LintRule;
+use apollo_compiler::validation::DiagnosticCode;
+use apollo_compiler::validation::Severity;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+type Query {
+  user(user_id: ID!): User
+  "A list"
+  users: [User]
+  oldUser: User @deprecated(reason: "Use `user` instead")
+  node(id: ID!): Node
+}
+
+type User {
+  id: ID!
+  role: Role
+}
+
+enum Role {
+  ADMIN
+  regularUser
+}
+
+type Orphan {
+  id: ID!
+}
+
+interface Node {
+  id: ID!
+}
+
+type ReachableThroughInterface implements Node {
+  id: ID!
+}
+
+type Unreachable {
+  node: Node
+}
+
+schema {
+  query: Query
+}
+"#;
+
+#[test]
+fn schema_lints() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let diagnostics = schema.lint(&LintConfig::new());
+    assert!(!diagnostics.has_errors());
+    let codes: Vec<_> = diagnostics
+        .iter()
+        .map(|d| (d.error.code(), d.error.severity()))
+        .collect();
+    assert_eq!(
+        codes,
+        [
+            (DiagnosticCode::NamingConvention, Severity::Warning),
+            (DiagnosticCode::NamingConvention, Severity::Warning),
+            (DiagnosticCode::UnusedType, Severity::Warning),
+            (DiagnosticCode::UnusedType, Severity::Warning),
+        ]
+    );
+    let expected = expect![[r#"
+        Warning: argument `Query.user(user_id:)` should be camelCase
+           ╭─[ schema.graphql:3:8 ]
+           │
+         3 │   user(user_id: ID!): User
+           │        ───┬───  
+           │           ╰───── not camelCase
+           │ 
+           │ Help: consider renaming to `userId`
+        ───╯
+        Warning: enum value `Role.regularUser` should be SCREAMING_SNAKE_CASE
+            ╭─[ schema.graphql:17:3 ]
+            │
+         17 │   regularUser
+            │   ─────┬─────  
+            │        ╰─────── not SCREAMING_SNAKE_CASE
+            │ 
+            │ Help: consider renaming to `REGULAR_USER`
+        ────╯
+        Warning: the type `Orphan` is never used
+            ╭─[ schema.graphql:20:6 ]
+            │
+         20 │ type Orphan {
+            │      ───┬──  
+            │         ╰──── not reachable from a root operation type
+            │ 
+            │ Help: remove this type, or use it in a field, argument, or union
+        ────╯
+        Warning: the type `Unreachable` is never used
+            ╭─[ schema.graphql:32:6 ]
+            │
+         32 │ type Unreachable {
+            │      ─────┬─────  
+            │           ╰─────── not reachable from a root operation type
+            │ 
+            │ Help: remove this type, or use it in a field, argument, or union
+        ────╯
+    "#]];
+    expected.assert_eq(&diagnostics.to_string());
+}
+
+#[test]
+fn lint_levels() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+
+    let mut config = LintConfig::new();
+    for &rule in LintRule::ALL {
+        config = config.allow(rule);
+    }
+    assert!(schema.lint(&config).is_empty());
+
+    let config = config
+        .deny(LintRule::NullableListItems)
+        .warn(LintRule::MissingDescription);
+    assert_eq!(
+        config.level_of(LintRule::NullableListItems),
+        LintLevel::Deny
+    );
+    let diagnostics = schema.lint(&config);
+    assert!(diagnostics.has_errors());
+    let errors: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| d.error.severity() == Severity::Error)
+        .map(|d| d.error.to_string())
+        .collect();
+    assert_eq!(
+        errors,
+        ["the field `Query.users` has a nullable list of nullable items type `[User]`"]
+    );
+    let missing_descriptions = diagnostics
+        .iter()
+        .filter(|d| d.error.code() == DiagnosticCode::MissingDescription)
+        .count();
+    // Everything except `Query.users`
+    assert_eq!(missing_descriptions, 20);
+}
+
+#[test]
+fn rule_names() {
+    for &rule in LintRule::ALL {
+        assert_eq!(LintRule::from_name(rule.name()), Some(rule));
+        assert_eq!(rule.code().name(), rule.name());
+    }
+    assert_eq!(LintRule::from_name("UniqueVariable"), None);
+}
+
+#[test]
+fn deprecated_usage() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let query = r#"
+      query {
+        oldUser { id }
+        ...F
+      }
+      fragment F on Query {
+        user(user_id: 1) { id }
+        oldUser { role }
+      }
+    "#;
+    let document = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+    let diagnostics = document.lint(&schema, &LintConfig::new());
+    assert_eq!(diagnostics.len(), 2);
+    let expected = expect![[r#"
+        Warning: the field `Query.oldUser` is deprecated
+           ╭─[ query.graphql:3:9 ]
+           │
+         3 │         oldUser { id }
+           │         ───────┬──────  
+           │                ╰──────── deprecated field selected here
+           │
+           ├─[ schema.graphql:6:17 ]
+           │
+         6 │   oldUser: User @deprecated(reason: "Use `user` instead")
+           │                 ────────────────────┬────────────────────  
+           │                                     ╰────────────────────── deprecated here
+           │ 
+           │ Note: reason for deprecation: Use `user` instead
+        ───╯
+        Warning: the field `Query.oldUser` is deprecated
+           ╭─[ query.graphql:8:9 ]
+           │
+         8 │         oldUser { role }
+           │         ────────┬───────  
+           │                 ╰───────── deprecated field selected here
+           │
+           ├─[ schema.graphql:6:17 ]
+           │
+         6 │   oldUser: User @deprecated(reason: "Use `user` instead")
+           │                 ────────────────────┬────────────────────  
+           │                                     ╰────────────────────── deprecated here
+           │ 
+           │ Note: reason for deprecation: Use `user` instead
+        ───╯
+    "#]];
+    expected.assert_eq(&diagnostics.to_string());
+
+    let log = diagnostics.to_sarif();
+    assert_eq!(log.runs[0].results[0].level, "warning");
+
+    let config = LintConfig::new().allow(LintRule::DeprecatedUsage);
+    assert!(document.lint(&schema, &config).is_empty());
+}
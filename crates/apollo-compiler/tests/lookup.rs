@@ -0,0 +1,201 @@
+use apollo_compiler::coord;
+use apollo_compiler::coordinate::SchemaCoordinate;
+use apollo_compiler::executable::ExecutableElement;
+use apollo_compiler::name;
+use apollo_compiler::parser::SourceSpan;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+
+const SCHEMA: &str = r#"
+type Query {
+  user(id: ID!, role: Role = ADMIN): User
+  search(filter: Filter): [Result] @deprecated(reason: "Use `user`")
+}
+
+type User implements Node {
+  id: ID!
+  name: String
+  friends: [User]
+}
+
+interface Node {
+  id: ID!
+}
+
+union Result = User
+
+enum Role {
+  ADMIN
+  GUEST
+}
+
+input Filter {
+  role: Role
+}
+"#;
+
+const QUERY: &str = r#"
+query GetUser($id: ID!) {
+  user(id: $id, role: GUEST) {
+    me: name
+    friends { ...Friend }
+  }
+  search(filter: { role: ADMIN }) {
+    ... on User { name }
+  }
+}
+
+fragment Friend on User {
+  name @skip(if: false)
+}
+"#;
+
+/// The byte offset of the `nth` occurrence of `needle` in `haystack`, plus `extra`
+fn offset(haystack: &str, needle: &str, nth: usize, extra: usize) -> usize {
+    haystack.match_indices(needle).nth(nth).unwrap().0 + extra
+}
+
+fn texts<'a>(source: &'a str, spans: &[SourceSpan]) -> Vec<(usize, &'a str)> {
+    spans
+        .iter()
+        .map(|span| (span.offset(), &source[span.offset()..span.end_offset()]))
+        .collect()
+}
+
+#[test]
+fn schema_element_at() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let file_id = *schema.sources.keys().last().unwrap();
+    let at = |needle: &str, nth: usize, extra: usize| {
+        schema
+            .element_at(file_id, offset(SCHEMA, needle, nth, extra))
+            .map(|coordinate| coordinate.to_string())
+    };
+    assert_eq!(at("user(", 0, 2).as_deref(), Some("Query.user"));
+    assert_eq!(at("id: ID!", 0, 1).as_deref(), Some("Query.user(id:)"));
+    // End of a name
+    assert_eq!(at("role:", 0, 4).as_deref(), Some("Query.user(role:)"));
+    assert_eq!(at("ADMIN", 0, 1).as_deref(), Some("Role.ADMIN"));
+    assert_eq!(at("ADMIN", 1, 0).as_deref(), Some("Role.ADMIN"));
+    assert_eq!(at("): User", 0, 4).as_deref(), Some("User"));
+    assert_eq!(at("@deprecated", 0, 3).as_deref(), Some("@deprecated"));
+    assert_eq!(at("reason:", 0, 0).as_deref(), Some("@deprecated(reason:)"));
+    assert_eq!(at("implements Node", 0, 13).as_deref(), Some("Node"));
+    assert_eq!(at("= User", 0, 3).as_deref(), Some("User"));
+    // Inside a definition but not on a more specific element
+    assert_eq!(at("  name: String", 0, 0).as_deref(), Some("User"));
+    assert_eq!(at("type Query", 0, 0).as_deref(), Some("Query"));
+    assert_eq!(at("\n", 0, 0), None);
+}
+
+#[test]
+fn schema_references() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let user: SchemaCoordinate = coord!(User).into();
+    let references = schema.references(&user);
+    assert_eq!(
+        texts(SCHEMA, &references),
+        [
+            (offset(SCHEMA, "User\n", 0, 0), "User"),
+            (offset(SCHEMA, "[User]", 0, 1), "User"),
+            (offset(SCHEMA, "= User", 0, 2), "User"),
+        ]
+    );
+    let admin: SchemaCoordinate = coord!(Role.ADMIN).into();
+    assert_eq!(
+        texts(SCHEMA, &schema.references(&admin)),
+        [(offset(SCHEMA, "ADMIN", 0, 0), "ADMIN")]
+    );
+}
+
+#[test]
+fn executable_element_at() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let document = ExecutableDocument::parse_and_validate(&schema, QUERY, "query.graphql").unwrap();
+    let file_id = *document.sources.keys().next().unwrap();
+    let at = |needle: &str, nth: usize, extra: usize| {
+        document.element_at(&schema, file_id, offset(QUERY, needle, nth, extra))
+    };
+    assert_eq!(
+        at("name", 0, 1),
+        Some(ExecutableElement::Field {
+            coordinate: coord!(User.name),
+            path: vec![name!(user), name!(me)],
+        })
+    );
+    // Alias
+    assert_eq!(
+        at("me:", 0, 0),
+        Some(ExecutableElement::Field {
+            coordinate: coord!(User.name),
+            path: vec![name!(user), name!(me)],
+        })
+    );
+    assert_eq!(
+        at("$id", 1, 1),
+        Some(ExecutableElement::Variable(name!(id)))
+    );
+    assert_eq!(
+        at("$id", 0, 1),
+        Some(ExecutableElement::Variable(name!(id)))
+    );
+    assert_eq!(
+        at("GUEST", 0, 2),
+        Some(ExecutableElement::Schema(coord!(Role.GUEST).into()))
+    );
+    assert_eq!(
+        at("role: ADMIN", 0, 0),
+        Some(ExecutableElement::Schema(coord!(Filter.role).into()))
+    );
+    assert_eq!(
+        at("...Friend", 0, 5),
+        Some(ExecutableElement::Fragment(name!(Friend)))
+    );
+    assert_eq!(
+        at("on User {", 0, 4),
+        Some(ExecutableElement::Schema(coord!(User).into()))
+    );
+    assert_eq!(
+        at("ID!", 0, 0),
+        Some(ExecutableElement::Schema(coord!(ID).into()))
+    );
+    assert_eq!(
+        at("if:", 0, 0),
+        Some(ExecutableElement::Schema(coord!(@skip(if:)).into()))
+    );
+    assert_eq!(
+        at("name @skip", 0, 0),
+        Some(ExecutableElement::Field {
+            coordinate: coord!(User.name),
+            path: vec![name!(name)],
+        })
+    );
+    assert_eq!(
+        at("query GetUser", 0, 0),
+        Some(ExecutableElement::Operation(Some(name!(GetUser))))
+    );
+}
+
+#[test]
+fn executable_references() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let document = ExecutableDocument::parse_and_validate(&schema, QUERY, "query.graphql").unwrap();
+
+    let name: SchemaCoordinate = coord!(User.name).into();
+    let references = document.references(&schema, &name);
+    assert_eq!(
+        texts(QUERY, &references),
+        [
+            (offset(QUERY, "name", 0, 0), "name"),
+            (offset(QUERY, "name", 1, 0), "name"),
+            (offset(QUERY, "name", 2, 0), "name"),
+        ]
+    );
+
+    let user: SchemaCoordinate = coord!(User).into();
+    assert_eq!(document.references(&schema, &user).len(), 2);
+    let admin: SchemaCoordinate = coord!(Role.ADMIN).into();
+    assert_eq!(document.references(&schema, &admin).len(), 1);
+    let skip: SchemaCoordinate = coord!(@skip).into();
+    assert_eq!(document.references(&schema, &skip).len(), 1);
+}
@@ -1,18 +1,33 @@
+mod comments;
+mod completion;
+mod cost;
+mod diagnostic_codes;
 mod executable;
+mod execution;
 mod extensions;
 mod field_set;
 mod field_type;
+mod format;
+mod fragment_transforms;
 mod introspection;
 mod introspection_max_depth;
+mod lint;
 mod locations;
+mod lookup;
 mod merge_schemas;
 /// Formerly in src/lib.rs
 mod misc;
 mod name;
+mod normalize;
+mod one_of;
 mod parser;
+mod prune;
 mod schema;
+mod schema_diff;
 mod serde;
+mod skip_include;
 mod validation;
+mod visitor;
 
 #[path = "../examples/rename.rs"]
 mod rename;
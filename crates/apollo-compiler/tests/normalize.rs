@@ -0,0 +1,274 @@
+use apollo_compiler::executable::NormalizeOptions;
+use apollo_compiler::validation::Valid;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+type Query {
+  user(id: ID!, name: String, filter: Filter): User
+  node: Node
+  search(limit: Int, tags: [String]): [Result]
+}
+
+interface Node {
+  id: ID!
+}
+
+type User implements Node {
+  id: ID!
+  name: String
+  friends(first: Int, role: Role): [User]
+}
+
+type Group implements Node {
+  id: ID!
+  members: [User]
+}
+
+union Result = User | Group
+
+enum Role {
+  ADMIN
+  GUEST
+}
+
+input Filter {
+  role: Role
+  name: String
+}
+"#;
+
+fn schema() -> Valid<Schema> {
+    Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap()
+}
+
+fn normalize(source: &str, options: &NormalizeOptions) -> String {
+    let schema = schema();
+    let document = ExecutableDocument::parse(&schema, source, "query.graphql").unwrap();
+    document.normalize(&schema, options).to_string()
+}
+
+#[test]
+fn inline_fragments_and_merge_fields() {
+    let source = r#"
+        # A comment
+        query GetUser($id: ID!) {
+          user(name: "Ada", id: $id) {
+            ...UserFields
+            name
+            ... on User { id }
+            friends(role: ADMIN, first: 10) { name }
+          }
+          node {
+            ... on User { name }
+            ... on Group { members { id } }
+            ... on User { id }
+            id
+          }
+        }
+
+        fragment UserFields on User {
+          friends(first: 10, role: ADMIN) { id }
+          name
+        }
+
+        fragment Unused on User { id }
+    "#;
+    expect![[r#"
+        query GetUser($id: ID!) {
+          node {
+            id
+            ... on Group {
+              members {
+                id
+              }
+            }
+            ... on User {
+              id
+              name
+            }
+          }
+          user(id: $id, name: "") {
+            friends(first: 0, role: ADMIN) {
+              id
+              name
+            }
+            id
+            name
+          }
+        }
+    "#]]
+    .assert_eq(&normalize(source, &NormalizeOptions::new()));
+}
+
+#[test]
+fn keep_fragments_and_literals() {
+    let source = r#"
+        query {
+          search(tags: ["a", "b"], limit: 5) { ...ResultFields }
+          user(id: 1, filter: { role: GUEST, name: "x" }) { ...UserFields ...UserFields }
+        }
+
+        fragment UserFields on User { name id }
+        fragment ResultFields on Result { ... on User { ...UserFields } __typename }
+        fragment Unused on User { id }
+    "#;
+    let options = NormalizeOptions::new()
+        .inline_fragments(false)
+        .hide_literals(false);
+    expect![[r#"
+        {
+          search(limit: 5, tags: ["a", "b"]) {
+            ...ResultFields
+          }
+          user(filter: {name: "x", role: GUEST}, id: 1) {
+            ...UserFields
+          }
+        }
+
+        fragment ResultFields on Result {
+          __typename
+          ... on User {
+            ...UserFields
+          }
+        }
+
+        fragment UserFields on User {
+          id
+          name
+        }
+    "#]]
+    .assert_eq(&normalize(source, &options));
+
+    let options = NormalizeOptions::new().inline_fragments(false);
+    expect![[r#"
+        {
+          search(limit: 0, tags: ["", ""]) {
+            ...ResultFields
+          }
+          user(filter: {name: "", role: GUEST}, id: 0) {
+            ...UserFields
+          }
+        }
+
+        fragment ResultFields on Result {
+          __typename
+          ... on User {
+            ...UserFields
+          }
+        }
+
+        fragment UserFields on User {
+          id
+          name
+        }
+    "#]]
+    .assert_eq(&normalize(source, &options));
+}
+
+#[test]
+fn directives_prevent_merging() {
+    let source = r#"
+        query($skip: Boolean!) {
+          user(id: 1) {
+            name @skip(if: $skip)
+            name
+            ... on User @include(if: $skip) { id }
+          }
+        }
+    "#;
+    expect![[r#"
+        query($skip: Boolean!) {
+          user(id: 0) {
+            name
+            name @skip(if: $skip)
+            ... @include(if: $skip) {
+              id
+            }
+          }
+        }
+    "#]]
+    .assert_eq(&normalize(source, &NormalizeOptions::new()));
+}
+
+#[test]
+fn operation_signature() {
+    let schema = schema();
+    let source = r#"
+        query A { user(id: 1) { ...F name } }
+        query B { node { id } }
+        fragment F on User { id }
+    "#;
+    let document = ExecutableDocument::parse_and_validate(&schema, source, "a.graphql").unwrap();
+    let options = NormalizeOptions::new();
+    let signature = document
+        .operation_signature(&schema, Some("A"), &options)
+        .unwrap();
+    assert_eq!(signature, r#"query A { user(id: 0) { id name } }"#);
+
+    // Formatting, order, and literal values do not change the signature
+    let other_source = r#"
+        query A {
+          user(id: 2) {
+            name
+            id
+          }
+        }
+    "#;
+    let other = ExecutableDocument::parse_and_validate(&schema, other_source, "b.graphql").unwrap();
+    let hash = document
+        .operation_signature_hash(&schema, Some("A"), &options)
+        .unwrap();
+    assert_eq!(
+        hash,
+        other
+            .operation_signature_hash(&schema, None, &options)
+            .unwrap()
+    );
+    assert_eq!(hash.len(), 64);
+
+    let options = NormalizeOptions::new().inline_fragments(false);
+    let signature = document
+        .operation_signature(&schema, Some("A"), &options)
+        .unwrap();
+    assert_eq!(
+        signature,
+        r#"query A { user(id: 0) { name ...F } } fragment F on User { id }"#
+    );
+
+    assert!(document
+        .operation_signature(&schema, None, &options)
+        .is_err());
+}
+
+#[test]
+fn merge_fields_like_validation() {
+    // Arguments and input object fields in a different order can be merged
+    let source = r#"
+        {
+          user(id: 1, filter: { role: GUEST, name: "x" }) { name }
+          user(filter: { name: "x", role: GUEST }, id: 1) { id }
+          search(tags: ["a"]) { __typename }
+        }
+    "#;
+    expect![[r#"
+        {
+          search(tags: [""]) {
+            __typename
+          }
+          user(filter: {name: "", role: GUEST}, id: 0) {
+            id
+            name
+          }
+        }
+    "#]]
+    .assert_eq(&normalize(source, &NormalizeOptions::new()));
+
+    // Lists of different lengths do not share a placeholder
+    let other = normalize(
+        r#"{ search(tags: ["a", "b"]) { __typename } }"#,
+        &NormalizeOptions::new(),
+    );
+    assert!(other.contains(r#"search(tags: ["", ""])"#));
+}
@@ -0,0 +1,111 @@
+use apollo_compiler::introspection;
+use apollo_compiler::request::coerce_variable_values;
+use apollo_compiler::response::JsonMap;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+    type Query {
+        pet(by: PetBy!): String
+    }
+
+    input PetBy @oneOf {
+        id: ID
+        name: String
+    }
+"#;
+
+#[test]
+fn schema_validation() {
+    let sdl = r#"
+        type Query {
+            pet(by: PetBy): String
+        }
+
+        input PetBy @oneOf {
+            id: ID!
+            name: String = "Rex"
+        }
+    "#;
+    let errors = Schema::parse_and_validate(sdl, "schema.graphql")
+        .unwrap_err()
+        .errors;
+    let messages: Vec<_> = errors
+        .iter()
+        .map(|diagnostic| diagnostic.error.unstable_compat_message().unwrap())
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "OneOf input field PetBy.id must be nullable.",
+            "OneOf input field PetBy.name cannot have a default value.",
+        ]
+    );
+
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    assert!(schema.get_input_object("PetBy").unwrap().is_one_of());
+}
+
+#[test]
+fn input_coercion() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let query = "query($by: PetBy!) { pet(by: $by) }";
+    let document = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let coerce = |json: serde_json_bytes::Value| {
+        let variables: JsonMap = [("by".into(), json)].into_iter().collect();
+        match coerce_variable_values(&schema, operation, &variables) {
+            Ok(_) => "ok".to_owned(),
+            Err(error) => error.message().to_string(),
+        }
+    };
+    expect!["ok"].assert_eq(&coerce(serde_json_bytes::json!({"id": "1"})));
+    expect!["OneOf input object PetBy must have exactly one field"]
+        .assert_eq(&coerce(serde_json_bytes::json!({"id": "1", "name": "Rex"})));
+    expect!["OneOf input object PetBy must have exactly one field"]
+        .assert_eq(&coerce(serde_json_bytes::json!({})));
+    expect!["null value for field PetBy.name of OneOf input object"]
+        .assert_eq(&coerce(serde_json_bytes::json!({"name": null})));
+}
+
+#[test]
+fn introspection_round_trip() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let options = introspection::FullQueryOptions::new().one_of(true);
+    let document = introspection::full_query(&schema, &options).unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let variables = coerce_variable_values(&schema, operation, &JsonMap::new()).unwrap();
+    let response = introspection::partial_execute(
+        &schema,
+        &schema.implementers_map(),
+        &document,
+        operation,
+        &variables,
+    )
+    .unwrap();
+    let json = serde_json_bytes::to_value(&response).unwrap();
+    let types = json["data"]["__schema"]["types"].as_array().unwrap();
+    let is_one_of = |name: &str| {
+        types
+            .iter()
+            .find(|ty| ty["name"] == name)
+            .map(|ty| ty["isOneOf"].clone())
+            .unwrap()
+    };
+    assert_eq!(is_one_of("PetBy"), true);
+    assert_eq!(is_one_of("Query"), serde_json_bytes::Value::Null);
+
+    let from_introspection = Schema::from_introspection_json(&json["data"]).unwrap();
+    let expected = expect![[r#"
+        type Query {
+          pet(by: PetBy!): String
+        }
+
+        input PetBy @oneOf {
+          id: ID
+          name: String
+        }
+    "#]];
+    expected.assert_eq(&from_introspection.to_string());
+}
@@ -1,4 +1,8 @@
 use apollo_compiler::parser::Parser;
+use apollo_compiler::parser::SyntaxErrorKind;
+use apollo_compiler::parser::TokenKind;
+use apollo_compiler::Name;
+use apollo_compiler::Schema;
 
 #[test]
 fn it_errors_when_selection_set_recursion_limit_exceeded() {
@@ -98,3 +102,168 @@ fn it_errors_with_multiple_limits() {
     );
     assert!(errors.contains("doc.graphql:6:25"), "{errors}");
 }
+
+#[test]
+fn syntax_error_kinds() {
+    let input = r#"
+        type Query {
+          a(arg: Int: Int
+          b: "unterminated
+        }
+    "#;
+    let errors = Schema::parse(input, "schema.graphql").unwrap_err().errors;
+    let kinds: Vec<_> = errors
+        .iter()
+        .map(|diagnostic| diagnostic.error.syntax_error_kind())
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            Some(SyntaxErrorKind::ExpectedToken {
+                expected: TokenKind::RParen,
+                found: TokenKind::Colon
+            }),
+            Some(SyntaxErrorKind::UnterminatedString),
+            // Missing type after `b:`
+            Some(SyntaxErrorKind::UnexpectedEof),
+            Some(SyntaxErrorKind::ExpectedToken {
+                expected: TokenKind::RCurly,
+                found: TokenKind::Eof
+            }),
+        ]
+    );
+
+    let invalid = Parser::new()
+        .token_limit(5)
+        .parse_ast(input, "schema.graphql")
+        .unwrap_err();
+    let kinds: Vec<_> = invalid
+        .errors
+        .iter()
+        .map(|diagnostic| diagnostic.error.syntax_error_kind())
+        .collect();
+    assert_eq!(kinds, [Some(SyntaxErrorKind::LimitExceeded)]);
+
+    let schema = Schema::parse("type Query { a: Undefined }", "schema.graphql").unwrap();
+    let errors = schema.validate().unwrap_err().errors;
+    assert_eq!(
+        errors.iter().next().unwrap().error.syntax_error_kind(),
+        None
+    );
+}
+
+#[test]
+fn partial_definitions() {
+    let input = r#"
+type Query {
+  user: User
+
+type User {
+  name: String
+  age Int
+  email:
+}
+
+type {
+  orphan: Int
+}
+
+type {
+  other: Int
+}
+
+query Q($id: ) {
+  user { name }
+}
+
+fragment on User {
+  name
+}
+"#;
+    let ast_errors = apollo_compiler::ast::Document::parse(input, "doc.graphql").unwrap_err();
+    expect_test::expect![[r#"
+        type Query {
+          user: User
+        }
+
+        type User {
+          name: String
+          age: Int
+          email: __missing
+        }
+
+        type __missing {
+          orphan: Int
+        }
+
+        type __missing {
+          other: Int
+        }
+
+        query Q($id: __missing) {
+          user {
+            name
+          }
+        }
+
+        fragment __missing on User {
+          name
+        }
+    "#]]
+    .assert_eq(&ast_errors.partial.to_string());
+
+    let schema_input = input.split("query").next().unwrap();
+    let invalid = Schema::parse(schema_input, "schema.graphql").unwrap_err();
+    // Only syntax errors, no collisions between placeholder names
+    assert!(invalid
+        .errors
+        .iter()
+        .all(|diagnostic| diagnostic.error.syntax_error_kind().is_some()));
+    let user = invalid.partial.get_object("User").unwrap();
+    assert_eq!(user.fields.len(), 3);
+    assert_eq!(user.fields["email"].ty, apollo_compiler::ty!(__missing));
+    // Nameless `type {` definitions are left out, so the missing type does not resolve
+    assert!(invalid
+        .partial
+        .types
+        .get(user.fields["email"].ty.inner_named_type())
+        .is_none());
+    assert!(!invalid.partial.types.contains_key(&Name::MISSING));
+
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let invalid = apollo_compiler::ExecutableDocument::parse(
+        &schema,
+        "{ ...__missing } fragment on Query { a }",
+        "doc.graphql",
+    )
+    .unwrap_err();
+    assert!(invalid.partial.fragments.is_empty());
+}
+
+#[test]
+fn written_missing_name_collisions() {
+    // `__missing` written in the source is not a placeholder from syntax error recovery
+    let input = "type Query { a: Int } type __missing { a: Int } type __missing { b: Int }";
+    let invalid = Schema::parse(input, "schema.graphql").unwrap_err();
+    expect_test::expect![[r#"
+        Error: the type `__missing` is defined multiple times in the schema
+           ╭─[ schema.graphql:1:54 ]
+           │
+         1 │ type Query { a: Int } type __missing { a: Int } type __missing { b: Int }
+           │                            ────┬────                 ────┬────  
+           │                                ╰──────────────────────────────── previous definition of `__missing` here
+           │                                                          │      
+           │                                                          ╰────── `__missing` redefined here
+           │ 
+           │ Help: remove or rename one of the definitions, or use `extend`
+        ───╯
+    "#]]
+    .assert_eq(&invalid.errors.to_string());
+
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let input =
+        "{ ...__missing } fragment __missing on Query { a } fragment __missing on Query { a }";
+    let invalid =
+        apollo_compiler::ExecutableDocument::parse(&schema, input, "doc.graphql").unwrap_err();
+    assert_eq!(invalid.errors.len(), 1);
+}
@@ -0,0 +1,324 @@
+use apollo_compiler::coord;
+use apollo_compiler::coordinate::SchemaCoordinateLookup;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+#[test]
+fn prune_unreachable_types_and_directives() {
+    let input = r#"
+        directive @tag(name: String!) on FIELD_DEFINITION | OBJECT
+        directive @unused(reason: Reason) on FIELD_DEFINITION
+        directive @trace on FIELD
+
+        type Query {
+          node(id: ID!): Node
+          search: SearchResult
+        }
+
+        interface Node {
+          id: ID!
+        }
+
+        interface Named {
+          name: String
+        }
+
+        type User implements Node & Named @tag(name: "users") {
+          id: ID!
+          name: String
+        }
+
+        type Post implements Node {
+          id: ID!
+          author: User
+        }
+
+        type Comment {
+          text: String
+        }
+
+        union SearchResult = Post
+
+        enum Reason {
+          DEPRECATED
+        }
+
+        input Filter {
+          name: String
+        }
+
+        type Orphan {
+          filter(filter: Filter): Int
+        }
+    "#;
+    let schema = Schema::parse_and_validate(input, "schema.graphql").unwrap();
+    let pruned = schema.prune(&[]).unwrap();
+    let expected = expect![[r#"
+        directive @tag(name: String!) on FIELD_DEFINITION | OBJECT
+
+        directive @trace on FIELD
+
+        type Query {
+          node(id: ID!): Node
+          search: SearchResult
+        }
+
+        interface Node {
+          id: ID!
+        }
+
+        type User implements Node @tag(name: "users") {
+          id: ID!
+          name: String
+        }
+
+        type Post implements Node {
+          id: ID!
+          author: User
+        }
+
+        union SearchResult = Post
+    "#]];
+    expected.assert_eq(&pruned.to_string());
+
+    let pruned = schema
+        .prune(&[coord!(Orphan).into(), coord!(@unused).into()])
+        .unwrap();
+    let expected = expect![[r#"
+        directive @tag(name: String!) on FIELD_DEFINITION | OBJECT
+
+        directive @unused(reason: Reason) on FIELD_DEFINITION
+
+        directive @trace on FIELD
+
+        type Query {
+          node(id: ID!): Node
+          search: SearchResult
+        }
+
+        interface Node {
+          id: ID!
+        }
+
+        type User implements Node @tag(name: "users") {
+          id: ID!
+          name: String
+        }
+
+        type Post implements Node {
+          id: ID!
+          author: User
+        }
+
+        union SearchResult = Post
+
+        enum Reason {
+          DEPRECATED
+        }
+
+        input Filter {
+          name: String
+        }
+
+        type Orphan {
+          filter(filter: Filter): Int
+        }
+    "#]];
+    expected.assert_eq(&pruned.to_string());
+}
+
+#[test]
+fn filter_internal_elements() {
+    let input = r#"
+        directive @internal on OBJECT | INTERFACE | FIELD_DEFINITION | ARGUMENT_DEFINITION | ENUM_VALUE | INPUT_FIELD_DEFINITION
+        directive @audit(level: Level!) on FIELD_DEFINITION
+
+        type Query {
+          me: User
+          users(filter: Filter = { role: ADMIN }, limit: Int @internal): [User]
+          search: SearchResult
+          admin: Admin
+        }
+
+        interface Entity {
+          id: ID!
+          secret: String
+        }
+
+        type User implements Entity {
+          id: ID!
+          secret: String @internal
+          role: Role
+          admin: Admin @audit(level: HIGH)
+        }
+
+        type Admin @internal {
+          id: ID!
+        }
+
+        type Bot {
+          admin: Admin
+        }
+
+        union SearchResult = User | Admin | Bot
+
+        enum Role {
+          ADMIN @internal
+          MEMBER
+        }
+
+        enum Level {
+          HIGH @internal
+          LOW
+        }
+
+        input Filter {
+          role: Role
+          name: String
+        }
+    "#;
+    let schema = Schema::parse_and_validate(input, "schema.graphql").unwrap();
+    let filtered = schema
+        .filter(|_, element| match element {
+            SchemaCoordinateLookup::Type(ty) => ty.directives().has("internal"),
+            SchemaCoordinateLookup::Field(field) => field.directives.has("internal"),
+            SchemaCoordinateLookup::Argument(argument) => argument.directives.has("internal"),
+            SchemaCoordinateLookup::EnumValue(value) => value.directives.has("internal"),
+            SchemaCoordinateLookup::InputField(field) => field.directives.has("internal"),
+            _ => false,
+        })
+        .unwrap();
+    let expected = expect![[r#"
+        directive @internal on OBJECT | INTERFACE | FIELD_DEFINITION | ARGUMENT_DEFINITION | ENUM_VALUE | INPUT_FIELD_DEFINITION
+
+        directive @audit(level: Level!) on FIELD_DEFINITION
+
+        type Query {
+          me: User
+          users: [User]
+          search: SearchResult
+        }
+
+        interface Entity {
+          id: ID!
+          secret: String
+        }
+
+        type User {
+          id: ID!
+          role: Role
+        }
+
+        union SearchResult = User
+
+        enum Role {
+          MEMBER
+        }
+
+        enum Level {
+          LOW
+        }
+
+        input Filter {
+          role: Role
+          name: String
+        }
+    "#]];
+    expected.assert_eq(&filtered.to_string());
+
+    let pruned = filtered.prune(&[]).unwrap();
+    let expected = expect![[r#"
+        type Query {
+          me: User
+          users: [User]
+          search: SearchResult
+        }
+
+        type User {
+          id: ID!
+          role: Role
+        }
+
+        union SearchResult = User
+
+        enum Role {
+          MEMBER
+        }
+    "#]];
+    expected.assert_eq(&pruned.to_string());
+}
+
+#[test]
+fn filter_invalid_implementation_field_types() {
+    let input = r#"
+        directive @internal on FIELD_DEFINITION
+
+        type Query {
+          feeds: [Feed]
+        }
+
+        interface Node {
+          id: ID!
+          secret: String
+        }
+
+        type Post implements Node {
+          id: ID!
+          secret: String @internal
+        }
+
+        interface Feed {
+          latest: Node
+          all: [Node!]!
+        }
+
+        type PostFeed implements Feed {
+          latest: Post
+          all: [Node!]!
+        }
+
+        type NodeFeed implements Feed {
+          latest: Node
+          all: [Node!]!
+        }
+    "#;
+    let schema = Schema::parse_and_validate(input, "schema.graphql").unwrap();
+    let filtered = schema
+        .filter(|_, element| match element {
+            SchemaCoordinateLookup::Field(field) => field.directives.has("internal"),
+            _ => false,
+        })
+        .unwrap();
+    let expected = expect![[r#"
+        directive @internal on FIELD_DEFINITION
+
+        type Query {
+          feeds: [Feed]
+        }
+
+        interface Node {
+          id: ID!
+          secret: String
+        }
+
+        type Post {
+          id: ID!
+        }
+
+        interface Feed {
+          latest: Node
+          all: [Node!]!
+        }
+
+        type PostFeed {
+          latest: Post
+          all: [Node!]!
+        }
+
+        type NodeFeed implements Feed {
+          latest: Node
+          all: [Node!]!
+        }
+    "#]];
+    expected.assert_eq(&filtered.to_string());
+}
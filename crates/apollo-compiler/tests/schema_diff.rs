@@ -0,0 +1,439 @@
+use apollo_compiler::schema;
+use apollo_compiler::schema::ChangeSeverity;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+fn summary(old: &str, new: &str) -> String {
+    let old = Schema::parse_and_validate(old, "old.graphql").unwrap();
+    let new = Schema::parse_and_validate(new, "new.graphql").unwrap();
+    let diff = schema::diff(&old, &new);
+    diff.changes
+        .iter()
+        .map(|change| format!("{}: {change}\n", change.severity()))
+        .collect()
+}
+
+#[test]
+fn no_changes() {
+    let sdl = "type Query { a(b: Int = 1): [String!] @deprecated }";
+    let old = Schema::parse_and_validate(sdl, "old.graphql").unwrap();
+    // Same definitions, different locations
+    let new = Schema::parse_and_validate(format!("\n\n{sdl}"), "new.graphql").unwrap();
+    let diff = schema::diff(&old, &new);
+    assert!(diff.is_empty());
+    assert_eq!(diff.max_severity(), None);
+}
+
+#[test]
+fn types_and_fields() {
+    let old = r#"
+        type Query {
+            object: Object
+            union: U
+            removed: Int
+            narrowed: [Int]
+            widened: Int!
+            changed: Int
+            deprecated: Int
+        }
+        interface I { id: ID! }
+        type Object implements I { id: ID! }
+        type Other { id: ID! }
+        union U = Object | Other
+        scalar Kind
+        type Removed { a: Int }
+    "#;
+    let new = r#"
+        type Query {
+            object: Object
+            union: U
+            narrowed: [Int!]!
+            widened: Int
+            changed: String
+            deprecated: Int @deprecated
+            added: Int
+        }
+        interface I { id: ID! }
+        interface J { id: ID! }
+        "Now with a description"
+        type Object implements J { id: ID! }
+        type Other { id: ID! }
+        union U = Other | New
+        interface Kind { a: Int }
+        type New { a: Int }
+    "#;
+    expect![[r#"
+        breaking: field `Query.removed` was removed
+        safe: field `Query.narrowed` changed type from `[Int]` to `[Int!]!`
+        breaking: field `Query.widened` changed type from `Int!` to `Int`
+        breaking: field `Query.changed` changed type from `Int` to `String`
+        safe: `Query.deprecated` was deprecated
+        safe: field `Query.added` was added
+        safe: description of `Object` changed
+        breaking: `Object` no longer implements interface `I`
+        dangerous: `Object` now implements interface `J`
+        breaking: `Object` was removed from union `U`
+        dangerous: `New` was added to union `U`
+        breaking: `Kind` changed from a scalar type to an interface type
+        breaking: type `Removed` was removed
+        safe: type `J` was added
+        safe: type `New` was added
+    "#]]
+    .assert_eq(&summary(old, new));
+}
+
+#[test]
+fn output_subtypes() {
+    let old = r#"
+        type Query {
+            node: Node
+            nodes: [Node]
+            result: Result
+            other: Node
+        }
+        interface Node { id: ID! }
+        type User implements Node { id: ID! }
+        type Post { id: ID! }
+        union Result = User | Post
+    "#;
+    let new = r#"
+        type Query {
+            node: User!
+            nodes: [User!]
+            result: Post
+            other: Post
+        }
+        interface Node { id: ID! }
+        type User implements Node { id: ID! }
+        type Post { id: ID! }
+        union Result = User | Post
+    "#;
+    expect![[r#"
+        safe: field `Query.node` changed type from `Node` to `User!`
+        safe: field `Query.nodes` changed type from `[Node]` to `[User!]`
+        safe: field `Query.result` changed type from `Result` to `Post`
+        breaking: field `Query.other` changed type from `Node` to `Post`
+    "#]]
+    .assert_eq(&summary(old, new));
+}
+
+#[test]
+fn root_operations() {
+    let old = r#"
+        schema { query: Query, mutation: Mutation }
+        type Query { a: Int }
+        type Mutation { a: Int }
+        type OtherMutation { a: Int }
+    "#;
+    let new = r#"
+        schema { query: Query, mutation: OtherMutation, subscription: Subscription }
+        type Query { a: Int }
+        type Mutation { a: Int }
+        type OtherMutation { a: Int }
+        type Subscription { a: Int }
+    "#;
+    expect![[r#"
+        breaking: root mutation operation type changed from `Mutation` to `OtherMutation`
+        safe: root subscription operation type `Subscription` was added
+        safe: type `Subscription` was added
+    "#]]
+    .assert_eq(&summary(old, new));
+    expect![[r#"
+        breaking: root mutation operation type `Mutation` was removed
+    "#]]
+    .assert_eq(&summary(
+        old,
+        "schema { query: Query } type Query { a: Int } type Mutation { a: Int } type OtherMutation { a: Int }",
+    ));
+
+    let old = Schema::parse_and_validate(old, "old.graphql").unwrap();
+    let new = Schema::parse_and_validate(new, "new.graphql").unwrap();
+    let doc = "query Q { a } mutation M { a }";
+    let document = ExecutableDocument::parse_and_validate(&old, doc, "doc.graphql").unwrap();
+    let diff = schema::diff(&old, &new);
+    let affected: Vec<_> = diff
+        .affected_operations(&old, &new, &document)
+        .iter()
+        .map(|operation| operation.name.as_ref().unwrap().to_string())
+        .collect();
+    assert_eq!(affected, ["M"]);
+}
+
+#[test]
+fn input_values() {
+    let old = r#"
+        type Query {
+            field(
+                removed: Int
+                narrowed: Int
+                widened: Int!
+                default: Int = 1
+                input: In
+            ): Int
+        }
+        input In {
+            removed: Int
+            widened: [Int!]!
+            default: E = A
+        }
+        enum E { A B C }
+    "#;
+    let new = r#"
+        type Query {
+            field(
+                narrowed: Int!
+                widened: Int
+                default: Int = 2
+                input: In
+                optional: Int
+                required: Int!
+                requiredWithDefault: Int! = 0
+            ): Int
+        }
+        input In {
+            widened: [Int]
+            default: E
+            optional: Int
+            required: Int!
+        }
+        enum E { A B @deprecated D }
+    "#;
+    expect![[r#"
+        breaking: argument `Query.field(removed:)` was removed
+        breaking: argument `Query.field(narrowed:)` changed type from `Int` to `Int!`
+        safe: argument `Query.field(widened:)` changed type from `Int!` to `Int`
+        dangerous: default value of `Query.field(default:)` changed from `1` to `2`
+        dangerous: optional argument `Query.field(optional:)` was added
+        breaking: required argument `Query.field(required:)` was added
+        dangerous: optional argument `Query.field(requiredWithDefault:)` was added
+        breaking: input field `In.removed` was removed
+        safe: input field `In.widened` changed type from `[Int!]!` to `[Int]`
+        dangerous: default value `A` was removed from `In.default`
+        dangerous: optional input field `In.optional` was added
+        breaking: required input field `In.required` was added
+        safe: `E.B` was deprecated
+        breaking: enum value `E.C` was removed
+        dangerous: enum value `E.D` was added
+    "#]]
+    .assert_eq(&summary(old, new));
+}
+
+#[test]
+fn directives() {
+    let old = r#"
+        type Query { a: Int }
+        directive @removed on FIELD
+        directive @changed(removed: Int, optional: Int) repeatable on FIELD | QUERY
+    "#;
+    let new = r#"
+        type Query { a: Int }
+        directive @changed(optional: Int, added: Int, required: Int!) on FIELD | MUTATION
+        directive @added on FIELD
+    "#;
+    expect![[r#"
+        breaking: directive `@removed` was removed
+        breaking: directive `@changed` is no longer repeatable
+        breaking: location QUERY was removed from directive `@changed`
+        safe: location MUTATION was added to directive `@changed`
+        breaking: argument `@changed(removed:)` was removed
+        safe: optional argument `@changed(added:)` was added
+        breaking: required argument `@changed(required:)` was added
+        safe: directive `@added` was added
+    "#]]
+    .assert_eq(&summary(old, new));
+}
+
+#[test]
+fn diagnostics() {
+    let old =
+        Schema::parse_and_validate("type Query {\n  a: Int\n  b: Int\n}\n", "old.graphql").unwrap();
+    let new = Schema::parse_and_validate("type Query {\n  a: String\n}\n", "new.graphql").unwrap();
+    let diff = schema::diff(&old, &new);
+    assert!(diff.has_breaking_changes());
+    assert_eq!(diff.with_severity(ChangeSeverity::Breaking).count(), 2);
+    expect![[r#"
+        Error: field `Query.a` changed type from `Int` to `String`
+           ╭─[ old.graphql:2:3 ]
+           │
+         2 │   a: Int
+           │   ───┬──  
+           │      ╰──── in the old schema
+           │
+           ├─[ new.graphql:2:3 ]
+           │
+         2 │   a: String
+           │   ────┬────  
+           │       ╰────── in the new schema
+           │ 
+           │ Note: this is a breaking change
+        ───╯
+        Error: field `Query.b` was removed
+           ╭─[ old.graphql:3:3 ]
+           │
+         3 │   b: Int
+           │   ───┬──  
+           │      ╰──── in the old schema
+           │
+           ├─[ new.graphql:1:1 ]
+           │
+         1 │ ╭─▶ type Query {
+           ┆ ┆   
+         3 │ ├─▶ }
+           │ │       
+           │ ╰─────── in the new schema
+           │     
+           │     Note: this is a breaking change
+        ───╯
+    "#]]
+    .assert_eq(&diff.to_string());
+}
+
+#[test]
+fn affected_operations() {
+    let old = r#"
+        type Query {
+            user(id: ID!): User
+            users(filter: Filter, limit: Int): [User]
+            node(id: ID): Node
+        }
+        interface Node { id: ID! }
+        type User implements Node { id: ID! name: String, email: String }
+        type Post implements Node { id: ID! }
+        input Filter { name: String, role: Role }
+        enum Role { ADMIN USER }
+        directive @tag(name: String) on FIELD | QUERY
+    "#;
+    let new = r#"
+        type Query {
+            user(id: ID!, version: Int!): User
+            users(filter: Filter, limit: Int!): [User]
+            node(id: ID!): Node
+        }
+        interface Node { id: ID! }
+        type User implements Node { id: ID! name: String }
+        scalar Post
+        input Filter { name: String, role: Role }
+        enum Role { USER }
+        directive @tag(name: String) on FIELD
+    "#;
+    let doc = r#"
+        query Unaffected($limit: Int = 10) {
+            users(limit: $limit) { name }
+        }
+        query Affected($id: ID, $filter: Filter) @tag {
+            users(limit: 10, filter: { role: ADMIN }) { ...UserFields }
+            node(id: $id) { ... on Post { id } }
+            other: users(filter: $filter, limit: 3) { id }
+        }
+        query AlsoAffected {
+            user(id: "1") { ...UserFields }
+            again: user(id: "1") { ...UserFields }
+        }
+        fragment UserFields on User {
+            email @tag(name: "e")
+        }
+    "#;
+    let old = Schema::parse_and_validate(old, "old.graphql").unwrap();
+    let new = Schema::parse_and_validate(new, "new.graphql").unwrap();
+    let document = ExecutableDocument::parse_and_validate(&old, doc, "doc.graphql").unwrap();
+    let diff = schema::diff(&old, &new);
+    let affected = diff.affected_operations(&old, &new, &document);
+    let summary: String = affected
+        .iter()
+        .map(|operation| {
+            let name = operation.name.as_ref().unwrap();
+            let mut out = format!("{name}:\n");
+            for impact in &operation.impacts {
+                let location = impact.location.unwrap().line_column(&document.sources);
+                out += &format!("  {}: {impact}\n", location.unwrap().line);
+            }
+            out
+        })
+        .collect();
+    expect![[r#"
+        Affected:
+          5: enum value `Role.ADMIN` was removed
+          5: location QUERY was removed from directive `@tag`
+          6: enum value `Role.ADMIN` was removed
+          15: field `User.email` was removed
+          7: argument `Query.node(id:)` changed type from `ID` to `ID!`
+          7: `Post` changed from an object type to a scalar type
+        AlsoAffected:
+          11: required argument `Query.user(version:)` was added
+          15: field `User.email` was removed
+          12: required argument `Query.user(version:)` was added
+    "#]]
+    .assert_eq(&summary);
+
+    let operation = affected
+        .iter()
+        .find(|op| op.name.as_ref().unwrap() == "AlsoAffected");
+    let diagnostic = operation.unwrap().iter(&document.sources).next().unwrap();
+    expect![[r#"
+        Error: required argument `Query.user(version:)` was added
+            ╭─[ doc.graphql:11:13 ]
+            │
+         11 │             user(id: "1") { ...UserFields }
+            │             ───────────────┬───────────────  
+            │                            ╰───────────────── affected by this change
+        ────╯
+    "#]]
+    .assert_eq(&diagnostic.to_string());
+}
+
+#[test]
+fn affected_operations_interfaces_and_variables() {
+    let old = r#"
+        type Query { user: User, node: Node, search(a: Filter, b: Filter): [Node] }
+        interface Node { id: ID! }
+        type User implements Node { id: ID! }
+        input Filter { role: Role }
+        enum Role { ADMIN USER }
+    "#;
+    let new = r#"
+        type Query { user: User, node: Node, search(a: Filter, b: Filter): [Node] }
+        interface Node { id: ID! }
+        type User { id: ID! }
+        input Filter { role: Role }
+        enum Role { USER }
+    "#;
+    let doc = r#"
+        query Spread {
+            user { ... on Node { id } ...NodeFields }
+        }
+        query Variables(
+            $a: Filter
+            $b: Filter
+        ) {
+            search(a: $a, b: $b) { id }
+        }
+        fragment NodeFields on Node { id }
+    "#;
+    let old = Schema::parse_and_validate(old, "old.graphql").unwrap();
+    let new = Schema::parse_and_validate(new, "new.graphql").unwrap();
+    let document = ExecutableDocument::parse_and_validate(&old, doc, "doc.graphql").unwrap();
+    let diff = schema::diff(&old, &new);
+    let summary: String = diff
+        .affected_operations(&old, &new, &document)
+        .iter()
+        .map(|operation| {
+            let name = operation.name.as_ref().unwrap();
+            let mut out = format!("{name}:\n");
+            for impact in &operation.impacts {
+                let location = impact.location.unwrap().line_column(&document.sources);
+                out += &format!("  {}: {impact}\n", location.unwrap().line);
+            }
+            out
+        })
+        .collect();
+    expect![[r#"
+        Spread:
+          3: `User` no longer implements interface `Node`
+          11: `User` no longer implements interface `Node`
+        Variables:
+          6: enum value `Role.ADMIN` was removed
+          7: enum value `Role.ADMIN` was removed
+    "#]]
+    .assert_eq(&summary);
+}
@@ -0,0 +1,238 @@
+use apollo_compiler::request::coerce_variable_values;
+use apollo_compiler::response::JsonMap;
+use apollo_compiler::validation::Valid;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+type Query {
+  me: User
+}
+
+type User {
+  id: ID!
+  name: String
+  email: String
+  friends: [User]
+}
+"#;
+
+fn evaluate(source: &str, variables: serde_json_bytes::Value) -> String {
+    evaluate_document(source, variables).to_string()
+}
+
+fn evaluate_document(source: &str, variables: serde_json_bytes::Value) -> ExecutableDocument {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let document =
+        ExecutableDocument::parse_and_validate(&schema, source, "query.graphql").unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let variables: JsonMap = variables.as_object().unwrap().clone();
+    let variables: Valid<JsonMap> = coerce_variable_values(&schema, operation, &variables).unwrap();
+    document.evaluate_skip_include(operation, &variables)
+}
+
+#[test]
+fn remove_skipped_selections() {
+    let source = r#"
+        query Me($withEmail: Boolean!, $skipFriends: Boolean = true, $other: Boolean!) {
+          me {
+            id
+            name @skip(if: false)
+            email @include(if: $withEmail)
+            friends @skip(if: $skipFriends) { id }
+            ... @include(if: $other) @skip(if: false) { name }
+            ... @include(if: true) { email @include(if: false) }
+          }
+        }
+    "#;
+    let expected = expect![[r#"
+        query Me {
+          me {
+            id
+            name
+            email
+            ... {
+              name
+            }
+          }
+        }
+    "#]];
+    expected.assert_eq(&evaluate(
+        source,
+        serde_json_bytes::json!({"withEmail": true, "other": true}),
+    ));
+
+    let expected = expect![[r#"
+        query Me {
+          me {
+            id
+            name
+            friends {
+              id
+            }
+          }
+        }
+    "#]];
+    expected.assert_eq(&evaluate(
+        source,
+        serde_json_bytes::json!({"withEmail": false, "skipFriends": false, "other": false}),
+    ));
+}
+
+#[test]
+fn evaluate_used_fragments_only() {
+    let source = r#"
+        query Me($full: Boolean!) {
+          me {
+            ...Basic
+            ...Full @include(if: $full)
+          }
+        }
+
+        fragment Basic on User {
+          id
+          friends @include(if: $full) { ...Friend }
+        }
+
+        fragment Friend on User {
+          name
+        }
+
+        fragment Full on User {
+          name
+          email
+        }
+    "#;
+    let expected = expect![[r#"
+        query Me {
+          me {
+            ...Basic
+          }
+        }
+
+        fragment Basic on User {
+          id
+        }
+    "#]];
+    expected.assert_eq(&evaluate(source, serde_json_bytes::json!({"full": false})));
+
+    let expected = expect![[r#"
+        query Me {
+          me {
+            ...Basic
+            ...Full
+          }
+        }
+
+        fragment Basic on User {
+          id
+          friends {
+            ...Friend
+          }
+        }
+
+        fragment Friend on User {
+          name
+        }
+
+        fragment Full on User {
+          name
+          email
+        }
+    "#]];
+    expected.assert_eq(&evaluate(source, serde_json_bytes::json!({"full": true})));
+}
+
+#[test]
+fn remove_empty_fragments_and_fields() {
+    let source = r#"
+        query Me($full: Boolean!) {
+          me {
+            id
+            ...Contact
+            friends {
+              ...Contact
+              ... on User { ...Contact }
+            }
+          }
+        }
+
+        fragment Contact on User {
+          name @include(if: $full)
+          ...Email
+        }
+
+        fragment Email on User {
+          email @include(if: $full)
+        }
+    "#;
+    let expected = expect![[r#"
+        query Me {
+          me {
+            id
+          }
+        }
+    "#]];
+    expected.assert_eq(&evaluate(source, serde_json_bytes::json!({"full": false})));
+
+    let expected = expect![[r#"
+        query Me {
+          me {
+            id
+            ...Contact
+            friends {
+              ...Contact
+              ... on User {
+                ...Contact
+              }
+            }
+          }
+        }
+
+        fragment Contact on User {
+          name
+          ...Email
+        }
+
+        fragment Email on User {
+          email
+        }
+    "#]];
+    expected.assert_eq(&evaluate(source, serde_json_bytes::json!({"full": true})));
+}
+
+#[test]
+fn remove_unused_variables() {
+    let source = r#"
+        query Me($skip: Boolean!, $full: Boolean!, $friends: Boolean!) {
+          me {
+            id
+            name @skip(if: $skip)
+            ...Contact
+            friends @include(if: $friends) { ...Contact }
+          }
+        }
+
+        fragment Contact on User {
+          email @include(if: $full)
+        }
+    "#;
+    let variables = serde_json_bytes::json!({"skip": true, "full": true, "friends": false});
+    let expected = expect![[r#"
+        query Me {
+          me {
+            id
+            ...Contact
+          }
+        }
+
+        fragment Contact on User {
+          email
+        }
+    "#]];
+    let evaluated = evaluate_document(source, variables);
+    expected.assert_eq(&evaluated.to_string());
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    evaluated.validate(&schema).unwrap();
+}
@@ -128,6 +128,41 @@ fn serialize_and_reparse_ast() {
     }
 }
 
+#[test]
+#[serial]
+fn format_is_idempotent() {
+    FileId::reset();
+    let test_data_dir = test_data_dir();
+    for (input_path, input) in collect_graphql_files(&test_data_dir, &["ok", "diagnostics"]) {
+        let original =
+            ast::Document::parse(&input, "input.graphql").unwrap_or_else(|invalid| invalid.partial);
+        for sort in [false, true] {
+            let format = |doc: &ast::Document| {
+                doc.format()
+                    .max_line_width(60)
+                    .sort_definitions(sort)
+                    .sort_fields(sort)
+                    .sort_enum_values(sort)
+                    .sort_directives(sort)
+                    .to_string()
+            };
+            let formatted = format(&original);
+            let reparsed = ast::Document::parse(&formatted, "formatted.graphql")
+                .unwrap_or_else(|invalid| invalid.partial);
+            if !sort && original != reparsed {
+                panic!("Formatting changed the AST of {input_path:?}:\n{formatted}");
+            }
+            let formatted_again = format(&reparsed);
+            if formatted != formatted_again {
+                panic!(
+                    "Formatting is not idempotent for {input_path:?}:\n\
+                     {formatted}\n=>\n{formatted_again}"
+                );
+            }
+        }
+    }
+}
+
 /// Compares input code taken from a `.graphql` file in test_fixtures and its
 /// expected output in the corresponding `.txt` file.
 ///
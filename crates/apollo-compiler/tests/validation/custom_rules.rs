@@ -0,0 +1,283 @@
+use apollo_compiler::executable::Operation;
+use apollo_compiler::executable::Selection;
+use apollo_compiler::validation::DiagnosticCode;
+use apollo_compiler::validation::ExecutableValidationContext;
+use apollo_compiler::validation::ExecutableValidationRule;
+use apollo_compiler::validation::OperationValidationContext;
+use apollo_compiler::validation::RuleDiagnostics;
+use apollo_compiler::validation::SchemaValidationRule;
+use apollo_compiler::validation::Validator;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Node;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+type Query {
+  user(id: ID!): User
+  node: Node
+}
+
+type Mutation {
+  createUser(input: CreateUserInput!): User
+  deleteUser(id: ID!): Boolean
+}
+
+input CreateUserInput {
+  name: String
+}
+
+interface Node {
+  id: ID!
+}
+
+type User implements Node {
+  id: ID!
+  name: String
+}
+"#;
+
+/// Mutation fields must have an `input` argument
+struct MutationInputArgument;
+
+impl SchemaValidationRule for MutationInputArgument {
+    fn name(&self) -> &str {
+        "MutationInputArgument"
+    }
+
+    fn validate(&self, schema: &Schema, diagnostics: &mut RuleDiagnostics<'_>) {
+        let Some(mutation) = schema
+            .root_operation(apollo_compiler::ast::OperationType::Mutation)
+            .and_then(|name| schema.get_object(name))
+        else {
+            return;
+        };
+        for field in mutation.fields.values() {
+            if field.argument_by_name("input").is_none() {
+                diagnostics.push(
+                    field.location(),
+                    format!(
+                        "mutation field `{}` must have an `input` argument",
+                        field.name
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Root fields must not be aliased
+struct NoRootAliases;
+
+impl ExecutableValidationRule for NoRootAliases {
+    fn name(&self) -> &str {
+        "NoRootAliases"
+    }
+
+    fn validate_operation(
+        &self,
+        operation: &Node<Operation>,
+        _context: &OperationValidationContext<'_>,
+        diagnostics: &mut RuleDiagnostics<'_>,
+    ) {
+        for selection in &operation.selection_set.selections {
+            if let Selection::Field(field) = selection {
+                if let Some(alias) = &field.alias {
+                    diagnostics.push(
+                        alias.location(),
+                        format!("root field `{}` must not be aliased", field.name),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Like `NoRootAliases`, only in mutations
+struct NoMutationAliases;
+
+impl ExecutableValidationRule for NoMutationAliases {
+    fn name(&self) -> &str {
+        "NoMutationAliases"
+    }
+
+    fn validate(
+        &self,
+        document: &ExecutableDocument,
+        context: &ExecutableValidationContext<'_>,
+        diagnostics: &mut RuleDiagnostics<'_>,
+    ) {
+        for operation in document.operations.iter() {
+            if operation.operation_type.is_mutation() {
+                let operation_context = context.operation_context(&operation.variables);
+                NoRootAliases.validate_operation(operation, &operation_context, diagnostics)
+            }
+        }
+    }
+}
+
+/// Only one implementation of each interface selected in the document
+struct SingleImplementation;
+
+impl ExecutableValidationRule for SingleImplementation {
+    fn name(&self) -> &str {
+        "SingleImplementation"
+    }
+
+    fn validate(
+        &self,
+        _document: &ExecutableDocument,
+        context: &ExecutableValidationContext<'_>,
+        diagnostics: &mut RuleDiagnostics<'_>,
+    ) {
+        assert!(context.schema().is_some());
+        for (interface, implementers) in context.implementers_map() {
+            if implementers.objects.len() != 1 {
+                diagnostics.push(None, format!("`{interface}` has many implementations"));
+            }
+        }
+    }
+}
+
+#[test]
+fn custom_schema_rule() {
+    let validator = Validator::new().schema_rule(MutationInputArgument);
+    let schema = Schema::parse(SCHEMA, "schema.graphql").unwrap();
+    let errors = validator.validate_schema(schema).unwrap_err().errors;
+    expect![[r#"
+        Error: mutation field `deleteUser` must have an `input` argument
+           ╭─[ schema.graphql:9:3 ]
+           │
+         9 │   deleteUser(id: ID!): Boolean
+           │   ──────────────┬─────────────  
+           │                 ╰─────────────── reported by the `MutationInputArgument` rule
+        ───╯
+    "#]]
+    .assert_eq(&errors.to_string());
+    let diagnostic = errors.iter().next().unwrap();
+    assert_eq!(diagnostic.error.code(), DiagnosticCode::CustomRule);
+    assert_eq!(
+        diagnostic.error.custom_rule(),
+        Some("MutationInputArgument")
+    );
+
+    // Built-in validation still runs
+    let schema = Schema::parse("type Query { a: Undefined }", "schema.graphql").unwrap();
+    let errors = validator.validate_schema(schema).unwrap_err().errors;
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors.iter().next().unwrap().error.code(),
+        DiagnosticCode::UndefinedDefinition
+    );
+}
+
+#[test]
+fn custom_executable_rule() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let validator = Validator::new()
+        .executable_rule(NoRootAliases)
+        .executable_rule(SingleImplementation);
+    let input = r#"
+        query Q { me: user(id: 1) { name } node { id } }
+        mutation M { createUser(input: {}) { id } }
+    "#;
+    let document = ExecutableDocument::parse(&schema, input, "query.graphql").unwrap();
+    let errors = validator
+        .validate_executable(&schema, document)
+        .unwrap_err()
+        .errors;
+    let rules: Vec<_> = errors
+        .iter()
+        .map(|diagnostic| diagnostic.error.custom_rule().unwrap().to_owned())
+        .collect();
+    assert_eq!(rules, ["NoRootAliases"]);
+    expect![[r#"
+        Error: root field `user` must not be aliased
+           ╭─[ query.graphql:2:19 ]
+           │
+         2 │         query Q { me: user(id: 1) { name } node { id } }
+           │                   ─┬  
+           │                    ╰── reported by the `NoRootAliases` rule
+        ───╯
+    "#]]
+    .assert_eq(&errors.to_string());
+
+    let document =
+        ExecutableDocument::parse(&schema, "{ user(id: 1) { name } }", "query.graphql").unwrap();
+    validator.validate_executable(&schema, document).unwrap();
+}
+
+#[test]
+fn custom_document_rule_with_operation_context() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let validator = Validator::new().executable_rule(NoMutationAliases);
+    let input = r#"
+        query Q { me: user(id: 1) { name } }
+        mutation M { new: createUser(input: {}) { id } }
+    "#;
+    let document = ExecutableDocument::parse(&schema, input, "query.graphql").unwrap();
+    let errors = validator
+        .validate_executable(&schema, document)
+        .unwrap_err()
+        .errors;
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors.iter().next().unwrap().error.to_string(),
+        "root field `createUser` must not be aliased"
+    );
+}
+
+#[test]
+fn disable_built_in_rule() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let input = "query($unused: ID) { user(id: 1) { name } }  fragment F on User { id }";
+    let document = ExecutableDocument::parse(&schema, input, "query.graphql").unwrap();
+
+    let errors = Validator::new()
+        .validate_executable(&schema, document.clone())
+        .unwrap_err()
+        .errors;
+    let codes: Vec<_> = errors.iter().map(|d| d.error.code()).collect();
+    assert_eq!(
+        codes,
+        [
+            DiagnosticCode::UnusedVariable,
+            DiagnosticCode::UnusedFragment
+        ]
+    );
+
+    let errors = Validator::new()
+        .disable(DiagnosticCode::UnusedFragment)
+        .validate_executable(&schema, document.clone())
+        .unwrap_err()
+        .errors;
+    let codes: Vec<_> = errors.iter().map(|d| d.error.code()).collect();
+    assert_eq!(codes, [DiagnosticCode::UnusedVariable]);
+
+    Validator::new()
+        .disable(DiagnosticCode::UnusedFragment)
+        .disable(DiagnosticCode::UnusedVariable)
+        .validate_executable(&schema, document)
+        .unwrap();
+
+    // The recursion check does not run when all of its codes are disabled
+    let input = "type Query { a(arg: A): Int }  input A { b: B! }  input B { a: A! }";
+    let schema = Schema::parse(input, "schema.graphql").unwrap();
+    let errors = Validator::new()
+        .validate_schema(schema.clone())
+        .unwrap_err()
+        .errors;
+    let codes: Vec<_> = errors.iter().map(|d| d.error.code()).collect();
+    assert_eq!(
+        codes,
+        [
+            DiagnosticCode::RecursiveInputObjectDefinition,
+            DiagnosticCode::RecursiveInputObjectDefinition
+        ]
+    );
+    Validator::new()
+        .disable(DiagnosticCode::RecursiveInputObjectDefinition)
+        .disable(DiagnosticCode::DeeplyNestedType)
+        .validate_schema(schema)
+        .unwrap();
+}
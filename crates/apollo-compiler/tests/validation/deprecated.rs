@@ -0,0 +1,92 @@
+use apollo_compiler::validation::DeprecatedUsageKind;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const SCHEMA: &str = r#"
+type Query {
+  user(id: ID!, legacyId: Int @deprecated(reason: "Use `id`")): User
+  search(filter: Filter, order: Order = ASC): [User]
+  oldUser: User @deprecated
+}
+
+type User {
+  id: ID!
+  name: String
+  fullName: String @deprecated(reason: "Use `name`")
+}
+
+input Filter {
+  name: String
+  nickname: String @deprecated(reason: null)
+  order: Order
+}
+
+enum Order {
+  ASC
+  DESC
+  RANDOM @deprecated(reason: "Not stable")
+}
+
+directive @log(level: Int, verbose: Boolean @deprecated) on FIELD
+"#;
+
+#[test]
+fn deprecated_usages() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let query = r#"
+      query($order: Order = RANDOM) {
+        oldUser { name }
+        user(id: 1, legacyId: 2) { fullName @log(verbose: true) }
+        search(filter: { name: "a", nickname: "b", order: RANDOM }, order: $order) {
+          ...F
+        }
+      }
+      fragment F on User {
+        fullName
+        name
+      }
+    "#;
+    let document = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+    let usages = document.deprecated_usages(&schema);
+    let lines: Vec<_> = usages
+        .iter()
+        .map(|usage| {
+            let range = usage.location.unwrap().line_column_range(&document.sources);
+            format!(
+                "{}:{} {} {}: {:?}",
+                range.as_ref().unwrap().start.line,
+                range.as_ref().unwrap().start.column,
+                usage.kind,
+                usage.coordinate,
+                usage.reason
+            )
+        })
+        .collect();
+    expect![[r#"
+        [
+            "2:29 enum value Order.RANDOM: Some(\"Not stable\")",
+            "3:9 field Query.oldUser: Some(\"No longer supported\")",
+            "4:21 argument Query.user(legacyId:): Some(\"Use `id`\")",
+            "4:36 field User.fullName: Some(\"Use `name`\")",
+            "4:50 argument @log(verbose:): Some(\"No longer supported\")",
+            "5:37 input field Filter.nickname: None",
+            "5:59 enum value Order.RANDOM: Some(\"Not stable\")",
+            "10:9 field User.fullName: Some(\"Use `name`\")",
+        ]
+    "#]]
+    .assert_debug_eq(&lines);
+
+    assert_eq!(usages[0].kind, DeprecatedUsageKind::EnumValue);
+    assert!(usages
+        .iter()
+        .all(|usage| usage.deprecated_location.is_some()));
+}
+
+#[test]
+fn no_deprecated_usages() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let query = "{ user(id: 1) { name } search(order: DESC) { id } }";
+    let document = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+    assert!(document.deprecated_usages(&schema).is_empty());
+}
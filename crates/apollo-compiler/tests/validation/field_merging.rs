@@ -76,8 +76,7 @@ const GRAPHQL_JS_TEST_SCHEMA: &str = r#"
     stringListField: [String]
   }
 
-  # TODO oneOf not supported in apollo-rs
-  input OneOfInput { # @oneOf
+  input OneOfInput @oneOf {
     stringField: String
     intField: Int
   }
@@ -475,6 +474,34 @@ fn different_order_input_args() {
     );
 }
 
+#[test]
+fn conflicting_list_arg_lengths() {
+    expect_errors(
+        r#"
+      {
+        complicatedArgs {
+          stringListArgField(stringListArg: ["a"])
+          stringListArgField(stringListArg: ["a", "b"])
+        }
+      }
+    "#,
+        expect![[r#"
+            Error: operation must not provide conflicting field arguments for the same name `stringListArgField`
+               ╭─[ query.graphql:4:5 ]
+               │
+             3 │     stringListArgField(stringListArg: ["a"])
+               │     ────────────────────┬───────────────────  
+               │                         ╰───────────────────── `ComplicatedArgs.stringListArgField(stringListArg:)` is used with one argument value here
+             4 │     stringListArgField(stringListArg: ["a", "b"])
+               │     ──────────────────────┬──────────────────────  
+               │                           ╰──────────────────────── but a different value here
+               │ 
+               │ Help: The same name cannot be selected multiple times with different arguments, because it's not clear which set of arguments should be used to fill the response. If you intend to use diverging arguments, consider adding an alias to differentiate
+            ───╯
+        "#]],
+    );
+}
+
 mod field_conflicts {
     use apollo_compiler::validation::Valid;
     use apollo_compiler::ExecutableDocument;
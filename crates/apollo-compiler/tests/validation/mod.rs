@@ -1,3 +1,5 @@
+mod custom_rules;
+mod deprecated;
 mod field_merging;
 mod interface;
 mod object;
@@ -0,0 +1,699 @@
+use apollo_compiler::ast;
+use apollo_compiler::executable::Argument;
+use apollo_compiler::executable::Directive;
+use apollo_compiler::executable::Field;
+use apollo_compiler::executable::FragmentSpread;
+use apollo_compiler::executable::InlineFragment;
+use apollo_compiler::executable::SelectionSet;
+use apollo_compiler::executable::TypeInfo;
+use apollo_compiler::executable::Value;
+use apollo_compiler::executable::VisitControl;
+use apollo_compiler::executable::Visitor;
+use apollo_compiler::executable::VisitorMut;
+use apollo_compiler::name;
+use apollo_compiler::schema;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Name;
+use apollo_compiler::Node;
+use apollo_compiler::Schema;
+use expect_test::expect;
+use std::fmt::Write;
+
+const SCHEMA: &str = r#"
+    type Query {
+        search(filter: Filter, first: Int = 10): [Result]
+        secret: String
+    }
+    union Result = Product | Review
+    type Product { name: String, reviews: [Review] }
+    type Review { body: String @deprecated }
+    input Filter { tags: [String!], nested: Filter }
+    directive @tag(names: [String]) on FIELD | FRAGMENT_SPREAD
+"#;
+
+const QUERY: &str = r#"
+    query($first: Int = 5) {
+        search(filter: { tags: ["a"], nested: { tags: null } }, first: $first) {
+            __typename
+            ... on Product { name reviews { ...ReviewFields } }
+        }
+        secret
+    }
+    fragment ReviewFields on Review { body @tag(names: ["x"]) }
+"#;
+
+/// Logs visited nodes with type information
+#[derive(Default)]
+struct Log {
+    out: String,
+    depth: usize,
+}
+
+impl Log {
+    fn line(&mut self, info: &TypeInfo<'_>, node: impl std::fmt::Display) {
+        let indent = "  ".repeat(self.depth);
+        let _ = write!(self.out, "{indent}{node}");
+        if let Some(ty) = info.parent_type() {
+            let _ = write!(self.out, " | parent: {}", ty.name());
+        }
+        if let Some(def) = info.field_definition() {
+            let _ = write!(self.out, " | field: {}: {}", def.name, def.ty);
+        }
+        if let Some(def) = info.directive_definition() {
+            let _ = write!(self.out, " | directive: @{}", def.name);
+        }
+        if let Some(def) = info.argument_definition() {
+            let _ = write!(self.out, " | argument: {}", def.name);
+        }
+        if let Some(ty) = info.input_type() {
+            let _ = write!(self.out, " | input: {ty}");
+        }
+        if let Some(ty) = info.parent_input_type() {
+            let _ = write!(self.out, " | parent input: {ty}");
+        }
+        self.out.push('\n');
+    }
+}
+
+impl<'doc> Visitor<'doc> for Log {
+    fn enter_variable_definition(
+        &mut self,
+        info: &TypeInfo<'_>,
+        variable: &'doc Node<apollo_compiler::executable::VariableDefinition>,
+    ) -> VisitControl {
+        self.line(info, format_args!("${}", variable.name));
+        VisitControl::Continue
+    }
+
+    fn enter_field(&mut self, info: &TypeInfo<'_>, field: &'doc Node<Field>) -> VisitControl {
+        self.line(info, &field.name);
+        self.depth += 1;
+        VisitControl::Continue
+    }
+
+    fn leave_field(&mut self, _info: &TypeInfo<'_>, _field: &'doc Node<Field>) {
+        self.depth -= 1;
+    }
+
+    fn enter_inline_fragment(
+        &mut self,
+        info: &TypeInfo<'_>,
+        inline: &'doc Node<InlineFragment>,
+    ) -> VisitControl {
+        self.line(
+            info,
+            format_args!("... on {}", inline.type_condition.as_ref().unwrap()),
+        );
+        VisitControl::Continue
+    }
+
+    fn enter_fragment_spread(
+        &mut self,
+        info: &TypeInfo<'_>,
+        spread: &'doc Node<FragmentSpread>,
+    ) -> VisitControl {
+        self.line(info, format_args!("...{}", spread.fragment_name));
+        VisitControl::Continue
+    }
+
+    fn enter_directive(
+        &mut self,
+        info: &TypeInfo<'_>,
+        directive: &'doc Node<Directive>,
+    ) -> VisitControl {
+        self.line(info, format_args!("@{}", directive.name));
+        VisitControl::Continue
+    }
+
+    fn enter_argument(
+        &mut self,
+        info: &TypeInfo<'_>,
+        argument: &'doc Node<Argument>,
+    ) -> VisitControl {
+        self.line(info, format_args!("{}:", argument.name));
+        self.depth += 1;
+        VisitControl::Continue
+    }
+
+    fn leave_argument(&mut self, _info: &TypeInfo<'_>, _argument: &'doc Node<Argument>) {
+        self.depth -= 1;
+    }
+
+    fn enter_value(&mut self, info: &TypeInfo<'_>, value: &'doc Node<Value>) -> VisitControl {
+        self.line(info, value.serialize().no_indent());
+        VisitControl::Continue
+    }
+}
+
+#[test]
+fn type_info() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let doc = ExecutableDocument::parse_and_validate(&schema, QUERY, "query.graphql").unwrap();
+    let mut log = Log::default();
+    doc.walk(&schema, &mut log);
+    expect![[r#"
+        $first | parent: Query | input: Int
+        5 | parent: Query | input: Int
+        search | parent: Query | field: search: [Result]
+          filter: | parent: Query | field: search: [Result] | argument: filter | input: Filter
+            {tags: ["a"], nested: {tags: null}} | parent: Query | field: search: [Result] | argument: filter | input: Filter
+            ["a"] | parent: Query | field: search: [Result] | argument: filter | input: [String!] | parent input: Filter
+            "a" | parent: Query | field: search: [Result] | argument: filter | input: String! | parent input: [String!]
+            {tags: null} | parent: Query | field: search: [Result] | argument: filter | input: Filter | parent input: Filter
+            null | parent: Query | field: search: [Result] | argument: filter | input: [String!] | parent input: Filter
+          first: | parent: Query | field: search: [Result] | argument: first | input: Int
+            $first | parent: Query | field: search: [Result] | argument: first | input: Int
+          __typename | parent: Result | field: __typename: String!
+          ... on Product | parent: Result | field: search: [Result]
+          name | parent: Product | field: name: String
+          reviews | parent: Product | field: reviews: [Review]
+            ...ReviewFields | parent: Review | field: reviews: [Review]
+        secret | parent: Query | field: secret: String
+        body | parent: Review | field: body: String
+          @tag | parent: Review | field: body: String | directive: @tag
+          names: | parent: Review | field: body: String | directive: @tag | argument: names | input: [String]
+            ["x"] | parent: Review | field: body: String | directive: @tag | argument: names | input: [String]
+            "x" | parent: Review | field: body: String | directive: @tag | argument: names | input: String | parent input: [String]
+    "#]]
+    .assert_eq(&log.out);
+}
+
+#[test]
+fn skip_and_stop() {
+    struct Fields {
+        names: Vec<String>,
+        control: fn(&str) -> VisitControl,
+    }
+
+    impl<'doc> Visitor<'doc> for Fields {
+        fn enter_field(&mut self, _info: &TypeInfo<'_>, field: &'doc Node<Field>) -> VisitControl {
+            self.names.push(field.name.to_string());
+            (self.control)(&field.name)
+        }
+
+        fn leave_field(&mut self, _info: &TypeInfo<'_>, field: &'doc Node<Field>) {
+            self.names.push(format!("/{}", field.name));
+        }
+    }
+
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let doc = ExecutableDocument::parse_and_validate(&schema, QUERY, "query.graphql").unwrap();
+    let mut visitor = Fields {
+        names: Vec::new(),
+        control: |name| match name {
+            "search" => VisitControl::SkipChildren,
+            _ => VisitControl::Continue,
+        },
+    };
+    doc.walk(&schema, &mut visitor);
+    assert_eq!(
+        visitor.names,
+        ["search", "secret", "/secret", "body", "/body"]
+    );
+
+    let mut visitor = Fields {
+        names: Vec::new(),
+        control: |name| match name {
+            "name" => VisitControl::Stop,
+            _ => VisitControl::Continue,
+        },
+    };
+    doc.walk(&schema, &mut visitor);
+    assert_eq!(
+        visitor.names,
+        ["search", "__typename", "/__typename", "name"]
+    );
+}
+
+#[test]
+fn mutate() {
+    /// Removes deprecated fields, and replaces literal strings in `@tag` with `"redacted"`
+    struct Redact;
+
+    impl VisitorMut for Redact {
+        fn leave_selection_set(&mut self, info: &TypeInfo<'_>, selection_set: &mut SelectionSet) {
+            let schema = info.schema();
+            selection_set.selections.retain(|selection| {
+                selection.as_field().is_none_or(|field| {
+                    let def = schema.type_field(&selection_set.ty, &field.name).unwrap();
+                    !def.directives.has("deprecated")
+                })
+            })
+        }
+
+        fn enter_value(&mut self, info: &TypeInfo<'_>, value: &mut Node<Value>) -> VisitControl {
+            let in_tag = info
+                .directive_definition()
+                .is_some_and(|def| def.name == "tag");
+            if in_tag && matches!(**value, Value::String(_)) {
+                *value = Node::new(Value::String("redacted".into()));
+            }
+            VisitControl::Continue
+        }
+
+        fn enter_field(&mut self, info: &TypeInfo<'_>, field: &mut Node<Field>) -> VisitControl {
+            if info.field_definition().unwrap().ty.is_list() {
+                field.make_mut().alias = Some(name!(list));
+            }
+            VisitControl::Continue
+        }
+    }
+
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let query = r#"
+        {
+            search {
+                ... on Review { __typename body }
+                ... on Product { reviews { body id: __typename } ...Tagged }
+            }
+        }
+        fragment Tagged on Product { name @tag(names: ["secret", null]) }
+    "#;
+    let mut doc = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql")
+        .unwrap()
+        .into_inner();
+    doc.walk_mut(&schema, &mut Redact);
+    expect![[r#"
+        {
+          list: search {
+            ... on Review {
+              __typename
+            }
+            ... on Product {
+              list: reviews {
+                id: __typename
+              }
+              ...Tagged
+            }
+          }
+        }
+
+        fragment Tagged on Product {
+          name @tag(names: ["redacted", null])
+        }
+    "#]]
+    .assert_eq(&doc.to_string());
+}
+
+#[test]
+fn mutate_only_changed_nodes() {
+    /// Replaces `first` argument values with `1`
+    struct FirstOne;
+
+    impl VisitorMut for FirstOne {
+        fn enter_argument(
+            &mut self,
+            _info: &TypeInfo<'_>,
+            argument: &mut Node<Argument>,
+        ) -> VisitControl {
+            if argument.name == "first" {
+                argument.make_mut().value = Node::new(Value::Int(1.into()));
+            }
+            VisitControl::Continue
+        }
+    }
+
+    struct Noop;
+
+    impl VisitorMut for Noop {}
+
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let original = ExecutableDocument::parse_and_validate(&schema, QUERY, "query.graphql")
+        .unwrap()
+        .into_inner();
+
+    let mut doc = original.clone();
+    doc.walk_mut(&schema, &mut Noop);
+    let operation = original.operations.anonymous.as_ref().unwrap();
+    assert!(doc.operations.anonymous.as_ref().unwrap().ptr_eq(operation));
+    assert!(doc.fragments["ReviewFields"].ptr_eq(&original.fragments["ReviewFields"]));
+
+    let mut doc = original.clone();
+    doc.walk_mut(&schema, &mut FirstOne);
+    let new_operation = doc.operations.anonymous.as_ref().unwrap();
+    assert!(!new_operation.ptr_eq(operation));
+    let field = |operation: &ExecutableDocument, index: usize| {
+        operation
+            .operations
+            .anonymous
+            .as_ref()
+            .unwrap()
+            .selection_set
+            .selections[index]
+            .as_field()
+            .unwrap()
+            .clone()
+    };
+    // `search` has the changed argument, `secret` is still shared
+    assert!(!field(&doc, 0).ptr_eq(&field(&original, 0)));
+    assert!(field(&doc, 1).ptr_eq(&field(&original, 1)));
+    assert!(doc.fragments["ReviewFields"].ptr_eq(&original.fragments["ReviewFields"]));
+    assert_eq!(field(&doc, 0).arguments[1].value.to_string(), "1",);
+}
+
+#[test]
+fn type_info_follows_renamed_nodes() {
+    /// Renames `secret` to `search`, and logs field definitions seen by children
+    #[derive(Default)]
+    struct Rename(Vec<String>);
+
+    impl VisitorMut for Rename {
+        fn enter_field(&mut self, info: &TypeInfo<'_>, field: &mut Node<Field>) -> VisitControl {
+            let before = info.field_definition().map(|def| def.name.to_string());
+            if field.name == "secret" {
+                let field = field.make_mut();
+                field.name = name!(search);
+                field.selection_set = SelectionSet::new(name!(Result));
+            }
+            self.0.push(format!("enter {}: {before:?}", field.name));
+            VisitControl::Continue
+        }
+
+        fn enter_selection_set(
+            &mut self,
+            info: &TypeInfo<'_>,
+            _selection_set: &mut SelectionSet,
+        ) -> VisitControl {
+            let parent = info.parent_type().map(|ty| ty.name().to_string());
+            self.0.push(format!("selection set: {parent:?}"));
+            VisitControl::Continue
+        }
+
+        fn leave_field(&mut self, info: &TypeInfo<'_>, field: &mut Node<Field>) {
+            let after = info.field_definition().map(|def| def.name.to_string());
+            self.0.push(format!("leave {}: {after:?}", field.name));
+        }
+    }
+
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let mut doc = ExecutableDocument::parse_and_validate(&schema, "{ secret }", "query.graphql")
+        .unwrap()
+        .into_inner();
+    let mut visitor = Rename::default();
+    doc.walk_mut(&schema, &mut visitor);
+    assert_eq!(
+        visitor.0,
+        [
+            "selection set: Some(\"Query\")",
+            "enter search: Some(\"secret\")",
+            "selection set: Some(\"Result\")",
+            "leave search: Some(\"search\")",
+        ]
+    );
+}
+
+/// Logs visited type system nodes
+#[derive(Default)]
+struct TypeSystemLog(Vec<String>);
+
+impl<'doc> schema::Visitor<'doc> for TypeSystemLog {
+    fn enter_definition(&mut self, definition: &'doc ast::Definition) -> VisitControl {
+        let name = definition.name().map(|name| name.as_str());
+        let extension = if definition.is_extension_definition() {
+            "extension"
+        } else {
+            "definition"
+        };
+        self.0.push(format!("{extension} {name:?}"));
+        VisitControl::Continue
+    }
+
+    fn enter_schema_definition(
+        &mut self,
+        _definition: &'doc Node<schema::SchemaDefinition>,
+    ) -> VisitControl {
+        self.0.push("schema".into());
+        VisitControl::Continue
+    }
+
+    fn enter_directive_definition(
+        &mut self,
+        definition: &'doc Node<schema::DirectiveDefinition>,
+    ) -> VisitControl {
+        self.0.push(format!("directive @{}", definition.name));
+        VisitControl::Continue
+    }
+
+    fn enter_type(&mut self, ty: &'doc schema::ExtendedType) -> VisitControl {
+        self.0.push(format!("type {}", ty.name()));
+        // Skip children of scalars
+        if ty.is_scalar() {
+            VisitControl::SkipChildren
+        } else {
+            VisitControl::Continue
+        }
+    }
+
+    fn enter_field_definition(
+        &mut self,
+        field: &'doc Node<schema::FieldDefinition>,
+    ) -> VisitControl {
+        self.0.push(format!("  field {}", field.name));
+        VisitControl::Continue
+    }
+
+    fn enter_input_value_definition(
+        &mut self,
+        definition: &'doc Node<schema::InputValueDefinition>,
+    ) -> VisitControl {
+        self.0.push(format!("  input value {}", definition.name));
+        VisitControl::Continue
+    }
+
+    fn enter_enum_value_definition(
+        &mut self,
+        definition: &'doc Node<schema::EnumValueDefinition>,
+    ) -> VisitControl {
+        self.0.push(format!("  enum value {}", definition.value));
+        VisitControl::Continue
+    }
+
+    fn enter_directive(&mut self, directive: &'doc Node<Directive>) -> VisitControl {
+        self.0.push(format!("  @{}", directive.name));
+        VisitControl::Continue
+    }
+
+    fn enter_value(&mut self, value: &'doc Node<Value>) -> VisitControl {
+        self.0
+            .push(format!("  value {}", value.serialize().no_indent()));
+        VisitControl::Continue
+    }
+
+    fn named_type(&mut self, name: &'doc schema::NamedType) {
+        self.0.push(format!("  -> {name}"));
+    }
+}
+
+const TYPE_SYSTEM: &str = r#"
+    directive @tag(names: [String] = ["a"]) on OBJECT | ENUM_VALUE
+    scalar Date @specifiedBy(url: "https://example.com")
+    type Query implements Node @tag { node(id: ID!): Node, date: Date }
+    interface Node { id: ID! }
+    union Result = Query
+    enum Level { LOW @tag(names: ["b"]) }
+    input Filter { level: Level = LOW }
+"#;
+
+#[test]
+fn walk_type_system() {
+    let schema = Schema::parse(TYPE_SYSTEM, "schema.graphql").unwrap();
+    let mut visitor = TypeSystemLog::default();
+    schema.walk(&mut visitor);
+    let expected = expect![[r#"
+        schema
+          -> Query
+        directive @tag
+          input value names
+          -> String
+          value ["a"]
+          value "a"
+        type Date
+        type Query
+          -> Node
+          @tag
+          field node
+          input value id
+          -> ID
+          -> Node
+          field date
+          -> Date
+        type Node
+          field id
+          -> ID
+        type Result
+          -> Query
+        type Level
+          enum value LOW
+          @tag
+          value ["b"]
+          value "b"
+        type Filter
+          input value level
+          -> Level
+          value LOW"#]];
+    expected.assert_eq(&visitor.0.join("\n"));
+
+    let source = format!("{TYPE_SYSTEM} extend type Query {{ level: Level }} {{ date }}");
+    let document = ast::Document::parse(source, "doc.graphql").unwrap();
+    let mut visitor = TypeSystemLog::default();
+    document.walk(&mut visitor);
+    let expected = expect![[r#"
+        definition Some("tag")
+          input value names
+          -> String
+          value ["a"]
+          value "a"
+        definition Some("Date")
+          @specifiedBy
+          value "https://example.com"
+        definition Some("Query")
+          -> Node
+          @tag
+          field node
+          input value id
+          -> ID
+          -> Node
+          field date
+          -> Date
+        definition Some("Node")
+          field id
+          -> ID
+        definition Some("Result")
+          -> Query
+        definition Some("Level")
+          enum value LOW
+          @tag
+          value ["b"]
+          value "b"
+        definition Some("Filter")
+          input value level
+          -> Level
+          value LOW
+        extension Some("Query")
+          field level
+          -> Level"#]];
+    expected.assert_eq(&visitor.0.join("\n"));
+}
+
+#[test]
+fn mutate_type_system() {
+    /// Renames `Level` to `Priority` and its values to lowercase, and removes `@tag`
+    struct Rename;
+
+    impl schema::VisitorMut for Rename {
+        fn enter_type(&mut self, ty: &mut schema::ExtendedType) -> VisitControl {
+            if let schema::ExtendedType::Enum(def) = ty {
+                def.make_mut().name = name!(Priority);
+            }
+            VisitControl::Continue
+        }
+
+        fn enter_enum_value_definition(
+            &mut self,
+            definition: &mut Node<schema::EnumValueDefinition>,
+        ) -> VisitControl {
+            let lowercase = definition.value.to_lowercase();
+            definition.make_mut().value = Name::new(&lowercase).unwrap();
+            VisitControl::Continue
+        }
+
+        fn enter_definition(&mut self, definition: &mut ast::Definition) -> VisitControl {
+            if let ast::Definition::EnumTypeDefinition(def) = definition {
+                def.make_mut().name = name!(Priority);
+            }
+            VisitControl::Continue
+        }
+
+        fn leave_directive(&mut self, directive: &mut Node<Directive>) {
+            if directive.name == "tag" {
+                directive.make_mut().arguments.clear()
+            }
+        }
+
+        fn enter_value(&mut self, value: &mut Node<Value>) -> VisitControl {
+            if let Value::Enum(name) = value.as_ref() {
+                let lowercase = name.to_lowercase();
+                *value = Node::new(Value::Enum(Name::new(&lowercase).unwrap()));
+            }
+            VisitControl::Continue
+        }
+
+        fn named_type(&mut self, name: &mut schema::NamedType) {
+            if *name == "Level" {
+                *name = name!(Priority)
+            }
+        }
+    }
+
+    let original = Schema::parse(TYPE_SYSTEM, "schema.graphql").unwrap();
+    let mut schema = original.clone();
+    schema.walk_mut(&mut Rename);
+    let expected = expect![[r#"
+        directive @tag(names: [String] = ["a"]) on OBJECT | ENUM_VALUE
+
+        scalar Date @specifiedBy(url: "https://example.com")
+
+        type Query implements Node @tag {
+          node(id: ID!): Node
+          date: Date
+        }
+
+        interface Node {
+          id: ID!
+        }
+
+        union Result = Query
+
+        enum Priority {
+          low @tag
+        }
+
+        input Filter {
+          level: Priority = low
+        }
+    "#]];
+    expected.assert_eq(&schema.to_string());
+    // Map keys follow renamed definitions
+    assert!(schema.types["Priority"].is_enum());
+    assert!(schema
+        .get_enum("Priority")
+        .unwrap()
+        .values
+        .contains_key("low"));
+    // Unmodified nodes are still shared
+    assert!(schema.types["Node"] == original.types["Node"]);
+    let (Some(schema::ExtendedType::Interface(a)), Some(schema::ExtendedType::Interface(b))) =
+        (schema.types.get("Node"), original.types.get("Node"))
+    else {
+        panic!("expected interfaces")
+    };
+    assert!(a.ptr_eq(b));
+
+    let mut document = ast::Document::parse(TYPE_SYSTEM, "doc.graphql").unwrap();
+    document.walk_mut(&mut Rename);
+    let expected = expect![[r#"
+        directive @tag(names: [String] = ["a"]) on OBJECT | ENUM_VALUE
+
+        scalar Date @specifiedBy(url: "https://example.com")
+
+        type Query implements Node @tag {
+          node(id: ID!): Node
+          date: Date
+        }
+
+        interface Node {
+          id: ID!
+        }
+
+        union Result = Query
+
+        enum Priority {
+          low @tag
+        }
+
+        input Filter {
+          level: Priority = low
+        }
+    "#]];
+    expected.assert_eq(&document.to_string());
+}
@@ -1,3 +1,4 @@
+use crate::TokenKind;
 use std::fmt;
 
 /// An `Error` type for operations performed in the lexer and the parser.
@@ -63,6 +64,7 @@ impl fmt::Display for ErrorData {
 #[derive(PartialEq, Eq, Clone, Hash, thiserror::Error)]
 #[error("ERROR@{index}:{} {message:?} {data}", .index + .data.len())]
 pub struct Error {
+    pub(crate) kind: ErrorKind,
     pub(crate) message: String,
     pub(crate) data: ErrorData,
     pub(crate) index: usize,
@@ -72,6 +74,7 @@ impl Error {
     /// Create a new instance of `Error` with a `Location`.
     pub fn with_loc<S: Into<String>>(message: S, data: String, index: usize) -> Self {
         Self {
+            kind: ErrorKind::Custom,
             message: message.into(),
             data: ErrorData::Text(data),
             index,
@@ -80,6 +83,7 @@ impl Error {
 
     pub fn limit<S: Into<String>>(message: S, index: usize) -> Self {
         Self {
+            kind: ErrorKind::LimitExceeded,
             message: message.into(),
             data: ErrorData::LimitExceeded,
             index,
@@ -88,12 +92,37 @@ impl Error {
 
     pub fn eof<S: Into<String>>(message: S, index: usize) -> Self {
         Self {
+            kind: ErrorKind::UnexpectedEof,
             message: message.into(),
             data: ErrorData::Eof,
             index,
         }
     }
 
+    pub(crate) fn new<S: Into<String>>(
+        kind: ErrorKind,
+        message: S,
+        data: String,
+        index: usize,
+    ) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            data: ErrorData::Text(data),
+            index,
+        }
+    }
+
+    pub(crate) fn with_kind(mut self, kind: ErrorKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Get the error's kind.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
     /// Get a reference to the error's data. This is usually the token that
     /// `apollo-parser` has found to be lexically or syntactically incorrect.
     pub fn data(&self) -> &str {
@@ -139,3 +168,43 @@ impl fmt::Debug for Error {
         )
     }
 }
+
+/// The kind of an [`Error`], for handling errors programmatically.
+///
+/// Unlike the error [message][Error::message], which is meant for humans and can change
+/// between versions, the meaning of each kind is stable.
+/// New kinds may be added in future versions.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// A character that cannot start a token, such as `?`,
+    /// or a `-` that is not followed by a digit.
+    UnexpectedCharacter,
+    /// A string or block string that is missing its closing quotes.
+    UnterminatedString,
+    /// A line break in a string that is not a block string.
+    UnexpectedLineTerminator,
+    /// An escape sequence in a string that is invalid or incomplete, such as `\x` or `\u12`.
+    InvalidEscapeSequence,
+    /// A malformed number, such as `012`, `1.`, or `1e`,
+    /// or a number immediately followed by a name, such as `1a`.
+    InvalidNumber,
+    /// One or two dots that are not part of a `...` spread.
+    UnterminatedSpread,
+    /// A token of a specific kind was required.
+    ///
+    /// If the input ended instead, `found` is [`TokenKind::Eof`].
+    ExpectedToken {
+        expected: TokenKind,
+        found: TokenKind,
+    },
+    /// A token that is not valid at this position.
+    UnexpectedToken { found: TokenKind },
+    /// The input ended where more tokens were required.
+    UnexpectedEof,
+    /// The token limit or the recursion limit was reached.
+    /// Parsing stops there, so this is the last error.
+    LimitExceeded,
+    /// An error created with [`Error::with_loc`].
+    Custom,
+}
@@ -0,0 +1,192 @@
+use crate::lexer::Checkpoint;
+use crate::Error;
+use crate::Lexer;
+use crate::LimitTracker;
+use crate::Token;
+use std::io;
+use std::io::BufRead;
+
+/// Lexes GraphQL source text read incrementally, for example from a pipe or a large file.
+///
+/// Only the text of the token being read needs to be held in memory:
+/// each call to [`next_chunk`][Self::next_chunk] reads one chunk from the reader
+/// and returns the tokens it completes.
+/// Token and error indices are byte offsets from the start of the whole input,
+/// so the result is the same as lexing the whole input at once with [`Lexer`].
+/// ```rust
+/// use apollo_parser::ChunkedLexer;
+/// use apollo_parser::TokenKind;
+///
+/// let input = "type Query { name: String }".as_bytes();
+/// let mut lexer = ChunkedLexer::new(input);
+/// let mut names = vec![];
+/// while let Some((tokens, errors)) = lexer.next_chunk()? {
+///     assert_eq!(errors.len(), 0);
+///     for token in tokens {
+///         if token.kind() == TokenKind::Name {
+///             names.push((token.data().to_owned(), token.index()));
+///         }
+///     }
+/// }
+/// assert_eq!(names[2], ("name".to_owned(), 13));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct ChunkedLexer<R> {
+    reader: R,
+    /// Text read but not yet returned, starting with the incomplete last token
+    buffer: String,
+    /// Text of `buffer` returned by the previous call to `next_chunk`
+    consumed: usize,
+    /// Byte offset of the start of `buffer` in the whole input
+    offset: usize,
+    /// Where to continue lexing the incomplete token at the start of `buffer`,
+    /// so that a large token is not lexed again from its start for each chunk
+    checkpoint: Option<Checkpoint>,
+    /// Bytes of a UTF-8 sequence split across chunks
+    incomplete_char: Vec<u8>,
+    finished: bool,
+    pub(crate) limit_tracker: LimitTracker,
+}
+
+impl<R: BufRead> ChunkedLexer<R> {
+    /// Create a lexer reading GraphQL source text from `reader`.
+    ///
+    /// The size of chunks is the buffer size of the reader.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: String::new(),
+            consumed: 0,
+            offset: 0,
+            checkpoint: None,
+            incomplete_char: Vec::new(),
+            finished: false,
+            limit_tracker: LimitTracker::new(usize::MAX),
+        }
+    }
+
+    /// Configure the maximum number of tokens, including errors, for the whole input.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit_tracker = LimitTracker::new(limit);
+        self
+    }
+
+    /// Read the next chunk of input and lex the tokens it completes.
+    ///
+    /// Returns `Ok(None)` after the end of the input, or after reaching the token limit.
+    /// The last token of a chunk may continue in the next one,
+    /// so it is returned on the next call instead.
+    /// The [`TokenKind::Eof`][crate::TokenKind::Eof] token is returned
+    /// on the call that reaches the end of the input.
+    ///
+    /// Returns an error of kind [`io::ErrorKind::InvalidData`] if the input is not UTF-8.
+    pub fn next_chunk(&mut self) -> io::Result<Option<(Vec<Token<'_>>, Vec<Error>)>> {
+        if self.finished {
+            return Ok(None);
+        }
+        self.buffer.drain(..self.consumed);
+        self.offset += self.consumed;
+        self.consumed = 0;
+        let at_eof = self.read_chunk()?;
+
+        let mut lexer = Lexer::new(&self.buffer);
+        lexer.cursor.record_checkpoints = !at_eof;
+        if let Some(checkpoint) = self.checkpoint.take() {
+            lexer.cursor.resume_at(checkpoint);
+        }
+        let mut items = vec![];
+        loop {
+            let start = lexer.cursor.index();
+            let Some(item) = lexer.next() else { break };
+            items.push((start, item));
+        }
+        if !at_eof {
+            // Drop the end of input token, and keep the last token for the next chunk
+            // as it may continue past the end of this one.
+            items.pop();
+            if let Some((start, _)) = items.pop() {
+                self.consumed = start;
+                self.checkpoint = lexer
+                    .cursor
+                    .checkpoint
+                    .take()
+                    .filter(|checkpoint| checkpoint.start == start)
+                    .map(|checkpoint| Checkpoint {
+                        start: 0,
+                        next: checkpoint.next - start,
+                        ..checkpoint
+                    });
+            }
+        }
+
+        let mut tokens = vec![];
+        let mut errors = vec![];
+        for (start, item) in items {
+            if self.limit_tracker.check_and_increment() {
+                errors.push(Error::limit(
+                    "token limit reached, aborting lexing",
+                    self.offset + start,
+                ));
+                self.finished = true;
+                return Ok(Some((tokens, errors)));
+            }
+            match item {
+                Ok(mut token) => {
+                    token.index += self.offset;
+                    tokens.push(token);
+                }
+                Err(mut error) => {
+                    error.index += self.offset;
+                    errors.push(error);
+                }
+            }
+        }
+        self.finished = at_eof;
+        Ok(Some((tokens, errors)))
+    }
+
+    /// Append the next chunk of input to the buffer. Returns whether the input has ended.
+    fn read_chunk(&mut self) -> io::Result<bool> {
+        let chunk = loop {
+            match self.reader.fill_buf() {
+                Ok(chunk) => break chunk,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            }
+        };
+        if chunk.is_empty() {
+            if !self.incomplete_char.is_empty() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "stream did not contain valid UTF-8",
+                ));
+            }
+            return Ok(true);
+        }
+        let len = chunk.len();
+        self.incomplete_char.extend_from_slice(chunk);
+        self.reader.consume(len);
+
+        let text = match std::str::from_utf8(&self.incomplete_char) {
+            Ok(text) => text,
+            // A character continues in the next chunk
+            Err(error) if error.error_len().is_none() => {
+                // Cannot fail: these bytes were just validated
+                std::str::from_utf8(&self.incomplete_char[..error.valid_up_to()]).unwrap()
+            }
+            Err(error) => return Err(io::Error::new(io::ErrorKind::InvalidData, error)),
+        };
+        self.buffer.push_str(text);
+        let valid_len = text.len();
+        self.incomplete_char.drain(..valid_len);
+        Ok(false)
+    }
+}
+
+impl<R> ChunkedLexer<R> {
+    /// Returns whether the end of the input or the token limit was reached.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
@@ -1,3 +1,6 @@
+use crate::lexer::Checkpoint;
+use crate::lexer::State;
+use crate::lexer::TokenKind;
 use crate::Error;
 use std::str::CharIndices;
 
@@ -8,8 +11,16 @@ pub(crate) struct Cursor<'a> {
     pub(super) offset: usize,
     pub(super) source: &'a str,
     chars: CharIndices<'a>,
+    /// Byte index in `source` of the start of `chars`
+    chars_start: usize,
     pending: Option<char>,
     pub(crate) err: Option<Error>,
+    /// Whether to record checkpoints, for [`ChunkedLexer`][super::ChunkedLexer]
+    pub(super) record_checkpoints: bool,
+    /// Where lexing of the last token reaching the end of `source` can continue
+    pub(super) checkpoint: Option<Checkpoint>,
+    /// State of the token that the next call to `advance` continues
+    pub(super) resume: Option<(State, TokenKind)>,
 }
 
 impl<'a> Cursor<'a> {
@@ -20,9 +31,23 @@ impl<'a> Cursor<'a> {
             pending: None,
             source: input,
             chars: input.char_indices(),
+            chars_start: 0,
             err: None,
+            record_checkpoints: false,
+            checkpoint: None,
+            resume: None,
         }
     }
+
+    /// Continue lexing a token from a checkpoint, with `source` starting at the token
+    pub(super) fn resume_at(&mut self, checkpoint: Checkpoint) {
+        debug_assert_eq!(checkpoint.start, 0);
+        self.offset = checkpoint.next;
+        self.chars = self.source[checkpoint.next..].char_indices();
+        self.chars_start = checkpoint.next;
+        self.err = checkpoint.err;
+        self.resume = Some((checkpoint.state, checkpoint.kind));
+    }
 }
 
 impl<'a> Cursor<'a> {
@@ -36,6 +61,20 @@ impl<'a> Cursor<'a> {
         self.pending.is_some()
     }
 
+    /// Byte index of the next character to lex
+    pub(super) fn next_index(&self) -> usize {
+        if self.pending.is_some() {
+            self.offset
+        } else {
+            self.chars_start + self.chars.offset()
+        }
+    }
+
+    fn next_char(&mut self) -> Option<(usize, char)> {
+        let (pos, c) = self.chars.next()?;
+        Some((self.chars_start + pos, c))
+    }
+
     /// Moves to the next character.
     pub(crate) fn prev_str(&mut self) -> &'a str {
         let slice = &self.source[self.index..self.offset];
@@ -53,7 +92,7 @@ impl<'a> Cursor<'a> {
     pub(crate) fn current_str(&mut self) -> &'a str {
         self.pending = None;
 
-        if let Some((pos, next)) = self.chars.next() {
+        if let Some((pos, next)) = self.next_char() {
             let current = self.index;
 
             self.index = pos;
@@ -80,7 +119,7 @@ impl<'a> Cursor<'a> {
             return None;
         }
 
-        let (pos, c) = self.chars.next()?;
+        let (pos, c) = self.next_char()?;
         self.offset = pos;
 
         Some(c)
@@ -92,7 +131,7 @@ impl<'a> Cursor<'a> {
             panic!("dont call eatc when a character is pending");
         }
 
-        if let Some((pos, c_in)) = self.chars.next() {
+        if let Some((pos, c_in)) = self.next_char() {
             self.offset = pos;
 
             if c_in == c {
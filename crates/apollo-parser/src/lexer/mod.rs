@@ -1,3 +1,4 @@
+mod chunked;
 mod cursor;
 mod lookup;
 mod token;
@@ -5,7 +6,9 @@ mod token_kind;
 
 use crate::lexer::cursor::Cursor;
 use crate::Error;
+use crate::ErrorKind;
 use crate::LimitTracker;
+pub use chunked::ChunkedLexer;
 pub use token::Token;
 pub use token_kind::TokenKind;
 
@@ -34,7 +37,7 @@ pub struct Lexer<'a> {
     pub(crate) limit_tracker: LimitTracker,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 enum State {
     Start,
     Ident,
@@ -57,6 +60,19 @@ enum State {
     MinusSign,
 }
 
+/// Lexer state in the middle of a token,
+/// to continue lexing it when more input is available
+#[derive(Debug, Clone)]
+struct Checkpoint {
+    state: State,
+    kind: TokenKind,
+    /// Byte index of the start of the token
+    start: usize,
+    /// Byte index of the next character to lex
+    next: usize,
+    err: Option<Error>,
+}
+
 impl<'a> Lexer<'a> {
     /// Create a lexer for a GraphQL source text.
     ///
@@ -135,15 +151,23 @@ impl<'a> Iterator for Lexer<'a> {
 
 impl<'a> Cursor<'a> {
     fn advance(&mut self) -> Result<Token<'a>, Error> {
-        let mut state = State::Start;
+        let (mut state, kind) = self.resume.take().unwrap_or((State::Start, TokenKind::Eof));
         let mut token = Token {
-            kind: TokenKind::Eof,
+            kind,
             data: "",
             index: self.index(),
         };
 
         loop {
+            if self.record_checkpoints {
+                self.record_checkpoint(state, &token)
+            }
             let Some(c) = self.bump() else {
+                if self.record_checkpoints && !matches!(state, State::Start) {
+                    // Lexing continues from the checkpoint when there is more input,
+                    // so skip building an error that may copy the whole token.
+                    return Ok(token);
+                }
                 return self.eof(state, token);
             };
             match state {
@@ -194,7 +218,8 @@ impl<'a> Cursor<'a> {
                             state = State::Whitespace;
                         }
                         c => {
-                            return Err(Error::with_loc(
+                            return Err(Error::new(
+                                ErrorKind::UnexpectedCharacter,
                                 format!("Unexpected character \"{}\"", c),
                                 self.current_str().to_string(),
                                 token.index,
@@ -255,7 +280,8 @@ impl<'a> Cursor<'a> {
                 },
                 State::StringLiteralEscapedUnicode(remaining) => match c {
                     '"' => {
-                        self.add_err(Error::with_loc(
+                        self.add_err(Error::new(
+                            ErrorKind::InvalidEscapeSequence,
                             "incomplete unicode escape sequence",
                             c.to_string(),
                             token.index,
@@ -264,7 +290,8 @@ impl<'a> Cursor<'a> {
                         return self.done(token);
                     }
                     c if !c.is_ascii_hexdigit() => {
-                        self.add_err(Error::with_loc(
+                        self.add_err(Error::new(
+                            ErrorKind::InvalidEscapeSequence,
                             "invalid unicode escape sequence",
                             c.to_string(),
                             0,
@@ -287,7 +314,8 @@ impl<'a> Cursor<'a> {
                                 // changes both here and in `ast/node_ext.rs`
                                 let escape_sequence_start = hex_start - 2; // include "\u"
                                 let escape_sequence = &self.source[escape_sequence_start..hex_end];
-                                self.add_err(Error::with_loc(
+                                self.add_err(Error::new(
+                                    ErrorKind::InvalidEscapeSequence,
                                     "surrogate code point is invalid in unicode escape sequence \
                                      (paired surrogate not supported yet: \
                                      https://github.com/apollographql/apollo-rs/issues/657)",
@@ -307,7 +335,8 @@ impl<'a> Cursor<'a> {
                         return self.done(token);
                     }
                     curr if is_line_terminator(curr) => {
-                        self.add_err(Error::with_loc(
+                        self.add_err(Error::new(
+                            ErrorKind::UnexpectedLineTerminator,
                             "unexpected line terminator",
                             "".to_string(),
                             0,
@@ -347,7 +376,8 @@ impl<'a> Cursor<'a> {
                         state = State::StringLiteralEscapedUnicode(4);
                     }
                     _ => {
-                        self.add_err(Error::with_loc(
+                        self.add_err(Error::new(
+                            ErrorKind::InvalidEscapeSequence,
                             "unexpected escaped character",
                             c.to_string(),
                             0,
@@ -366,14 +396,16 @@ impl<'a> Cursor<'a> {
                         state = State::ExponentIndicator;
                     }
                     _ if c.is_ascii_digit() => {
-                        return Err(Error::with_loc(
+                        return Err(Error::new(
+                            ErrorKind::InvalidNumber,
                             "Numbers must not have non-significant leading zeroes",
                             self.current_str().to_string(),
                             token.index,
                         ));
                     }
                     _ if lookup::is_namestart(c) => {
-                        return Err(Error::with_loc(
+                        return Err(Error::new(
+                            ErrorKind::InvalidNumber,
                             format!("Unexpected character `{c}` as integer suffix"),
                             self.current_str().to_string(),
                             token.index,
@@ -395,7 +427,8 @@ impl<'a> Cursor<'a> {
                         state = State::ExponentIndicator;
                     }
                     _ if lookup::is_namestart(c) => {
-                        return Err(Error::with_loc(
+                        return Err(Error::new(
+                            ErrorKind::InvalidNumber,
                             format!("Unexpected character `{c}` as integer suffix"),
                             self.current_str().to_string(),
                             token.index,
@@ -411,7 +444,8 @@ impl<'a> Cursor<'a> {
                         state = State::FractionalPart;
                     }
                     _ => {
-                        return Err(Error::with_loc(
+                        return Err(Error::new(
+                            ErrorKind::InvalidNumber,
                             format!("Unexpected character `{c}`, expected fractional digit"),
                             self.current_str().to_string(),
                             token.index,
@@ -424,7 +458,8 @@ impl<'a> Cursor<'a> {
                         state = State::ExponentIndicator;
                     }
                     _ if c == '.' || lookup::is_namestart(c) => {
-                        return Err(Error::with_loc(
+                        return Err(Error::new(
+                            ErrorKind::InvalidNumber,
                             format!("Unexpected character `{c}` as float suffix"),
                             self.current_str().to_string(),
                             token.index,
@@ -443,7 +478,8 @@ impl<'a> Cursor<'a> {
                         state = State::ExponentSign;
                     }
                     _ => {
-                        return Err(Error::with_loc(
+                        return Err(Error::new(
+                            ErrorKind::InvalidNumber,
                             format!("Unexpected character `{c}`, expected exponent digit or sign"),
                             self.current_str().to_string(),
                             token.index,
@@ -455,7 +491,8 @@ impl<'a> Cursor<'a> {
                         state = State::ExponentDigit;
                     }
                     _ => {
-                        return Err(Error::with_loc(
+                        return Err(Error::new(
+                            ErrorKind::InvalidNumber,
                             format!("Unexpected character `{c}`, expected exponent digit"),
                             self.current_str().to_string(),
                             token.index,
@@ -467,7 +504,8 @@ impl<'a> Cursor<'a> {
                         state = State::ExponentDigit;
                     }
                     _ if c == '.' || lookup::is_namestart(c) => {
-                        return Err(Error::with_loc(
+                        return Err(Error::new(
+                            ErrorKind::InvalidNumber,
                             format!("Unexpected character `{c}` as float suffix"),
                             self.current_str().to_string(),
                             token.index,
@@ -493,7 +531,8 @@ impl<'a> Cursor<'a> {
                         state = State::IntegerPart;
                     }
                     _ => {
-                        return Err(Error::with_loc(
+                        return Err(Error::new(
+                            ErrorKind::UnexpectedCharacter,
                             format!("Unexpected character `{c}`"),
                             self.current_str().to_string(),
                             token.index,
@@ -511,6 +550,22 @@ impl<'a> Cursor<'a> {
         }
     }
 
+    fn record_checkpoint(&mut self, state: State, token: &Token<'a>) {
+        let next = self.next_index();
+        // An iteration consumes at most 3 characters of up to 4 bytes,
+        // so a token reaching the end of the input passes through this range.
+        // Before the end, previous iterations looked ahead at actual characters.
+        if next < self.source.len() && next + 12 >= self.source.len() {
+            self.checkpoint = Some(Checkpoint {
+                state,
+                kind: token.kind,
+                start: token.index,
+                next,
+                err: self.err.clone(),
+            })
+        }
+    }
+
     fn eof(&mut self, state: State, mut token: Token<'a>) -> Result<Token<'a>, Error> {
         match state {
             State::Start => {
@@ -520,7 +575,8 @@ impl<'a> Cursor<'a> {
             State::StringLiteralStart => {
                 let curr = self.current_str();
 
-                Err(Error::with_loc(
+                Err(Error::new(
+                    ErrorKind::UnterminatedString,
                     "unexpected end of data while lexing string value",
                     curr.to_string(),
                     token.index,
@@ -533,20 +589,23 @@ impl<'a> Cursor<'a> {
             | State::StringLiteralBackslash => {
                 let curr = self.drain();
 
-                Err(Error::with_loc(
+                Err(Error::new(
+                    ErrorKind::UnterminatedString,
                     "unterminated string value",
                     curr.to_string(),
                     token.index,
                 ))
             }
             State::SpreadOperator => self.unterminated_spread_operator(&token),
-            State::MinusSign => Err(Error::with_loc(
+            State::MinusSign => Err(Error::new(
+                ErrorKind::UnexpectedCharacter,
                 "Unexpected character \"-\"",
                 self.current_str().to_string(),
                 token.index,
             )),
             State::DecimalPoint | State::ExponentIndicator | State::ExponentSign => {
-                Err(Error::with_loc(
+                Err(Error::new(
+                    ErrorKind::InvalidNumber,
                     "Unexpected EOF in float value",
                     self.current_str().to_string(),
                     token.index,
@@ -578,7 +637,8 @@ impl<'a> Cursor<'a> {
             self.current_str()
         };
 
-        Err(Error::with_loc(
+        Err(Error::new(
+            ErrorKind::UnterminatedSpread,
             "Unterminated spread operator",
             data.to_string(),
             token.index,
@@ -680,7 +740,12 @@ type Query {
         assert_eq!(
             errors,
             &[
-                Error::with_loc("Unterminated spread operator", "..".to_string(), 13),
+                Error::new(
+                    ErrorKind::UnterminatedSpread,
+                    "Unterminated spread operator",
+                    "..".to_string(),
+                    13
+                ),
                 Error::limit("token limit reached, aborting lexing", 18),
             ],
         );
@@ -750,7 +815,8 @@ Escape character followed by a quote:
         assert_eq!(tokens[3].data, r#""""\"""""""#);
         assert_eq!(
             errors,
-            &[Error::with_loc(
+            &[Error::new(
+                ErrorKind::UnterminatedString,
                 "unterminated string value",
                 r#""""\"""
         "#
@@ -772,11 +838,103 @@ type Query {
         dbg!(tokens);
         assert_eq!(
             errors,
-            &[Error::with_loc(
+            &[Error::new(
+                ErrorKind::UnexpectedCharacter,
                 "Unexpected character \"/\"",
                 "/".to_string(),
                 33,
             )]
         );
     }
+
+    fn lex_chunked(input: &str, chunk_size: usize, limit: usize) -> (String, String) {
+        let reader = std::io::BufReader::with_capacity(chunk_size, input.as_bytes());
+        let mut lexer = ChunkedLexer::new(reader).with_limit(limit);
+        let mut tokens = String::new();
+        let mut errors = String::new();
+        while let Some((chunk_tokens, chunk_errors)) = lexer.next_chunk().unwrap() {
+            for token in chunk_tokens {
+                tokens += &format!("{token:?}\n");
+            }
+            for error in chunk_errors {
+                errors += &format!("{error:?}\n");
+            }
+        }
+        assert!(lexer.is_finished());
+        (tokens, errors)
+    }
+
+    #[test]
+    fn chunked_input() {
+        let input = r#"
+# Schema with "strings", ünïcödé, and errors
+type Query {
+  name(arg: Float = -1.5e10, other: [Int] = [0, 12]): String @deprecated(reason: "€uro")
+  """
+  Block string
+  spanning \""" lines
+  """
+  bad: 01 ..spread / ok
+}
+"unterminated
+        "#;
+        let (tokens, errors) = Lexer::new(input).lex();
+        let expected_tokens: String = tokens.iter().map(|t| format!("{t:?}\n")).collect();
+        let expected_errors: String = errors.iter().map(|e| format!("{e:?}\n")).collect();
+        assert!(!expected_errors.is_empty());
+        for chunk_size in [1, 2, 3, 5, 8, 64, 4096] {
+            let (tokens, errors) = lex_chunked(input, chunk_size, usize::MAX);
+            assert_eq!(tokens, expected_tokens, "chunk size {chunk_size}");
+            assert_eq!(errors, expected_errors, "chunk size {chunk_size}");
+        }
+    }
+
+    #[test]
+    fn chunked_large_tokens() {
+        // Tokens longer than chunks continue from where the previous chunk stopped lexing them
+        let long = "ünïcödé \\u00e9 \\x ".repeat(50);
+        let input = format!(
+            "{{ a(s: \"{long}\", b: \"\"\"{long} \\\"\"\" \"\" \"\"\"\") }} # {long}\n\"{long}"
+        );
+        let (tokens, errors) = Lexer::new(&input).lex();
+        let expected_tokens: String = tokens.iter().map(|t| format!("{t:?}\n")).collect();
+        let expected_errors: String = errors.iter().map(|e| format!("{e:?}\n")).collect();
+        for chunk_size in 1..=16 {
+            let (tokens, errors) = lex_chunked(&input, chunk_size, usize::MAX);
+            assert_eq!(tokens, expected_tokens, "chunk size {chunk_size}");
+            assert_eq!(errors, expected_errors, "chunk size {chunk_size}");
+        }
+    }
+
+    #[test]
+    fn chunked_token_limit() {
+        let input = "type Query { ..a a a a a a a a a }";
+        let (tokens, errors) = Lexer::new(input).with_limit(10).lex();
+        let expected_tokens: String = tokens.iter().map(|t| format!("{t:?}\n")).collect();
+        let expected_errors: String = errors.iter().map(|e| format!("{e:?}\n")).collect();
+        for chunk_size in [1, 4, 100] {
+            let (tokens, errors) = lex_chunked(input, chunk_size, 10);
+            assert_eq!(tokens, expected_tokens);
+            assert_eq!(errors, expected_errors);
+        }
+    }
+
+    #[test]
+    fn chunked_invalid_utf8() {
+        let mut lexer = ChunkedLexer::new(&b"type \xFF"[..]);
+        let error = lexer.next_chunk().unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+
+        // Truncated multi-byte character at the end of the input
+        let reader = std::io::BufReader::with_capacity(1, &b"type \xC3"[..]);
+        let mut lexer = ChunkedLexer::new(reader);
+        let error = loop {
+            match lexer.next_chunk() {
+                Ok(Some(_)) => {}
+                Ok(None) => panic!("expected an error"),
+                Err(error) => break error,
+            }
+        };
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
 }
@@ -10,6 +10,8 @@ mod limit;
 mod parser;
 
 pub use crate::error::Error;
+pub use crate::error::ErrorKind;
+pub use crate::lexer::ChunkedLexer;
 pub use crate::lexer::Lexer;
 pub use crate::lexer::Token;
 pub use crate::lexer::TokenKind;
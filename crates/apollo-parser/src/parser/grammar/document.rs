@@ -76,6 +76,30 @@ fn select_definition(def: &str, p: &mut Parser) {
     }
 }
 
+/// Returns whether the next tokens start a definition that cannot continue
+/// a fields definition or an enum values definition, such as `type Name {`.
+///
+/// This lets these lists recover from a missing closing brace
+/// without consuming the following definition.
+pub(crate) fn at_type_definition_start(p: &mut Parser) -> bool {
+    p.peek();
+    let (keyword, start) = match p.peek_data_n(1) {
+        Some("extend") => (p.peek_data_n(2), 2),
+        keyword => (keyword, 1),
+    };
+    let after_keyword = |n: usize| p.peek_n(start + n);
+    match keyword {
+        Some("type" | "interface" | "input" | "enum") => {
+            after_keyword(1) == Some(TokenKind::Name) && after_keyword(2) == Some(TokenKind::LCurly)
+        }
+        Some("union") => {
+            after_keyword(1) == Some(TokenKind::Name) && after_keyword(2) == Some(TokenKind::Eq)
+        }
+        Some("schema") => after_keyword(1) == Some(TokenKind::LCurly),
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::cst;
@@ -2,6 +2,7 @@
 
 use crate::parser::grammar::description;
 use crate::parser::grammar::directive;
+use crate::parser::grammar::document;
 use crate::parser::grammar::name;
 use crate::parser::grammar::value;
 use crate::parser::grammar::value::Constness;
@@ -29,7 +30,7 @@ pub(crate) fn enum_type_definition(p: &mut Parser) {
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
-        _ => p.err("expected a Name"),
+        _ => p.err_expected(TokenKind::Name, "expected a Name"),
     }
 
     if let Some(T![@]) = p.peek() {
@@ -55,7 +56,7 @@ pub(crate) fn enum_type_extension(p: &mut Parser) {
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
-        _ => p.err("expected a Name"),
+        _ => p.err_expected(TokenKind::Name, "expected a Name"),
     }
 
     if let Some(T![@]) = p.peek() {
@@ -87,6 +88,7 @@ pub(crate) fn enum_values_definition(p: &mut Parser) {
     }
 
     p.peek_while(|p, kind| match kind {
+        TokenKind::Name if document::at_type_definition_start(p) => ControlFlow::Break(()),
         TokenKind::Name | TokenKind::StringValue => {
             enum_value_definition(p);
             ControlFlow::Continue(())
@@ -3,6 +3,7 @@
 use crate::parser::grammar::argument;
 use crate::parser::grammar::description;
 use crate::parser::grammar::directive;
+use crate::parser::grammar::document;
 use crate::parser::grammar::name;
 use crate::parser::grammar::selection;
 use crate::parser::grammar::ty;
@@ -27,7 +28,7 @@ pub(crate) fn field(p: &mut Parser) {
         }
         name::name(p)
     } else {
-        p.err("expected a Name");
+        p.err_expected(TokenKind::Name, "expected a Name");
     }
 
     if let Some(T!['(']) = p.peek() {
@@ -57,6 +58,7 @@ pub(crate) fn fields_definition(p: &mut Parser) {
     }
 
     p.peek_while(|p, kind| match kind {
+        TokenKind::Name if document::at_type_definition_start(p) => ControlFlow::Break(()),
         TokenKind::Name | TokenKind::StringValue => {
             field_definition(p);
             ControlFlow::Continue(())
@@ -99,6 +101,12 @@ pub(crate) fn field_definition(p: &mut Parser) {
                 p.err("expected a Type");
             }
         }
+    } else if ty::at_type_after_missing_colon(p) {
+        p.err_expected(T![:], "expected a colon");
+        ty::ty(p);
+        if let Some(T![@]) = p.peek() {
+            directive::directives(p, Constness::Const);
+        }
     } else {
         p.err("expected a type");
     }
@@ -105,7 +105,7 @@ pub(crate) fn fragment_spread(p: &mut Parser) {
         Some(TokenKind::Name) => {
             fragment_name(p);
         }
-        _ => p.err("expected a Name"),
+        _ => p.err_expected(TokenKind::Name, "expected a Name"),
     }
 
     if let Some(T![@]) = p.peek() {
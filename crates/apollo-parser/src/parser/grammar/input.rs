@@ -1,5 +1,6 @@
 use crate::parser::grammar::description;
 use crate::parser::grammar::directive;
+use crate::parser::grammar::document;
 use crate::parser::grammar::name;
 use crate::parser::grammar::ty;
 use crate::parser::grammar::value;
@@ -29,7 +30,7 @@ pub(crate) fn input_object_type_definition(p: &mut Parser) {
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
-        _ => p.err("expected a Name"),
+        _ => p.err_expected(TokenKind::Name, "expected a Name"),
     }
 
     if let Some(T![@]) = p.peek() {
@@ -55,7 +56,7 @@ pub(crate) fn input_object_type_extension(p: &mut Parser) {
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
-        _ => p.err("expected a Name"),
+        _ => p.err_expected(TokenKind::Name, "expected a Name"),
     }
 
     if let Some(T![@]) = p.peek() {
@@ -86,7 +87,9 @@ pub(crate) fn input_fields_definition(p: &mut Parser) {
         p.err("expected an Input Value Definition");
     }
     p.peek_while(|p, kind| {
-        if matches!(kind, TokenKind::Name | TokenKind::StringValue) {
+        if kind == TokenKind::Name && document::at_type_definition_start(p) {
+            ControlFlow::Break(())
+        } else if matches!(kind, TokenKind::Name | TokenKind::StringValue) {
             input_value_definition(p);
             ControlFlow::Continue(())
         } else {
@@ -125,7 +128,16 @@ pub(crate) fn input_value_definition(p: &mut Parser) {
             }
             _ => p.err("expected a Type"),
         }
+    } else if ty::at_type_after_missing_colon(p) {
+        p.err_expected(T![:], "expected a colon");
+        ty::ty(p);
+        if let Some(T![=]) = p.peek() {
+            value::default_value(p);
+        }
+        if let Some(T![@]) = p.peek() {
+            directive::directives(p, Constness::Const);
+        }
     } else {
-        p.err("expected a Name");
+        p.err_expected(TokenKind::Name, "expected a Name");
     }
 }
@@ -26,7 +26,7 @@ pub(crate) fn interface_type_definition(p: &mut Parser) {
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
-        _ => p.err("expected a Name"),
+        _ => p.err_expected(TokenKind::Name, "expected a Name"),
     }
 
     if let Some("implements") = p.peek_data() {
@@ -57,7 +57,7 @@ pub(crate) fn interface_type_extension(p: &mut Parser) {
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
-        _ => p.err("expected a Name"),
+        _ => p.err_expected(TokenKind::Name, "expected a Name"),
     }
 
     if let Some("implements") = p.peek_data() {
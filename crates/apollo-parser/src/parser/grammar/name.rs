@@ -14,7 +14,7 @@ pub(crate) fn name(p: &mut Parser) {
             validate_name(p.peek_data().unwrap(), p);
             p.bump(SyntaxKind::IDENT);
         }
-        _ => p.err("expected a Name"),
+        _ => p.err_expected(TokenKind::Name, "expected a Name"),
     }
 }
 
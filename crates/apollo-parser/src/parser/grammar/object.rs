@@ -29,7 +29,7 @@ pub(crate) fn object_type_definition(p: &mut Parser) {
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
-        _ => p.err("expected a name"),
+        _ => p.err_expected(TokenKind::Name, "expected a name"),
     }
 
     if let Some(TokenKind::Name) = p.peek() {
@@ -64,7 +64,7 @@ pub(crate) fn object_type_extension(p: &mut Parser) {
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
-        _ => p.err("expected a Name"),
+        _ => p.err_expected(TokenKind::Name, "expected a Name"),
     }
 
     if let Some("implements") = p.peek_data() {
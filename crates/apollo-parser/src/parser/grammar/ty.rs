@@ -1,3 +1,4 @@
+use crate::parser::grammar::document;
 use crate::parser::grammar::name;
 use crate::Parser;
 use crate::SyntaxKind;
@@ -32,6 +33,20 @@ pub(crate) fn ty(p: &mut Parser) {
     }
 }
 
+/// Returns whether the next tokens look like the type of a field or input value definition
+/// whose `:` is missing, as in `name String`.
+///
+/// A name followed by `:` or `(` starts the next definition instead.
+pub(crate) fn at_type_after_missing_colon(p: &mut Parser) -> bool {
+    match p.peek() {
+        Some(T!['[']) => true,
+        Some(TokenKind::Name) => {
+            !matches!(p.peek_n(2), Some(T![:] | T!['('])) && !document::at_type_definition_start(p)
+        }
+        _ => false,
+    }
+}
+
 /// Returns the type on success, or the TokenKind that caused an error.
 ///
 /// When errors occur deeper inside nested types like lists, this function
@@ -49,7 +49,7 @@ pub(crate) fn variable_definition(p: &mut Parser) {
             p.err("expected a Type");
         }
     } else {
-        p.err("expected a Name");
+        p.err_expected(TokenKind::Name, "expected a Name");
     }
 }
 
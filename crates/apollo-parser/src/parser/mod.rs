@@ -1,5 +1,6 @@
 mod generated;
 mod language;
+mod reparse;
 mod syntax_tree;
 mod token_text;
 
@@ -10,6 +11,7 @@ use crate::cst::SelectionSet;
 use crate::cst::Type;
 use crate::lexer::Lexer;
 use crate::Error;
+use crate::ErrorKind;
 use crate::LimitTracker;
 use crate::Token;
 use crate::TokenKind;
@@ -81,6 +83,8 @@ pub(crate) use token_text::TokenText;
 /// ```
 #[derive(Debug)]
 pub struct Parser<'input> {
+    /// The source text, used by incremental reparsing.
+    input: &'input str,
     lexer: Lexer<'input>,
     /// Store one lookahead token so we don't need to reparse things as much.
     current_token: Option<Token<'input>>,
@@ -115,6 +119,7 @@ impl<'input> Parser<'input> {
         let lexer = Lexer::new(input);
 
         Self {
+            input,
             lexer,
             current_token: None,
             builder: Rc::new(RefCell::new(SyntaxTreeBuilder::new())),
@@ -283,7 +288,14 @@ impl<'input> Parser<'input> {
             Error::eof(message, current.index())
         } else {
             // this needs to be the computed location
-            Error::with_loc(message, current.data().to_string(), current.index())
+            Error::new(
+                ErrorKind::UnexpectedToken {
+                    found: current.kind,
+                },
+                message,
+                current.data().to_string(),
+                current.index(),
+            )
         };
         self.push_err(err);
     }
@@ -299,7 +311,34 @@ impl<'input> Parser<'input> {
             Error::eof(message, current.index())
         } else {
             // this needs to be the computed location
-            Error::with_loc(message, current.data().to_string(), current.index())
+            Error::new(
+                ErrorKind::UnexpectedToken {
+                    found: current.kind,
+                },
+                message,
+                current.data().to_string(),
+                current.index(),
+            )
+        };
+        self.push_err(err);
+    }
+
+    /// Create a parser error at the current location for a missing token of the `expected` kind,
+    /// and push it into the error vector.
+    pub(crate) fn err_expected(&mut self, expected: TokenKind, message: &str) {
+        let current = if let Some(current) = self.current() {
+            current
+        } else {
+            return;
+        };
+        let kind = ErrorKind::ExpectedToken {
+            expected,
+            found: current.kind,
+        };
+        let err = if current.kind == TokenKind::Eof {
+            Error::eof(message, current.index()).with_kind(kind)
+        } else {
+            Error::new(kind, message, current.data().to_string(), current.index())
         };
         self.push_err(err);
     }
@@ -316,7 +355,14 @@ impl<'input> Parser<'input> {
             Error::eof(message, current.index())
         } else {
             // this needs to be the computed location
-            Error::with_loc(message, current.data().to_string(), current.index())
+            Error::new(
+                ErrorKind::UnexpectedToken {
+                    found: current.kind,
+                },
+                message,
+                current.data().to_string(),
+                current.index(),
+            )
         };
 
         // Keep the error in the parse tree for position information
@@ -334,7 +380,7 @@ impl<'input> Parser<'input> {
         let Some(current) = self.current() else {
             return;
         };
-        let is_eof = current.kind == TokenKind::Eof;
+        let found = current.kind;
         let data = current.data();
         let index = current.index();
 
@@ -343,12 +389,16 @@ impl<'input> Parser<'input> {
             return;
         }
 
-        let err = if is_eof {
+        let error_kind = ErrorKind::ExpectedToken {
+            expected: token,
+            found,
+        };
+        let err = if found == TokenKind::Eof {
             let message = format!("expected {kind:?}, got EOF");
-            Error::eof(message, index)
+            Error::eof(message, index).with_kind(error_kind)
         } else {
             let message = format!("expected {kind:?}, got {data}");
-            Error::with_loc(message, data.to_string(), index)
+            Error::new(error_kind, message, data.to_string(), index)
         };
 
         self.push_err(err);
@@ -585,8 +635,10 @@ mod tests {
     use super::DEFAULT_RECURSION_LIMIT;
     use crate::cst;
     use crate::Error;
+    use crate::ErrorKind;
     use crate::Parser;
     use crate::SyntaxTree;
+    use crate::TokenKind;
     use expect_test::expect;
 
     #[test]
@@ -654,7 +706,15 @@ mod tests {
         let mut errors = cst.errors();
         assert_eq!(
             errors.next(),
-            Some(&Error::with_loc("expected a Name", ")".to_string(), 70))
+            Some(&Error::new(
+                ErrorKind::ExpectedToken {
+                    expected: TokenKind::Name,
+                    found: TokenKind::RParen
+                },
+                "expected a Name",
+                ")".to_string(),
+                70
+            ))
         );
         // index 113 is immediately after the comment, before the newline
         assert_eq!(
@@ -705,6 +765,68 @@ mod tests {
         tree.assert_eq(&format!("{:#?}", cst.document().syntax));
     }
 
+    #[test]
+    fn error_kinds() {
+        let kinds = |source: &str| {
+            Parser::new(source)
+                .parse()
+                .errors()
+                .map(|error| error.kind())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            kinds("type Query { a: Int ? }"),
+            [ErrorKind::UnexpectedCharacter]
+        );
+        assert_eq!(
+            kinds(r#"{ a(b: "c) }"#),
+            [
+                ErrorKind::UnterminatedString,
+                ErrorKind::UnexpectedEof,
+                ErrorKind::ExpectedToken {
+                    expected: TokenKind::RParen,
+                    found: TokenKind::Eof
+                },
+                ErrorKind::ExpectedToken {
+                    expected: TokenKind::RCurly,
+                    found: TokenKind::Eof
+                },
+            ]
+        );
+        let (_, errors) = crate::Lexer::new(r#""\x" 01 1.e"#).lex();
+        assert_eq!(
+            errors.iter().map(|error| error.kind()).collect::<Vec<_>>(),
+            [
+                ErrorKind::InvalidEscapeSequence,
+                ErrorKind::InvalidNumber,
+                ErrorKind::InvalidNumber
+            ]
+        );
+        assert_eq!(
+            kinds("type { a: Int }"),
+            [ErrorKind::ExpectedToken {
+                expected: TokenKind::Name,
+                found: TokenKind::LCurly
+            }]
+        );
+        assert_eq!(
+            kinds("{ a(b: ) }"),
+            [ErrorKind::UnexpectedToken {
+                found: TokenKind::RParen
+            }]
+        );
+        assert_eq!(kinds(""), [ErrorKind::UnexpectedEof]);
+        assert_eq!(
+            Parser::new("{ a { b } }")
+                .recursion_limit(1)
+                .parse()
+                .errors()
+                .map(|error| error.kind())
+                .collect::<Vec<_>>(),
+            [ErrorKind::LimitExceeded]
+        );
+    }
+
     #[test]
     fn tree_with_syntax_errors() {
         use crate::cst::Definition;
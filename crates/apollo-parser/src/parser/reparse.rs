@@ -0,0 +1,590 @@
+use crate::error::ErrorData;
+use crate::parser::grammar;
+use crate::Error;
+use crate::Lexer;
+use crate::LimitTracker;
+use crate::Parser;
+use crate::SyntaxKind;
+use crate::SyntaxNode;
+use crate::SyntaxTree;
+use crate::TextRange;
+use crate::TokenKind;
+use rowan::GreenNode;
+use rowan::NodeOrToken;
+use rowan::TextSize;
+use std::ops::Range;
+use std::rc::Rc;
+
+/// An edit checked against the previous tree and the new input:
+/// `start..old_end` in the previous text was replaced with `start..new_end` of the new input.
+struct Edit {
+    start: usize,
+    old_end: usize,
+    new_end: usize,
+}
+
+/// A part of the document that is parsed again.
+/// It covers `start..old_end` in the previous text and `start..new_end` in the new input.
+struct Region {
+    start: usize,
+    old_end: usize,
+    new_end: usize,
+}
+
+/// The result of parsing a region on its own.
+struct Part {
+    green: GreenNode,
+    errors: Vec<Error>,
+    recursion_high: usize,
+    tokens: usize,
+}
+
+impl<'input> Parser<'input> {
+    /// Parse the input, reusing the syntax tree of a previous version of the same document.
+    ///
+    /// `previous` must be the result of parsing the previous text, and the input of this parser
+    /// must be that text with the byte `range` replaced by `replacement`.
+    /// This is the kind of change that text editors report on every keystroke.
+    ///
+    /// Only the innermost selection set that contains the edit is parsed again,
+    /// or otherwise the top-level definitions around the edit.
+    /// Other parts of the previous tree are shared with the new one.
+    /// When the edit changes how the rest of the document would be parsed,
+    /// for example by adding an unbalanced `{`,
+    /// or when the previous tree and the edit do not match the input,
+    /// this falls back to parsing the entire input.
+    ///
+    /// In all cases, the result is equal to that of [`parse`][Self::parse] on the same input
+    /// with the same configuration, including errors and limits usage.
+    ///
+    /// ```rust
+    /// use apollo_parser::Parser;
+    ///
+    /// let source = "type Query { a: Int }\n\nquery { a }";
+    /// let tree = Parser::new(source).parse();
+    ///
+    /// // Rename the `a` field selection to `b`
+    /// let edited = "type Query { a: Int }\n\nquery { b }";
+    /// let tree = Parser::new(edited).reparse(&tree, 31..32, "b");
+    /// assert_eq!(tree, Parser::new(edited).parse());
+    /// ```
+    pub fn reparse(
+        self,
+        previous: &SyntaxTree,
+        range: Range<usize>,
+        replacement: &str,
+    ) -> SyntaxTree {
+        match self.try_reparse(previous, range, replacement) {
+            Some(tree) => tree,
+            None => self.parse(),
+        }
+    }
+
+    fn try_reparse(
+        &self,
+        previous: &SyntaxTree,
+        range: Range<usize>,
+        replacement: &str,
+    ) -> Option<SyntaxTree> {
+        // Parts of a tree that reached a limit are incomplete, and cannot be reused.
+        // A different limit could also change what part of the document is incomplete.
+        let recursion = previous.recursion_limit;
+        let tokens = previous.token_limit;
+        if recursion.limit != self.recursion_limit.limit
+            || tokens.limit != self.lexer.limit_tracker.limit
+            || recursion.high > recursion.limit
+            || tokens.high > tokens.limit
+        {
+            return None;
+        }
+
+        let root = previous.syntax_node();
+        let edit = self.check_edit(&root, range, replacement)?;
+        self.reparse_selection_set(previous, &root, &edit)
+            .or_else(|| self.reparse_definitions(previous, &root, &edit))
+    }
+
+    /// Check that the input is the text of `root` with the edit applied.
+    ///
+    /// Because the lexer does not keep invalid characters in the tree,
+    /// this also checks that `root` contains the entire previous text without lexer errors.
+    fn check_edit(
+        &self,
+        root: &SyntaxNode,
+        range: Range<usize>,
+        replacement: &str,
+    ) -> Option<Edit> {
+        let old_len = usize::from(root.text_range().end());
+        let Range { start, end } = range;
+        if start > end
+            || end > old_len
+            || old_len - (end - start) + replacement.len() != self.input.len()
+        {
+            return None;
+        }
+        let edit = Edit {
+            start,
+            old_end: end,
+            new_end: start + replacement.len(),
+        };
+        let text = root.text();
+        let prefix = text.slice(text_range(0, start)?);
+        let suffix = text.slice(text_range(end, old_len)?);
+        (self.input.get(start..edit.new_end)? == replacement
+            && prefix == self.input.get(..start)?
+            && suffix == self.input.get(edit.new_end..)?)
+        .then_some(edit)
+    }
+
+    /// Parse again the innermost selection set whose braces contain the edit.
+    ///
+    /// A selection set ends at its closing brace regardless of what follows,
+    /// so as long as the new text is still a single selection set,
+    /// parsing it on its own gives the same result as in the entire document.
+    fn reparse_selection_set(
+        &self,
+        previous: &SyntaxTree,
+        root: &SyntaxNode,
+        edit: &Edit,
+    ) -> Option<SyntaxTree> {
+        let covering = match root.covering_element(text_range(edit.start, edit.old_end)?) {
+            NodeOrToken::Node(node) => node,
+            NodeOrToken::Token(token) => token.parent()?,
+        };
+        let selection_set = covering
+            .ancestors()
+            .filter(|node| node.kind() == SyntaxKind::SELECTION_SET)
+            .find(|node| {
+                let range = node.text_range();
+                usize::from(range.start()) < edit.start
+                    && edit.old_end < usize::from(range.end())
+                    && node
+                        .first_token()
+                        .is_some_and(|token| token.kind() == SyntaxKind::L_CURLY)
+                    && is_closed(node)
+            })?;
+        // Nested selection sets are the only syntax that increments the recursion count
+        // while parsing another selection set
+        let depth = selection_set
+            .ancestors()
+            .skip(1)
+            .filter(|node| node.kind() == SyntaxKind::SELECTION_SET)
+            .count();
+
+        let range = selection_set.text_range();
+        let region = Region::new(edit, range.start().into(), range.end().into());
+        let old_text = selection_set.text().to_string();
+        let part = self.parse_part(
+            self.input.get(region.start..region.new_end)?,
+            depth,
+            grammar::selection::selection_set,
+        )?;
+        if part.green.kind() != rowan::SyntaxKind(SyntaxKind::SELECTION_SET as u16)
+            || has_eof_error(&part.errors)
+        {
+            return None;
+        }
+        let green = selection_set.replace_with(part.green.clone());
+        // Errors reported at the opening brace come from the parent node
+        self.splice(
+            previous,
+            &region,
+            green,
+            part,
+            &old_text,
+            depth,
+            true,
+            grammar::selection::selection_set,
+        )
+    }
+
+    /// Parse again the top-level definitions around the edit.
+    ///
+    /// Definitions that end with their closing brace are complete regardless of what follows,
+    /// so parsing can start again after them.
+    fn reparse_definitions(
+        &self,
+        previous: &SyntaxTree,
+        root: &SyntaxNode,
+        edit: &Edit,
+    ) -> Option<SyntaxTree> {
+        let old_len = usize::from(root.text_range().end());
+        let mut start = 0;
+        let mut end = old_len;
+        let boundaries = root
+            .children()
+            .filter(is_closed)
+            .map(|definition| usize::from(definition.text_range().end()));
+        for boundary in boundaries {
+            if boundary <= edit.start {
+                start = boundary;
+            } else if boundary > edit.old_end {
+                end = boundary;
+                break;
+            }
+        }
+        if start == 0 && end == old_len {
+            // That would be the entire document
+            return None;
+        }
+
+        let region = Region::new(edit, start, end);
+        let to_end = end == old_len;
+        let new_text = self.input.get(region.start..region.new_end)?;
+        if start != 0 && is_blank(new_text) {
+            // Parsing this on its own would report an empty document
+            return None;
+        }
+        let part = self.parse_part(new_text, 0, grammar::document::document)?;
+        if !to_end {
+            // The last definition must still end at the end of the region, with a closing brace
+            let last_definition = SyntaxNode::new_root(part.green.clone())
+                .last_child_or_token()?
+                .into_node()?;
+            if !is_closed(&last_definition) || has_eof_error(&part.errors) {
+                return None;
+            }
+        }
+
+        let children = root
+            .children_with_tokens()
+            .enumerate()
+            .filter(|(_, child)| {
+                let range = child.text_range();
+                usize::from(range.start()) >= start && usize::from(range.end()) <= end
+            })
+            .map(|(index, _)| index);
+        let first = children.clone().next()?;
+        let last = children.last()?;
+        let green = root.green().splice_children(
+            first..last + 1,
+            part.green.children().map(|child| child.to_owned()),
+        );
+        let old_text = root.text().slice(text_range(start, end)?).to_string();
+        self.splice(
+            previous,
+            &region,
+            green,
+            part,
+            &old_text,
+            0,
+            false,
+            grammar::document::document,
+        )
+    }
+
+    /// Parse `text` on its own with the given grammar, starting at a recursion depth.
+    ///
+    /// Returns `None` if the grammar does not consume the entire text,
+    /// or on lexer errors: those can affect how the text that follows is lexed.
+    fn parse_part(
+        &self,
+        text: &str,
+        depth: usize,
+        grammar: impl FnOnce(&mut Parser<'_>),
+    ) -> Option<Part> {
+        let tokens = count_tokens(text)?;
+        let mut parser = Parser::new(text);
+        parser.recursion_limit = LimitTracker {
+            current: depth,
+            high: depth,
+            limit: self.recursion_limit.limit,
+        };
+        grammar(&mut parser);
+        if parser.peek() != Some(TokenKind::Eof) {
+            return None;
+        }
+        parser.push_ignored();
+        let builder = Rc::try_unwrap(parser.builder).ok()?.into_inner();
+        Some(Part {
+            green: builder.finish(),
+            errors: parser.errors,
+            recursion_high: parser.recursion_limit.high,
+            tokens,
+        })
+    }
+
+    /// Combine the previous tree with a reparsed region.
+    #[allow(clippy::too_many_arguments)]
+    fn splice(
+        &self,
+        previous: &SyntaxTree,
+        region: &Region,
+        green: GreenNode,
+        part: Part,
+        old_text: &str,
+        depth: usize,
+        errors_at_start_are_before: bool,
+        grammar: impl FnOnce(&mut Parser<'_>),
+    ) -> Option<SyntaxTree> {
+        let recursion_limit = self.recursion_limit.limit;
+        if part.recursion_high > recursion_limit {
+            return None;
+        }
+        // The high-water mark of the previous tree was reached either in the region or elsewhere
+        let previous_high = previous.recursion_limit.high;
+        let recursion_high = if part.recursion_high >= previous_high {
+            part.recursion_high
+        } else if self.parse_part(old_text, depth, grammar)?.recursion_high < previous_high {
+            previous_high
+        } else {
+            return None;
+        };
+
+        let token_limit = self.lexer.limit_tracker.limit;
+        let tokens = previous.token_limit.high - count_tokens(old_text)? + part.tokens;
+        if tokens > token_limit {
+            return None;
+        }
+
+        let to_end = region.old_end == usize::from(previous.syntax_node().text_range().end());
+        let errors = splice_errors(
+            &previous.errors,
+            region,
+            part.errors,
+            errors_at_start_are_before,
+            to_end,
+        )?;
+        Some(SyntaxTree::new(
+            green,
+            errors,
+            LimitTracker {
+                current: 0,
+                high: recursion_high,
+                limit: recursion_limit,
+            },
+            LimitTracker {
+                current: tokens,
+                high: tokens,
+                limit: token_limit,
+            },
+        ))
+    }
+}
+
+impl Region {
+    fn new(edit: &Edit, start: usize, old_end: usize) -> Self {
+        Self {
+            start,
+            old_end,
+            new_end: old_end - edit.old_end + edit.new_end,
+        }
+    }
+}
+
+/// Replace errors of the previous tree within the region with errors from parsing it again.
+///
+/// Parsing before and after the region does not depend on its content,
+/// so it reports the same errors as in the previous tree, in the same order.
+fn splice_errors(
+    previous: &[Error],
+    region: &Region,
+    reparsed: Vec<Error>,
+    at_start_is_before: bool,
+    to_end: bool,
+) -> Option<Vec<Error>> {
+    let is_before = |error: &Error| {
+        error.index < region.start || (at_start_is_before && error.index == region.start)
+    };
+    let before = previous.iter().take_while(|error| is_before(error)).count();
+    let rest = &previous[before..];
+    let within = if to_end {
+        rest.len()
+    } else {
+        rest.iter()
+            .take_while(|error| error.index < region.old_end)
+            .count()
+    };
+    let after = &rest[within..];
+    if rest.iter().any(is_before) || after.iter().any(|error| error.index < region.old_end) {
+        return None;
+    }
+
+    let mut errors = previous[..before].to_vec();
+    errors.extend(reparsed.into_iter().map(|mut error| {
+        error.index += region.start;
+        error
+    }));
+    errors.extend(after.iter().map(|error| {
+        let mut error = error.clone();
+        error.index = error.index - region.old_end + region.new_end;
+        error
+    }));
+    Some(errors)
+}
+
+/// Returns whether `node` ends with a closing brace that matches all opening braces within it.
+///
+/// A node that ends with the closing brace of a nested node can still be incomplete,
+/// in which case parsing it continued after its end.
+fn is_closed(node: &SyntaxNode) -> bool {
+    let mut depth = 0_usize;
+    for token in node
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+    {
+        match token.kind() {
+            SyntaxKind::L_CURLY => depth += 1,
+            SyntaxKind::R_CURLY => match depth.checked_sub(1) {
+                Some(new_depth) => depth = new_depth,
+                None => return false,
+            },
+            _ => {}
+        }
+    }
+    depth == 0
+        && node
+            .last_token()
+            .is_some_and(|token| token.kind() == SyntaxKind::R_CURLY)
+}
+
+fn has_eof_error(errors: &[Error]) -> bool {
+    errors.iter().any(|error| error.data == ErrorData::Eof)
+}
+
+/// Returns the number of tokens in `text` not counting the end of file,
+/// or `None` if there is a lexer error.
+fn count_tokens(text: &str) -> Option<usize> {
+    let mut count = 0;
+    for token in Lexer::new(text) {
+        token.ok()?;
+        count += 1;
+    }
+    Some(count - 1)
+}
+
+/// Returns whether `text` only contains ignored tokens
+fn is_blank(text: &str) -> bool {
+    Lexer::new(text).all(|token| {
+        token.is_ok_and(|token| {
+            matches!(
+                token.kind(),
+                TokenKind::Whitespace | TokenKind::Comment | TokenKind::Comma | TokenKind::Eof
+            )
+        })
+    })
+}
+
+fn text_range(start: usize, end: usize) -> Option<TextRange> {
+    Some(TextRange::new(
+        TextSize::try_from(start).ok()?,
+        TextSize::try_from(end).ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Parser;
+
+    /// Apply an edit, and check that reparsing gives the same tree as a full parse.
+    /// Returns whether the previous tree was reused.
+    #[track_caller]
+    fn check(source: &str, range: std::ops::Range<usize>, replacement: &str) -> bool {
+        let previous = Parser::new(source).parse();
+        let mut edited = source.to_owned();
+        edited.replace_range(range.clone(), replacement);
+        let expected = Parser::new(&edited).parse();
+        let reparsed = Parser::new(&edited).reparse(&previous, range.clone(), replacement);
+        assert_eq!(reparsed, expected, "{edited}");
+        assert_eq!(reparsed.errors, expected.errors);
+        Parser::new(&edited)
+            .try_reparse(&previous, range, replacement)
+            .is_some()
+    }
+
+    fn edit(source: &str, find: &str, replacement: &str) -> bool {
+        let start = source.find(find).unwrap();
+        check(source, start..start + find.len(), replacement)
+    }
+
+    const SOURCE: &str = r#"
+        type Query {
+          user(id: ID!): User
+        }
+
+        "A user"
+        type User {
+          name: String
+        }
+
+        query GetUser($id: ID!) {
+          user(id: $id) {
+            name
+          }
+        }
+
+        fragment F on User { name }
+    "#;
+
+    #[test]
+    fn reparse_selection_set() {
+        assert!(edit(
+            SOURCE,
+            "name\n          }",
+            "name nickname\n          }"
+        ));
+        assert!(edit(SOURCE, "user(id: $id)", "me: user(id: $id)"));
+        // Now an error
+        assert!(edit(SOURCE, "{ name }", "{ name(: }"));
+        // Unbalanced braces change the rest of the document
+        assert!(!edit(SOURCE, "name\n          }", "name {\n          }"));
+    }
+
+    #[test]
+    fn reparse_definitions() {
+        assert!(edit(SOURCE, "type User", "type Person"));
+        assert!(edit(SOURCE, "\"A user\"", ""));
+        assert!(edit(SOURCE, "type Query", "extend type Query"));
+        // Insert a new definition between two others
+        let start = SOURCE.find("\"A user\"").unwrap();
+        assert!(check(SOURCE, start..start, "scalar Date\n"));
+        assert!(edit(SOURCE, "fragment F", "fragment G"));
+        // Without its closing brace, the type extends to the end of the next definition
+        assert!(edit(SOURCE, "name: String\n        }", "name: String\n"));
+        // The last definition can end anywhere
+        assert!(edit(
+            SOURCE,
+            "fragment F on User { name }",
+            "fragment F on User {"
+        ));
+    }
+
+    #[test]
+    fn reparse_with_errors() {
+        let source = "type A { a: }\n\ntype B { b: Int }\n\n{ c }\n\n{ d( }";
+        assert!(edit(source, "b: Int", "b: Int!"));
+        assert!(edit(source, "b: Int", "b:"));
+        assert!(edit(source, "{ c }", "{ c(x: 1) }"));
+        assert!(edit(source, "a: }", "a: Int }"));
+    }
+
+    #[test]
+    fn fall_back_to_full_parse() {
+        // Lexer errors
+        assert!(!edit(SOURCE, "name: String", "name: ?String"));
+        // Edit that does not match the previous text
+        let previous = Parser::new("{ a }").parse();
+        let tree = Parser::new("{ b }").reparse(&previous, 2..3, "c");
+        assert_eq!(tree, Parser::new("{ b }").parse());
+        // Limits
+        let source = "{ a { b } }";
+        let previous = Parser::new(source).parse();
+        let limited = |source| Parser::new(source).recursion_limit(1);
+        let tree = limited("{ a { c } }").reparse(&previous, 6..7, "c");
+        assert_eq!(tree, limited("{ a { c } }").parse());
+        assert_eq!(tree.recursion_limit().high, 2);
+    }
+
+    #[test]
+    fn limits_usage() {
+        let source = "{ a { b { c } } }\n\n{ d }";
+        assert_eq!(Parser::new(source).parse().recursion_limit().high, 3);
+        // The deepest selection set is removed
+        assert!(edit(source, "{ c }", "{ }"));
+        // The new high-water mark is only known after parsing the entire document
+        assert!(!edit(source, "b { c }", "b"));
+        // A deeper selection set is added elsewhere
+        assert!(edit(source, "{ d }", "{ d { e { f { g } } } }"));
+        assert!(edit(source, "{ d }", "{ d e f g }"));
+    }
+}
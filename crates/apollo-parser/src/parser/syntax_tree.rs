@@ -71,6 +71,21 @@ const _: () = {
 };
 
 impl<T: CstNode> SyntaxTree<T> {
+    pub(crate) fn new(
+        green: GreenNode,
+        errors: Vec<Error>,
+        recursion_limit: LimitTracker,
+        token_limit: LimitTracker,
+    ) -> Self {
+        Self {
+            green,
+            errors,
+            recursion_limit,
+            token_limit,
+            _phantom: PhantomData,
+        }
+    }
+
     /// Get a reference to the syntax tree's errors.
     pub fn errors(&self) -> Iter<'_, crate::Error> {
         self.errors.iter()
@@ -217,6 +232,11 @@ impl SyntaxTreeBuilder {
         self.builder.token(rowan::SyntaxKind(kind as u16), text);
     }
 
+    /// Finish building and return the root node, for parsing part of a document.
+    pub(crate) fn finish(self) -> GreenNode {
+        self.builder.finish()
+    }
+
     pub(crate) fn finish_document(
         self,
         errors: Vec<Error>,
@@ -0,0 +1,47 @@
+#![no_main]
+
+use apollo_compiler::ast::Document;
+use libfuzzer_sys::fuzz_target;
+use log::debug;
+
+fuzz_target!(|input: &str| {
+    let _ = env_logger::try_init();
+    debug!("{input}");
+
+    let doc = Document::parse(input, "original.graphql").unwrap_or_else(|invalid| invalid.partial);
+    for sort in [false, true] {
+        let format = |doc: &Document| {
+            doc.format()
+                .max_line_width(40)
+                .sort_definitions(sort)
+                .sort_fields(sort)
+                .sort_enum_values(sort)
+                .sort_directives(sort)
+                .to_string()
+        };
+        let formatted = format(&doc);
+        debug!("=> Formatted (sort: {sort}):\n{formatted}");
+
+        let doc2 = Document::parse(&formatted, "formatted.graphql").unwrap();
+        if !sort && doc != doc2 {
+            panic!(
+                "Formatted and reparsed to a different AST \
+                 (run with RUST_LOG=debug for details)"
+            )
+        }
+        let formatted2 = format(&doc2);
+        debug!("=> Formatted again:\n{formatted2}");
+        if formatted != formatted2 {
+            println!(
+                "{}",
+                similar_asserts::SimpleDiff::from_str(
+                    &formatted,
+                    &formatted2,
+                    "formatted",
+                    "formatted again"
+                )
+            );
+            panic!("Formatting is not idempotent (run with RUST_LOG=debug for details)")
+        }
+    }
+});
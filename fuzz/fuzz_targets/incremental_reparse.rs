@@ -0,0 +1,37 @@
+#![no_main]
+use apollo_parser::Parser;
+use libfuzzer_sys::fuzz_target;
+use log::debug;
+
+fuzz_target!(|input: (&str, usize, usize, &str)| {
+    let _ = env_logger::try_init();
+    let (source, start, len, replacement) = input;
+
+    let start = floor_char_boundary(source, start % (source.len() + 1));
+    let end = floor_char_boundary(source, start + len % (source.len() - start + 1));
+    let mut edited = source.to_owned();
+    edited.replace_range(start..end, replacement);
+    debug!("{source:?}\n=> replace {start}..{end} with {replacement:?}\n=> {edited:?}");
+
+    let previous = Parser::new(source).parse();
+    let reparsed = Parser::new(&edited).reparse(&previous, start..end, replacement);
+    let expected = Parser::new(&edited).parse();
+    similar_asserts::assert_eq!(
+        format!(
+            "{reparsed:#?}\n{:#?}",
+            reparsed.errors().collect::<Vec<_>>()
+        ),
+        format!(
+            "{expected:#?}\n{:#?}",
+            expected.errors().collect::<Vec<_>>()
+        ),
+    );
+    assert_eq!(reparsed, expected);
+});
+
+fn floor_char_boundary(s: &str, mut index: usize) -> usize {
+    while !s.is_char_boundary(index) {
+        index -= 1
+    }
+    index
+}